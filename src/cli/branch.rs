@@ -0,0 +1,44 @@
+//! `toktrack branch` subcommand for per-branch Claude Code usage
+
+use clap::{Args, Subcommand};
+
+use super::session::SessionSource;
+use super::{one_of, JsonFormat};
+use crate::services::{Aggregator, DataLoaderService};
+use crate::types::Result;
+
+/// Per-branch usage
+#[derive(Args, Debug)]
+pub struct BranchArgs {
+    #[command(subcommand)]
+    pub action: BranchAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BranchAction {
+    /// Cost and tokens per git branch of each request, costliest first
+    List {
+        /// Source CLI (only claude-code records branches)
+        #[arg(long, default_value = "claude-code", value_parser = one_of::<SessionSource>(&["claude-code"]))]
+        source: SessionSource,
+
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
+    },
+}
+
+impl BranchArgs {
+    pub fn run(self, loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+        match self.action {
+            BranchAction::List { .. } => {
+                let entries = loader.load_project_entries();
+                println!(
+                    "{}",
+                    format.render(&Aggregator::branch_summaries(&entries))?
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+//! `toktrack budget` subcommand for daily, weekly and monthly spending limits
+
+use std::fmt::Write;
+
+use chrono::Local;
+use clap::{ArgGroup, Args, Subcommand};
+
+use super::{load_data, JsonFormat};
+use crate::services::budget::{BudgetService, BudgetStatus};
+use crate::services::DataLoaderService;
+use crate::types::Result;
+
+/// Width of the `budget status` progress bar, in cells
+const BAR_WIDTH: usize = 20;
+
+/// Manage spending limits
+#[derive(Args, Debug)]
+pub struct BudgetArgs {
+    #[command(subcommand)]
+    pub action: BudgetAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BudgetAction {
+    /// Set one or more limits in USD; limits not given are kept
+    #[command(group(ArgGroup::new("limits").required(true).multiple(true)))]
+    Set {
+        #[arg(long, value_name = "USD", group = "limits")]
+        daily: Option<f64>,
+
+        #[arg(long, value_name = "USD", group = "limits")]
+        weekly: Option<f64>,
+
+        #[arg(long, value_name = "USD", group = "limits")]
+        monthly: Option<f64>,
+    },
+
+    /// Exit with code 1 if the current day, week or month is over its limit
+    Check {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Spend against each limit
+    Status {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+impl BudgetArgs {
+    /// Returns false when `check` found a period over budget
+    pub fn run(self, loader: DataLoaderService, format: JsonFormat) -> Result<bool> {
+        let service = BudgetService::new()?;
+        let (check, json) = match self.action {
+            BudgetAction::Set {
+                daily,
+                weekly,
+                monthly,
+            } => {
+                service.set(daily, weekly, monthly)?;
+                println!("Budget saved");
+                return Ok(true);
+            }
+            BudgetAction::Check { json } => (true, json),
+            BudgetAction::Status { json } => (false, json),
+        };
+
+        let config = service.load()?;
+        let week_start = loader.config().week_start;
+        let status = BudgetService::status(
+            &config,
+            &load_data(loader)?,
+            Local::now().date_naive(),
+            week_start,
+        );
+        if json {
+            println!("{}", format.render(&status)?);
+        } else if status.is_empty() {
+            println!("No budget set; add one with `toktrack budget set --daily <USD>`");
+        } else if check {
+            println!("{}", check_report(&status));
+        } else {
+            println!("{}", status_table(&status));
+        }
+        Ok(!check || status.iter().all(|s| !s.over_budget))
+    }
+}
+
+/// `Daily budget exceeded: $12.40 of $10.00` per period over its limit, else one OK line
+fn check_report(status: &[BudgetStatus]) -> String {
+    let over: Vec<String> = status
+        .iter()
+        .filter(|s| s.over_budget)
+        .map(|s| {
+            format!(
+                "{} budget exceeded: ${:.2} of ${:.2}",
+                s.period.label(),
+                s.spent_usd,
+                s.limit_usd
+            )
+        })
+        .collect();
+    if over.is_empty() {
+        "Within budget".to_string()
+    } else {
+        over.join("\n")
+    }
+}
+
+/// `Weekly   [███████████████░░░░░]  $15.00 / $20.00  (75%)` per limit
+fn status_table(status: &[BudgetStatus]) -> String {
+    let mut out = String::new();
+    for s in status {
+        let filled = ((s.percent_used / 100.0 * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+        let _ = write!(
+            out,
+            "{}{:<8} [{}{}]  ${:.2} / ${:.2}  ({:.0}%)",
+            if out.is_empty() { "" } else { "\n" },
+            s.period.label(),
+            "█".repeat(filled),
+            "░".repeat(BAR_WIDTH - filled),
+            s.spent_usd,
+            s.limit_usd,
+            s.percent_used
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::budget::BudgetPeriod;
+
+    fn status(period: BudgetPeriod, spent: f64, limit: f64) -> BudgetStatus {
+        BudgetStatus {
+            period,
+            limit_usd: limit,
+            spent_usd: spent,
+            percent_used: spent / limit * 100.0,
+            over_budget: spent > limit,
+        }
+    }
+
+    #[test]
+    fn test_status_table_bars() {
+        let table = status_table(&[
+            status(BudgetPeriod::Weekly, 15.0, 20.0),
+            status(BudgetPeriod::Monthly, 300.0, 100.0),
+        ]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            format!(
+                "Weekly   [{}{}]  $15.00 / $20.00  (75%)",
+                "█".repeat(15),
+                "░".repeat(5)
+            )
+        );
+        assert_eq!(
+            lines[1],
+            format!("Monthly  [{}]  $300.00 / $100.00  (300%)", "█".repeat(20))
+        );
+    }
+
+    #[test]
+    fn test_check_report() {
+        assert_eq!(
+            check_report(&[status(BudgetPeriod::Daily, 2.0, 10.0)]),
+            "Within budget"
+        );
+        assert_eq!(
+            check_report(&[
+                status(BudgetPeriod::Daily, 12.4, 10.0),
+                status(BudgetPeriod::Weekly, 15.0, 20.0),
+            ]),
+            "Daily budget exceeded: $12.40 of $10.00"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! `toktrack cache` subcommand for cache maintenance
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+use super::export::parse_date;
+use crate::services::cache::CacheMetadata;
+use crate::services::data_loader::CacheRebuild;
+use crate::services::{DailySummaryCacheService, DataLoaderService};
+use crate::types::Result;
+
+/// Manage daily summary caches
+#[derive(Args, Debug)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub action: CacheAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Delete every daily cache and rebuild it from a full parse
+    Rebuild,
+
+    /// Delete one CLI's daily cache, or all of them
+    Clear {
+        /// Cache name (e.g. claude-code); all caches if omitted
+        cli: Option<String>,
+    },
+
+    /// Show version, day count, date range, size and age of the daily caches
+    Inspect {
+        /// Cache name (e.g. claude-code); all caches if omitted
+        cli: Option<String>,
+    },
+
+    /// Drop cached days before a date from every daily cache
+    Prune {
+        /// Keep days on or after this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        before: String,
+
+        /// Only report how many days would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Zip the cache directory into ~/.toktrack/backups/
+    Backup {
+        /// Write the archive here instead
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+
+    /// Replace the caches with a backup, backing up the current ones first
+    Restore {
+        /// Archive made by `toktrack cache backup`
+        backup: PathBuf,
+    },
+}
+
+impl CacheArgs {
+    pub fn run(self, loader: DataLoaderService) -> Result<()> {
+        let cache = || DailySummaryCacheService::new(Some(loader.config()));
+        match self.action {
+            CacheAction::Rebuild => {
+                let rebuild = loader.rebuild_caches()?;
+                println!("{}", rebuild_report(&rebuild));
+            }
+            CacheAction::Clear { cli: Some(cli) } => {
+                cache()?.clear(&cli)?;
+                println!("Cleared {} cache", cli);
+            }
+            CacheAction::Clear { cli: None } => {
+                println!("Cleared {} cache file(s)", cache()?.clear_all()?);
+            }
+            CacheAction::Inspect { cli } => {
+                let cache = cache()?;
+                let clis = match cli {
+                    Some(cli) => vec![cli],
+                    None => cache.cached_clis()?,
+                };
+                let now = chrono::Utc::now().timestamp();
+                let mut lines = Vec::new();
+                for cli in clis {
+                    lines.push(match cache.inspect(&cli)? {
+                        Some(meta) => inspect_line(&meta, now),
+                        None => format!("{}: no cache", cli),
+                    });
+                }
+                if lines.is_empty() {
+                    lines.push("No daily caches".to_string());
+                }
+                println!("{}", lines.join("\n"));
+            }
+            CacheAction::Prune { before, dry_run } => {
+                let before = parse_date(&before)?;
+                let cache = cache()?;
+                let verb = if dry_run {
+                    "would be removed"
+                } else {
+                    "removed"
+                };
+                for cli in cache.cached_clis()? {
+                    let removed = cache.prune(&cli, before, dry_run)?;
+                    println!("{}: {} day(s) {}", cli, removed, verb);
+                }
+            }
+            CacheAction::Backup { output } => {
+                let path = cache()?.backup(output.as_deref())?;
+                println!("Backed up cache to {}", path.display());
+            }
+            CacheAction::Restore { backup } => {
+                cache()?.restore(&backup)?;
+                println!("Restored cache from {}", backup.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `claude-code: v7, 42 day(s) 2026-01-01..2026-02-11, 18311 bytes, updated 3h ago`
+fn inspect_line(meta: &CacheMetadata, now: i64) -> String {
+    let range = match (meta.earliest, meta.latest) {
+        (Some(earliest), Some(latest)) => format!(" {}..{}", earliest, latest),
+        _ => String::new(),
+    };
+    format!(
+        "{}: v{}, {} day(s){}, {} bytes, updated {} ago",
+        meta.cli,
+        meta.version,
+        meta.entries,
+        range,
+        meta.size_bytes,
+        format_age(now - meta.updated_at)
+    )
+}
+
+/// Coarsest whole unit of an age in seconds: `45s`, `12m`, `3h`, `5d`
+pub(super) fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// One line for the cleared caches, then one per CLI with its rebuilt day count
+fn rebuild_report(rebuild: &CacheRebuild) -> String {
+    let mut lines = vec![format!("Cleared {} cache file(s)", rebuild.cleared)];
+    lines.extend(
+        rebuild
+            .days
+            .iter()
+            .map(|(cli, days)| format!("{}: {} day(s) rebuilt", cli, days)),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect_line() {
+        let meta = CacheMetadata {
+            cli: "claude-code".to_string(),
+            version: 7,
+            entries: 42,
+            earliest: parse_date("2026-01-01").ok(),
+            latest: parse_date("2026-02-11").ok(),
+            size_bytes: 18311,
+            updated_at: 1_000_000,
+        };
+        assert_eq!(
+            inspect_line(&meta, 1_000_000 + 3 * 3600 + 59),
+            "claude-code: v7, 42 day(s) 2026-01-01..2026-02-11, 18311 bytes, updated 3h ago"
+        );
+        let empty = CacheMetadata {
+            entries: 0,
+            earliest: None,
+            latest: None,
+            ..meta
+        };
+        assert_eq!(
+            inspect_line(&empty, 1_000_000),
+            "claude-code: v7, 0 day(s), 18311 bytes, updated 0s ago"
+        );
+    }
+
+    #[test]
+    fn test_format_age_units() {
+        assert_eq!(format_age(-5), "0s");
+        assert_eq!(format_age(59), "59s");
+        assert_eq!(format_age(60), "1m");
+        assert_eq!(format_age(86399), "23h");
+        assert_eq!(format_age(2 * 86400 + 5), "2d");
+    }
+
+    #[test]
+    fn test_rebuild_report_lists_each_cli() {
+        let rebuild = CacheRebuild {
+            cleared: 3,
+            days: vec![("claude".to_string(), 42), ("codex".to_string(), 0)],
+        };
+        assert_eq!(
+            rebuild_report(&rebuild),
+            "Cleared 3 cache file(s)\nclaude: 42 day(s) rebuilt\ncodex: 0 day(s) rebuilt"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! `toktrack completions <shell>`: shell completion scripts for every subcommand and flag
+
+use std::io::Write;
+
+use clap::Command;
+use clap_complete::Shell;
+
+use crate::types::Result;
+
+/// Comment block at the top of each script explaining how to install it
+fn install_header(shell: Shell, bin: &str) -> String {
+    let install = match shell {
+        Shell::Bash => format!(
+            "source <({bin} completions bash)\n#      or: {bin} completions bash > /etc/bash_completion.d/{bin}"
+        ),
+        Shell::Zsh => format!(
+            "{bin} completions zsh > \"${{fpath[1]}}/_{bin}\"\n#      then restart zsh (or run `compinit`)"
+        ),
+        Shell::Fish => format!("{bin} completions fish > ~/.config/fish/completions/{bin}.fish"),
+        Shell::PowerShell => format!(
+            "{bin} completions powershell | Out-String | Invoke-Expression\n#      (add that line to $PROFILE to keep it)"
+        ),
+        _ => format!("{bin} completions {shell} > <your completions dir>"),
+    };
+    format!("# {shell} completion for {bin}\n# Install: {install}\n\n")
+}
+
+/// Write the completion script for `shell` to `out`
+pub fn generate(shell: Shell, mut cmd: Command, out: &mut impl Write) -> Result<()> {
+    let bin = cmd.get_name().to_string();
+    out.write_all(install_header(shell, &bin).as_bytes())?;
+    clap_complete::generate(shell, &mut cmd, bin, out);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::CommandFactory;
+
+    fn script(shell: Shell) -> String {
+        let mut out = Vec::new();
+        generate(shell, Cli::command(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_bash_covers_subcommands_flags_and_values() {
+        let bash = script(Shell::Bash);
+        assert!(bash.starts_with(
+            "# bash completion for toktrack\n# Install: source <(toktrack completions bash)\n"
+        ));
+        assert!(bash.contains("> /etc/bash_completion.d/toktrack"));
+        for sub in [
+            "daily",
+            "weekly",
+            "monthly",
+            "stats",
+            "export",
+            "sessions",
+            "completions",
+        ] {
+            assert!(
+                bash.contains(&format!("toktrack__subcmd__{sub})")),
+                "missing {sub}"
+            );
+        }
+        for flag in ["--from", "--to", "--format", "--output", "--json"] {
+            assert!(bash.contains(flag), "missing {flag}");
+        }
+        assert!(bash.contains("\"json csv ndjson\""));
+        assert!(bash.contains("bash elvish fish powershell zsh"));
+    }
+
+    #[test]
+    fn test_other_shells_have_header() {
+        for (shell, first) in [
+            (Shell::Zsh, "# zsh completion for toktrack"),
+            (Shell::Fish, "# fish completion for toktrack"),
+            (Shell::PowerShell, "# powershell completion for toktrack"),
+        ] {
+            let script = script(shell);
+            assert!(script.starts_with(first), "{shell}");
+            assert!(script.contains("export"), "{shell}");
+        }
+    }
+}
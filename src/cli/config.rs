@@ -0,0 +1,36 @@
+//! `toktrack config` subcommand for the settings in `config.toml`
+
+use clap::{Args, Subcommand};
+
+use super::JsonFormat;
+use crate::config::Config;
+use crate::types::Result;
+
+/// Inspect settings
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Effective settings: the config file merged over the defaults
+    Show {
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
+    },
+}
+
+impl ConfigArgs {
+    pub fn run(self, format: JsonFormat) -> Result<()> {
+        match self.action {
+            ConfigAction::Show { .. } => {
+                // Load again so an invalid file is reported as an error, not a warning
+                println!("{}", format.render(&Config::load()?)?);
+            }
+        }
+        Ok(())
+    }
+}
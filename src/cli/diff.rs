@@ -0,0 +1,356 @@
+//! `toktrack diff <period>`: this day/week/month against the previous one
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use serde::Serialize;
+
+use super::{load_result, JsonFormat};
+use crate::config::WeekStart;
+use crate::services::{Aggregator, DataLoaderService};
+use crate::types::{DailySummary, Result};
+
+/// Period length compared by `toktrack diff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl std::str::FromStr for DiffPeriod {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            _ => Err(format!("'{s}' is not one of: day, week, month")),
+        }
+    }
+}
+
+impl DiffPeriod {
+    /// First day of the period containing `date`
+    fn start_of(self, date: NaiveDate, week_start: WeekStart) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => {
+                let days_into_week = match week_start {
+                    WeekStart::Sunday => date.weekday().num_days_from_sunday(),
+                    WeekStart::Monday => date.weekday().num_days_from_monday(),
+                };
+                date - Duration::days(days_into_week as i64)
+            }
+            Self::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// First day of the period before the one starting on `start`
+    fn previous_start(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => start - Duration::days(1),
+            Self::Week => start - Duration::weeks(1),
+            Self::Month => start.checked_sub_months(Months::new(1)).unwrap_or(start),
+        }
+    }
+
+    /// Daily summaries rolled up into rows of this period, keyed by their first day
+    fn rows(self, daily: &[DailySummary], week_start: WeekStart) -> Vec<DailySummary> {
+        match self {
+            Self::Day => daily.to_vec(),
+            Self::Week => Aggregator::weekly_starting(daily, week_start),
+            Self::Month => Aggregator::monthly(daily),
+        }
+    }
+
+    /// Labels for the current and previous period in the text output
+    fn labels(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Day => ("Today", "yesterday"),
+            Self::Week => ("This week", "last week"),
+            Self::Month => ("This month", "last month"),
+        }
+    }
+}
+
+/// Cost and tokens of one source or model in both periods
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffRow {
+    pub name: String,
+    pub current_cost_usd: f64,
+    pub previous_cost_usd: f64,
+    pub delta_cost_usd: f64,
+    pub current_tokens: u64,
+    pub previous_tokens: u64,
+    pub token_delta: i64,
+}
+
+impl DiffRow {
+    fn new(name: String, current: (f64, u64), previous: (f64, u64)) -> Self {
+        Self {
+            name,
+            current_cost_usd: current.0,
+            previous_cost_usd: previous.0,
+            delta_cost_usd: current.0 - previous.0,
+            current_tokens: current.1,
+            previous_tokens: previous.1,
+            token_delta: current.1 as i64 - previous.1 as i64,
+        }
+    }
+}
+
+/// Output of `toktrack diff`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeriodDiff {
+    pub period: DiffPeriod,
+    pub current_period_start: NaiveDate,
+    pub previous_period_start: NaiveDate,
+    pub current_cost_usd: f64,
+    pub previous_cost_usd: f64,
+    pub delta_cost_usd: f64,
+    /// Cost change relative to the previous period; None when it cost nothing
+    pub percent_change: Option<f64>,
+    pub current_tokens: u64,
+    pub previous_tokens: u64,
+    pub token_delta: i64,
+    /// Per source, largest current cost first
+    pub by_source: Vec<DiffRow>,
+    /// Per model, largest current cost first
+    pub by_model: Vec<DiffRow>,
+}
+
+impl PeriodDiff {
+    /// Compare the period containing `today` with the one before it
+    pub fn compute(
+        period: DiffPeriod,
+        summaries: &[DailySummary],
+        source_summaries: &HashMap<String, Vec<DailySummary>>,
+        today: NaiveDate,
+        week_start: WeekStart,
+    ) -> Self {
+        let current_start = period.start_of(today, week_start);
+        let previous_start = period.previous_start(current_start);
+        let pick = |daily: &[DailySummary]| {
+            let mut rows = period.rows(daily, week_start).into_iter();
+            let current = rows.clone().find(|s| s.date == current_start);
+            let previous = rows.find(|s| s.date == previous_start);
+            (current, previous)
+        };
+        let totals = |s: &Option<DailySummary>| {
+            s.as_ref()
+                .map_or((0.0, 0), |s| (s.total_cost_usd, s.total_tokens()))
+        };
+
+        let (current, previous) = pick(summaries);
+        let (current_cost, current_tokens) = totals(&current);
+        let (previous_cost, previous_tokens) = totals(&previous);
+
+        let mut by_source: Vec<DiffRow> = source_summaries
+            .iter()
+            .map(|(source, daily)| {
+                let (current, previous) = pick(daily);
+                DiffRow::new(source.clone(), totals(&current), totals(&previous))
+            })
+            .filter(|row| row.current_tokens > 0 || row.previous_tokens > 0)
+            .collect();
+        sort_rows(&mut by_source);
+
+        let model_totals = |s: &Option<DailySummary>, name: &str| {
+            s.as_ref()
+                .and_then(|s| s.models.get(name))
+                .map_or((0.0, 0), |m| (m.cost_usd, m.total_tokens()))
+        };
+        let names: BTreeSet<&String> = current
+            .iter()
+            .chain(previous.iter())
+            .flat_map(|s| s.models.keys())
+            .collect();
+        let mut by_model: Vec<DiffRow> = names
+            .into_iter()
+            .map(|name| {
+                DiffRow::new(
+                    name.clone(),
+                    model_totals(&current, name),
+                    model_totals(&previous, name),
+                )
+            })
+            .collect();
+        sort_rows(&mut by_model);
+
+        Self {
+            period,
+            current_period_start: current_start,
+            previous_period_start: previous_start,
+            current_cost_usd: current_cost,
+            previous_cost_usd: previous_cost,
+            delta_cost_usd: current_cost - previous_cost,
+            percent_change: (previous_cost > 0.0)
+                .then(|| (current_cost - previous_cost) / previous_cost * 100.0),
+            current_tokens,
+            previous_tokens,
+            token_delta: current_tokens as i64 - previous_tokens as i64,
+            by_source,
+            by_model,
+        }
+    }
+
+    /// One line, e.g. `This week: $4.23 vs last week: $3.10 (+36.5%)`
+    pub fn summary_line(&self) -> String {
+        let (current, previous) = self.period.labels();
+        let mut line = format!(
+            "{}: ${:.2} vs {}: ${:.2}",
+            current, self.current_cost_usd, previous, self.previous_cost_usd
+        );
+        if let Some(percent) = self.percent_change {
+            line.push_str(&format!(" ({:+.1}%)", percent));
+        }
+        line
+    }
+}
+
+/// Largest current cost first, then by name
+fn sort_rows(rows: &mut [DiffRow]) {
+    rows.sort_by(|a, b| {
+        b.current_cost_usd
+            .total_cmp(&a.current_cost_usd)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Print the comparison as text, or as JSON with `json`
+pub fn run_diff(
+    loader: DataLoaderService,
+    period: DiffPeriod,
+    json: bool,
+    format: JsonFormat,
+) -> Result<()> {
+    let week_start = loader.config().week_start;
+    let result = load_result(loader)?;
+    let diff = PeriodDiff::compute(
+        period,
+        &result.summaries,
+        &result.source_summaries,
+        Local::now().date_naive(),
+        week_start,
+    );
+    if json {
+        println!("{}", format.render(&diff)?);
+    } else {
+        println!("{}", diff.summary_line());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ModelUsage;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn day(date: NaiveDate, model: &str, tokens: u64, cost: f64) -> DailySummary {
+        let mut models = HashMap::new();
+        models.insert(
+            model.to_string(),
+            ModelUsage {
+                input_tokens: tokens,
+                cost_usd: cost,
+                count: 1,
+                ..ModelUsage::default()
+            },
+        );
+        DailySummary {
+            date,
+            total_input_tokens: tokens,
+            total_output_tokens: 0,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models,
+            cost_estimated: false,
+        }
+    }
+
+    #[test]
+    fn test_period_starts() {
+        // Wednesday 2026-03-18
+        let wed = date(2026, 3, 18);
+        assert_eq!(
+            DiffPeriod::Week.start_of(wed, WeekStart::Sunday),
+            date(2026, 3, 15)
+        );
+        assert_eq!(
+            DiffPeriod::Week.start_of(wed, WeekStart::Monday),
+            date(2026, 3, 16)
+        );
+        assert_eq!(
+            DiffPeriod::Month.previous_start(DiffPeriod::Month.start_of(wed, WeekStart::Sunday)),
+            date(2026, 2, 1)
+        );
+        assert_eq!(DiffPeriod::Day.previous_start(wed), date(2026, 3, 17));
+    }
+
+    #[test]
+    fn test_week_diff_with_breakdowns() {
+        let claude = vec![
+            day(date(2026, 3, 9), "claude-sonnet-4", 1000, 3.10),
+            day(date(2026, 3, 16), "claude-sonnet-4", 1000, 2.00),
+        ];
+        let codex = vec![day(date(2026, 3, 17), "gpt-5", 500, 2.23)];
+        let all = vec![claude[0].clone(), claude[1].clone(), codex[0].clone()];
+        let sources = HashMap::from([
+            ("claude-code".to_string(), claude),
+            ("codex".to_string(), codex),
+        ]);
+
+        let diff = PeriodDiff::compute(
+            DiffPeriod::Week,
+            &all,
+            &sources,
+            date(2026, 3, 18),
+            WeekStart::Monday,
+        );
+
+        assert_eq!(diff.current_period_start, date(2026, 3, 16));
+        assert_eq!(diff.previous_period_start, date(2026, 3, 9));
+        assert!((diff.current_cost_usd - 4.23).abs() < 1e-9);
+        assert!((diff.previous_cost_usd - 3.10).abs() < 1e-9);
+        assert_eq!(diff.current_tokens, 1500);
+        assert_eq!(diff.token_delta, 500);
+        assert_eq!(
+            diff.summary_line(),
+            "This week: $4.23 vs last week: $3.10 (+36.5%)"
+        );
+
+        let names = |rows: &[DiffRow]| rows.iter().map(|r| r.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.by_source), vec!["codex", "claude-code"]);
+        assert_eq!(names(&diff.by_model), vec!["gpt-5", "claude-sonnet-4"]);
+        assert!((diff.by_model[1].delta_cost_usd + 1.10).abs() < 1e-9);
+        assert_eq!(diff.by_model[0].previous_tokens, 0);
+    }
+
+    #[test]
+    fn test_no_previous_usage_has_no_percent() {
+        let all = vec![day(date(2026, 3, 18), "claude-sonnet-4", 10, 1.0)];
+        let diff = PeriodDiff::compute(
+            DiffPeriod::Day,
+            &all,
+            &HashMap::new(),
+            date(2026, 3, 18),
+            WeekStart::Sunday,
+        );
+        assert_eq!(diff.percent_change, None);
+        assert_eq!(diff.summary_line(), "Today: $1.00 vs yesterday: $0.00");
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["period"], "day");
+        assert_eq!(json["current_period_start"], "2026-03-18");
+        assert!(json["percent_change"].is_null());
+    }
+}
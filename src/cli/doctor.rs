@@ -0,0 +1,336 @@
+//! `toktrack doctor`: health checks for data directories, caches and the install
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use chrono::Utc;
+use serde::Serialize;
+
+use super::cache::format_age;
+use super::JsonFormat;
+use crate::config::Config;
+use crate::parsers::{CLIParser, ParserRegistry};
+use crate::services::cache::CACHE_VERSION;
+use crate::services::data_dir::data_dir;
+use crate::services::pricing::PricingCache;
+use crate::services::{DailySummaryCacheService, PricingService};
+use crate::types::Result;
+
+/// Pricing older than this means fetches have been failing for a while
+const PRICING_STALE_SECS: i64 = 7 * 86400;
+
+/// Free space in the data directory below which a warning is shown
+const LOW_DISK_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Outcome of one check; only `Fail` makes `doctor` exit with code 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Ok => "✓",
+            Self::Warn => "⚠",
+            Self::Fail => "✗",
+        }
+    }
+}
+
+/// One line of the `toktrack doctor` report
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl HealthCheck {
+    fn new(name: impl Into<String>, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Run every check and print the report. Returns false when any check failed.
+pub fn run_doctor(config: &Config, json: bool, format: JsonFormat) -> Result<bool> {
+    let mut checks = parser_checks(ParserRegistry::new(Some(config)).parsers());
+    match DailySummaryCacheService::new(Some(config)) {
+        Ok(cache) => checks.extend(cache_checks(&cache)),
+        Err(e) => checks.push(HealthCheck::new("cache", CheckStatus::Fail, e.to_string())),
+    }
+    checks.push(match PricingService::default_cache_path() {
+        Ok(path) => pricing_check(&path, Utc::now().timestamp()),
+        Err(e) => HealthCheck::new("pricing", CheckStatus::Fail, e.to_string()),
+    });
+    checks.push(disk_check());
+    checks.push(npm_check(npm_installed_version().as_deref()));
+
+    if json {
+        println!("{}", format.render(&checks)?);
+    } else {
+        for check in &checks {
+            println!(
+                "{} {}: {}",
+                check.status.symbol(),
+                check.name,
+                check.message
+            );
+        }
+    }
+    Ok(checks.iter().all(|c| c.status != CheckStatus::Fail))
+}
+
+/// Each parser's data directory exists and holds usage files
+fn parser_checks(parsers: &[Box<dyn CLIParser>]) -> Vec<HealthCheck> {
+    parsers
+        .iter()
+        .map(|p| {
+            let dir = p.data_dir().display();
+            let (status, message) = if !p.data_dir_exists() {
+                (CheckStatus::Warn, format!("{} not found", dir))
+            } else {
+                match p.collect_files().len() {
+                    0 => (CheckStatus::Warn, format!("no usage files in {}", dir)),
+                    n => (CheckStatus::Ok, format!("{} file(s) in {}", n, dir)),
+                }
+            };
+            HealthCheck::new(p.name(), status, message)
+        })
+        .collect()
+}
+
+/// Each daily cache file parses and is at the current version
+fn cache_checks(cache: &DailySummaryCacheService) -> Vec<HealthCheck> {
+    let clis = match cache.cached_clis() {
+        Ok(clis) => clis,
+        Err(e) => return vec![HealthCheck::new("cache", CheckStatus::Fail, e.to_string())],
+    };
+    if clis.is_empty() {
+        return vec![HealthCheck::new(
+            "cache",
+            CheckStatus::Warn,
+            "no caches yet; they are built on the next run",
+        )];
+    }
+    let now = Utc::now().timestamp();
+    clis.into_iter()
+        .map(|cli| {
+            let name = format!("{} cache", cli);
+            match cache.inspect(&cli) {
+                Ok(Some(meta)) if cache.is_version_current(&cli) => HealthCheck::new(
+                    name,
+                    CheckStatus::Ok,
+                    format!(
+                        "v{}, {} day(s), updated {} ago",
+                        meta.version,
+                        meta.entries,
+                        format_age(now - meta.updated_at)
+                    ),
+                ),
+                Ok(Some(meta)) => HealthCheck::new(
+                    name,
+                    CheckStatus::Warn,
+                    format!(
+                        "v{} is outdated (current v{}); rebuilt on the next run",
+                        meta.version, CACHE_VERSION
+                    ),
+                ),
+                Ok(None) => HealthCheck::new(name, CheckStatus::Warn, "removed during check"),
+                Err(e) => HealthCheck::new(name, CheckStatus::Fail, format!("unreadable: {}", e)),
+            }
+        })
+        .collect()
+}
+
+/// The LiteLLM pricing cache at `path` exists, parses, and how old it is
+fn pricing_check(path: &Path, now: i64) -> HealthCheck {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return HealthCheck::new(
+                "pricing",
+                CheckStatus::Warn,
+                format!("{} not found; fetched on the next run", path.display()),
+            )
+        }
+        Err(e) => return HealthCheck::new("pricing", CheckStatus::Fail, e.to_string()),
+    };
+    let cache: PricingCache = match serde_json::from_str(&content) {
+        Ok(cache) => cache,
+        Err(e) => {
+            return HealthCheck::new(
+                "pricing",
+                CheckStatus::Fail,
+                format!("{} is not valid pricing JSON: {}", path.display(), e),
+            )
+        }
+    };
+    if let Some(date) = cache.bundled_at {
+        return HealthCheck::new(
+            "pricing",
+            CheckStatus::Warn,
+            format!("using built-in prices from {}; fetch has failed", date),
+        );
+    }
+    let age = now - cache.fetched_at;
+    let status = if age > PRICING_STALE_SECS {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Ok
+    };
+    HealthCheck::new(
+        "pricing",
+        status,
+        format!(
+            "{} models, updated {} ago",
+            cache.models.len(),
+            format_age(age)
+        ),
+    )
+}
+
+/// Free space on the volume holding `~/.toktrack/`
+fn disk_check() -> HealthCheck {
+    let Some(dir) = data_dir() else {
+        return HealthCheck::new("disk", CheckStatus::Fail, "cannot determine home directory");
+    };
+    // The data directory may not exist before the first run
+    let probe = dir
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(dir.as_path());
+    match fs2::available_space(probe) {
+        Ok(bytes) => disk_status(bytes),
+        Err(e) => HealthCheck::new(
+            "disk",
+            CheckStatus::Warn,
+            format!("cannot read free space: {}", e),
+        ),
+    }
+}
+
+fn disk_status(available_bytes: u64) -> HealthCheck {
+    let status = if available_bytes < LOW_DISK_BYTES {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Ok
+    };
+    HealthCheck::new(
+        "disk",
+        status,
+        format!(
+            "{:.1} GB free",
+            available_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+        ),
+    )
+}
+
+/// Version of the globally installed npm package, if npm and the package exist
+fn npm_installed_version() -> Option<String> {
+    let output = Command::new("npm")
+        .args(["ls", "-g", "toktrack", "--depth=0", "--json"])
+        .output()
+        .ok()?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    json["dependencies"]["toktrack"]["version"]
+        .as_str()
+        .map(String::from)
+}
+
+/// This binary's version against the npm-installed one
+fn npm_check(installed: Option<&str>) -> HealthCheck {
+    let current = env!("CARGO_PKG_VERSION");
+    match installed {
+        None => HealthCheck::new(
+            "version",
+            CheckStatus::Ok,
+            format!("{} (not installed via npm)", current),
+        ),
+        Some(v) if v.trim_start_matches('v') == current => {
+            HealthCheck::new("version", CheckStatus::Ok, format!("{} (npm)", current))
+        }
+        Some(v) => HealthCheck::new(
+            "version",
+            CheckStatus::Warn,
+            format!(
+                "running {} but npm has {}; another install may be first on PATH",
+                current, v
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pricing_check() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("pricing.json");
+        assert_eq!(pricing_check(&path, 0).status, CheckStatus::Warn);
+
+        fs::write(&path, r#"{"fetched_at": 1000, "models": {}}"#).unwrap();
+        let fresh = pricing_check(&path, 1000 + 3 * 3600);
+        assert_eq!(fresh.status, CheckStatus::Ok);
+        assert_eq!(fresh.message, "0 models, updated 3h ago");
+        assert_eq!(
+            pricing_check(&path, 1000 + 8 * 86400).status,
+            CheckStatus::Warn
+        );
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(pricing_check(&path, 0).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_cache_checks() {
+        let tmp = TempDir::new().unwrap();
+        let cache = DailySummaryCacheService::with_cache_dir(tmp.path().to_path_buf());
+        assert_eq!(cache_checks(&cache)[0].status, CheckStatus::Warn);
+
+        cache.load_or_compute("claude-code", &[]).unwrap();
+        fs::write(
+            cache.cache_path("codex"),
+            r#"{"cli":"codex","version":1,"updated_at":0,"summaries":[]}"#,
+        )
+        .unwrap();
+        fs::write(cache.cache_path("gemini"), "{").unwrap();
+
+        let statuses: Vec<(String, CheckStatus)> = cache_checks(&cache)
+            .into_iter()
+            .map(|c| (c.name, c.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("claude-code cache".to_string(), CheckStatus::Ok),
+                ("codex cache".to_string(), CheckStatus::Warn),
+                ("gemini cache".to_string(), CheckStatus::Fail),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_npm_check_and_disk_status() {
+        let current = env!("CARGO_PKG_VERSION");
+        assert_eq!(npm_check(None).status, CheckStatus::Ok);
+        assert_eq!(npm_check(Some(current)).status, CheckStatus::Ok);
+        assert_eq!(npm_check(Some("0.0.1")).status, CheckStatus::Warn);
+
+        assert_eq!(disk_status(50 * 1024 * 1024).status, CheckStatus::Warn);
+        let ok = disk_status(3 * 1024 * 1024 * 1024);
+        assert_eq!(ok.status, CheckStatus::Ok);
+        assert_eq!(ok.message, "3.0 GB free");
+    }
+}
@@ -0,0 +1,245 @@
+//! `toktrack export`: per-day, per-source, per-model rows as JSON, CSV or NDJSON
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::JsonFormat;
+use crate::types::{DailySummary, Result, ToktrackError};
+
+/// CSV header, matching the fields of `ExportRow`
+const CSV_HEADER: &str = "date,source,model,input_tokens,output_tokens,cache_read_tokens,cache_creation_tokens,thinking_tokens,requests,cost_usd";
+
+/// Output format for `toktrack export`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Ndjson,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(format!("'{s}' is not one of: json, csv, ndjson")),
+        }
+    }
+}
+
+/// One model's usage on one day from one source
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ExportRow {
+    pub date: NaiveDate,
+    pub source: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub thinking_tokens: u64,
+    pub requests: u64,
+    pub cost_usd: f64,
+}
+
+/// Parse a `--from`/`--to` date (`YYYY-MM-DD`)
+pub fn parse_date(s: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| {
+        ToktrackError::Parse(format!("invalid date '{}' (expected YYYY-MM-DD): {}", s, e))
+    })
+}
+
+/// One row per (date, source, model) within `from..=to`, sorted by date, source, model
+pub fn export_rows(
+    source_summaries: &HashMap<String, Vec<DailySummary>>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Vec<ExportRow> {
+    let in_range = |date: NaiveDate| from.is_none_or(|f| date >= f) && to.is_none_or(|t| date <= t);
+    let mut rows: Vec<ExportRow> = source_summaries
+        .iter()
+        .flat_map(|(source, summaries)| {
+            summaries
+                .iter()
+                .filter(move |s| in_range(s.date))
+                .flat_map(move |s| {
+                    s.models.iter().map(move |(model, usage)| ExportRow {
+                        date: s.date,
+                        source: source.clone(),
+                        model: model.clone(),
+                        input_tokens: usage.input_tokens,
+                        output_tokens: usage.output_tokens,
+                        cache_read_tokens: usage.cache_read_tokens,
+                        cache_creation_tokens: usage.cache_creation_tokens,
+                        thinking_tokens: usage.thinking_tokens,
+                        requests: usage.count,
+                        cost_usd: usage.cost_usd,
+                    })
+                })
+        })
+        .collect();
+    rows.sort_by(|a, b| (a.date, &a.source, &a.model).cmp(&(b.date, &b.source, &b.model)));
+    rows
+}
+
+/// Render rows in `format`; `json` honours `--compact`
+pub fn render(rows: &[ExportRow], format: ExportFormat, json: JsonFormat) -> Result<String> {
+    match format {
+        ExportFormat::Json => json.render(rows),
+        ExportFormat::Ndjson => JsonFormat::Lines.render(rows),
+        ExportFormat::Csv => Ok(to_csv(rows)),
+    }
+}
+
+fn to_csv(rows: &[ExportRow]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    for row in rows {
+        let _ = write!(
+            out,
+            "\n{},{},{},{},{},{},{},{},{},{}",
+            row.date,
+            csv_field(&row.source),
+            csv_field(&row.model),
+            row.input_tokens,
+            row.output_tokens,
+            row.cache_read_tokens,
+            row.cache_creation_tokens,
+            row.thinking_tokens,
+            row.requests,
+            row.cost_usd
+        );
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or newline
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ModelUsage;
+
+    fn usage(input: u64, cost: f64) -> ModelUsage {
+        ModelUsage {
+            input_tokens: input,
+            output_tokens: 10,
+            count: 2,
+            cost_usd: cost,
+            ..ModelUsage::default()
+        }
+    }
+
+    fn day(d: u32, models: &[(&str, ModelUsage)]) -> DailySummary {
+        DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 3, d).unwrap(),
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: 0.0,
+            models: models
+                .iter()
+                .map(|(name, u)| (name.to_string(), u.clone()))
+                .collect(),
+            cost_estimated: false,
+        }
+    }
+
+    fn sources() -> HashMap<String, Vec<DailySummary>> {
+        HashMap::from([
+            (
+                "claude-code".to_string(),
+                vec![
+                    day(
+                        1,
+                        &[
+                            ("claude-sonnet-4-5", usage(100, 1.0)),
+                            ("claude-opus-4-5", usage(50, 2.0)),
+                            ("claude-haiku-4-5", usage(20, 0.1)),
+                        ],
+                    ),
+                    day(3, &[("claude-sonnet-4-5", usage(300, 3.0))]),
+                ],
+            ),
+            (
+                "codex".to_string(),
+                vec![day(2, &[("gpt-4.1", usage(200, 0.5))])],
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_one_row_per_model_per_day() {
+        let rows = export_rows(&sources(), None, None);
+        assert_eq!(rows.len(), 5);
+        let keys: Vec<(u32, &str)> = rows
+            .iter()
+            .map(|r| (chrono::Datelike::day(&r.date), r.model.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (1, "claude-haiku-4-5"),
+                (1, "claude-opus-4-5"),
+                (1, "claude-sonnet-4-5"),
+                (2, "gpt-4.1"),
+                (3, "claude-sonnet-4-5"),
+            ]
+        );
+        assert_eq!(rows[3].source, "codex");
+        assert_eq!(rows[3].requests, 2);
+    }
+
+    #[test]
+    fn test_date_range_is_inclusive() {
+        let from = parse_date("2026-03-02").ok();
+        let to = parse_date("2026-03-03").ok();
+        let rows = export_rows(&sources(), from, to);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(export_rows(&sources(), None, from).len(), 4);
+    }
+
+    #[test]
+    fn test_invalid_date_is_parse_error() {
+        assert!(matches!(
+            parse_date("03/02/2026"),
+            Err(ToktrackError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let rows = export_rows(&sources(), parse_date("2026-03-02").ok(), None);
+        let csv = render(&rows, ExportFormat::Csv, JsonFormat::Pretty).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "2026-03-02,codex,gpt-4.1,200,10,0,0,0,2,0.5");
+        assert_eq!(lines.len(), 3);
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn test_ndjson_output() {
+        let rows = export_rows(&sources(), None, None);
+        let ndjson = render(&rows, ExportFormat::Ndjson, JsonFormat::Pretty).unwrap();
+        assert_eq!(ndjson.lines().count(), 5);
+        let first: serde_json::Value =
+            serde_json::from_str(ndjson.lines().next().unwrap()).unwrap();
+        assert_eq!(first["model"], "claude-haiku-4-5");
+    }
+}
@@ -0,0 +1,143 @@
+//! `toktrack forecast`: projected spend from the recent daily rate
+
+use serde::Serialize;
+
+use super::{load_data, JsonFormat};
+use crate::services::{Aggregator, DataLoaderService};
+use crate::types::{DailySummary, Result, ToktrackError};
+
+/// Fewest days of usage a forecast is based on
+const MIN_DAYS: usize = 3;
+
+/// z-score of a two-sided 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// Output of `toktrack forecast`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Forecast {
+    pub projected_cost_usd: f64,
+    /// Standard deviation of the projected total, treating days as independent
+    pub std_deviation_usd: f64,
+    /// 95% interval around the projection, floored at zero
+    pub confidence_interval_low: f64,
+    pub confidence_interval_high: f64,
+    pub avg_daily_cost_usd: f64,
+    pub lookback_days_used: usize,
+    pub forecast_days: u32,
+}
+
+impl Forecast {
+    /// Project `days` of spend from the last `lookback_days` days with usage.
+    /// `summaries` must be sorted ascending by date.
+    pub fn compute(summaries: &[DailySummary], days: u32, lookback_days: u32) -> Result<Self> {
+        let recent = &summaries[summaries.len().saturating_sub(lookback_days as usize)..];
+        if recent.len() < MIN_DAYS {
+            return Err(ToktrackError::Parse(format!(
+                "Need at least {} days of usage to forecast, found {}",
+                MIN_DAYS,
+                recent.len()
+            )));
+        }
+
+        let mean = recent.iter().map(|s| s.total_cost_usd).sum::<f64>() / recent.len() as f64;
+        let projected = days as f64 * mean;
+        let std_dev = Aggregator::variance_cost(recent).sqrt() * (days as f64).sqrt();
+        Ok(Self {
+            projected_cost_usd: projected,
+            std_deviation_usd: std_dev,
+            confidence_interval_low: (projected - Z_95 * std_dev).max(0.0),
+            confidence_interval_high: projected + Z_95 * std_dev,
+            avg_daily_cost_usd: mean,
+            lookback_days_used: recent.len(),
+            forecast_days: days,
+        })
+    }
+
+    /// e.g. `Projected cost (30 days): $12.45 ± $3.20 (95% CI: $6.18–$18.72)`
+    pub fn summary_line(&self) -> String {
+        format!(
+            "Projected cost ({} days): ${:.2} ± ${:.2} (95% CI: ${:.2}–${:.2})",
+            self.forecast_days,
+            self.projected_cost_usd,
+            self.std_deviation_usd,
+            self.confidence_interval_low,
+            self.confidence_interval_high
+        )
+    }
+}
+
+/// Print the forecast as text, or as JSON with `json`
+pub fn run_forecast(
+    loader: DataLoaderService,
+    days: u32,
+    lookback_days: u32,
+    json: bool,
+    format: JsonFormat,
+) -> Result<()> {
+    let mut summaries = load_data(loader)?;
+    summaries.sort_by_key(|s| s.date);
+    let forecast = Forecast::compute(&summaries, days, lookback_days)?;
+    if json {
+        println!("{}", format.render(&forecast)?);
+    } else {
+        println!("{}", forecast.summary_line());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+
+    fn days(costs: &[f64]) -> Vec<DailySummary> {
+        costs
+            .iter()
+            .enumerate()
+            .map(|(i, &cost)| DailySummary {
+                date: NaiveDate::from_ymd_opt(2026, 3, 1 + i as u32).unwrap(),
+                total_input_tokens: 0,
+                total_output_tokens: 0,
+                total_cache_read_tokens: 0,
+                total_cache_creation_tokens: 0,
+                total_thinking_tokens: 0,
+                total_cost_usd: cost,
+                models: HashMap::new(),
+                cost_estimated: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_projection_uses_lookback_window() {
+        // Only the last 4 days (1, 3, 1, 3) count: mean 2, variance 1
+        let forecast = Forecast::compute(&days(&[100.0, 1.0, 3.0, 1.0, 3.0]), 4, 4).unwrap();
+        assert_eq!(forecast.lookback_days_used, 4);
+        assert!((forecast.avg_daily_cost_usd - 2.0).abs() < 1e-9);
+        assert!((forecast.projected_cost_usd - 8.0).abs() < 1e-9);
+        assert!((forecast.std_deviation_usd - 2.0).abs() < 1e-9);
+        assert!((forecast.confidence_interval_low - 4.08).abs() < 1e-9);
+        assert!((forecast.confidence_interval_high - 11.92).abs() < 1e-9);
+        assert_eq!(
+            forecast.summary_line(),
+            "Projected cost (4 days): $8.00 ± $2.00 (95% CI: $4.08–$11.92)"
+        );
+    }
+
+    #[test]
+    fn test_interval_is_floored_at_zero() {
+        let forecast = Forecast::compute(&days(&[0.0, 0.0, 9.0]), 1, 14).unwrap();
+        assert_eq!(forecast.lookback_days_used, 3);
+        assert_eq!(forecast.confidence_interval_low, 0.0);
+    }
+
+    #[test]
+    fn test_too_few_days_is_error() {
+        assert!(matches!(
+            Forecast::compute(&days(&[1.0, 2.0]), 30, 14),
+            Err(ToktrackError::Parse(_))
+        ));
+        assert!(Forecast::compute(&days(&[1.0, 2.0, 3.0]), 30, 2).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! Markdown rendering for `toktrack stats --markdown`
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+
+use crate::services::display_name;
+use crate::tui::widgets::overview::format_number;
+use crate::types::{DailySummary, ModelUsage, StatsData};
+
+/// Number of models listed in the "Top models" table
+const TOP_MODELS: usize = 5;
+
+/// Render stats, longest streak and the top models by cost as a Markdown section
+pub fn stats_markdown(
+    stats: &StatsData,
+    summaries: &[DailySummary],
+    models: &HashMap<String, ModelUsage>,
+) -> String {
+    let mut out = String::new();
+    let peak = stats
+        .peak_day
+        .map(|(date, tokens)| format!("{} ({} tokens)", date, format_number(tokens)))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    out.push_str("## Usage stats\n\n");
+    out.push_str("| Metric | Value |\n|---|---:|\n");
+    let _ = writeln!(
+        out,
+        "| Total tokens | {} |",
+        format_number(stats.total_tokens)
+    );
+    let _ = writeln!(
+        out,
+        "| Daily average | {} tokens |",
+        format_number(stats.daily_avg_tokens)
+    );
+    let _ = writeln!(out, "| Peak day | {} |", peak);
+    let _ = writeln!(out, "| Total cost | ${:.2} |", stats.total_cost);
+    let _ = writeln!(out, "| Daily avg cost | ${:.2} |", stats.daily_avg_cost);
+    let _ = writeln!(out, "| Active days | {} |", stats.active_days);
+    let _ = writeln!(
+        out,
+        "| Longest streak | {} days |",
+        longest_streak(summaries.iter().map(|s| s.date))
+    );
+
+    let mut ranked: Vec<(&String, &ModelUsage)> = models.iter().collect();
+    ranked.sort_by(|a, b| b.1.cost_usd.total_cmp(&a.1.cost_usd).then(a.0.cmp(b.0)));
+    if !ranked.is_empty() {
+        out.push_str("\n### Top models\n\n");
+        out.push_str("| Model | Tokens | Cost | Share |\n|---|---:|---:|---:|\n");
+        for (name, usage) in ranked.into_iter().take(TOP_MODELS) {
+            let tokens = usage.total_tokens();
+            let share = if stats.total_cost > 0.0 {
+                usage.cost_usd / stats.total_cost * 100.0
+            } else {
+                0.0
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | ${:.2} | {:.1}% |",
+                display_name(name),
+                format_number(tokens),
+                usage.cost_usd,
+                share
+            );
+        }
+    }
+    out
+}
+
+/// Longest run of consecutive calendar days with usage
+fn longest_streak(dates: impl Iterator<Item = NaiveDate>) -> u32 {
+    let mut dates: Vec<NaiveDate> = dates.collect();
+    dates.sort_unstable();
+    dates.dedup();
+
+    let mut longest = 0;
+    let mut current = 0;
+    let mut prev: Option<NaiveDate> = None;
+    for date in dates {
+        current = match prev {
+            Some(p) if p.succ_opt() == Some(date) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        prev = Some(date);
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Aggregator;
+
+    fn make_summary(day: u32, model: &str, input: u64, output: u64, cost: f64) -> DailySummary {
+        DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            total_input_tokens: input,
+            total_output_tokens: output,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::from([(
+                model.to_string(),
+                ModelUsage {
+                    input_tokens: input,
+                    output_tokens: output,
+                    cost_usd: cost,
+                    count: 1,
+                    ..ModelUsage::default()
+                },
+            )]),
+            cost_estimated: false,
+        }
+    }
+
+    #[test]
+    fn test_stats_markdown_golden() {
+        let summaries = vec![
+            make_summary(2, "claude-sonnet-4-5", 12_000, 3_000, 1.50),
+            make_summary(3, "claude-sonnet-4-5", 40_000, 10_000, 4.25),
+            make_summary(4, "gpt-4.1", 8_000, 2_000, 0.75),
+            make_summary(9, "claude-opus-4-5", 20_000, 5_000, 3.50),
+        ];
+        let stats = StatsData::from_daily_summaries(&summaries);
+        let models = Aggregator::by_model_from_daily(&summaries);
+
+        let markdown = stats_markdown(&stats, &summaries, &models);
+        assert_eq!(
+            markdown,
+            include_str!("../../tests/fixtures/markdown/stats.md")
+        );
+    }
+
+    #[test]
+    fn test_stats_markdown_empty_has_no_models_table() {
+        let stats = StatsData::from_daily_summaries(&[]);
+        let markdown = stats_markdown(&stats, &[], &HashMap::new());
+        assert!(markdown.contains("| Peak day | N/A |"));
+        assert!(markdown.contains("| Longest streak | 0 days |"));
+        assert!(!markdown.contains("Top models"));
+    }
+
+    #[test]
+    fn test_longest_streak() {
+        let d = |day| NaiveDate::from_ymd_opt(2026, 2, day).unwrap();
+        assert_eq!(longest_streak([].into_iter()), 0);
+        assert_eq!(longest_streak([d(5)].into_iter()), 1);
+        // Unsorted with a duplicate; Feb 27-28 then Mar 1 spans a month boundary
+        let dates = [
+            d(28),
+            d(3),
+            d(27),
+            d(4),
+            d(3),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+        ];
+        assert_eq!(longest_streak(dates.into_iter()), 3);
+    }
+}
@@ -1,14 +1,53 @@
 //! CLI command handling
 
 pub mod annotate;
+pub mod branch;
+pub mod budget;
+pub mod cache;
+mod completions;
+pub mod config;
+mod diff;
+mod doctor;
+mod export;
+mod forecast;
+mod markdown;
+pub mod model;
+pub mod note;
+pub mod project;
+pub mod session;
+mod top;
 
-use clap::{Parser, Subcommand};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand};
 
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, NaiveDate, Timelike, Utc};
+use serde::Serialize;
+
+use self::diff::DiffPeriod;
+use self::export::ExportFormat;
+use crate::config::Config;
+use crate::parsers::{CLIParser, ParserRegistry};
+use crate::services::data_dir;
+use crate::services::data_loader::LoadResult;
+use crate::services::day_notes::DayNotesService;
+use crate::services::run_state::{filter_since, RunStateService};
+use crate::services::watcher::FileWatcher;
 use crate::services::{Aggregator, DataLoaderService};
 use crate::tui::widgets::daily::DailyViewMode;
 use crate::tui::widgets::tabs::Tab;
 use crate::tui::TuiConfig;
-use crate::types::{DailySummary, Result, StatsData, ToktrackError};
+use crate::types::{
+    DailySummary, DetailGranularity, ModelUsage, Result, SessionHighlights, SessionInfo,
+    SourceUsage, StatsData, ToktrackError, TotalSummary,
+};
 
 /// Ultra-fast AI CLI token usage tracker
 #[derive(Parser)]
@@ -17,6 +56,89 @@ use crate::types::{DailySummary, Result, StatsData, ToktrackError};
 pub struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Collapse models below this percent of total cost into an "other" row (TUI only, 0 = show all)
+    #[arg(long, global = true, default_value_t = 0.0, value_parser = parse_percent)]
+    other_threshold: f64,
+
+    /// Width of the daily table sparkline in characters (TUI only, default: fits the Usage column)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(1..=60))]
+    sparkline_width: Option<u16>,
+
+    /// Scale weekly sparklines to the last N weeks so an old outlier week doesn't flatten recent ones (TUI only)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    weekly_scale_weeks: Option<u16>,
+
+    /// Decimal places for costs in the model breakdown popup (default: 2)
+    #[arg(long, global = true, value_name = "DIGITS", value_parser = clap::value_parser!(u8).range(0..=6))]
+    round_cost: Option<u8>,
+
+    /// Minimum terminal width for the dashboard; narrower panes show a resize message (TUI only, default: 69)
+    #[arg(long, global = true, value_name = "COLS")]
+    min_cols: Option<u16>,
+
+    /// Minimum terminal height for the dashboard; shorter panes show a resize message (TUI only, default: 10)
+    #[arg(long, global = true, value_name = "ROWS")]
+    min_rows: Option<u16>,
+
+    /// Include synthetic entries (e.g. Claude `<synthetic>` responses) that are skipped by default
+    #[arg(long, global = true)]
+    include_synthetic: bool,
+
+    /// Parse with serde_json instead of simd-json (slower, for debugging parse failures)
+    #[arg(long, global = true)]
+    safe_parse: bool,
+
+    /// Cache parsed entries per file in raw/ under the cache directory so unchanged files are not re-parsed
+    #[arg(long, global = true)]
+    raw_cache: bool,
+
+    /// Leave out today's in-progress usage so totals cover completed days only
+    #[arg(long, global = true)]
+    exclude_today: bool,
+
+    /// Prune cached days older than N days on every load. Opt-in: usage of pruned
+    /// days is lost for good once the CLI's own logs are gone
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    max_age_days: Option<u32>,
+
+    /// Keep caches, pricing and state in toktrack-data/ beside the executable instead of ~/.toktrack
+    #[arg(long, global = true)]
+    portable: bool,
+
+    /// List sources with zero usage (e.g. an empty data dir) instead of hiding them
+    #[arg(long, global = true)]
+    show_empty_sources: bool,
+
+    /// Add other machines' `daily --json` exports (*.json) found in DIR to the totals
+    #[arg(long, global = true, value_name = "DIR")]
+    merge_dir: Option<PathBuf>,
+
+    /// Print --json output on a single line
+    #[arg(long, global = true)]
+    compact: bool,
+
+    /// Print --json arrays as JSON Lines (one element per line); objects print compact
+    #[arg(long, global = true, conflicts_with = "compact")]
+    jsonl: bool,
+}
+
+/// Parse a percentage argument in the range 0..=100
+fn parse_percent(s: &str) -> std::result::Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("'{s}' is not a number"))?;
+    if (0.0..=100.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("{value} is not between 0 and 100"))
+    }
+}
+
+/// Value parser for a `FromStr` enum that also lists `values` in `--help` and completions
+fn one_of<T>(values: &'static [&'static str]) -> impl TypedValueParser<Value = T>
+where
+    T: std::str::FromStr<Err = String> + Clone + Send + Sync + 'static,
+{
+    PossibleValuesParser::new(values).try_map(|s| s.parse::<T>())
 }
 
 #[derive(Subcommand)]
@@ -29,13 +151,89 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        /// Only include days since the previous --since-last-run report
+        #[arg(long, requires = "json")]
+        since_last_run: bool,
+
+        /// Only the N highest-cost days, sorted by cost descending
+        #[arg(long, value_name = "N", requires = "json")]
+        top: Option<usize>,
+
+        /// Keep running and re-emit JSON (one NDJSON line) every --interval seconds
+        /// while usage files change, until Ctrl-C
+        #[arg(long, requires = "json", conflicts_with = "since_last_run")]
+        watch: bool,
+
+        /// Seconds between --watch runs
+        #[arg(long, value_name = "SECS", default_value_t = DEFAULT_WATCH_INTERVAL_SECS, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Pivot JSON into one daily series per key (model)
+        #[arg(long, value_name = "KEY", requires = "json", conflicts_with_all = ["top", "watch"])]
+        group_by: Option<DailyGroupBy>,
+
+        /// Break one day (--date, default today) down by local hour
+        #[arg(
+            long,
+            requires = "json",
+            conflicts_with_all = ["since_last_run", "top", "watch", "group_by"]
+        )]
+        hourly: bool,
+
+        /// Add a centered N-day moving average of cost to each day
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "json",
+            value_parser = clap::value_parser!(u16).range(1..),
+            conflicts_with_all = ["since_last_run", "top", "watch", "group_by", "hourly"]
+        )]
+        moving_average: Option<u16>,
+
+        /// Add the running total of cost up to each day
+        #[arg(
+            long,
+            requires = "json",
+            conflicts_with_all = ["since_last_run", "top", "watch", "group_by", "hourly", "moving_average"]
+        )]
+        cumulative: bool,
+
+        /// Day for --hourly (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE", requires = "hourly")]
+        date: Option<String>,
+
+        /// Only these sources, comma-separated (e.g. claude-code,codex)
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        source: Option<Vec<String>>,
     },
 
-    /// Show usage statistics (TUI stats tab, or JSON with --json)
+    /// Show usage statistics (TUI stats tab, JSON with --json, or Markdown with --markdown)
+    #[command(group(ArgGroup::new("stats_output").args(["json", "markdown"])))]
     Stats {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        /// Output as a Markdown section (totals, streak, top models)
+        #[arg(long, conflicts_with = "json")]
+        markdown: bool,
+
+        /// Usage per weekday, Monday first (JSON only)
+        #[arg(long, requires = "json")]
+        by_day_of_week: bool,
+
+        /// Re-run every --interval seconds until Ctrl-C
+        #[arg(long, requires = "stats_output")]
+        watch: bool,
+
+        /// Seconds between --watch runs
+        #[arg(long, value_name = "SECS", default_value_t = DEFAULT_WATCH_INTERVAL_SECS, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Only these sources, comma-separated (e.g. claude-code,codex)
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        source: Option<Vec<String>>,
     },
 
     /// Show weekly usage (TUI daily tab weekly mode, or JSON with --json)
@@ -43,6 +241,18 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        /// Re-run every --interval seconds until Ctrl-C
+        #[arg(long, requires = "json")]
+        watch: bool,
+
+        /// Seconds between --watch runs
+        #[arg(long, value_name = "SECS", default_value_t = DEFAULT_WATCH_INTERVAL_SECS, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Only these sources, comma-separated (e.g. claude-code,codex)
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        source: Option<Vec<String>>,
     },
 
     /// Show monthly usage (TUI daily tab monthly mode, or JSON with --json)
@@ -50,186 +260,2464 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        /// Re-run every --interval seconds until Ctrl-C
+        #[arg(long, requires = "json")]
+        watch: bool,
+
+        /// Seconds between --watch runs
+        #[arg(long, value_name = "SECS", default_value_t = DEFAULT_WATCH_INTERVAL_SECS, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Only these sources, comma-separated (e.g. claude-code,codex)
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        source: Option<Vec<String>>,
+    },
+
+    /// Show quarterly usage (TUI daily tab quarterly mode, or JSON with --json)
+    Quarterly {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only these sources, comma-separated (e.g. claude-code,codex)
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        source: Option<Vec<String>>,
+    },
+
+    /// Show Claude Code sessions (TUI sessions tab, or JSON with --json)
+    Sessions {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Round session detail timestamps to exact|minute|hour, merging same-model requests (TUI only)
+        #[arg(long, default_value = "exact", conflicts_with = "json")]
+        granularity: DetailGranularity,
+    },
+
+    /// Snapshot of totals, models, sources, stats and the last 30 days as one JSON document
+    Report {
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
+    },
+
+    /// Claude Code session cost and tokens per git branch, costliest first
+    Branches {
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
+    },
+
+    /// Usage per model, costliest first
+    Models {
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
+
+        /// Group models under each source CLI
+        #[arg(long)]
+        by_source: bool,
+    },
+
+    /// Usage per provider (Anthropic, OpenAI, ...) across source CLIs
+    Providers {
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
+    },
+
+    /// Registered parsers with their data directory and whether it exists
+    Parsers {
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
     },
 
     /// Annotate session metadata (issue, tags, notes)
     Annotate(annotate::AnnotateArgs),
+
+    /// Annotate days with notes (shown in the daily table and JSON)
+    Note(note::NoteArgs),
+
+    /// Cache maintenance (rebuild, clear, inspect, prune)
+    Cache(cache::CacheArgs),
+
+    /// Daily, weekly and monthly spending limits (~/.toktrack/budget.toml)
+    Budget(budget::BudgetArgs),
+
+    /// Check data directories, caches, pricing, disk space and the install
+    Doctor {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Claude Code usage per project directory
+    Project(project::ProjectArgs),
+
+    /// Claude Code usage per git branch
+    Branch(branch::BranchArgs),
+
+    /// Browse a source's sessions and export one session's requests
+    Session(session::SessionArgs),
+
+    /// Settings from ~/.toktrack/config.toml
+    Config(config::ConfigArgs),
+
+    /// Model pricing overrides
+    Model(model::ModelArgs),
+
+    /// Export per-day, per-source, per-model usage as JSON, CSV or NDJSON
+    Export {
+        /// Output format
+        #[arg(long, default_value = "json", value_parser = one_of::<ExportFormat>(&["json", "csv", "ndjson"]))]
+        format: ExportFormat,
+
+        /// Only days on or after this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        from: Option<String>,
+
+        /// Only days on or before this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        to: Option<String>,
+
+        /// Write to this file instead of stdout
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Only these sources, comma-separated (e.g. claude-code,codex)
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        source: Option<Vec<String>>,
+    },
+
+    /// Compare this day, week or month with the previous one
+    Diff {
+        /// Period length: day, week or month
+        #[arg(value_parser = one_of::<DiffPeriod>(&["day", "week", "month"]))]
+        period: DiffPeriod,
+
+        /// Output as JSON, with per-source and per-model breakdowns
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Project spend over the next days from the recent daily average
+    Forecast {
+        /// Days to project
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+
+        /// Recent days with usage to average over
+        #[arg(long, default_value_t = 14)]
+        lookback_days: u32,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Claude Code sessions ranked by cost or tokens
+    Top {
+        /// Number of sessions to show
+        #[arg(short, long, default_value_t = 10)]
+        n: usize,
+
+        /// Rank by cost or tokens
+        #[arg(long, default_value = "cost", value_parser = one_of::<top::TopSortBy>(&["cost", "tokens"]))]
+        by: top::TopSortBy,
+
+        /// Source CLI (only claude-code has sessions)
+        #[arg(long, value_parser = one_of::<session::SessionSource>(&["claude-code"]))]
+        source: Option<session::SessionSource>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print a shell completion script (bash, zsh, fish, powershell or elvish) to stdout
+    Completions { shell: clap_complete::Shell },
 }
 
 impl Cli {
+    /// Whether this invocation opens the TUI rather than printing output
+    fn opens_tui(&self) -> bool {
+        match &self.command {
+            None | Some(Commands::Tui) => true,
+            Some(
+                Commands::Daily { json, .. }
+                | Commands::Weekly { json, .. }
+                | Commands::Monthly { json, .. }
+                | Commands::Quarterly { json, .. }
+                | Commands::Sessions { json, .. },
+            ) => !json,
+            Some(Commands::Stats { json, markdown, .. }) => !json && !markdown,
+            Some(_) => false,
+        }
+    }
+
+    /// First TUI-only flag set for a command that doesn't open the TUI
+    fn misplaced_tui_flag(&self) -> Option<&'static str> {
+        if self.opens_tui() {
+            return None;
+        }
+        [
+            (self.other_threshold > 0.0, "--other-threshold"),
+            (self.sparkline_width.is_some(), "--sparkline-width"),
+            (self.weekly_scale_weeks.is_some(), "--weekly-scale-weeks"),
+            (self.min_cols.is_some(), "--min-cols"),
+            (self.min_rows.is_some(), "--min-rows"),
+        ]
+        .into_iter()
+        .find_map(|(set, flag)| set.then_some(flag))
+    }
+
     pub fn run(self) -> anyhow::Result<()> {
+        if let Some(flag) = self.misplaced_tui_flag() {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!("{} only applies to the TUI", flag),
+                )
+                .exit();
+        }
+        if self.portable {
+            data_dir::set_data_dir(data_dir::portable_dir()?);
+        }
+        let config = Config::load_or_default();
+        let base = TuiConfig {
+            config: config.clone(),
+            other_threshold: self.other_threshold,
+            sparkline_width: self.sparkline_width.map(usize::from),
+            weekly_scale_weeks: self.weekly_scale_weeks.map(usize::from),
+            min_cols: self.min_cols,
+            min_rows: self.min_rows,
+            include_synthetic: self.include_synthetic,
+            safe_parse: self.safe_parse,
+            raw_cache: self.raw_cache,
+            exclude_today: self.exclude_today,
+            max_age_days: self.max_age_days,
+            merge_dir: self.merge_dir.clone(),
+            show_empty_sources: self.show_empty_sources,
+            cost_precision: self.round_cost.map(usize::from),
+            ..TuiConfig::default()
+        };
+        let loader = || {
+            DataLoaderService::from_config(config.clone())
+                .with_include_synthetic(self.include_synthetic)
+                .with_safe_parse(self.safe_parse)
+                .with_raw_cache(self.raw_cache)
+                .with_exclude_today(self.exclude_today)
+                .with_max_age_days(self.max_age_days)
+                .with_merge_dir(self.merge_dir.clone())
+                .with_show_empty_sources(self.show_empty_sources)
+        };
+        let format = JsonFormat::from_flags(self.compact, self.jsonl);
         match self.command {
-            None | Some(Commands::Tui) => crate::tui::run(TuiConfig::default()),
-            Some(Commands::Daily { json }) => {
-                if json {
-                    Ok(run_daily_json()?)
+            None | Some(Commands::Tui) => crate::tui::run(base),
+            Some(Commands::Daily {
+                json,
+                since_last_run,
+                top,
+                watch,
+                interval,
+                group_by,
+                hourly,
+                date,
+                moving_average,
+                cumulative,
+                source,
+            }) => {
+                let loader = || loader().with_sources(source.clone());
+                if json && cumulative {
+                    Ok(run_daily_cumulative_json(loader(), format)?)
+                } else if let (true, Some(window)) = (json, moving_average) {
+                    Ok(run_daily_moving_average_json(
+                        loader(),
+                        usize::from(window),
+                        format,
+                    )?)
+                } else if json && hourly {
+                    Ok(run_daily_hourly_json(loader(), date.as_deref(), format)?)
+                } else if json && watch {
+                    Ok(run_watch(&config, interval, WatchOutput::Lines, || {
+                        daily_watch_line(loader(), top)
+                    })?)
+                } else if json {
+                    Ok(run_daily_json(
+                        loader(),
+                        since_last_run,
+                        top,
+                        group_by,
+                        format,
+                    )?)
                 } else {
                     crate::tui::run(TuiConfig {
                         initial_view_mode: DailyViewMode::Daily,
                         initial_tab: None,
+                        sources: source,
+                        ..base
                     })
                 }
             }
-            Some(Commands::Stats { json }) => {
-                if json {
-                    Ok(run_stats_json()?)
+            Some(Commands::Stats {
+                json,
+                markdown,
+                by_day_of_week,
+                watch,
+                interval,
+                source,
+            }) => {
+                let loader = || loader().with_sources(source.clone());
+                if watch {
+                    let output = if json {
+                        WatchOutput::Json
+                    } else {
+                        WatchOutput::Text
+                    };
+                    Ok(run_watch(&config, interval, output, || {
+                        if by_day_of_week {
+                            day_of_week_json(loader(), format)
+                        } else if json {
+                            stats_json(loader(), format)
+                        } else {
+                            stats_markdown(loader())
+                        }
+                    })?)
+                } else if by_day_of_week {
+                    Ok(run_day_of_week_json(loader(), format)?)
+                } else if json {
+                    Ok(run_stats_json(loader(), format)?)
+                } else if markdown {
+                    Ok(run_stats_markdown(loader())?)
                 } else {
                     crate::tui::run(TuiConfig {
                         initial_view_mode: DailyViewMode::Daily,
                         initial_tab: Some(Tab::Stats),
+                        sources: source,
+                        ..base
                     })
                 }
             }
-            Some(Commands::Weekly { json }) => {
-                if json {
-                    Ok(run_weekly_json()?)
+            Some(Commands::Weekly {
+                json,
+                watch,
+                interval,
+                source,
+            }) => {
+                let loader = || loader().with_sources(source.clone());
+                if watch {
+                    Ok(run_watch(&config, interval, WatchOutput::Json, || {
+                        weekly_json(loader(), format)
+                    })?)
+                } else if json {
+                    Ok(run_weekly_json(loader(), format)?)
                 } else {
                     crate::tui::run(TuiConfig {
                         initial_view_mode: DailyViewMode::Weekly,
                         initial_tab: None,
+                        sources: source,
+                        ..base
                     })
                 }
             }
-            Some(Commands::Monthly { json }) => {
-                if json {
-                    Ok(run_monthly_json()?)
+            Some(Commands::Monthly {
+                json,
+                watch,
+                interval,
+                source,
+            }) => {
+                let loader = || loader().with_sources(source.clone());
+                if watch {
+                    Ok(run_watch(&config, interval, WatchOutput::Json, || {
+                        monthly_json(loader(), format)
+                    })?)
+                } else if json {
+                    Ok(run_monthly_json(loader(), format)?)
                 } else {
                     crate::tui::run(TuiConfig {
                         initial_view_mode: DailyViewMode::Monthly,
                         initial_tab: None,
+                        sources: source,
+                        ..base
+                    })
+                }
+            }
+            Some(Commands::Quarterly { json, source }) => {
+                let loader = || loader().with_sources(source.clone());
+                if json {
+                    Ok(run_quarterly_json(loader(), format)?)
+                } else {
+                    crate::tui::run(TuiConfig {
+                        initial_view_mode: DailyViewMode::Quarterly,
+                        initial_tab: None,
+                        sources: source,
+                        ..base
+                    })
+                }
+            }
+            Some(Commands::Sessions { json, granularity }) => {
+                if json {
+                    Ok(run_sessions_json(loader(), format)?)
+                } else {
+                    crate::tui::run(TuiConfig {
+                        initial_view_mode: DailyViewMode::Daily,
+                        initial_tab: Some(Tab::Sessions),
+                        detail_granularity: granularity,
+                        ..base
                     })
                 }
             }
+            Some(Commands::Report { .. }) => Ok(run_report_json(loader(), format)?),
+            Some(Commands::Branches { .. }) => Ok(run_branches_json(loader(), format)?),
+            Some(Commands::Models { by_source, .. }) => {
+                Ok(run_models_json(loader(), by_source, format)?)
+            }
+            Some(Commands::Providers { .. }) => Ok(run_providers_json(loader(), format)?),
+            Some(Commands::Parsers { .. }) => Ok(run_parsers_json(&config, format)?),
             Some(Commands::Annotate(args)) => Ok(args.run()?),
+            Some(Commands::Note(args)) => Ok(args.run()?),
+            Some(Commands::Cache(args)) => Ok(args.run(loader())?),
+            Some(Commands::Budget(args)) => {
+                if !args.run(loader(), format)? {
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
+            Some(Commands::Doctor { json }) => {
+                if !doctor::run_doctor(&config, json, format)? {
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
+            Some(Commands::Project(args)) => Ok(args.run(loader(), format)?),
+            Some(Commands::Branch(args)) => Ok(args.run(loader(), format)?),
+            Some(Commands::Session(args)) => Ok(args.run(loader(), format)?),
+            Some(Commands::Config(args)) => Ok(args.run(format)?),
+            Some(Commands::Model(args)) => Ok(args.run()?),
+            Some(Commands::Diff { period, json }) => {
+                Ok(diff::run_diff(loader(), period, json, format)?)
+            }
+            Some(Commands::Forecast {
+                days,
+                lookback_days,
+                json,
+            }) => Ok(forecast::run_forecast(
+                loader(),
+                days,
+                lookback_days,
+                json,
+                format,
+            )?),
+            Some(Commands::Top {
+                n,
+                by,
+                source,
+                json,
+            }) => {
+                let sources = source.map(|s| vec![s.parser_name().to_string()]);
+                Ok(top::run_top(
+                    loader().with_sources(sources),
+                    n,
+                    by,
+                    json,
+                    format,
+                )?)
+            }
+            Some(Commands::Completions { shell }) => Ok(completions::generate(
+                shell,
+                Cli::command(),
+                &mut std::io::stdout(),
+            )?),
+            Some(Commands::Export {
+                format: export_format,
+                from,
+                to,
+                output,
+                source,
+            }) => Ok(run_export(
+                loader().with_sources(source),
+                export_format,
+                from.as_deref(),
+                to.as_deref(),
+                output,
+                format,
+            )?),
         }
     }
 }
 
 /// Load and process usage data from all CLI parsers.
 /// Uses cache-first strategy via DataLoaderService.
-fn load_data() -> Result<Vec<DailySummary>> {
-    let result = DataLoaderService::new().load()?;
-    Ok(result.summaries)
+fn load_data(loader: DataLoaderService) -> Result<Vec<DailySummary>> {
+    Ok(load_result(loader)?.summaries)
 }
 
-/// Output daily summaries as JSON
-fn run_daily_json() -> Result<()> {
-    let mut summaries = load_data()?;
-    summaries.sort_by(|a, b| b.date.cmp(&a.date));
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&summaries)
-            .map_err(|e| ToktrackError::Parse(e.to_string()))?
-    );
+/// Load everything (summaries plus per-source usage), reporting parse warnings
+fn load_result(loader: DataLoaderService) -> Result<LoadResult> {
+    let result = loader.load()?;
+    if result.parse_stats.truncated_lines > 0 {
+        eprintln!(
+            "[toktrack] Note: skipped {} partially written line(s); totals will include them once the CLI finishes writing",
+            result.parse_stats.truncated_lines
+        );
+    }
+    if result.parse_stats.invalid_costs > 0 {
+        eprintln!(
+            "[toktrack] Warning: {} entries reported an invalid cost (NaN, infinite or negative); counted as $0",
+            result.parse_stats.invalid_costs
+        );
+    }
+    Ok(result)
+}
+
+/// Output daily summaries as JSON.
+/// With `since_last_run`, only days on/after the previous run are included
+/// and the stored run time is updated. With `top`, only the N costliest days
+/// are output, most expensive first. With `group_by`, days are pivoted into
+/// one series per key instead.
+fn run_daily_json(
+    loader: DataLoaderService,
+    since_last_run: bool,
+    top: Option<usize>,
+    group_by: Option<DailyGroupBy>,
+    format: JsonFormat,
+) -> Result<()> {
+    let mut summaries = load_data(loader)?;
+    let state_service = if since_last_run {
+        let service = RunStateService::new()?;
+        summaries = filter_since(summaries, service.load().last_run);
+        Some(service)
+    } else {
+        None
+    };
+    let output = match group_by {
+        Some(DailyGroupBy::Model) => format.render(&by_model_series(&summaries))?,
+        None => {
+            let summaries = order_daily(summaries, top);
+            let notes = DayNotesService::new()
+                .map(|service| service.load())
+                .unwrap_or_default();
+            format.render(&with_notes(&summaries, &notes))?
+        }
+    };
+    if let Some(service) = state_service {
+        record_run(&service, Utc::now())?;
+    }
+    println!("{}", output);
     Ok(())
 }
 
-/// Output weekly summaries as JSON
-fn run_weekly_json() -> Result<()> {
-    let summaries = load_data()?;
-    let mut weekly = Aggregator::weekly(&summaries);
-    weekly.sort_by(|a, b| b.date.cmp(&a.date));
+/// Output each day's cost with its centered `window`-day moving average,
+/// oldest first, as `{date, daily_cost, moving_avg_<window>d}`
+fn run_daily_moving_average_json(
+    loader: DataLoaderService,
+    window: usize,
+    format: JsonFormat,
+) -> Result<()> {
+    let summaries = load_data(loader)?;
     println!(
         "{}",
-        serde_json::to_string_pretty(&weekly).map_err(|e| ToktrackError::Parse(e.to_string()))?
+        format.render(&moving_average_rows(&summaries, window))?
     );
     Ok(())
 }
 
-/// Output monthly summaries as JSON
-fn run_monthly_json() -> Result<()> {
-    let summaries = load_data()?;
-    let mut monthly = Aggregator::monthly(&summaries);
-    monthly.sort_by(|a, b| b.date.cmp(&a.date));
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&monthly).map_err(|e| ToktrackError::Parse(e.to_string()))?
-    );
+fn moving_average_rows(summaries: &[DailySummary], window: usize) -> Vec<serde_json::Value> {
+    let key = format!("moving_avg_{}d", window);
+    let mut costs: Vec<(NaiveDate, f64)> = summaries
+        .iter()
+        .map(|s| (s.date, s.total_cost_usd))
+        .collect();
+    costs.sort_by_key(|(date, _)| *date);
+    costs
+        .into_iter()
+        .zip(Aggregator::moving_average(summaries, window))
+        .map(|((date, cost), (_, avg))| {
+            let mut row = serde_json::Map::new();
+            row.insert("date".into(), serde_json::json!(date));
+            row.insert("daily_cost".into(), serde_json::json!(cost));
+            row.insert(key.clone(), serde_json::json!(avg));
+            serde_json::Value::Object(row)
+        })
+        .collect()
+}
+
+/// Output each day's cost with the running total so far, oldest first,
+/// as `{date, daily_cost, cumulative_cost}`
+fn run_daily_cumulative_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    let summaries = load_data(loader)?;
+    println!("{}", format.render(&cumulative_rows(&summaries))?);
     Ok(())
 }
 
-/// Output stats as JSON
-fn run_stats_json() -> Result<()> {
-    let summaries = load_data()?;
-    let stats = StatsData::from_daily_summaries(&summaries);
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&stats).map_err(|e| ToktrackError::Parse(e.to_string()))?
-    );
+fn cumulative_rows(summaries: &[DailySummary]) -> Vec<serde_json::Value> {
+    let mut days = summaries.to_vec();
+    days.sort_by_key(|s| s.date);
+    days.iter()
+        .zip(Aggregator::cumulative(&days))
+        .map(|(day, (date, total))| {
+            serde_json::json!({
+                "date": date,
+                "daily_cost": day.total_cost_usd,
+                "cumulative_cost": total,
+            })
+        })
+        .collect()
+}
+
+/// Output one day's usage per local hour as JSON. For today, hours after the
+/// current one are left out.
+fn run_daily_hourly_json(
+    loader: DataLoaderService,
+    date: Option<&str>,
+    format: JsonFormat,
+) -> Result<()> {
+    let now = Local::now();
+    let date = match date {
+        Some(date) => export::parse_date(date)?,
+        None => now.date_naive(),
+    };
+    let entries = loader.load_entries_on(date)?;
+    let mut hourly = Aggregator::hourly(&entries);
+    if date == now.date_naive() {
+        hourly.truncate(now.hour() as usize + 1);
+    }
+    println!("{}", format.render(&hourly)?);
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Layout of `--json` output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// Indented, multi-line (default)
+    #[default]
+    Pretty,
+    /// Single line (`--compact`)
+    Compact,
+    /// One compact array element per line (`--jsonl`)
+    Lines,
+}
 
-    #[test]
-    fn test_cli_parse_no_args() {
-        let cli = Cli::try_parse_from(["toktrack"]).unwrap();
-        assert!(cli.command.is_none());
+impl JsonFormat {
+    fn from_flags(compact: bool, jsonl: bool) -> Self {
+        if jsonl {
+            Self::Lines
+        } else if compact {
+            Self::Compact
+        } else {
+            Self::Pretty
+        }
     }
 
-    #[test]
-    fn test_cli_parse_daily() {
-        let cli = Cli::try_parse_from(["toktrack", "daily"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Daily { json: false })));
+    /// Serialize `value` in this layout. `Lines` only splits top-level arrays;
+    /// other values are printed compact.
+    fn render<T: Serialize + ?Sized>(self, value: &T) -> Result<String> {
+        let to_parse_error = |e: serde_json::Error| ToktrackError::Parse(e.to_string());
+        match self {
+            Self::Pretty => serde_json::to_string_pretty(value).map_err(to_parse_error),
+            Self::Compact => serde_json::to_string(value).map_err(to_parse_error),
+            Self::Lines => match serde_json::to_value(value).map_err(to_parse_error)? {
+                serde_json::Value::Array(items) => Ok(items
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")),
+                other => Ok(other.to_string()),
+            },
+        }
     }
+}
 
-    #[test]
-    fn test_cli_parse_daily_json() {
-        let cli = Cli::try_parse_from(["toktrack", "daily", "--json"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Daily { json: true })));
+/// Most recent day first, or only the `top` N costliest days
+fn order_daily(mut summaries: Vec<DailySummary>, top: Option<usize>) -> Vec<DailySummary> {
+    match top {
+        Some(n) => top_by_cost(summaries, n),
+        None => {
+            summaries.sort_by(|a, b| b.date.cmp(&a.date));
+            summaries
+        }
     }
+}
 
-    #[test]
-    fn test_cli_parse_stats() {
-        let cli = Cli::try_parse_from(["toktrack", "stats"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Stats { json: false })));
+/// Daily JSON (with notes) as a value, for watch-mode emissions
+fn daily_json_value(loader: DataLoaderService, top: Option<usize>) -> Result<serde_json::Value> {
+    let summaries = order_daily(load_data(loader)?, top);
+    let notes = DayNotesService::new()
+        .map(|service| service.load())
+        .unwrap_or_default();
+    serde_json::to_value(with_notes(&summaries, &notes))
+        .map_err(|e| ToktrackError::Parse(e.to_string()))
+}
+
+/// One `--watch` NDJSON line: `{"emitted_at", "daily"}`
+fn daily_watch_line(loader: DataLoaderService, top: Option<usize>) -> Result<String> {
+    let line = serde_json::json!({
+        "emitted_at": Utc::now().to_rfc3339(),
+        "daily": daily_json_value(loader, top)?,
+    });
+    Ok(line.to_string())
+}
+
+/// Default seconds between `--watch` runs
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 60;
+
+/// How often a sleeping `WatchLoop` checks for Ctrl-C
+const WATCH_STOP_POLL: Duration = Duration::from_millis(100);
+
+/// How `WatchLoop` separates consecutive outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchOutput {
+    /// JSON documents, separated by an empty line
+    Json,
+    /// Text, separated by a `---` line
+    Text,
+    /// One NDJSON line per run, nothing in between
+    Lines,
+}
+
+impl WatchOutput {
+    fn separator(self) -> Option<&'static str> {
+        match self {
+            Self::Json => Some(""),
+            Self::Text => Some("---"),
+            Self::Lines => None,
+        }
     }
+}
 
-    #[test]
-    fn test_cli_parse_stats_json() {
-        let cli = Cli::try_parse_from(["toktrack", "stats", "--json"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Stats { json: true })));
+/// `--watch`: run a query now and every `interval` after, until Ctrl-C.
+/// Later runs are skipped while no parser's files changed since the last
+/// successful run. Runs reload through the cache warm path, which reads only
+/// the lines appended to recent files.
+struct WatchLoop {
+    watcher: FileWatcher,
+    interval: Duration,
+    output: WatchOutput,
+    stop: Arc<AtomicBool>,
+}
+
+impl WatchLoop {
+    fn new(parsers: Vec<Box<dyn CLIParser>>, interval_secs: u64, output: WatchOutput) -> Self {
+        Self {
+            watcher: FileWatcher::new(parsers),
+            interval: Duration::from_secs(interval_secs),
+            output,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
     }
 
-    #[test]
-    fn test_cli_parse_weekly() {
-        let cli = Cli::try_parse_from(["toktrack", "weekly"]).unwrap();
-        assert!(matches!(
-            cli.command,
-            Some(Commands::Weekly { json: false })
-        ));
+    /// Stop after the current run on Ctrl-C; a second Ctrl-C exits at once
+    fn stop_on_sigint(self) -> Result<Self> {
+        use signal_hook::consts::SIGINT;
+        signal_hook::flag::register_conditional_shutdown(SIGINT, 130, Arc::clone(&self.stop))?;
+        signal_hook::flag::register(SIGINT, Arc::clone(&self.stop))?;
+        Ok(self)
     }
 
-    #[test]
-    fn test_cli_parse_weekly_json() {
-        let cli = Cli::try_parse_from(["toktrack", "weekly", "--json"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Weekly { json: true })));
+    /// Write each output of `query` to `out` until stopped. Failed runs are
+    /// reported and retried next time; only successful runs are separated.
+    fn run(
+        &mut self,
+        out: &mut impl Write,
+        mut query: impl FnMut() -> Result<String>,
+    ) -> Result<()> {
+        let mut due = true;
+        let mut printed = false;
+        while !self.stop.load(Ordering::Relaxed) {
+            if due || self.watcher.changed() {
+                match query() {
+                    Ok(output) => {
+                        if let (true, Some(separator)) = (printed, self.output.separator()) {
+                            writeln!(out, "{}", separator)?;
+                        }
+                        writeln!(out, "{}", output)?;
+                        out.flush()?;
+                        printed = true;
+                        due = false;
+                    }
+                    Err(e) => {
+                        eprintln!("[toktrack] Warning: {}", e);
+                        due = true;
+                    }
+                }
+            }
+            self.sleep();
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_cli_parse_monthly() {
-        let cli = Cli::try_parse_from(["toktrack", "monthly"]).unwrap();
-        assert!(matches!(
-            cli.command,
-            Some(Commands::Monthly { json: false })
-        ));
+    /// Wait out `interval`, returning early once stopped
+    fn sleep(&self) {
+        let deadline = Instant::now() + self.interval;
+        while !self.stop.load(Ordering::Relaxed) {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return;
+            }
+            std::thread::sleep(left.min(WATCH_STOP_POLL));
+        }
     }
+}
 
-    #[test]
-    fn test_cli_parse_monthly_json() {
-        let cli = Cli::try_parse_from(["toktrack", "monthly", "--json"]).unwrap();
-        assert!(matches!(
-            cli.command,
-            Some(Commands::Monthly { json: true })
-        ));
+/// Run `query` in a `WatchLoop` over every parser's files, printing to stdout
+fn run_watch(
+    config: &Config,
+    interval_secs: u64,
+    output: WatchOutput,
+    query: impl FnMut() -> Result<String>,
+) -> Result<()> {
+    WatchLoop::new(
+        ParserRegistry::new(Some(config)).into_parsers(),
+        interval_secs,
+        output,
+    )
+    .stop_on_sigint()?
+    .run(&mut std::io::stdout(), query)
+}
+
+/// Daily summary with its day note (if any) for JSON output
+#[derive(Serialize)]
+struct NotedSummary<'a> {
+    #[serde(flatten)]
+    summary: &'a DailySummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<&'a str>,
+}
+
+/// Attach day notes to summaries by date
+fn with_notes<'a>(
+    summaries: &'a [DailySummary],
+    notes: &'a BTreeMap<NaiveDate, String>,
+) -> Vec<NotedSummary<'a>> {
+    summaries
+        .iter()
+        .map(|summary| NotedSummary {
+            summary,
+            note: notes.get(&summary.date).map(String::as_str),
+        })
+        .collect()
+}
+
+/// Key for `daily --group-by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DailyGroupBy {
+    Model,
+}
+
+impl std::str::FromStr for DailyGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "model" => Ok(Self::Model),
+            _ => Err(format!("'{s}' is not one of: model")),
+        }
     }
+}
 
-    #[test]
-    fn test_cli_parse_backup_removed() {
-        // backup subcommand should no longer exist
-        let result = Cli::try_parse_from(["toktrack", "backup"]);
-        assert!(result.is_err());
+/// One day of a model's series in `daily --group-by model`
+#[derive(Debug, PartialEq, Serialize)]
+struct SeriesPoint {
+    date: NaiveDate,
+    tokens: u64,
+    cost: f64,
+}
+
+/// Per-model daily series, oldest day first; days without the model are omitted
+fn by_model_series(summaries: &[DailySummary]) -> BTreeMap<String, Vec<SeriesPoint>> {
+    let mut days: Vec<&DailySummary> = summaries.iter().collect();
+    days.sort_by_key(|s| s.date);
+    let mut series: BTreeMap<String, Vec<SeriesPoint>> = BTreeMap::new();
+    for day in days {
+        for (model, usage) in &day.models {
+            series.entry(model.clone()).or_default().push(SeriesPoint {
+                date: day.date,
+                tokens: usage.total_tokens(),
+                cost: usage.cost_usd,
+            });
+        }
+    }
+    series
+}
+
+/// The `n` highest-cost days, most expensive first (ties: most recent first)
+fn top_by_cost(mut summaries: Vec<DailySummary>, n: usize) -> Vec<DailySummary> {
+    summaries.sort_by(|a, b| {
+        b.total_cost_usd
+            .total_cmp(&a.total_cost_usd)
+            .then_with(|| b.date.cmp(&a.date))
+    });
+    summaries.truncate(n);
+    summaries
+}
+
+/// Store `now` as the last successful run time
+fn record_run(service: &RunStateService, now: DateTime<Utc>) -> Result<()> {
+    let mut state = service.load();
+    state.last_run = Some(now);
+    service.save(&state)
+}
+
+/// Output weekly summaries as JSON
+fn run_weekly_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    println!("{}", weekly_json(loader, format)?);
+    Ok(())
+}
+
+/// Weekly summaries rendered as JSON
+fn weekly_json(loader: DataLoaderService, format: JsonFormat) -> Result<String> {
+    let week_start = loader.config().week_start;
+    let summaries = load_data(loader)?;
+    let mut weekly = Aggregator::weekly_starting(&summaries, week_start);
+    weekly.sort_by(|a, b| b.date.cmp(&a.date));
+    format.render(&weekly)
+}
+
+/// Output monthly summaries as JSON
+fn run_monthly_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    println!("{}", monthly_json(loader, format)?);
+    Ok(())
+}
+
+/// Monthly summaries rendered as JSON
+fn monthly_json(loader: DataLoaderService, format: JsonFormat) -> Result<String> {
+    let summaries = load_data(loader)?;
+    let mut monthly = Aggregator::monthly(&summaries);
+    monthly.sort_by(|a, b| b.date.cmp(&a.date));
+    format.render(&monthly)
+}
+
+/// Output quarterly summaries as JSON
+fn run_quarterly_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    let summaries = load_data(loader)?;
+    let mut quarterly = Aggregator::quarterly(&summaries);
+    quarterly.sort_by(|a, b| b.date.cmp(&a.date));
+    println!("{}", format.render(&quarterly)?);
+    Ok(())
+}
+
+/// Output stats as JSON
+fn run_stats_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    println!("{}", stats_json(loader, format)?);
+    Ok(())
+}
+
+/// Stats rendered as JSON
+fn stats_json(loader: DataLoaderService, format: JsonFormat) -> Result<String> {
+    let summaries = load_data(loader)?;
+    format.render(&StatsData::from_daily_summaries(&summaries))
+}
+
+/// Output usage per weekday as JSON
+fn run_day_of_week_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    println!("{}", day_of_week_json(loader, format)?);
+    Ok(())
+}
+
+/// Usage per weekday rendered as JSON
+fn day_of_week_json(loader: DataLoaderService, format: JsonFormat) -> Result<String> {
+    let summaries = load_data(loader)?;
+    format.render(&Aggregator::by_day_of_week(&summaries))
+}
+
+/// Output stats as Markdown
+fn run_stats_markdown(loader: DataLoaderService) -> Result<()> {
+    println!("{}", stats_markdown(loader)?);
+    Ok(())
+}
+
+/// Stats as a Markdown section, without the final newline
+fn stats_markdown(loader: DataLoaderService) -> Result<String> {
+    let summaries = load_data(loader)?;
+    let stats = StatsData::from_daily_summaries(&summaries);
+    let models = Aggregator::by_model_from_daily(&summaries);
+    let markdown = markdown::stats_markdown(&stats, &summaries, &models);
+    Ok(markdown.trim_end_matches('\n').to_string())
+}
+
+/// Write export rows to `output` (or stdout), filtered to `from..=to`
+fn run_export(
+    loader: DataLoaderService,
+    export_format: ExportFormat,
+    from: Option<&str>,
+    to: Option<&str>,
+    output: Option<PathBuf>,
+    json: JsonFormat,
+) -> Result<()> {
+    let from = from.map(export::parse_date).transpose()?;
+    let to = to.map(export::parse_date).transpose()?;
+    let result = load_result(loader)?;
+    let rows = export::export_rows(&result.source_summaries, from, to);
+    let rendered = export::render(&rows, export_format, json)?;
+    match output {
+        Some(path) => std::fs::write(path, rendered + "\n")?,
+        None => println!("{}", rendered),
+    }
+    Ok(())
+}
+
+/// Days of daily history included in `report --json`
+const REPORT_DAYS: i64 = 30;
+
+/// `report --json` output: one snapshot with a named section per view
+#[derive(Serialize)]
+struct Report {
+    totals: TotalSummary,
+    models: BTreeMap<String, ModelUsage>,
+    sources: Vec<SourceUsage>,
+    stats: StatsData,
+    /// Last `REPORT_DAYS` days up to `today`, most recent first
+    daily: Vec<DailySummary>,
+}
+
+impl Report {
+    fn new(summaries: Vec<DailySummary>, sources: Vec<SourceUsage>, today: NaiveDate) -> Self {
+        let cutoff = today - chrono::Duration::days(REPORT_DAYS - 1);
+        let mut daily: Vec<DailySummary> = summaries
+            .iter()
+            .filter(|s| s.date >= cutoff && s.date <= today)
+            .cloned()
+            .collect();
+        daily.sort_by(|a, b| b.date.cmp(&a.date));
+        Self {
+            totals: Aggregator::total_from_daily(&summaries),
+            models: Aggregator::by_model_from_daily(&summaries)
+                .into_iter()
+                .collect(),
+            sources,
+            stats: StatsData::from_daily_summaries(&summaries),
+            daily,
+        }
+    }
+}
+
+/// Output the all-in-one report as JSON
+fn run_report_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    let result = load_result(loader)?;
+    let report = Report::new(
+        result.summaries,
+        result.source_usage,
+        Local::now().date_naive(),
+    );
+    println!("{}", format.render(&report)?);
+    Ok(())
+}
+
+/// Sessions JSON output: all sessions plus the standout ones
+#[derive(Serialize)]
+struct SessionsReport<'a> {
+    sessions: &'a [SessionInfo],
+    highlights: SessionHighlights,
+}
+
+impl<'a> SessionsReport<'a> {
+    fn new(sessions: &'a [SessionInfo]) -> Self {
+        Self {
+            sessions,
+            highlights: SessionHighlights::from_sessions(sessions),
+        }
+    }
+}
+
+/// Output sessions as JSON (most recent first)
+fn run_sessions_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    let sessions = loader.load_sessions();
+    println!("{}", format.render(&SessionsReport::new(&sessions))?);
+    Ok(())
+}
+
+/// Output per-branch session usage as JSON
+fn run_branches_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    let sessions = loader.load_sessions();
+    println!("{}", format.render(&Aggregator::by_branch(&sessions))?);
+    Ok(())
+}
+
+/// Output usage per model, or per source and model with `by_source`, as JSON
+fn run_models_json(loader: DataLoaderService, by_source: bool, format: JsonFormat) -> Result<()> {
+    let result = load_result(loader)?;
+    if by_source {
+        let nested = result
+            .source_summaries
+            .iter()
+            .map(|(source, summaries)| (source.clone(), Aggregator::by_model_from_daily(summaries)))
+            .collect();
+        println!(
+            "{}",
+            format.render(&Aggregator::source_model_breakdown(nested))?
+        );
+    } else {
+        let models = Aggregator::models_by_cost(Aggregator::by_model_from_daily(&result.summaries));
+        println!("{}", format.render(&models)?);
+    }
+    Ok(())
+}
+
+fn run_providers_json(loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+    let result = load_result(loader)?;
+    println!("{}", format.render(&result.provider_usage)?);
+    Ok(())
+}
+
+/// One row of `toktrack parsers --json`
+#[derive(Debug, PartialEq, Serialize)]
+struct ParserInfo {
+    name: String,
+    data_dir: PathBuf,
+    active: bool,
+}
+
+fn parser_infos(registry: &ParserRegistry) -> Vec<ParserInfo> {
+    registry
+        .parsers()
+        .iter()
+        .map(|p| ParserInfo {
+            name: p.name().to_string(),
+            data_dir: p.data_dir().to_path_buf(),
+            active: p.data_dir_exists(),
+        })
+        .collect()
+}
+
+fn run_parsers_json(config: &Config, format: JsonFormat) -> Result<()> {
+    let registry = ParserRegistry::new(Some(config));
+    println!("{}", format.render(&parser_infos(&registry))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_parse_no_args() {
+        let cli = Cli::try_parse_from(["toktrack"]).unwrap();
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn test_cli_parse_daily() {
+        let cli = Cli::try_parse_from(["toktrack", "daily"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daily { json: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_daily_json() {
+        let cli = Cli::try_parse_from(["toktrack", "daily", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daily { json: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_stats() {
+        let cli = Cli::try_parse_from(["toktrack", "stats"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats { json: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_stats_by_day_of_week() {
+        let cli = Cli::try_parse_from(["toktrack", "stats", "--by-day-of-week", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats {
+                json: true,
+                by_day_of_week: true,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "stats", "--by-day-of-week"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_stats_json() {
+        let cli = Cli::try_parse_from(["toktrack", "stats", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats { json: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_stats_markdown() {
+        let cli = Cli::try_parse_from(["toktrack", "stats", "--markdown"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats {
+                json: false,
+                markdown: true,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "stats", "--json", "--markdown"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_weekly() {
+        let cli = Cli::try_parse_from(["toktrack", "weekly"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Weekly { json: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_weekly_json() {
+        let cli = Cli::try_parse_from(["toktrack", "weekly", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Weekly { json: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_monthly() {
+        let cli = Cli::try_parse_from(["toktrack", "monthly"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Monthly { json: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_monthly_json() {
+        let cli = Cli::try_parse_from(["toktrack", "monthly", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Monthly { json: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_quarterly_json() {
+        let cli = Cli::try_parse_from(["toktrack", "quarterly", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Quarterly { json: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_backup_removed() {
+        // backup subcommand should no longer exist
+        let result = Cli::try_parse_from(["toktrack", "backup"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_other_threshold() {
+        let cli = Cli::try_parse_from(["toktrack", "daily", "--other-threshold", "1.5"]).unwrap();
+        assert!((cli.other_threshold - 1.5).abs() < f64::EPSILON);
+
+        let cli = Cli::try_parse_from(["toktrack"]).unwrap();
+        assert_eq!(cli.other_threshold, 0.0);
+    }
+
+    #[test]
+    fn test_cli_opens_tui() {
+        let opens_tui = |args: &[&str]| Cli::try_parse_from(args).unwrap().opens_tui();
+        assert!(opens_tui(&["toktrack"]));
+        assert!(opens_tui(&["toktrack", "daily"]));
+        assert!(opens_tui(&["toktrack", "stats"]));
+        assert!(!opens_tui(&["toktrack", "daily", "--json"]));
+        assert!(!opens_tui(&["toktrack", "stats", "--markdown"]));
+        assert!(!opens_tui(&["toktrack", "models", "--json"]));
+    }
+
+    #[test]
+    fn test_cli_misplaced_tui_flag() {
+        let misplaced = |args: &[&str]| Cli::try_parse_from(args).unwrap().misplaced_tui_flag();
+        assert_eq!(
+            misplaced(&["toktrack", "daily", "--other-threshold", "1"]),
+            None
+        );
+        assert_eq!(
+            misplaced(&["toktrack", "daily", "--json", "--other-threshold", "1"]),
+            Some("--other-threshold")
+        );
+        assert_eq!(misplaced(&["toktrack", "--sparkline-width", "10"]), None);
+        assert_eq!(
+            misplaced(&["toktrack", "stats", "--markdown", "--sparkline-width", "10"]),
+            Some("--sparkline-width")
+        );
+        assert_eq!(
+            misplaced(&["toktrack", "weekly", "--json", "--weekly-scale-weeks", "4"]),
+            Some("--weekly-scale-weeks")
+        );
+        assert_eq!(misplaced(&["toktrack", "tui", "--min-cols", "80"]), None);
+        assert_eq!(
+            misplaced(&["toktrack", "sessions", "--json", "--min-rows", "20"]),
+            Some("--min-rows")
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_other_threshold_out_of_range() {
+        assert!(Cli::try_parse_from(["toktrack", "--other-threshold", "150"]).is_err());
+        assert!(Cli::try_parse_from(["toktrack", "--other-threshold", "abc"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_daily_since_last_run() {
+        let cli = Cli::try_parse_from(["toktrack", "daily", "--json", "--since-last-run"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daily {
+                json: true,
+                since_last_run: true,
+                top: None,
+                watch: false,
+                interval: DEFAULT_WATCH_INTERVAL_SECS,
+                group_by: None,
+                hourly: false,
+                date: None,
+                moving_average: None,
+                cumulative: false,
+                source: None
+            })
+        ));
+
+        // Requires --json
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--since-last-run"]).is_err());
+    }
+
+    #[test]
+    fn test_since_last_run_two_runs_across_new_day() {
+        use crate::types::DailySummary;
+        use chrono::{Local, NaiveDate, TimeZone};
+        use std::collections::HashMap;
+
+        let make_summary = |date: NaiveDate| DailySummary {
+            date,
+            total_input_tokens: 10,
+            total_output_tokens: 5,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: 0.0,
+            models: HashMap::new(),
+            cost_estimated: false,
+        };
+        let at = |date: NaiveDate, hour: u32| {
+            Local
+                .from_local_datetime(&date.and_hms_opt(hour, 0, 0).unwrap())
+                .unwrap()
+                .to_utc()
+        };
+        let day1 = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let day3 = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let service = RunStateService::with_path(dir.path().join("state.json"));
+
+        // First run: nothing stored → no filter
+        let first = filter_since(
+            vec![make_summary(day1), make_summary(day2)],
+            service.load().last_run,
+        );
+        assert_eq!(first.len(), 2);
+        record_run(&service, at(day2, 18)).unwrap();
+
+        // Second run the next day: days before the last run's date are dropped
+        let second = filter_since(
+            vec![make_summary(day1), make_summary(day2), make_summary(day3)],
+            service.load().last_run,
+        );
+        let dates: Vec<NaiveDate> = second.iter().map(|s| s.date).collect();
+        assert_eq!(dates, vec![day2, day3]);
+        record_run(&service, at(day3, 9)).unwrap();
+        assert_eq!(service.load().last_run, Some(at(day3, 9)));
+    }
+
+    #[test]
+    fn test_cli_parse_sparkline_width() {
+        let cli = Cli::try_parse_from(["toktrack", "--sparkline-width", "24"]).unwrap();
+        assert_eq!(cli.sparkline_width, Some(24));
+
+        assert!(Cli::try_parse_from(["toktrack", "--sparkline-width", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_note_add_and_clear() {
+        let cli =
+            Cli::try_parse_from(["toktrack", "note", "add", "2026-02-03", "big refactor"]).unwrap();
+        match cli.command {
+            Some(Commands::Note(args)) => match args.action {
+                note::NoteAction::Add { date, text } => {
+                    assert_eq!(date, NaiveDate::from_ymd_opt(2026, 2, 3).unwrap());
+                    assert_eq!(text, "big refactor");
+                }
+                other => panic!("unexpected action: {:?}", other),
+            },
+            _ => panic!("expected note command"),
+        }
+
+        let cli = Cli::try_parse_from(["toktrack", "note", "clear", "2026-02-03"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Note(note::NoteArgs {
+                action: note::NoteAction::Clear { .. }
+            }))
+        ));
+
+        assert!(Cli::try_parse_from(["toktrack", "note", "add", "not-a-date", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_branches_requires_json() {
+        let cli = Cli::try_parse_from(["toktrack", "branches", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Branches { json: true })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "branches"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_models_by_source() {
+        let cli = Cli::try_parse_from(["toktrack", "models", "--by-source", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Models {
+                json: true,
+                by_source: true
+            })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "models", "--by-source"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_cache_rebuild() {
+        let cli = Cli::try_parse_from(["toktrack", "cache", "rebuild"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Cache(cache::CacheArgs {
+                action: cache::CacheAction::Rebuild
+            }))
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "cache"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_cache_clear_inspect_prune() {
+        let cli = Cli::try_parse_from(["toktrack", "cache", "clear", "codex"]).unwrap();
+        match cli.command {
+            Some(Commands::Cache(cache::CacheArgs {
+                action: cache::CacheAction::Clear { cli },
+            })) => assert_eq!(cli.as_deref(), Some("codex")),
+            _ => panic!("expected cache clear"),
+        }
+        let cli = Cli::try_parse_from(["toktrack", "cache", "inspect"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Cache(cache::CacheArgs {
+                action: cache::CacheAction::Inspect { cli: None }
+            }))
+        ));
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "cache",
+            "prune",
+            "--before",
+            "2026-01-01",
+            "--dry-run",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Cache(cache::CacheArgs {
+                action: cache::CacheAction::Prune { before, dry_run },
+            })) => {
+                assert_eq!(before, "2026-01-01");
+                assert!(dry_run);
+            }
+            _ => panic!("expected cache prune"),
+        }
+        assert!(Cli::try_parse_from(["toktrack", "cache", "prune"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_budget() {
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "budget",
+            "set",
+            "--daily",
+            "10",
+            "--monthly",
+            "150",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Budget(budget::BudgetArgs {
+                action:
+                    budget::BudgetAction::Set {
+                        daily,
+                        weekly,
+                        monthly,
+                    },
+            })) => assert_eq!((daily, weekly, monthly), (Some(10.0), None, Some(150.0))),
+            _ => panic!("expected budget set"),
+        }
+        assert!(Cli::try_parse_from(["toktrack", "budget", "set"]).is_err());
+        let cli = Cli::try_parse_from(["toktrack", "budget", "check", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Budget(budget::BudgetArgs {
+                action: budget::BudgetAction::Check { json: true }
+            }))
+        ));
+        let cli = Cli::try_parse_from(["toktrack", "budget", "status"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Budget(budget::BudgetArgs {
+                action: budget::BudgetAction::Status { json: false }
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_cache_backup_restore() {
+        let cli = Cli::try_parse_from(["toktrack", "cache", "backup", "-o", "b.zip"]).unwrap();
+        match cli.command {
+            Some(Commands::Cache(cache::CacheArgs {
+                action: cache::CacheAction::Backup { output },
+            })) => assert_eq!(output, Some(PathBuf::from("b.zip"))),
+            _ => panic!("expected cache backup"),
+        }
+        let cli = Cli::try_parse_from(["toktrack", "cache", "restore", "b.zip"]).unwrap();
+        match cli.command {
+            Some(Commands::Cache(cache::CacheArgs {
+                action: cache::CacheAction::Restore { backup },
+            })) => assert_eq!(backup, PathBuf::from("b.zip")),
+            _ => panic!("expected cache restore"),
+        }
+        assert!(Cli::try_parse_from(["toktrack", "cache", "restore"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_project_list() {
+        let cli = Cli::try_parse_from(["toktrack", "project", "list", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Project(project::ProjectArgs {
+                action: project::ProjectAction::List { json: true }
+            }))
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "project", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_top() {
+        let cli = Cli::try_parse_from(["toktrack", "top"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Top {
+                n: 10,
+                by: top::TopSortBy::Cost,
+                source: None,
+                json: false
+            })
+        ));
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "top",
+            "-n",
+            "5",
+            "--by",
+            "tokens",
+            "--source",
+            "claude-code",
+            "--json",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Top {
+                n: 5,
+                by: top::TopSortBy::Tokens,
+                source: Some(session::SessionSource::ClaudeCode),
+                json: true
+            })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "top", "--by", "messages"]).is_err());
+        assert!(Cli::try_parse_from(["toktrack", "top", "--source", "codex"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_doctor() {
+        let cli = Cli::try_parse_from(["toktrack", "doctor", "--json"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Doctor { json: true })));
+        let cli = Cli::try_parse_from(["toktrack", "doctor"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Doctor { json: false })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_branch_list() {
+        let cli = Cli::try_parse_from(["toktrack", "branch", "list", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Branch(branch::BranchArgs {
+                action: branch::BranchAction::List {
+                    source: session::SessionSource::ClaudeCode,
+                    json: true
+                }
+            }))
+        ));
+        assert!(Cli::try_parse_from([
+            "toktrack",
+            "branch",
+            "list",
+            "--source",
+            "claude-code",
+            "--json"
+        ])
+        .is_ok());
+        assert!(
+            Cli::try_parse_from(["toktrack", "branch", "list", "--source", "codex", "--json"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["toktrack", "branch", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_parsers() {
+        let cli = Cli::try_parse_from(["toktrack", "parsers", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Parsers { json: true })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "parsers"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_providers() {
+        let cli = Cli::try_parse_from(["toktrack", "providers", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Providers { json: true })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "providers"]).is_err());
+    }
+
+    #[test]
+    fn test_parser_infos_lists_every_parser() {
+        let config = Config {
+            data_dirs: std::collections::HashMap::from([(
+                "codex".to_string(),
+                PathBuf::from("/nonexistent/toktrack-codex"),
+            )]),
+            ..Config::default()
+        };
+        let infos = parser_infos(&ParserRegistry::new(Some(&config)));
+        assert_eq!(infos.len(), 8);
+        let codex = infos.iter().find(|i| i.name == "codex").unwrap();
+        assert_eq!(codex.data_dir, PathBuf::from("/nonexistent/toktrack-codex"));
+        assert!(!codex.active);
+    }
+
+    #[test]
+    fn test_cli_parse_session_requires_source() {
+        let cli = Cli::try_parse_from(["toktrack", "session", "list", "--source", "claude-code"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Session(session::SessionArgs {
+                action: session::SessionAction::List { json: false, .. }
+            }))
+        ));
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "session",
+            "export",
+            "abc-123",
+            "--source",
+            "claude-code",
+            "--format",
+            "csv",
+            "-o",
+            "out.csv",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Session(session::SessionArgs {
+                action:
+                    session::SessionAction::Export {
+                        session_id,
+                        format,
+                        output,
+                        ..
+                    },
+            })) => {
+                assert_eq!(session_id, "abc-123");
+                assert_eq!(format, ExportFormat::Csv);
+                assert_eq!(output, Some(PathBuf::from("out.csv")));
+            }
+            _ => panic!("expected session export"),
+        }
+        assert!(Cli::try_parse_from(["toktrack", "session", "show", "abc-123"]).is_err());
+        assert!(Cli::try_parse_from(["toktrack", "session", "list", "--source", "codex"]).is_err());
+        assert!(Cli::try_parse_from([
+            "toktrack",
+            "session",
+            "export",
+            "abc-123",
+            "--source",
+            "claude-code",
+            "--format",
+            "ndjson",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_config_show() {
+        let cli = Cli::try_parse_from(["toktrack", "config", "show", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config(config::ConfigArgs {
+                action: config::ConfigAction::Show { json: true }
+            }))
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "config", "show"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_model_update_prices() {
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "model",
+            "update-prices",
+            "--source",
+            "custom",
+            "--file",
+            "prices.json",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Model(model::ModelArgs {
+                action: model::ModelAction::UpdatePrices { source, file },
+            })) => {
+                assert_eq!(source, model::PriceSource::Custom);
+                assert_eq!(file, PathBuf::from("prices.json"));
+            }
+            _ => panic!("expected model update-prices"),
+        }
+        assert!(Cli::try_parse_from([
+            "toktrack",
+            "model",
+            "update-prices",
+            "--source",
+            "litellm",
+            "--file",
+            "prices.json",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_daily_json_includes_note_when_present() {
+        use std::collections::HashMap;
+
+        let make = |d: u32| DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 2, d).unwrap(),
+            total_input_tokens: 10,
+            total_output_tokens: 5,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: 0.0,
+            models: HashMap::new(),
+            cost_estimated: false,
+        };
+        let summaries = vec![make(3), make(4)];
+        let mut notes = BTreeMap::new();
+        notes.insert(
+            NaiveDate::from_ymd_opt(2026, 2, 3).unwrap(),
+            "prod incident".to_string(),
+        );
+
+        let value = serde_json::to_value(with_notes(&summaries, &notes)).unwrap();
+        assert_eq!(value[0]["date"], "2026-02-03");
+        assert_eq!(value[0]["note"], "prod incident");
+        assert_eq!(value[0]["total_input_tokens"], 10);
+        assert!(value[1].get("note").is_none());
+    }
+
+    #[test]
+    fn test_cli_parse_sessions_json() {
+        let cli = Cli::try_parse_from(["toktrack", "sessions", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sessions { json: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_sessions_granularity() {
+        let cli = Cli::try_parse_from(["toktrack", "sessions", "--granularity", "minute"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sessions {
+                json: false,
+                granularity: DetailGranularity::Minute
+            })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "sessions", "--granularity", "day"]).is_err());
+        assert!(
+            Cli::try_parse_from(["toktrack", "sessions", "--json", "--granularity", "hour"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["toktrack", "sessions", "--json"]).is_ok());
+    }
+
+    #[test]
+    fn test_sessions_json_highlights_busiest_sessions() {
+        use crate::parsers::ClaudeCodeParser;
+        use std::path::PathBuf;
+
+        // sess-pricey has the highest cost, sess-chatty the most tokens
+        let sessions = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/data/sessions"))
+            .parse_sessions_index(None, false);
+        assert_eq!(sessions.len(), 3);
+
+        let value = serde_json::to_value(SessionsReport::new(&sessions)).unwrap();
+        assert_eq!(value["sessions"].as_array().unwrap().len(), 3);
+        assert_eq!(value["highlights"]["most_expensive"], "sess-pricey");
+        assert_eq!(value["highlights"]["most_tokens"], "sess-chatty");
+    }
+
+    #[test]
+    fn test_cli_parse_daily_top() {
+        let cli = Cli::try_parse_from(["toktrack", "daily", "--json", "--top", "3"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daily { top: Some(3), .. })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--top", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_top_by_cost_orders_costliest_days() {
+        use chrono::Datelike;
+        use std::collections::HashMap;
+
+        let make = |d: u32, cost: f64| DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 3, d).unwrap(),
+            total_input_tokens: 10,
+            total_output_tokens: 5,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::new(),
+            cost_estimated: false,
+        };
+        let summaries = vec![
+            make(1, 1.50),
+            make(2, 9.00),
+            make(3, 0.25),
+            make(4, 4.75),
+            make(5, 9.00),
+        ];
+
+        let top: Vec<(u32, f64)> = top_by_cost(summaries.clone(), 3)
+            .iter()
+            .map(|s| (s.date.day(), s.total_cost_usd))
+            .collect();
+        // Equal costs: most recent day first
+        assert_eq!(top, vec![(5, 9.00), (2, 9.00), (4, 4.75)]);
+
+        assert_eq!(top_by_cost(summaries, 10).len(), 5);
+    }
+
+    #[test]
+    fn test_cli_parse_interval_watch() {
+        let cli = Cli::try_parse_from(["toktrack", "weekly", "--json", "--watch"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Weekly {
+                watch: true,
+                interval: DEFAULT_WATCH_INTERVAL_SECS,
+                ..
+            })
+        ));
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "monthly",
+            "--json",
+            "--watch",
+            "--interval",
+            "5",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Monthly {
+                watch: true,
+                interval: 5,
+                ..
+            })
+        ));
+        for args in [
+            &["toktrack", "weekly", "--watch"][..],
+            &["toktrack", "monthly", "--json", "--interval", "5"],
+            &[
+                "toktrack",
+                "monthly",
+                "--json",
+                "--watch",
+                "--interval",
+                "0",
+            ],
+            &["toktrack", "stats", "--watch"],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{:?}", args);
+        }
+        assert!(Cli::try_parse_from(["toktrack", "stats", "--markdown", "--watch"]).is_ok());
+        assert!(Cli::try_parse_from(["toktrack", "stats", "--json", "--watch"]).is_ok());
+    }
+
+    fn test_watch_loop(dir: &std::path::Path, output: WatchOutput) -> WatchLoop {
+        let parser = crate::parsers::ClaudeCodeParser::with_data_dir(dir.to_path_buf());
+        WatchLoop {
+            watcher: FileWatcher::new(vec![Box::new(parser)]).with_debounce(Duration::ZERO),
+            interval: Duration::ZERO,
+            output,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Append a line to `path`, so the watch loop sees a change
+    fn touch(path: &std::path::Path) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        writeln!(file, "{{}}").unwrap();
+    }
+
+    #[test]
+    fn test_watch_loop_reruns_after_change() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("session.jsonl");
+        touch(&file);
+        let mut watch = test_watch_loop(dir.path(), WatchOutput::Lines);
+        let stop = Arc::clone(&watch.stop);
+        let mut runs = 0;
+        let mut out = Vec::new();
+        watch
+            .run(&mut out, || {
+                runs += 1;
+                if runs == 2 {
+                    stop.store(true, Ordering::Relaxed);
+                } else {
+                    touch(&file);
+                }
+                Ok(format!("run {}", runs))
+            })
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "run 1\nrun 2\n");
+    }
+
+    #[test]
+    fn test_watch_loop_separates_only_successful_runs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("session.jsonl");
+        touch(&file);
+        let mut watch = test_watch_loop(dir.path(), WatchOutput::Text);
+        let stop = Arc::clone(&watch.stop);
+        let mut runs = 0;
+        let mut out = Vec::new();
+        watch
+            .run(&mut out, || {
+                runs += 1;
+                match runs {
+                    // Failed runs are retried without a file change
+                    1 | 3 | 4 => return Err(ToktrackError::NoData),
+                    2 => touch(&file),
+                    _ => stop.store(true, Ordering::Relaxed),
+                }
+                Ok(format!("run {}", runs))
+            })
+            .unwrap();
+        assert_eq!(runs, 5);
+        assert_eq!(String::from_utf8(out).unwrap(), "run 2\n---\nrun 5\n");
+    }
+
+    #[test]
+    fn test_cli_parse_daily_watch() {
+        let cli = Cli::try_parse_from(["toktrack", "daily", "--json", "--watch"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daily {
+                watch: true,
+                interval: DEFAULT_WATCH_INTERVAL_SECS,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--watch"]).is_err());
+        let cli =
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--watch", "--interval", "30"])
+                .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daily { interval: 30, .. })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--json", "--interval", "30"]).is_err());
+        assert!(Cli::try_parse_from([
+            "toktrack",
+            "daily",
+            "--json",
+            "--watch",
+            "--since-last-run"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_report_requires_json() {
+        let cli = Cli::try_parse_from(["toktrack", "report", "--json"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Report { json: true })));
+        assert!(Cli::try_parse_from(["toktrack", "report"]).is_err());
+    }
+
+    #[test]
+    fn test_report_sections_present_and_consistent() {
+        use std::collections::HashMap;
+
+        let make = |date: NaiveDate, model: &str, tokens: u64, cost: f64| DailySummary {
+            date,
+            total_input_tokens: tokens,
+            total_output_tokens: 0,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::from([(
+                model.to_string(),
+                ModelUsage {
+                    input_tokens: tokens,
+                    cost_usd: cost,
+                    count: 1,
+                    ..ModelUsage::default()
+                },
+            )]),
+            cost_estimated: false,
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        let summaries = vec![
+            make(today - chrono::Duration::days(45), "gpt-4.1", 400, 4.0),
+            make(
+                today - chrono::Duration::days(29),
+                "claude-opus-4",
+                200,
+                2.0,
+            ),
+            make(today, "claude-sonnet-4", 100, 1.0),
+        ];
+        let sources = vec![
+            SourceUsage {
+                source: "claude".to_string(),
+                total_tokens: 300,
+                total_cost_usd: 3.0,
+                by_model: Vec::new(),
+            },
+            SourceUsage {
+                source: "codex".to_string(),
+                total_tokens: 400,
+                total_cost_usd: 4.0,
+                by_model: Vec::new(),
+            },
+        ];
+
+        let report = Report::new(summaries, sources, today);
+        let json = serde_json::to_value(&report).unwrap();
+        for section in ["totals", "models", "sources", "stats", "daily"] {
+            assert!(json.get(section).is_some(), "missing section {section}");
+        }
+
+        assert_eq!(report.totals.total_cost_usd, 7.0);
+        assert_eq!(report.stats.total_cost, report.totals.total_cost_usd);
+        assert_eq!(report.stats.total_tokens, 700);
+        let model_cost: f64 = report.models.values().map(|m| m.cost_usd).sum();
+        assert_eq!(model_cost, report.totals.total_cost_usd);
+        let source_cost: f64 = report.sources.iter().map(|s| s.total_cost_usd).sum();
+        assert_eq!(source_cost, report.totals.total_cost_usd);
+
+        // Day 45 is outside the 30-day window; most recent first
+        let dates: Vec<NaiveDate> = report.daily.iter().map(|s| s.date).collect();
+        assert_eq!(dates, vec![today, today - chrono::Duration::days(29)]);
+    }
+
+    #[test]
+    fn test_cli_parse_include_synthetic_global() {
+        let cli =
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--include-synthetic"]).unwrap();
+        assert!(cli.include_synthetic);
+        assert!(!Cli::try_parse_from(["toktrack"]).unwrap().include_synthetic);
+    }
+
+    #[test]
+    fn test_cli_parse_safe_parse_global() {
+        let cli = Cli::try_parse_from(["toktrack", "stats", "--json", "--safe-parse"]).unwrap();
+        assert!(cli.safe_parse);
+        assert!(!Cli::try_parse_from(["toktrack"]).unwrap().safe_parse);
+    }
+
+    #[test]
+    fn test_cli_parse_raw_cache_global() {
+        let cli = Cli::try_parse_from(["toktrack", "daily", "--json", "--raw-cache"]).unwrap();
+        assert!(cli.raw_cache);
+        assert!(!Cli::try_parse_from(["toktrack"]).unwrap().raw_cache);
+    }
+
+    #[test]
+    fn test_cli_parse_round_cost() {
+        let cli = Cli::try_parse_from(["toktrack", "--round-cost", "4"]).unwrap();
+        assert_eq!(cli.round_cost, Some(4));
+        assert!(Cli::try_parse_from(["toktrack", "--round-cost", "7"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_portable_global() {
+        assert!(!Cli::try_parse_from(["toktrack"]).unwrap().portable);
+        let cli = Cli::try_parse_from(["toktrack", "daily", "--json", "--portable"]).unwrap();
+        assert!(cli.portable);
+    }
+
+    #[test]
+    fn test_cli_parse_show_empty_sources() {
+        assert!(
+            !Cli::try_parse_from(["toktrack"])
+                .unwrap()
+                .show_empty_sources
+        );
+        let cli = Cli::try_parse_from(["toktrack", "--show-empty-sources"]).unwrap();
+        assert!(cli.show_empty_sources);
+    }
+
+    #[test]
+    fn test_cli_parse_merge_dir() {
+        let cli =
+            Cli::try_parse_from(["toktrack", "stats", "--json", "--merge-dir", "/sync"]).unwrap();
+        assert_eq!(cli.merge_dir, Some(PathBuf::from("/sync")));
+    }
+
+    #[test]
+    fn test_cli_parse_daily_hourly() {
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "daily",
+            "--json",
+            "--hourly",
+            "--date",
+            "2025-01-15",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Daily { hourly, date, .. }) => {
+                assert!(hourly);
+                assert_eq!(date.as_deref(), Some("2025-01-15"));
+            }
+            _ => panic!("expected daily command"),
+        }
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--hourly"]).is_err());
+        assert!(
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--date", "2025-01-15"]).is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--hourly", "--top", "3"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_moving_average_rows_shape() {
+        use std::collections::HashMap;
+
+        let make = |d: u32, cost: f64| DailySummary {
+            date: NaiveDate::from_ymd_opt(2025, 1, d).unwrap(),
+            total_input_tokens: 10,
+            total_output_tokens: 5,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::new(),
+            cost_estimated: false,
+        };
+        let rows = moving_average_rows(&[make(15, 0.45), make(14, 0.19)], 7);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["date"], "2025-01-15");
+        assert_eq!(rows[1]["daily_cost"], 0.45);
+        assert!((rows[1]["moving_avg_7d"].as_f64().unwrap() - 0.32).abs() < 1e-9);
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--moving-average", "7"]).is_err());
+        assert!(
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--moving-average", "0"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cumulative_rows_shape() {
+        use std::collections::HashMap;
+
+        let make = |d: u32, cost: f64| DailySummary {
+            date: NaiveDate::from_ymd_opt(2025, 1, d).unwrap(),
+            total_input_tokens: 10,
+            total_output_tokens: 5,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::new(),
+            cost_estimated: false,
+        };
+        let rows = cumulative_rows(&[make(2, 0.25), make(1, 0.45)]);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["date"], "2025-01-01");
+        assert_eq!(rows[0]["cumulative_cost"], 0.45);
+        assert_eq!(rows[1]["daily_cost"], 0.25);
+        assert!((rows[1]["cumulative_cost"].as_f64().unwrap() - 0.70).abs() < 1e-9);
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--cumulative"]).is_err());
+        assert!(
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--cumulative", "--top", "3"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_export() {
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "export",
+            "--format",
+            "csv",
+            "--from",
+            "2026-03-01",
+            "-o",
+            "out.csv",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Export {
+                format,
+                from,
+                to,
+                output,
+                source,
+            }) => {
+                assert_eq!(format, ExportFormat::Csv);
+                assert_eq!(source, None);
+                assert_eq!(from.as_deref(), Some("2026-03-01"));
+                assert_eq!(to, None);
+                assert_eq!(output, Some(PathBuf::from("out.csv")));
+            }
+            _ => panic!("expected export command"),
+        }
+        assert!(Cli::try_parse_from(["toktrack", "export", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_source_filter() {
+        let cli = Cli::try_parse_from([
+            "toktrack",
+            "stats",
+            "--json",
+            "--source",
+            "claude-code,opencode",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Stats { source, .. }) => {
+                assert_eq!(
+                    source,
+                    Some(vec!["claude-code".to_string(), "opencode".to_string()])
+                );
+            }
+            _ => panic!("expected stats command"),
+        }
+        for sub in ["daily", "weekly", "monthly", "quarterly", "export"] {
+            assert!(
+                Cli::try_parse_from(["toktrack", sub, "--source", "codex"]).is_ok(),
+                "{sub}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_max_age_days() {
+        let cli =
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--max-age-days", "90"]).unwrap();
+        assert_eq!(cli.max_age_days, Some(90));
+        assert_eq!(
+            Cli::try_parse_from(["toktrack"]).unwrap().max_age_days,
+            None
+        );
+        assert!(Cli::try_parse_from(["toktrack", "--max-age-days", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_exclude_today_global() {
+        let cli = Cli::try_parse_from(["toktrack", "stats", "--json", "--exclude-today"]).unwrap();
+        assert!(cli.exclude_today);
+        assert!(!Cli::try_parse_from(["toktrack"]).unwrap().exclude_today);
+    }
+
+    #[test]
+    fn test_cli_parse_daily_group_by() {
+        let cli =
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--group-by", "model"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daily {
+                group_by: Some(DailyGroupBy::Model),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["toktrack", "daily", "--group-by", "model"]).is_err());
+        assert!(
+            Cli::try_parse_from(["toktrack", "daily", "--json", "--group-by", "source"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_by_model_series_is_sparse_and_date_ordered() {
+        use std::collections::HashMap;
+
+        let usage = |tokens: u64, cost: f64| ModelUsage {
+            input_tokens: tokens,
+            output_tokens: tokens / 2,
+            cost_usd: cost,
+            count: 1,
+            ..ModelUsage::default()
+        };
+        let make = |d: u32, models: Vec<(&str, ModelUsage)>| DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 3, d).unwrap(),
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: 0.0,
+            models: models
+                .into_iter()
+                .map(|(name, u)| (name.to_string(), u))
+                .collect::<HashMap<_, _>>(),
+            cost_estimated: false,
+        };
+        // Newest first, as loaded; opus is absent on the 2nd
+        let summaries = vec![
+            make(
+                3,
+                vec![("sonnet", usage(200, 2.0)), ("opus", usage(40, 4.0))],
+            ),
+            make(2, vec![("sonnet", usage(100, 1.0))]),
+            make(1, vec![("opus", usage(20, 2.0))]),
+        ];
+
+        let value = serde_json::to_value(by_model_series(&summaries)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "opus": [
+                    {"date": "2026-03-01", "tokens": 30, "cost": 2.0},
+                    {"date": "2026-03-03", "tokens": 60, "cost": 4.0},
+                ],
+                "sonnet": [
+                    {"date": "2026-03-02", "tokens": 150, "cost": 1.0},
+                    {"date": "2026-03-03", "tokens": 300, "cost": 2.0},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_json_format_flags() {
+        let parse = |args: &[&str]| {
+            let cli = Cli::try_parse_from(args).unwrap();
+            JsonFormat::from_flags(cli.compact, cli.jsonl)
+        };
+        assert_eq!(parse(&["toktrack", "stats", "--json"]), JsonFormat::Pretty);
+        assert_eq!(
+            parse(&["toktrack", "stats", "--json", "--compact"]),
+            JsonFormat::Compact
+        );
+        assert_eq!(
+            parse(&["toktrack", "weekly", "--json", "--jsonl"]),
+            JsonFormat::Lines
+        );
+        assert!(
+            Cli::try_parse_from(["toktrack", "stats", "--json", "--compact", "--jsonl"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_json_format_compact_stats_and_models() {
+        let summaries = vec![DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            total_input_tokens: 100,
+            total_output_tokens: 50,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: 1.25,
+            models: std::collections::HashMap::from([(
+                "claude-sonnet-4-5".to_string(),
+                ModelUsage {
+                    input_tokens: 100,
+                    output_tokens: 50,
+                    cost_usd: 1.25,
+                    count: 1,
+                    ..ModelUsage::default()
+                },
+            )]),
+            cost_estimated: false,
+        }];
+        let stats = StatsData::from_daily_summaries(&summaries);
+        let models: BTreeMap<String, ModelUsage> = Aggregator::by_model_from_daily(&summaries)
+            .into_iter()
+            .collect();
+
+        for value in [
+            serde_json::to_value(&stats).unwrap(),
+            serde_json::to_value(&models).unwrap(),
+        ] {
+            let compact = JsonFormat::Compact.render(&value).unwrap();
+            assert!(!compact.contains('\n'));
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(&compact).unwrap(),
+                value
+            );
+            // Objects have no lines to split
+            assert_eq!(JsonFormat::Lines.render(&value).unwrap(), compact);
+            assert!(JsonFormat::Pretty.render(&value).unwrap().contains('\n'));
+        }
+    }
+
+    #[test]
+    fn test_json_format_lines_splits_arrays() {
+        let value = serde_json::json!([{"a": 1}, {"a": 2}, {"a": 3}]);
+        assert_eq!(
+            JsonFormat::Lines.render(&value).unwrap(),
+            "{\"a\":1}\n{\"a\":2}\n{\"a\":3}"
+        );
+        assert_eq!(
+            JsonFormat::Lines.render(&serde_json::json!([])).unwrap(),
+            ""
+        );
     }
 }
@@ -0,0 +1,101 @@
+//! `toktrack model` subcommand for model pricing
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+
+use super::one_of;
+use crate::config::Config;
+use crate::services::pricing::ModelPricing;
+use crate::types::{Result, ToktrackError};
+
+/// Manage model pricing
+#[derive(Args, Debug)]
+pub struct ModelArgs {
+    #[command(subcommand)]
+    pub action: ModelAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ModelAction {
+    /// Import per-token prices into `[pricing.overrides]` of config.toml
+    UpdatePrices {
+        /// Where the prices come from
+        #[arg(long, value_parser = one_of::<PriceSource>(&["custom"]))]
+        source: PriceSource,
+
+        /// JSON object of model name to pricing (LiteLLM field names)
+        #[arg(long)]
+        file: PathBuf,
+    },
+}
+
+/// Source for `toktrack model update-prices`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    /// A user-supplied JSON file
+    Custom,
+}
+
+impl std::str::FromStr for PriceSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "custom" => Ok(Self::Custom),
+            _ => Err(format!("'{s}' is not one of: custom")),
+        }
+    }
+}
+
+impl ModelArgs {
+    pub fn run(self) -> Result<()> {
+        match self.action {
+            ModelAction::UpdatePrices {
+                source: PriceSource::Custom,
+                file,
+            } => {
+                let prices = read_prices(&file)?;
+                let count = prices.len();
+                let path = Config::import_pricing_overrides(prices)?;
+                println!(
+                    "Imported {} custom model price(s) into {}",
+                    count,
+                    path.display()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read a `{model: pricing}` JSON file
+fn read_prices(path: &Path) -> Result<HashMap<String, ModelPricing>> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| ToktrackError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_prices() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("prices.json");
+        fs::write(
+            &path,
+            r#"{"my-custom-model": {"input_cost_per_token": 3e-6, "output_cost_per_token": 15e-6}}"#,
+        )
+        .unwrap();
+        let prices = read_prices(&path).unwrap();
+        assert_eq!(prices["my-custom-model"].output_cost_per_token, Some(15e-6));
+
+        fs::write(&path, "[1, 2]").unwrap();
+        assert!(matches!(read_prices(&path), Err(ToktrackError::Parse(_))));
+    }
+}
@@ -0,0 +1,99 @@
+//! `toktrack note` subcommand for annotating days with notes
+
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+
+use crate::services::day_notes::DayNotesService;
+use crate::types::Result;
+
+/// Manage day notes
+#[derive(Args, Debug)]
+pub struct NoteArgs {
+    #[command(subcommand)]
+    pub action: NoteAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NoteAction {
+    /// Add or replace the note for a day
+    Add {
+        /// Day to annotate (YYYY-MM-DD)
+        date: NaiveDate,
+
+        /// Note text
+        text: String,
+    },
+
+    /// Remove the note for a day
+    Clear {
+        /// Day to clear (YYYY-MM-DD)
+        date: NaiveDate,
+    },
+}
+
+impl NoteArgs {
+    pub fn run(self) -> Result<()> {
+        let service = DayNotesService::new()?;
+        let message = self.run_with(&service)?;
+        println!("{}", message);
+        Ok(())
+    }
+
+    /// Apply the action and return a confirmation message
+    fn run_with(self, service: &DayNotesService) -> Result<String> {
+        match self.action {
+            NoteAction::Add { date, text } => {
+                service.set(date, text)?;
+                Ok(format!("Note saved for {}", date))
+            }
+            NoteAction::Clear { date } => {
+                if service.clear(date)? {
+                    Ok(format!("Note cleared for {}", date))
+                } else {
+                    Ok(format!("No note for {}", date))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_service() -> (TempDir, DayNotesService) {
+        let tmp = TempDir::new().unwrap();
+        let svc = DayNotesService::with_path(tmp.path().join("notes.json"));
+        (tmp, svc)
+    }
+
+    #[test]
+    fn test_note_add_then_clear_round_trip() {
+        let (_tmp, svc) = make_service();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+
+        let add = NoteArgs {
+            action: NoteAction::Add {
+                date,
+                text: "prod incident".to_string(),
+            },
+        };
+        assert_eq!(add.run_with(&svc).unwrap(), "Note saved for 2026-02-03");
+        assert_eq!(svc.load()[&date], "prod incident");
+
+        let clear = NoteArgs {
+            action: NoteAction::Clear { date },
+        };
+        assert_eq!(clear.run_with(&svc).unwrap(), "Note cleared for 2026-02-03");
+        assert!(svc.load().is_empty());
+
+        let clear_again = NoteArgs {
+            action: NoteAction::Clear { date },
+        };
+        assert_eq!(
+            clear_again.run_with(&svc).unwrap(),
+            "No note for 2026-02-03"
+        );
+    }
+}
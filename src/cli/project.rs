@@ -0,0 +1,39 @@
+//! `toktrack project` subcommand for per-project Claude Code usage
+
+use clap::{Args, Subcommand};
+
+use super::JsonFormat;
+use crate::services::{Aggregator, DataLoaderService};
+use crate::types::Result;
+
+/// Per-project usage
+#[derive(Args, Debug)]
+pub struct ProjectArgs {
+    #[command(subcommand)]
+    pub action: ProjectAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProjectAction {
+    /// Claude Code cost and tokens per project directory, costliest first
+    List {
+        /// Output as JSON (required)
+        #[arg(long, required = true)]
+        json: bool,
+    },
+}
+
+impl ProjectArgs {
+    pub fn run(self, loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+        match self.action {
+            ProjectAction::List { .. } => {
+                let entries = loader.load_project_entries();
+                println!(
+                    "{}",
+                    format.render(&Aggregator::project_summaries(&entries))?
+                );
+            }
+        }
+        Ok(())
+    }
+}
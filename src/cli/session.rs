@@ -0,0 +1,282 @@
+//! `toktrack session` subcommand for browsing and exporting Claude Code sessions
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use chrono::Local;
+use clap::{Args, Subcommand};
+
+use super::export::{csv_field, ExportFormat};
+use super::{one_of, JsonFormat};
+use crate::services::DataLoaderService;
+use crate::tui::widgets::overview::format_number;
+use crate::types::{
+    CostBreakdown, CostSource, Result, SessionDetailEntry, SessionInfo, ToktrackError,
+};
+
+/// CSV header for `toktrack session export`, matching `SessionDetailEntry`;
+/// `CostBreakdown::CSV_HEADER` columns follow
+const CSV_HEADER: &str = "timestamp,model,input_tokens,output_tokens,cache_read_tokens,cache_creation_tokens,cost_usd,cost_source";
+
+/// Browse and export sessions
+#[derive(Args, Debug)]
+pub struct SessionArgs {
+    #[command(subcommand)]
+    pub action: SessionAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SessionAction {
+    /// Sessions, most recently created first
+    List {
+        /// Source CLI (required; only claude-code has sessions)
+        #[arg(long, value_parser = one_of::<SessionSource>(&["claude-code"]))]
+        source: SessionSource,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Individual requests of one session
+    Show {
+        session_id: String,
+
+        /// Source CLI (required; only claude-code has sessions)
+        #[arg(long, value_parser = one_of::<SessionSource>(&["claude-code"]))]
+        source: SessionSource,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Write one session's requests as JSON or CSV
+    Export {
+        session_id: String,
+
+        /// Source CLI (required; only claude-code has sessions)
+        #[arg(long, value_parser = one_of::<SessionSource>(&["claude-code"]))]
+        source: SessionSource,
+
+        /// Output format
+        #[arg(long, default_value = "json", value_parser = one_of::<ExportFormat>(&["json", "csv"]))]
+        format: ExportFormat,
+
+        /// Write to this file instead of stdout
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+}
+
+/// CLIs with a sessions index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSource {
+    ClaudeCode,
+}
+
+impl std::str::FromStr for SessionSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "claude-code" => Ok(Self::ClaudeCode),
+            _ => Err(format!("'{s}' is not one of: claude-code")),
+        }
+    }
+}
+
+impl SessionSource {
+    /// Name of the parser the sessions come from
+    pub fn parser_name(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "claude-code",
+        }
+    }
+}
+
+impl SessionArgs {
+    pub fn run(self, loader: DataLoaderService, format: JsonFormat) -> Result<()> {
+        match self.action {
+            SessionAction::List { json, .. } => {
+                let mut sessions = loader.load_sessions();
+                sessions.sort_by(|a, b| b.created.cmp(&a.created));
+                if json {
+                    println!("{}", format.render(&sessions)?);
+                } else {
+                    println!("{}", sessions_table(&sessions));
+                }
+            }
+            SessionAction::Show {
+                session_id, json, ..
+            } => {
+                let entries = session_detail(&loader, &session_id)?;
+                if json {
+                    println!("{}", format.render(&entries)?);
+                } else {
+                    println!("{}", detail_table(&entries));
+                }
+            }
+            SessionAction::Export {
+                session_id,
+                format: export_format,
+                output,
+                ..
+            } => {
+                let entries = session_detail(&loader, &session_id)?;
+                let rendered = match export_format {
+                    ExportFormat::Csv => detail_csv(&entries),
+                    ExportFormat::Json | ExportFormat::Ndjson => format.render(&entries)?,
+                };
+                match output {
+                    Some(path) => std::fs::write(path, rendered + "\n")?,
+                    None => println!("{}", rendered),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Requests of the session with `session_id`, oldest first
+fn session_detail(loader: &DataLoaderService, session_id: &str) -> Result<Vec<SessionDetailEntry>> {
+    let session = loader
+        .load_sessions()
+        .into_iter()
+        .find(|s| s.session_id == session_id)
+        .ok_or_else(|| ToktrackError::Parse(format!("Unknown session: {}", session_id)))?;
+    Ok(loader.load_session_detail(&session.jsonl_path))
+}
+
+fn sessions_table(sessions: &[SessionInfo]) -> String {
+    let mut out = format!(
+        "{:<16}  {:<36}  {:<20}  {:<20}  {:>10}  {:>9}",
+        "CREATED", "SESSION", "PROJECT", "MODEL", "TOKENS", "COST"
+    );
+    for s in sessions {
+        let _ = write!(
+            out,
+            "\n{:<16}  {:<36}  {:<20}  {:<20}  {:>10}  {:>9}",
+            s.created.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+            s.session_id,
+            s.project,
+            s.primary_model,
+            format_number(s.total_tokens),
+            format!("${:.2}", s.total_cost_usd)
+        );
+    }
+    out
+}
+
+fn detail_table(entries: &[SessionDetailEntry]) -> String {
+    let mut out = format!(
+        "{:<19}  {:<20}  {:>8}  {:>8}  {:>10}  {:>10}  {:>9}",
+        "TIME", "MODEL", "INPUT", "OUTPUT", "CACHE READ", "CACHE NEW", "COST"
+    );
+    for e in entries {
+        let _ = write!(
+            out,
+            "\n{:<19}  {:<20}  {:>8}  {:>8}  {:>10}  {:>10}  {:>9}",
+            e.timestamp
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            e.model,
+            format_number(e.input_tokens),
+            format_number(e.output_tokens),
+            format_number(e.cache_read_tokens),
+            format_number(e.cache_creation_tokens),
+            format!("${:.4}", e.cost_usd)
+        );
+    }
+    out
+}
+
+fn detail_csv(entries: &[SessionDetailEntry]) -> String {
+    let mut out = format!("{},{}", CSV_HEADER, CostBreakdown::CSV_HEADER);
+    for e in entries {
+        let cost_source = match e.cost_source {
+            CostSource::Logged => "logged",
+            CostSource::Pricing => "pricing",
+        };
+        let _ = write!(
+            out,
+            "\n{},{},{},{},{},{},{},{},{}",
+            e.timestamp.to_rfc3339(),
+            csv_field(&e.model),
+            e.input_tokens,
+            e.output_tokens,
+            e.cache_read_tokens,
+            e.cache_creation_tokens,
+            e.cost_usd,
+            cost_source,
+            e.cost_breakdown
+                .map_or_else(|| ",".repeat(5), |b| b.to_csv_row())
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry(model: &str, input: u64, cost: f64) -> SessionDetailEntry {
+        SessionDetailEntry {
+            timestamp: Utc.with_ymd_and_hms(2026, 2, 11, 14, 3, 0).unwrap(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: 20,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cost_usd: cost,
+            cost_source: CostSource::Pricing,
+            rates: None,
+            cost_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn test_detail_csv() {
+        let mut priced = entry("Sonnet 4.5", 100, 0.5);
+        priced.cost_breakdown = Some(CostBreakdown {
+            input_cost: 0.25,
+            output_cost: 0.25,
+            total: 0.5,
+            ..CostBreakdown::default()
+        });
+        let csv = detail_csv(&[priced, entry("a,b", 1, 0.0)]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            format!("{},{}", CSV_HEADER, CostBreakdown::CSV_HEADER)
+        );
+        assert_eq!(
+            lines[1],
+            "2026-02-11T14:03:00+00:00,Sonnet 4.5,100,20,0,0,0.5,pricing,0.25,0.25,0,0,0,0.5"
+        );
+        assert_eq!(
+            lines[2],
+            "2026-02-11T14:03:00+00:00,\"a,b\",1,20,0,0,0,pricing,,,,,,"
+        );
+    }
+
+    #[test]
+    fn test_detail_table_has_one_row_per_request() {
+        let table = detail_table(&[entry("Sonnet 4.5", 1200, 0.5), entry("Opus 4.5", 5, 1.25)]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("TIME"));
+        assert!(lines[1].contains("Sonnet 4.5") && lines[1].ends_with("$0.5000"));
+        assert!(lines[2].contains("Opus 4.5") && lines[2].ends_with("$1.2500"));
+    }
+
+    #[test]
+    fn test_unknown_source_is_rejected() {
+        assert_eq!(
+            "claude-code".parse::<SessionSource>(),
+            Ok(SessionSource::ClaudeCode)
+        );
+        assert!("codex".parse::<SessionSource>().is_err());
+    }
+}
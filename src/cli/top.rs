@@ -0,0 +1,150 @@
+//! `toktrack top`: the costliest or largest sessions
+
+use std::fmt::Write;
+
+use chrono::Local;
+
+use super::JsonFormat;
+use crate::services::DataLoaderService;
+use crate::tui::widgets::overview::format_number;
+use crate::types::{Result, SessionInfo};
+
+/// Characters of the session id shown in the table
+const SESSION_ID_CHARS: usize = 12;
+
+/// What `toktrack top` ranks sessions by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopSortBy {
+    Cost,
+    Tokens,
+}
+
+impl std::str::FromStr for TopSortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "cost" => Ok(Self::Cost),
+            "tokens" => Ok(Self::Tokens),
+            _ => Err(format!("'{s}' is not one of: cost, tokens")),
+        }
+    }
+}
+
+/// The first `n` sessions by `by`, largest first; ties go to the newest session
+fn top_sessions(mut sessions: Vec<SessionInfo>, n: usize, by: TopSortBy) -> Vec<SessionInfo> {
+    sessions.sort_by(|a, b| {
+        let order = match by {
+            TopSortBy::Cost => b.total_cost_usd.total_cmp(&a.total_cost_usd),
+            TopSortBy::Tokens => b.total_tokens.cmp(&a.total_tokens),
+        };
+        order.then_with(|| b.created.cmp(&a.created))
+    });
+    sessions.truncate(n);
+    sessions
+}
+
+/// Print the top sessions as a table, or as JSON with `json`
+pub fn run_top(
+    loader: DataLoaderService,
+    n: usize,
+    by: TopSortBy,
+    json: bool,
+    format: JsonFormat,
+) -> Result<()> {
+    let top = top_sessions(loader.load_sessions(), n, by);
+    if json {
+        println!("{}", format.render(&top)?);
+    } else if top.is_empty() {
+        println!("No sessions found");
+    } else {
+        println!("{}", top_table(&top));
+    }
+    Ok(())
+}
+
+fn top_table(sessions: &[SessionInfo]) -> String {
+    let mut out = format!(
+        "{:>4}  {:<10}  {:<20}  {:<12}  {:<20}  {:>8}  {:>10}  {:>9}",
+        "#", "DATE", "PROJECT", "SESSION", "MODEL", "MESSAGES", "TOKENS", "COST"
+    );
+    for (rank, s) in sessions.iter().enumerate() {
+        let _ = write!(
+            out,
+            "\n{:>4}  {:<10}  {:<20}  {:<12}  {:<20}  {:>8}  {:>10}  {:>9}",
+            rank + 1,
+            s.created.with_timezone(&Local).format("%Y-%m-%d"),
+            s.project,
+            s.session_id
+                .chars()
+                .take(SESSION_ID_CHARS)
+                .collect::<String>(),
+            s.primary_model,
+            s.message_count,
+            format_number(s.total_tokens),
+            format!("${:.2}", s.total_cost_usd)
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn session(id: &str, day: u32, tokens: u64, cost: f64) -> SessionInfo {
+        let created = Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+        SessionInfo {
+            session_id: id.to_string(),
+            project: "webapp".to_string(),
+            project_path: "/home/user/webapp".to_string(),
+            summary: String::new(),
+            first_prompt: String::new(),
+            message_count: 4,
+            created,
+            modified: created,
+            git_branch: String::new(),
+            jsonl_path: String::new(),
+            total_cost_usd: cost,
+            total_tokens: tokens,
+            primary_model: "claude-sonnet-4".to_string(),
+            mixed_utc_offsets: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    fn ids(sessions: &[SessionInfo]) -> Vec<&str> {
+        sessions.iter().map(|s| s.session_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_top_sessions_by_cost_and_tokens() {
+        let sessions = vec![
+            session("a", 1, 5000, 1.00),
+            session("b", 2, 9000, 0.50),
+            session("c", 3, 1000, 2.00),
+            session("d", 4, 2000, 1.00),
+        ];
+        assert_eq!(
+            ids(&top_sessions(sessions.clone(), 3, TopSortBy::Cost)),
+            vec!["c", "d", "a"]
+        );
+        assert_eq!(
+            ids(&top_sessions(sessions.clone(), 2, TopSortBy::Tokens)),
+            vec!["b", "a"]
+        );
+        assert_eq!(top_sessions(sessions, 10, TopSortBy::Cost).len(), 4);
+        assert!(top_sessions(Vec::new(), 10, TopSortBy::Cost).is_empty());
+    }
+
+    #[test]
+    fn test_top_table_truncates_session_id() {
+        let table = top_table(&[session("0123456789abcdef", 1, 1500, 1.5)]);
+        let row = table.lines().nth(1).unwrap();
+        assert!(row.trim_start().starts_with("1  2026-03-01"));
+        assert!(row.contains("  0123456789ab  "));
+        assert!(!row.contains("0123456789abc"));
+        assert!(row.ends_with("$1.50"));
+    }
+}
@@ -0,0 +1,281 @@
+//! User configuration from `~/.toktrack/config.toml`
+//!
+//! Every field is optional in the file; missing fields keep their compiled-in
+//! defaults. `TOKTRACK_CONFIG_PATH` points at a different file.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::data_dir::data_dir;
+use crate::services::pricing::{ModelPricing, DEFAULT_PRICING_TTL_SECS};
+use crate::types::{Result, ToktrackError};
+
+/// Environment variable overriding the config file location
+pub const CONFIG_PATH_ENV: &str = "TOKTRACK_CONFIG_PATH";
+
+/// Config file name inside the data directory
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// First day of the week for weekly summaries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    Monday,
+    #[default]
+    Sunday,
+}
+
+/// `[pricing]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PricingConfig {
+    /// Per-token pricing by model name, taking precedence over LiteLLM's
+    pub overrides: HashMap<String, ModelPricing>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Data directory per parser name (e.g. `claude-code`, `codex`), replacing its default
+    pub data_dirs: HashMap<String, PathBuf>,
+    /// Directory for daily summary caches (default: `~/.toktrack/cache/`)
+    pub cache_dir: Option<PathBuf>,
+    /// `dark` or `light`; unset or anything else auto-detects from the terminal
+    pub theme: Option<String>,
+    /// Check GitHub for a newer release when the TUI starts
+    pub update_check_enabled: bool,
+    /// Refetch pricing data once the cached copy is older than this
+    pub pricing_ttl_secs: u64,
+    pub week_start: WeekStart,
+    pub pricing: PricingConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            data_dirs: HashMap::new(),
+            cache_dir: None,
+            theme: None,
+            update_check_enabled: true,
+            pricing_ttl_secs: DEFAULT_PRICING_TTL_SECS,
+            week_start: WeekStart::default(),
+            pricing: PricingConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Config file location: `TOKTRACK_CONFIG_PATH`, else `config.toml` in the data directory
+    pub fn path() -> Option<PathBuf> {
+        std::env::var_os(CONFIG_PATH_ENV)
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| data_dir().map(|dir| dir.join(CONFIG_FILE_NAME)))
+    }
+
+    /// Load the config file, or the defaults if there is none
+    pub fn load() -> Result<Config> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Like `load`, but an unreadable or invalid file falls back to the defaults with a warning
+    pub fn load_or_default() -> Config {
+        Self::load().unwrap_or_else(|e| {
+            eprintln!("[toktrack] Warning: {}; using default settings", e);
+            Config::default()
+        })
+    }
+
+    fn load_from(path: &Path) -> Result<Config> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_toml(&content)
+                .map_err(|e| ToktrackError::Config(format!("{}: {}", path.display(), e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn from_toml(content: &str) -> std::result::Result<Config, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    /// Add `prices` to `[pricing.overrides]` in the config file, replacing same-named
+    /// models. Other settings are kept; comments are not. Returns the file written.
+    pub fn import_pricing_overrides(prices: HashMap<String, ModelPricing>) -> Result<PathBuf> {
+        let path = Self::path()
+            .ok_or_else(|| ToktrackError::Config("Failed to get home directory".into()))?;
+        Self::import_pricing_overrides_into(&path, prices)?;
+        Ok(path)
+    }
+
+    fn import_pricing_overrides_into(
+        path: &Path,
+        prices: HashMap<String, ModelPricing>,
+    ) -> Result<()> {
+        let invalid =
+            |e: &dyn std::fmt::Display| ToktrackError::Config(format!("{}: {}", path.display(), e));
+        // Edit the raw table so defaults are not written out as explicit values
+        let mut table: toml::Table = match fs::read_to_string(path) {
+            Ok(content) => content.parse().map_err(|e| invalid(&e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let overrides = table
+            .entry("pricing")
+            .or_insert_with(|| toml::Table::new().into())
+            .as_table_mut()
+            .ok_or_else(|| invalid(&"`pricing` is not a table"))?
+            .entry("overrides")
+            .or_insert_with(|| toml::Table::new().into())
+            .as_table_mut()
+            .ok_or_else(|| invalid(&"`pricing.overrides` is not a table"))?;
+        for (model, pricing) in prices {
+            overrides.insert(
+                model,
+                toml::Value::try_from(pricing).map_err(|e| invalid(&e))?,
+            );
+        }
+
+        let content = toml::to_string(&table).map_err(|e| invalid(&e))?;
+        Self::from_toml(&content).map_err(|e| invalid(&e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Configured data directory for the parser named `name`
+    pub fn data_dir_for(&self, name: &str) -> Option<PathBuf> {
+        self.data_dirs.get(name).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_fields_keep_defaults() {
+        let config = Config::from_toml("theme = \"light\"\n").unwrap();
+        assert_eq!(config.theme.as_deref(), Some("light"));
+        assert!(config.update_check_enabled);
+        assert_eq!(config.pricing_ttl_secs, DEFAULT_PRICING_TTL_SECS);
+        assert_eq!(config.week_start, WeekStart::Sunday);
+        assert!(config.data_dirs.is_empty());
+    }
+
+    #[test]
+    fn test_full_config() {
+        let config = Config::from_toml(
+            r#"
+cache_dir = "/var/cache/toktrack"
+update_check_enabled = false
+pricing_ttl_secs = 86400
+week_start = "monday"
+
+[data_dirs]
+claude-code = "/mnt/backup/claude/projects"
+codex = "/mnt/backup/codex"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.cache_dir, Some(PathBuf::from("/var/cache/toktrack")));
+        assert!(!config.update_check_enabled);
+        assert_eq!(config.pricing_ttl_secs, 86400);
+        assert_eq!(config.week_start, WeekStart::Monday);
+        assert_eq!(
+            config.data_dir_for("claude-code"),
+            Some(PathBuf::from("/mnt/backup/claude/projects"))
+        );
+        assert_eq!(config.data_dir_for("gemini"), None);
+    }
+
+    #[test]
+    fn test_invalid_file_is_config_error() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "week_start = \"friday\"\n").unwrap();
+        assert!(matches!(
+            Config::load_from(&path),
+            Err(ToktrackError::Config(_))
+        ));
+
+        fs::write(&path, "pricing_ttl = 60\n").unwrap();
+        assert!(Config::load_from(&path).is_err());
+    }
+
+    #[test]
+    fn test_pricing_overrides_table() {
+        let config = Config::from_toml(
+            r#"
+[pricing.overrides]
+"my-custom-model" = { input_cost_per_token = 3e-6, output_cost_per_token = 15e-6 }
+"#,
+        )
+        .unwrap();
+        let pricing = &config.pricing.overrides["my-custom-model"];
+        assert_eq!(pricing.input_cost_per_token, Some(3e-6));
+        assert_eq!(pricing.output_cost_per_token, Some(15e-6));
+        assert_eq!(pricing.cache_read_input_token_cost, None);
+    }
+
+    #[test]
+    fn test_import_pricing_overrides_keeps_other_settings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "week_start = \"monday\"\n\n[pricing.overrides]\nold-model = { input_cost_per_token = 1e-6 }\n",
+        )
+        .unwrap();
+
+        let prices = HashMap::from([
+            (
+                "old-model".to_string(),
+                ModelPricing {
+                    input_cost_per_token: Some(2e-6),
+                    ..ModelPricing::default()
+                },
+            ),
+            (
+                "new-model".to_string(),
+                ModelPricing {
+                    output_cost_per_token: Some(5e-6),
+                    ..ModelPricing::default()
+                },
+            ),
+        ]);
+        Config::import_pricing_overrides_into(&path, prices).unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.week_start, WeekStart::Monday);
+        assert_eq!(config.pricing.overrides.len(), 2);
+        assert_eq!(
+            config.pricing.overrides["old-model"].input_cost_per_token,
+            Some(2e-6)
+        );
+        assert_eq!(
+            config.pricing.overrides["new-model"].output_cost_per_token,
+            Some(5e-6)
+        );
+        // Defaults stay implicit
+        assert!(!fs::read_to_string(&path)
+            .unwrap()
+            .contains("pricing_ttl_secs"));
+    }
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let dir = TempDir::new().unwrap();
+        let config = Config::load_from(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(config, Config::default());
+    }
+}
@@ -1,5 +1,6 @@
 //! toktrack - Ultra-fast AI CLI token usage tracker
 
+pub mod config;
 pub mod parsers;
 pub mod services;
 pub mod tui;
@@ -1,4 +1,5 @@
 mod cli;
+mod config;
 mod parsers;
 mod services;
 mod tui;
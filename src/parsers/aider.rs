@@ -0,0 +1,230 @@
+//! Aider JSONL parser
+
+use crate::types::{ParseStats, Result, ToktrackError, UsageEntry};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{read_complete_lines, CLIParser, JsonBackend};
+
+/// One line of Aider's structured usage log
+#[derive(Deserialize)]
+struct AiderJsonLine<'a> {
+    timestamp: &'a str,
+    #[serde(default)]
+    model: Option<&'a str>,
+    #[serde(default)]
+    send_tokens: u64,
+    #[serde(default)]
+    recv_tokens: u64,
+    #[serde(default)]
+    cost: Option<f64>,
+}
+
+/// Provider behind an Aider model name, from its prefix
+fn provider_for(model: &str) -> Option<&'static str> {
+    if model.starts_with("claude-") {
+        Some("anthropic")
+    } else if model.starts_with("gpt-") || model.starts_with("o1-") {
+        Some("openai")
+    } else {
+        None
+    }
+}
+
+/// Parser for Aider usage data
+pub struct AiderParser {
+    data_dir: PathBuf,
+    /// Truncated trailing lines skipped by `parse_file` (files still being written)
+    truncated_lines: AtomicU64,
+    json: JsonBackend,
+}
+
+impl AiderParser {
+    /// Create a new parser with default data directory (~/.aider/logs/)
+    pub fn new() -> Self {
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
+        let home = directories::BaseDirs::new()
+            .map(|d| d.home_dir().to_path_buf())
+            .unwrap_or_else(|| {
+                eprintln!("[toktrack] Warning: Could not determine home directory");
+                PathBuf::from(".")
+            });
+        home.join(".aider").join("logs")
+    }
+
+    /// Create a parser with a custom data directory (for testing)
+    pub fn with_data_dir(data_dir: PathBuf) -> Self {
+        Self {
+            data_dir,
+            truncated_lines: AtomicU64::new(0),
+            json: JsonBackend::default(),
+        }
+    }
+
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Parse a single JSONL line
+    fn parse_line(&self, line: &mut [u8]) -> Option<UsageEntry> {
+        if line.is_empty() {
+            return None;
+        }
+
+        let data: AiderJsonLine = self.json.decode(line).ok()?;
+        if data.send_tokens == 0 && data.recv_tokens == 0 {
+            return None;
+        }
+
+        let timestamp = match DateTime::parse_from_rfc3339(data.timestamp) {
+            Ok(dt) => dt.with_timezone(&Utc),
+            Err(_) => {
+                eprintln!(
+                    "[toktrack] Warning: Invalid timestamp '{}', skipping entry",
+                    data.timestamp
+                );
+                return None;
+            }
+        };
+
+        Some(UsageEntry {
+            timestamp,
+            model: data.model.map(str::to_string),
+            input_tokens: data.send_tokens,
+            output_tokens: data.recv_tokens,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            thinking_tokens: 0,
+            // Absent for free/local models; pricing fills it in later
+            cost_usd: data.cost,
+            message_id: None,
+            request_id: None,
+            source: Some("aider".into()),
+            provider: data.model.and_then(provider_for).map(str::to_string),
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
+        })
+    }
+}
+
+impl Default for AiderParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CLIParser for AiderParser {
+    fn name(&self) -> &str {
+        "aider"
+    }
+
+    fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
+    fn file_pattern(&self) -> &str {
+        "**/*.jsonl"
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
+        let file = File::open(path).map_err(ToktrackError::Io)?;
+        let reader = BufReader::new(file);
+        let mut entries = Vec::new();
+
+        let truncated = read_complete_lines(reader, |line| {
+            if let Some(entry) = self.parse_line(line) {
+                entries.push(entry);
+            }
+        });
+
+        if truncated {
+            self.truncated_lines.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(entries)
+    }
+
+    fn parse_stats(&self) -> ParseStats {
+        ParseStats {
+            truncated_lines: self.truncated_lines.load(Ordering::Relaxed),
+            ..ParseStats::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_fixture(name: &str) -> Vec<UsageEntry> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("data")
+            .join("aider")
+            .join(name);
+        AiderParser::with_data_dir(PathBuf::from("tests/data/aider"))
+            .parse_file(&path)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_anthropic_session() {
+        let entries = parse_fixture("anthropic-session.jsonl");
+        assert_eq!(entries.len(), 2);
+
+        let e1 = &entries[0];
+        assert_eq!(e1.model, Some("claude-sonnet-4-5".to_string()));
+        assert_eq!(e1.input_tokens, 4200);
+        assert_eq!(e1.output_tokens, 610);
+        assert_eq!(e1.cost_usd, Some(0.0218));
+        for entry in &entries {
+            assert_eq!(entry.provider, Some("anthropic".into()));
+            assert_eq!(entry.source, Some("aider".into()));
+        }
+    }
+
+    #[test]
+    fn test_parse_openai_session() {
+        let entries = parse_fixture("openai-session.jsonl");
+        // Zero-token line is skipped
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].provider, Some("openai".into()));
+        assert_eq!(entries[1].model, Some("o1-mini".to_string()));
+        assert_eq!(entries[1].provider, Some("openai".into()));
+
+        // Local model: unknown provider, cost left for pricing
+        assert_eq!(entries[2].model, Some("ollama/llama3".to_string()));
+        assert_eq!(entries[2].provider, None);
+        assert_eq!(entries[2].cost_usd, None);
+    }
+
+    #[test]
+    fn test_provider_for_model_prefix() {
+        assert_eq!(provider_for("claude-opus-4"), Some("anthropic"));
+        assert_eq!(provider_for("gpt-4.1"), Some("openai"));
+        assert_eq!(provider_for("o1-preview"), Some("openai"));
+        assert_eq!(provider_for("deepseek-coder"), None);
+    }
+
+    #[test]
+    fn test_parser_name_and_pattern() {
+        let parser = AiderParser::new();
+        assert_eq!(parser.name(), "aider");
+        assert_eq!(parser.file_pattern(), "**/*.jsonl");
+        assert!(parser.default_data_dir().ends_with(".aider/logs"));
+    }
+}
@@ -2,15 +2,18 @@
 
 use crate::services::normalizer::{display_name, normalize_model_name};
 use crate::services::PricingService;
-use crate::types::{Result, SessionDetailEntry, SessionInfo, ToktrackError, UsageEntry};
+use crate::types::{
+    CostSource, ParseStats, Result, SessionDetailEntry, SessionInfo, ToktrackError, UsageEntry,
+};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use super::CLIParser;
+use super::{read_complete_lines_counted, CLIParser, FileBookmark, JsonBackend};
 
 /// Claude Code JSONL line structure (assistant messages with usage)
 #[derive(Deserialize)]
@@ -52,6 +55,48 @@ struct SessionMetadataLine {
     message: Option<SessionMetadataMessage>,
 }
 
+/// Just the type, working directory and branch of a JSONL line, for project
+/// and branch attribution
+#[derive(Deserialize)]
+struct CwdLine {
+    #[serde(rename = "type")]
+    line_type: Option<String>,
+    cwd: Option<String>,
+    #[serde(rename = "gitBranch", default)]
+    git_branch: Option<String>,
+}
+
+/// Just the timestamp of a JSONL line, for UTC offset checks
+#[derive(Deserialize)]
+struct TimestampLine<'a> {
+    #[serde(borrow)]
+    timestamp: Option<&'a str>,
+}
+
+/// Distinct UTC offsets seen across a session's timestamps, in first-seen order
+#[derive(Default)]
+struct UtcOffsets(Vec<String>);
+
+impl UtcOffsets {
+    fn observe(&mut self, timestamp: &str) {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(timestamp) {
+            let offset = dt.offset().to_string();
+            if !self.0.contains(&offset) {
+                self.0.push(offset);
+            }
+        }
+    }
+
+    /// The offsets when the session mixes more than one, otherwise empty
+    fn mixed(self) -> Vec<String> {
+        if self.0.len() > 1 {
+            self.0
+        } else {
+            Vec::new()
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct SessionMetadataMessage {
     role: Option<String>,
@@ -61,45 +106,65 @@ struct SessionMetadataMessage {
 /// Parser for Claude Code usage data
 pub struct ClaudeCodeParser {
     data_dir: PathBuf,
+    /// Truncated trailing lines skipped by `parse_file` (files still being written)
+    truncated_lines: AtomicU64,
+    /// Entries read by `parse_file` with a NaN/infinite/negative `costUSD`
+    invalid_costs: AtomicU64,
+    json: JsonBackend,
 }
 
 impl ClaudeCodeParser {
     /// Create a new parser with default data directory (~/.claude/projects/)
     pub fn new() -> Self {
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
         let home = directories::BaseDirs::new()
             .map(|d| d.home_dir().to_path_buf())
             .unwrap_or_else(|| {
                 eprintln!("[toktrack] Warning: Could not determine home directory");
                 PathBuf::from(".")
             });
-        Self {
-            data_dir: home.join(".claude").join("projects"),
-        }
+        home.join(".claude").join("projects")
     }
 
     /// Create a parser with a custom data directory (for testing)
-    #[allow(dead_code)] // Used in tests
     pub fn with_data_dir(data_dir: PathBuf) -> Self {
-        Self { data_dir }
+        Self {
+            data_dir,
+            truncated_lines: AtomicU64::new(0),
+            invalid_costs: AtomicU64::new(0),
+            json: JsonBackend::default(),
+        }
     }
 
-    /// Parse a single JSONL line (zero-copy with borrowed strings)
-    fn parse_line(&self, line: &mut [u8]) -> Option<UsageEntry> {
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Parse a single JSONL line for the session views, skipping synthetic
+    /// responses unless `include_synthetic`
+    fn parse_line(&self, line: &mut [u8], include_synthetic: bool) -> Option<UsageEntry> {
+        self.parse_usage_line(line)
+            .filter(|entry| include_synthetic || !self.is_synthetic(entry))
+    }
+
+    /// Parse a single JSONL line (zero-copy with borrowed strings).
+    /// Synthetic responses are kept; callers filter them via `is_synthetic`.
+    fn parse_usage_line(&self, line: &mut [u8]) -> Option<UsageEntry> {
         if line.is_empty() {
             return None;
         }
 
-        let data: ClaudeJsonLine = simd_json::from_slice(line).ok()?;
+        let data: ClaudeJsonLine = self.json.decode(line).ok()?;
 
         // Only process lines with message and usage data
         let message = data.message.as_ref()?;
         let usage = message.usage.as_ref()?;
 
-        // Skip synthetic responses (no actual API call)
-        if message.model == Some("<synthetic>") {
-            return None;
-        }
-
         let timestamp = match DateTime::parse_from_rfc3339(data.timestamp) {
             Ok(dt) => dt.with_timezone(&Utc),
             Err(_) => {
@@ -124,10 +189,119 @@ impl ClaudeCodeParser {
             request_id: data.request_id.map(String::from),
             source: Some("claude".into()),
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         })
     }
 }
 
+impl ClaudeCodeParser {
+    /// Like `parse_file`, but tags each entry with `project_path` and
+    /// `git_branch`: the `cwd` and `gitBranch` of the most recent user-type
+    /// line before it in the same file
+    pub fn parse_file_with_metadata(&self, path: &Path) -> Result<Vec<UsageEntry>> {
+        self.read_entries(path, true)
+    }
+
+    /// Parse only lines appended since `bookmark`, then move it to the end of
+    /// the last complete line. Without a bookmark, or when it is stale (file
+    /// replaced or truncated), the whole file is read.
+    #[allow(dead_code)] // Part of parser API, used in tests
+    pub fn parse_file_incremental(
+        &self,
+        path: &Path,
+        bookmark: &mut Option<FileBookmark>,
+    ) -> Result<Vec<UsageEntry>> {
+        let meta = fs::metadata(path)?;
+        let start = bookmark
+            .as_ref()
+            .and_then(|b| b.resume_offset(path, &meta))
+            .unwrap_or(0);
+
+        let (entries, end) = self.read_entries_from(path, start, false)?;
+        *bookmark = Some(FileBookmark::new(path, &meta, end));
+        Ok(entries)
+    }
+
+    fn read_entries(&self, path: &Path, track_cwd: bool) -> Result<Vec<UsageEntry>> {
+        self.read_entries_from(path, 0, track_cwd)
+            .map(|(entries, _)| entries)
+    }
+
+    /// Entries from byte `offset` on, and the offset just past the last complete line
+    fn read_entries_from(
+        &self,
+        path: &Path,
+        offset: u64,
+        track_cwd: bool,
+    ) -> Result<(Vec<UsageEntry>, u64)> {
+        let mut file = File::open(path).map_err(ToktrackError::Io)?;
+        if offset > 0 {
+            file.seek(SeekFrom::Start(offset))?;
+        }
+        let reader = BufReader::new(file);
+        let mut entries = Vec::new();
+        let mut cwd: Option<String> = None;
+        let mut branch: Option<String> = None;
+
+        // Stream line-by-line to avoid loading entire file into memory
+        let (truncated, consumed) = read_complete_lines_counted(reader, |line| {
+            // Decoded before `parse_usage_line`, which may mutate the buffer
+            if track_cwd {
+                if let Ok(CwdLine {
+                    line_type: Some(t),
+                    cwd: dir,
+                    git_branch,
+                }) = serde_json::from_slice::<CwdLine>(line)
+                {
+                    if t == "user" {
+                        cwd = dir.or(cwd.take());
+                        branch = git_branch.or(branch.take());
+                    }
+                }
+            }
+            if let Some(mut entry) = self.parse_usage_line(line) {
+                if entry.has_invalid_cost() {
+                    entry.cost_usd = Some(0.0);
+                    self.invalid_costs.fetch_add(1, Ordering::Relaxed);
+                }
+                entry.project_path = cwd.clone();
+                entry.git_branch = branch.clone();
+                entries.push(entry);
+            }
+        });
+
+        if truncated {
+            self.truncated_lines.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok((entries, offset + consumed))
+    }
+}
+
+/// Pause before re-reading a sessions index that failed to parse
+/// (Claude Code may be rewriting it)
+const INDEX_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Read and parse a sessions index, re-reading once after `retry_delay` if the
+/// first read fails to parse. `None` if it is still unreadable or invalid.
+fn read_sessions_index(
+    path: &Path,
+    retry_delay: std::time::Duration,
+    mut read: impl FnMut(&Path) -> std::io::Result<String>,
+) -> Option<SessionsIndex> {
+    let parse = |content: std::io::Result<String>| {
+        content
+            .ok()
+            .and_then(|c| serde_json::from_str::<SessionsIndex>(&c).ok())
+    };
+    parse(read(path)).or_else(|| {
+        std::thread::sleep(retry_delay);
+        parse(read(path))
+    })
+}
+
 /// Sessions index file structure
 #[derive(Deserialize)]
 struct SessionsIndex {
@@ -160,7 +334,12 @@ struct SessionsIndexEntry {
 impl ClaudeCodeParser {
     /// Scan all sessions-index.json files and return session metadata with
     /// aggregated cost/token data from quick-parsing each session's JSONL.
-    pub fn parse_sessions_index(&self, pricing: Option<&PricingService>) -> Vec<SessionInfo> {
+    /// Synthetic responses count only with `include_synthetic`.
+    pub fn parse_sessions_index(
+        &self,
+        pricing: Option<&PricingService>,
+        include_synthetic: bool,
+    ) -> Vec<SessionInfo> {
         let pattern = self.data_dir.join("*/sessions-index.json");
         let index_files: Vec<PathBuf> = glob::glob(&pattern.to_string_lossy())
             .map(|paths| paths.filter_map(|e| e.ok()).collect())
@@ -170,14 +349,12 @@ impl ClaudeCodeParser {
         let mut indexed_paths: std::collections::HashSet<String> = std::collections::HashSet::new();
 
         for index_path in &index_files {
-            let content = match std::fs::read_to_string(index_path) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-
-            let index: SessionsIndex = match serde_json::from_str(&content) {
-                Ok(i) => i,
-                Err(_) => continue,
+            // A persistently invalid index is skipped; its project's sessions
+            // are still picked up by the JSONL discovery below.
+            let Some(index) = read_sessions_index(index_path, INDEX_RETRY_DELAY, |p| {
+                std::fs::read_to_string(p)
+            }) else {
+                continue;
             };
 
             for entry in index.entries {
@@ -199,8 +376,8 @@ impl ClaudeCodeParser {
                     .to_string();
 
                 // Quick-parse the JSONL to get cost/token/model aggregates
-                let (total_cost_usd, total_tokens, primary_model) =
-                    self.quick_parse_session_jsonl(&entry.full_path, pricing);
+                let (total_cost_usd, total_tokens, primary_model, mixed_utc_offsets) =
+                    self.quick_parse_session_jsonl(&entry.full_path, pricing, include_synthetic);
 
                 sessions.push(SessionInfo {
                     session_id: entry.session_id,
@@ -216,6 +393,7 @@ impl ClaudeCodeParser {
                     total_cost_usd,
                     total_tokens,
                     primary_model,
+                    mixed_utc_offsets,
                     metadata: None,
                 });
             }
@@ -233,7 +411,8 @@ impl ClaudeCodeParser {
                 continue;
             }
 
-            if let Some(session) = self.session_from_jsonl(&jsonl_path, pricing) {
+            if let Some(session) = self.session_from_jsonl(&jsonl_path, pricing, include_synthetic)
+            {
                 sessions.push(session);
             }
         }
@@ -249,6 +428,7 @@ impl ClaudeCodeParser {
         &self,
         jsonl_path: &Path,
         pricing: Option<&PricingService>,
+        include_synthetic: bool,
     ) -> Option<SessionInfo> {
         let file = File::open(jsonl_path).ok()?;
         let reader = BufReader::new(file);
@@ -263,6 +443,7 @@ impl ClaudeCodeParser {
         let mut total_cost: f64 = 0.0;
         let mut total_tokens: u64 = 0;
         let mut model_counts: HashMap<String, u64> = HashMap::new();
+        let mut offsets = UtcOffsets::default();
 
         for line_result in reader.lines() {
             let line = match line_result {
@@ -273,6 +454,7 @@ impl ClaudeCodeParser {
             // Try to parse metadata from user/assistant lines
             if let Ok(meta) = serde_json::from_str::<SessionMetadataLine>(&line) {
                 if let Some(ref ts) = meta.timestamp {
+                    offsets.observe(ts);
                     if let Ok(dt) = DateTime::parse_from_rfc3339(ts) {
                         let dt_utc = dt.with_timezone(&Utc);
                         if first_timestamp.is_none() {
@@ -320,12 +502,8 @@ impl ClaudeCodeParser {
 
             // Also parse for cost/token data via the existing parser
             let mut line_bytes = line.into_bytes();
-            if let Some(entry) = self.parse_line(&mut line_bytes) {
-                let tokens = entry.input_tokens
-                    + entry.output_tokens
-                    + entry.cache_read_tokens
-                    + entry.cache_creation_tokens;
-                total_tokens = total_tokens.saturating_add(tokens);
+            if let Some(entry) = self.parse_line(&mut line_bytes, include_synthetic) {
+                total_tokens = total_tokens.saturating_add(entry.total_tokens());
 
                 let cost = entry
                     .cost_usd
@@ -368,26 +546,30 @@ impl ClaudeCodeParser {
             total_cost_usd: total_cost,
             total_tokens,
             primary_model,
+            mixed_utc_offsets: offsets.mixed(),
             metadata: None,
         })
     }
 
-    /// Quick-parse a session JSONL to get aggregated cost, tokens, and primary model.
+    /// Quick-parse a session JSONL to get aggregated cost, tokens, primary model
+    /// and any mixed UTC offsets.
     fn quick_parse_session_jsonl(
         &self,
         jsonl_path: &str,
         pricing: Option<&PricingService>,
-    ) -> (f64, u64, String) {
+        include_synthetic: bool,
+    ) -> (f64, u64, String, Vec<String>) {
         let path = Path::new(jsonl_path);
         let file = match File::open(path) {
             Ok(f) => f,
-            Err(_) => return (0.0, 0, String::new()),
+            Err(_) => return (0.0, 0, String::new(), Vec::new()),
         };
         let reader = BufReader::new(file);
 
         let mut total_cost: f64 = 0.0;
         let mut total_tokens: u64 = 0;
         let mut model_counts: HashMap<String, u64> = HashMap::new();
+        let mut offsets = UtcOffsets::default();
 
         for line_result in reader.lines() {
             let line = match line_result {
@@ -395,13 +577,16 @@ impl ClaudeCodeParser {
                 _ => continue,
             };
 
+            if let Ok(TimestampLine {
+                timestamp: Some(ts),
+            }) = serde_json::from_str(&line)
+            {
+                offsets.observe(ts);
+            }
+
             let mut line_bytes = line.into_bytes();
-            if let Some(entry) = self.parse_line(&mut line_bytes) {
-                let tokens = entry.input_tokens
-                    + entry.output_tokens
-                    + entry.cache_read_tokens
-                    + entry.cache_creation_tokens;
-                total_tokens = total_tokens.saturating_add(tokens);
+            if let Some(entry) = self.parse_line(&mut line_bytes, include_synthetic) {
+                total_tokens = total_tokens.saturating_add(entry.total_tokens());
 
                 let cost = entry
                     .cost_usd
@@ -420,14 +605,16 @@ impl ClaudeCodeParser {
             .map(|(model, _)| display_name(&normalize_model_name(&model)))
             .unwrap_or_default();
 
-        (total_cost, total_tokens, primary_model)
+        (total_cost, total_tokens, primary_model, offsets.mixed())
     }
 
     /// Parse a session JSONL on-demand for the detail drill-down view.
     /// Returns individual request entries sorted by timestamp ascending.
     pub fn parse_session_detail(
+        &self,
         jsonl_path: &str,
         pricing: Option<&PricingService>,
+        include_synthetic: bool,
     ) -> Vec<SessionDetailEntry> {
         let path = Path::new(jsonl_path);
         let file = match File::open(path) {
@@ -435,7 +622,6 @@ impl ClaudeCodeParser {
             Err(_) => return Vec::new(),
         };
         let reader = BufReader::new(file);
-        let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("."));
 
         let mut entries = Vec::new();
 
@@ -446,7 +632,16 @@ impl ClaudeCodeParser {
             };
 
             let mut line_bytes = line.into_bytes();
-            if let Some(entry) = parser.parse_line(&mut line_bytes) {
+            if let Some(entry) = self.parse_line(&mut line_bytes, include_synthetic) {
+                let (cost_usd, cost_source, rates, cost_breakdown) = match entry.cost_usd {
+                    Some(cost) => (cost, CostSource::Logged, None, None),
+                    None => {
+                        let rates = pricing.and_then(|p| p.rates_for(&entry));
+                        let breakdown = rates.map(|r| r.breakdown(&entry));
+                        let cost = breakdown.map_or(0.0, |b| b.total);
+                        (cost, CostSource::Pricing, rates, breakdown)
+                    }
+                };
                 entries.push(SessionDetailEntry {
                     timestamp: entry.timestamp,
                     model: entry
@@ -458,9 +653,10 @@ impl ClaudeCodeParser {
                     output_tokens: entry.output_tokens,
                     cache_read_tokens: entry.cache_read_tokens,
                     cache_creation_tokens: entry.cache_creation_tokens,
-                    cost_usd: entry
-                        .cost_usd
-                        .unwrap_or_else(|| pricing.map_or(0.0, |p| p.calculate_cost(&entry))),
+                    cost_usd,
+                    cost_source,
+                    rates,
+                    cost_breakdown,
                 });
             }
         }
@@ -507,34 +703,36 @@ impl CLIParser for ClaudeCodeParser {
         &self.data_dir
     }
 
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
     fn file_pattern(&self) -> &str {
         "**/*.jsonl"
     }
 
     fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
-        let file = File::open(path).map_err(ToktrackError::Io)?;
-        let reader = BufReader::new(file);
-        let mut entries = Vec::new();
+        self.read_entries(path, false)
+    }
 
-        // Stream line-by-line to avoid loading entire file into memory
-        for line_result in reader.lines() {
-            let line = match line_result {
-                Ok(l) => l,
-                Err(_) => continue, // Skip lines with read errors
-            };
+    fn parse_file_from(&self, path: &Path, offset: u64) -> Option<Result<(Vec<UsageEntry>, u64)>> {
+        Some(self.read_entries_from(path, offset, false))
+    }
 
-            if line.is_empty() {
-                continue;
-            }
+    fn as_claude(&self) -> Option<&ClaudeCodeParser> {
+        Some(self)
+    }
 
-            // Convert to mutable bytes for simd-json
-            let mut line_bytes = line.into_bytes();
-            if let Some(entry) = self.parse_line(&mut line_bytes) {
-                entries.push(entry);
-            }
-        }
+    fn synthetic_models(&self) -> &[&str] {
+        // Placeholder responses Claude Code writes without an API call
+        &["<synthetic>"]
+    }
 
-        Ok(entries)
+    fn parse_stats(&self) -> ParseStats {
+        ParseStats {
+            truncated_lines: self.truncated_lines.load(Ordering::Relaxed),
+            invalid_costs: self.invalid_costs.load(Ordering::Relaxed),
+        }
     }
 }
 
@@ -550,6 +748,14 @@ mod tests {
             .join(name)
     }
 
+    /// Fixtures kept out of `tests/fixtures`, which the directory-wide tests scan
+    fn data_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("data")
+            .join(name)
+    }
+
     #[test]
     fn test_parse_claude_jsonl() {
         let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
@@ -557,8 +763,9 @@ mod tests {
             .parse_file(&fixture_path("claude-sample.jsonl"))
             .unwrap();
 
-        // Should parse 3 assistant messages (skipping user message and invalid line)
-        assert_eq!(entries.len(), 3);
+        // 3 assistant messages + 1 synthetic (skipping user message and invalid line);
+        // synthetic entries are filtered later by parse_and_dedup
+        assert_eq!(entries.len(), 4);
     }
 
     #[test]
@@ -609,7 +816,7 @@ mod tests {
     fn test_skip_invalid_lines() {
         let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
         let entries = parser
-            .parse_file(&fixture_path("claude-sample.jsonl"))
+            .parse_and_dedup(&[fixture_path("claude-sample.jsonl")])
             .unwrap();
 
         // Invalid JSON line should be skipped, not cause an error
@@ -620,7 +827,7 @@ mod tests {
     fn test_skip_user_messages() {
         let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
         let entries = parser
-            .parse_file(&fixture_path("claude-sample.jsonl"))
+            .parse_and_dedup(&[fixture_path("claude-sample.jsonl")])
             .unwrap();
 
         // User message has no usage, should be skipped
@@ -642,6 +849,37 @@ mod tests {
         assert_eq!(entries[2].dedup_hash(), None);
     }
 
+    #[test]
+    fn test_parse_file_with_metadata_attaches_cwd() {
+        let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/data/projects"));
+        let path = data_path("projects/project-session.jsonl");
+        let entries = parser.parse_file_with_metadata(&path).unwrap();
+        let paths: Vec<Option<&str>> = entries.iter().map(|e| e.project_path.as_deref()).collect();
+        // No user line before the first entry; cwd follows later user lines
+        assert_eq!(
+            paths,
+            vec![None, Some("/home/user/webapp"), Some("/home/user/api")]
+        );
+
+        // Plain parse_file leaves it unset
+        let plain = parser.parse_file(&path).unwrap();
+        assert!(plain.iter().all(|e| e.project_path.is_none()));
+    }
+
+    #[test]
+    fn test_parse_file_with_metadata_attaches_git_branch() {
+        let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/data/projects"));
+        let path = data_path("projects/project-session.jsonl");
+        let entries = parser.parse_file_with_metadata(&path).unwrap();
+        let branches: Vec<Option<&str>> = entries.iter().map(|e| e.git_branch.as_deref()).collect();
+        assert_eq!(branches, vec![None, Some("main"), Some("feat/api")]);
+        assert!(parser
+            .parse_file(&path)
+            .unwrap()
+            .iter()
+            .all(|e| e.git_branch.is_none()));
+    }
+
     #[test]
     fn test_parser_name() {
         let parser = ClaudeCodeParser::new();
@@ -669,18 +907,312 @@ mod tests {
     }
 
     #[test]
-    fn test_skip_synthetic_model() {
+    fn test_skip_truncated_last_line() {
         let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
         let entries = parser
+            .parse_file(&data_path("claude-truncated.jsonl"))
+            .unwrap();
+
+        // Final line has no trailing newline (still being written) → skipped
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message_id, Some("msg-101".to_string()));
+        assert_eq!(parser.parse_stats().truncated_lines, 1);
+    }
+
+    #[test]
+    fn test_complete_file_has_no_truncated_lines() {
+        let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
+        parser
             .parse_file(&fixture_path("claude-sample.jsonl"))
             .unwrap();
+        assert_eq!(parser.parse_stats(), ParseStats::default());
+    }
+
+    fn assistant_line(id: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"2026-01-15T10:00:00.000Z","requestId":"req-{id}","message":{{"id":"{id}","model":"claude-sonnet-4-20250514","usage":{{"input_tokens":100,"output_tokens":50}}}}}}"#
+        ) + "\n"
+    }
+
+    fn append(path: &Path, content: &str) {
+        use std::io::Write;
+        let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn ids(entries: &[UsageEntry]) -> Vec<String> {
+        entries
+            .iter()
+            .filter_map(|e| e.message_id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_incremental_reads_only_appended_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        fs::write(&path, assistant_line("a")).unwrap();
+        let parser = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf());
+
+        let mut bookmark = None;
+        let first = parser.parse_file_incremental(&path, &mut bookmark).unwrap();
+        assert_eq!(ids(&first), vec!["a"]);
+        assert_eq!(
+            bookmark.as_ref().unwrap().byte_offset,
+            fs::metadata(&path).unwrap().len()
+        );
+
+        // A line still being written is left for the next call
+        let line_b = assistant_line("b");
+        append(&path, &line_b[..20]);
+        let second = parser.parse_file_incremental(&path, &mut bookmark).unwrap();
+        assert!(second.is_empty());
 
-        // <synthetic> model entries should be filtered out
+        append(&path, &line_b[20..]);
+        append(&path, &assistant_line("c"));
+        let third = parser.parse_file_incremental(&path, &mut bookmark).unwrap();
+        assert_eq!(ids(&third), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_incremental_rereads_on_stale_bookmark() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        fs::write(&path, assistant_line("a") + &assistant_line("b")).unwrap();
+        let parser = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf());
+        let mut bookmark = None;
+        parser.parse_file_incremental(&path, &mut bookmark).unwrap();
+
+        // Truncated below the bookmark
+        fs::write(&path, assistant_line("x")).unwrap();
+        let entries = parser.parse_file_incremental(&path, &mut bookmark).unwrap();
+        assert_eq!(ids(&entries), vec!["x"]);
+
+        // Different inode: the file was replaced
+        bookmark.as_mut().unwrap().inode += 1;
+        let entries = parser.parse_file_incremental(&path, &mut bookmark).unwrap();
+        assert_eq!(ids(&entries), vec!["x"]);
+        let entries = parser.parse_file_incremental(&path, &mut bookmark).unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_negative_cost_zeroed_and_counted() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bad-cost.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"assistant","timestamp":"2026-01-15T10:00:00.000Z","message":{"model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":50}},"costUSD":-0.5}"#,
+                "\n",
+                r#"{"type":"assistant","timestamp":"2026-01-15T10:01:00.000Z","message":{"model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":50}},"costUSD":0.01}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let parser = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf());
+        let entries = parser.parse_file(&path).unwrap();
+        assert_eq!(entries[0].cost_usd, Some(0.0));
+        assert_eq!(entries[1].cost_usd, Some(0.01));
+        assert_eq!(parser.parse_stats().invalid_costs, 1);
+    }
+
+    #[test]
+    fn test_sessions_flag_mixed_utc_offsets() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let project = dir.path().join("-home-user-app");
+        std::fs::create_dir(&project).unwrap();
+        let line = |session: &str, kind: &str, ts: &str| {
+            format!(
+                r#"{{"type":"{kind}","sessionId":"{session}","timestamp":"{ts}","message":{{"role":"{kind}","model":"claude-sonnet-4-20250514","content":"hi","usage":{{"input_tokens":10,"output_tokens":5}}}}}}"#
+            )
+        };
+        // Offsets +09:00 and Z (= +00:00) within one session
+        let travel = [
+            line("travel", "user", "2026-01-15T18:00:00+09:00"),
+            line("travel", "assistant", "2026-01-15T09:01:00Z"),
+            line("travel", "assistant", "2026-01-15T09:02:00.000Z"),
+        ];
+        let home = [
+            line("home", "user", "2026-01-15T10:00:00Z"),
+            line("home", "assistant", "2026-01-15T10:01:00Z"),
+        ];
+        let travel_path = project.join("travel.jsonl");
+        std::fs::write(&travel_path, travel.join("\n") + "\n").unwrap();
+        std::fs::write(project.join("home.jsonl"), home.join("\n") + "\n").unwrap();
+
+        let parser = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf());
+        let offsets_of = |sessions: &[SessionInfo], id: &str| {
+            sessions
+                .iter()
+                .find(|s| s.session_id == id)
+                .unwrap()
+                .mixed_utc_offsets
+                .clone()
+        };
+
+        // JSONL fallback path
+        let sessions = parser.parse_sessions_index(None, false);
+        assert_eq!(offsets_of(&sessions, "travel"), vec!["+09:00", "+00:00"]);
+        assert!(offsets_of(&sessions, "home").is_empty());
+
+        // Indexed path (quick parse)
+        std::fs::write(
+            project.join("sessions-index.json"),
+            format!(
+                r#"{{"entries":[{{"sessionId":"travel","fullPath":"{}"}}]}}"#,
+                travel_path.display()
+            ),
+        )
+        .unwrap();
+        let sessions = parser.parse_sessions_index(None, false);
+        assert_eq!(offsets_of(&sessions, "travel"), vec!["+09:00", "+00:00"]);
+        assert!(offsets_of(&sessions, "home").is_empty());
+    }
+
+    #[test]
+    fn test_session_detail_cost_provenance() {
+        use crate::services::pricing::{ModelPricing, PricingCache};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let pricing_path = dir.path().join("pricing.json");
+        let cache = PricingCache {
+            fetched_at: Utc::now().timestamp(),
+            models: HashMap::from([(
+                "claude-sonnet-4-20250514".to_string(),
+                ModelPricing {
+                    input_cost_per_token: Some(0.000003),
+                    output_cost_per_token: Some(0.000015),
+                    cache_read_input_token_cost: None,
+                    cache_creation_input_token_cost: None,
+                    output_cost_per_reasoning_token: None,
+                    context_window_tokens: None,
+                },
+            )]),
+            bundled_at: None,
+        };
+        std::fs::write(&pricing_path, serde_json::to_string(&cache).unwrap()).unwrap();
+        let pricing = PricingService::from_cache_only_with_path(&pricing_path).unwrap();
+
+        let session = dir.path().join("session.jsonl");
+        let lines = [
+            r#"{"type":"assistant","timestamp":"2026-01-15T10:00:00Z","requestId":"req-1","costUSD":0.5,"message":{"id":"msg-1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":1000,"output_tokens":100}}}"#,
+            r#"{"type":"assistant","timestamp":"2026-01-15T10:01:00Z","requestId":"req-2","message":{"id":"msg-2","model":"claude-sonnet-4-20250514","usage":{"input_tokens":1000,"output_tokens":100}}}"#,
+        ];
+        std::fs::write(&session, lines.join("\n") + "\n").unwrap();
+
+        let parser = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf());
+        let entries =
+            parser.parse_session_detail(&session.to_string_lossy(), Some(&pricing), false);
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].cost_source, CostSource::Logged);
+        assert_eq!(entries[0].cost_usd, 0.5);
+        assert!(entries[0].rates.is_none());
+
+        assert_eq!(entries[1].cost_source, CostSource::Pricing);
+        let rates = entries[1].rates.unwrap();
+        assert_eq!(rates.input, 0.000003);
+        assert_eq!(rates.output, 0.000015);
+        assert!((entries[1].cost_usd - 0.0045).abs() < 1e-12);
+        assert!(entries[0].cost_breakdown.is_none());
+        let breakdown = entries[1].cost_breakdown.unwrap();
+        assert!((breakdown.input_cost - 0.003).abs() < 1e-12);
+        assert!((breakdown.output_cost - 0.0015).abs() < 1e-12);
+        assert_eq!(breakdown.total, entries[1].cost_usd);
+
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(json[0]["cost_source"], "logged");
+        assert!(json[0].get("rates").is_none());
+        assert_eq!(json[1]["cost_source"], "pricing");
+        assert_eq!(json[1]["rates"]["input"], 0.000003);
+    }
+
+    #[test]
+    fn test_session_detail_synthetic_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let session = dir.path().join("session.jsonl");
+        let lines = [
+            r#"{"type":"assistant","timestamp":"2026-01-15T10:00:00Z","requestId":"req-1","message":{"id":"msg-1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":10,"output_tokens":5}}}"#,
+            r#"{"type":"assistant","timestamp":"2026-01-15T10:01:00Z","requestId":"req-2","message":{"id":"msg-2","model":"<synthetic>","usage":{"input_tokens":0,"output_tokens":0}}}"#,
+        ];
+        std::fs::write(&session, lines.join("\n") + "\n").unwrap();
+        let parser = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf());
+        let path = session.to_string_lossy();
+
+        assert_eq!(parser.parse_session_detail(&path, None, false).len(), 1);
+        assert_eq!(parser.parse_session_detail(&path, None, true).len(), 2);
+    }
+
+    #[test]
+    fn test_sessions_index_retries_transient_parse_failure() {
+        let full = r#"{"entries":[{"sessionId":"s1","fullPath":"/tmp/s1.jsonl"}]}"#;
+        let mut reads = 0;
+        let index = read_sessions_index(
+            Path::new("sessions-index.json"),
+            std::time::Duration::ZERO,
+            |_| {
+                reads += 1;
+                // First read catches the file mid-rewrite
+                Ok(if reads == 1 { &full[..20] } else { full }.to_string())
+            },
+        )
+        .unwrap();
+        assert_eq!(reads, 2);
+        assert_eq!(index.entries[0].session_id, "s1");
+
+        let mut reads = 0;
+        let index = read_sessions_index(
+            Path::new("sessions-index.json"),
+            std::time::Duration::ZERO,
+            |_| {
+                reads += 1;
+                Ok("{\"entries\":[".to_string())
+            },
+        );
+        assert!(index.is_none());
+        assert_eq!(reads, 2);
+    }
+
+    #[test]
+    fn test_invalid_sessions_index_falls_back_to_jsonl() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let project = dir.path().join("-home-user-app");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(
+            project.join("s1.jsonl"),
+            r#"{"type":"user","sessionId":"s1","timestamp":"2026-01-15T10:00:00Z","message":{"role":"user","content":"hi"}}"#.to_string() + "\n",
+        )
+        .unwrap();
+        std::fs::write(project.join("sessions-index.json"), "{\"entries\":[").unwrap();
+
+        let sessions = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf())
+            .parse_sessions_index(None, false);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, "s1");
+    }
+
+    #[test]
+    fn test_skip_synthetic_model() {
+        let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
+        let files = vec![fixture_path("claude-sample.jsonl")];
+
+        // <synthetic> model entries are filtered out by default
+        let entries = parser.parse_and_dedup(&files).unwrap();
+        assert_eq!(entries.len(), 3);
         assert!(
             entries
                 .iter()
                 .all(|e| e.model != Some("<synthetic>".to_string())),
             "Synthetic model entries should be filtered out"
         );
+
+        // ...and kept when requested
+        let entries = parser
+            .parse_and_dedup_with_progress(&files, None, true)
+            .unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[3].model.as_deref(), Some("<synthetic>"));
     }
 }
@@ -0,0 +1,307 @@
+//! Cline / Roo Code JSONL parser
+//!
+//! Each task lives in `~/.cline/tasks/<task-id>/` with JSONL API logs and a
+//! `task.json` holding the task config (including `apiProvider`).
+
+use crate::types::{ParseStats, Result, ToktrackError, UsageEntry};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use super::{read_complete_lines, CLIParser, JsonBackend};
+
+/// Task config file beside each task's logs
+const TASK_CONFIG_FILE: &str = "task.json";
+
+/// One request/response pair in a task's API log
+#[derive(Deserialize)]
+struct ClineJsonLine<'a> {
+    /// Unix milliseconds
+    ts: i64,
+    #[serde(default, borrow)]
+    request: Option<ClineRequest<'a>>,
+    #[serde(default, borrow)]
+    response: Option<ClineResponse<'a>>,
+}
+
+#[derive(Deserialize)]
+struct ClineRequest<'a> {
+    #[serde(default)]
+    model: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct ClineResponse<'a> {
+    #[serde(default)]
+    id: Option<&'a str>,
+    #[serde(default)]
+    usage: Option<ClineUsage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClineUsage {
+    #[serde(default)]
+    tokens_in: u64,
+    #[serde(default)]
+    tokens_out: u64,
+    #[serde(default)]
+    cache_reads: u64,
+    #[serde(default)]
+    cache_writes: u64,
+    #[serde(default)]
+    cost: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClineTaskConfig {
+    #[serde(default)]
+    api_provider: Option<String>,
+}
+
+/// Bedrock model IDs are vendor-prefixed, optionally with a region
+/// (e.g. `anthropic.claude-…`, `us.anthropic.claude-…`)
+fn is_bedrock_model(model: &str) -> bool {
+    let model = ["us.", "eu.", "apac."]
+        .iter()
+        .find_map(|region| model.strip_prefix(region))
+        .unwrap_or(model);
+    ["anthropic.", "amazon.", "meta.", "mistral.", "cohere."]
+        .iter()
+        .any(|vendor| model.starts_with(vendor))
+}
+
+/// Parser for Cline usage data
+pub struct ClineParser {
+    data_dir: PathBuf,
+    /// `apiProvider` per task directory, so `task.json` is read once
+    providers: Mutex<HashMap<PathBuf, Option<String>>>,
+    /// Truncated trailing lines skipped by `parse_file` (files still being written)
+    truncated_lines: AtomicU64,
+    json: JsonBackend,
+}
+
+impl ClineParser {
+    /// Create a new parser with default data directory (~/.cline/)
+    pub fn new() -> Self {
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
+        let home = directories::BaseDirs::new()
+            .map(|d| d.home_dir().to_path_buf())
+            .unwrap_or_else(|| {
+                eprintln!("[toktrack] Warning: Could not determine home directory");
+                PathBuf::from(".")
+            });
+        home.join(".cline")
+    }
+
+    /// Create a parser with a custom data directory (for testing)
+    pub fn with_data_dir(data_dir: PathBuf) -> Self {
+        Self {
+            data_dir,
+            providers: Mutex::new(HashMap::new()),
+            truncated_lines: AtomicU64::new(0),
+            json: JsonBackend::default(),
+        }
+    }
+
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// `apiProvider` from the `task.json` beside `path`, cached per directory
+    fn task_provider(&self, path: &Path) -> Option<String> {
+        let dir = path.parent()?.to_path_buf();
+        let mut providers = self.providers.lock().unwrap_or_else(|e| e.into_inner());
+        providers
+            .entry(dir)
+            .or_insert_with_key(|dir| {
+                let mut content = fs::read(dir.join(TASK_CONFIG_FILE)).ok()?;
+                self.json
+                    .decode::<ClineTaskConfig>(&mut content)
+                    .ok()?
+                    .api_provider
+            })
+            .clone()
+    }
+
+    /// Parse a single JSONL line
+    fn parse_line(&self, line: &mut [u8], task_provider: Option<&str>) -> Option<UsageEntry> {
+        if line.is_empty() {
+            return None;
+        }
+
+        let data: ClineJsonLine = self.json.decode(line).ok()?;
+        let response = data.response?;
+        let usage = response.usage?;
+
+        let Some(timestamp) = DateTime::<Utc>::from_timestamp_millis(data.ts) else {
+            eprintln!(
+                "[toktrack] Warning: Invalid timestamp '{}', skipping entry",
+                data.ts
+            );
+            return None;
+        };
+
+        let model = data.request.and_then(|r| r.model);
+        let provider = if model.is_some_and(is_bedrock_model) {
+            Some("bedrock")
+        } else {
+            task_provider
+        };
+
+        Some(UsageEntry {
+            timestamp,
+            model: model.map(str::to_string),
+            input_tokens: usage.tokens_in,
+            output_tokens: usage.tokens_out,
+            cache_read_tokens: usage.cache_reads,
+            cache_creation_tokens: usage.cache_writes,
+            thinking_tokens: 0,
+            cost_usd: usage.cost,
+            message_id: response.id.map(str::to_string),
+            request_id: None,
+            source: Some("cline".into()),
+            provider: provider.map(str::to_string),
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
+        })
+    }
+}
+
+impl Default for ClineParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CLIParser for ClineParser {
+    fn name(&self) -> &str {
+        "cline"
+    }
+
+    fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
+    fn file_pattern(&self) -> &str {
+        "**/*.jsonl"
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
+        let file = File::open(path).map_err(ToktrackError::Io)?;
+        let reader = BufReader::new(file);
+        let task_provider = self.task_provider(path);
+        let mut entries = Vec::new();
+
+        let truncated = read_complete_lines(reader, |line| {
+            if let Some(entry) = self.parse_line(line, task_provider.as_deref()) {
+                entries.push(entry);
+            }
+        });
+
+        if truncated {
+            self.truncated_lines.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(entries)
+    }
+
+    fn parse_stats(&self) -> ParseStats {
+        ParseStats {
+            truncated_lines: self.truncated_lines.load(Ordering::Relaxed),
+            ..ParseStats::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_task(task: &str) -> Vec<UsageEntry> {
+        let parser = ClineParser::with_data_dir(PathBuf::from("tests/data/cline"));
+        let path = PathBuf::from("tests/data/cline/tasks")
+            .join(task)
+            .join("api_history.jsonl");
+        parser.parse_file(&path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_anthropic_task() {
+        let entries = parse_task("task-anthropic");
+        assert_eq!(entries.len(), 2);
+
+        let e1 = &entries[0];
+        assert_eq!(e1.model, Some("claude-sonnet-4-5".to_string()));
+        assert_eq!(e1.input_tokens, 1500);
+        assert_eq!(e1.output_tokens, 420);
+        assert_eq!(e1.cache_read_tokens, 8000);
+        assert_eq!(e1.cache_creation_tokens, 1200);
+        assert_eq!(e1.cost_usd, Some(0.0143));
+        assert_eq!(e1.message_id, Some("msg-a1".to_string()));
+        assert_eq!(e1.timestamp.to_rfc3339(), "2026-01-18T10:00:00+00:00");
+        for entry in &entries {
+            assert_eq!(entry.provider, Some("anthropic".into()));
+            assert_eq!(entry.source, Some("cline".into()));
+        }
+    }
+
+    #[test]
+    fn test_parse_openai_task() {
+        let entries = parse_task("task-openai");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].provider, Some("openai".into()));
+        assert_eq!(entries[0].cost_usd, None);
+    }
+
+    #[test]
+    fn test_parse_bedrock_task() {
+        let entries = parse_task("task-bedrock");
+        assert_eq!(entries.len(), 2);
+        for entry in &entries {
+            assert_eq!(entry.provider, Some("bedrock".into()));
+        }
+    }
+
+    #[test]
+    fn test_task_provider_cached_per_directory() {
+        let parser = ClineParser::with_data_dir(PathBuf::from("tests/data/cline"));
+        let path = Path::new("tests/data/cline/tasks/task-openai/api_history.jsonl");
+        assert_eq!(parser.task_provider(path), Some("openai".into()));
+        assert_eq!(parser.providers.lock().unwrap().len(), 1);
+        parser.parse_file(path).unwrap();
+        assert_eq!(parser.providers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_is_bedrock_model() {
+        assert!(is_bedrock_model("anthropic.claude-sonnet-4-5-v1:0"));
+        assert!(is_bedrock_model("us.anthropic.claude-sonnet-4-5-v1:0"));
+        assert!(!is_bedrock_model("claude-sonnet-4-5"));
+        assert!(!is_bedrock_model("gpt-4.1"));
+    }
+
+    #[test]
+    fn test_parser_name_and_pattern() {
+        let parser = ClineParser::new();
+        assert_eq!(parser.name(), "cline");
+        assert_eq!(parser.file_pattern(), "**/*.jsonl");
+        assert!(parser.default_data_dir().ends_with(".cline"));
+    }
+}
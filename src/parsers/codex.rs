@@ -1,13 +1,14 @@
 //! Codex CLI JSONL parser
 
-use crate::types::{Result, ToktrackError, UsageEntry};
+use crate::types::{ParseStats, Result, ToktrackError, UsageEntry};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use super::CLIParser;
+use super::{read_complete_lines, CLIParser, JsonBackend};
 
 /// Codex JSONL line types
 #[derive(Deserialize)]
@@ -56,26 +57,40 @@ struct TokenCountData {
 /// Parser for Codex CLI usage data
 pub struct CodexParser {
     data_dir: PathBuf,
+    /// Truncated trailing lines skipped by `parse_file` (files still being written)
+    truncated_lines: AtomicU64,
+    json: JsonBackend,
 }
 
 impl CodexParser {
     /// Create a new parser with default data directory (~/.codex/sessions/)
     pub fn new() -> Self {
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
         let home = directories::BaseDirs::new()
             .map(|d| d.home_dir().to_path_buf())
             .unwrap_or_else(|| {
                 eprintln!("[toktrack] Warning: Could not determine home directory");
                 PathBuf::from(".")
             });
-        Self {
-            data_dir: home.join(".codex").join("sessions"),
-        }
+        home.join(".codex").join("sessions")
     }
 
     /// Create a parser with a custom data directory (for testing)
-    #[allow(dead_code)]
     pub fn with_data_dir(data_dir: PathBuf) -> Self {
-        Self { data_dir }
+        Self {
+            data_dir,
+            truncated_lines: AtomicU64::new(0),
+            json: JsonBackend::default(),
+        }
+    }
+
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
     }
 
     /// Parse a single JSONL line
@@ -84,7 +99,7 @@ impl CodexParser {
             return ParseResult::Skip;
         }
 
-        let data: CodexJsonLine = match simd_json::from_slice(line) {
+        let data: CodexJsonLine = match self.json.decode(line) {
             Ok(d) => d,
             Err(_) => return ParseResult::Skip,
         };
@@ -173,6 +188,10 @@ impl CLIParser for CodexParser {
         &self.data_dir
     }
 
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
     fn file_pattern(&self) -> &str {
         "**/*.jsonl"
     }
@@ -189,18 +208,8 @@ impl CLIParser for CodexParser {
             cached_input_tokens: 0,
         };
 
-        for line_result in reader.lines() {
-            let line = match line_result {
-                Ok(l) => l,
-                Err(_) => continue,
-            };
-
-            if line.is_empty() {
-                continue;
-            }
-
-            let mut line_bytes = line.into_bytes();
-            match self.parse_line(&mut line_bytes) {
+        let truncated = read_complete_lines(reader, |line| {
+            match self.parse_line(line) {
                 ParseResult::Skip => {}
                 ParseResult::Model(m) => current_model = Some(m),
                 ParseResult::SessionId(id) => session_id = Some(id),
@@ -231,7 +240,7 @@ impl CLIParser for CodexParser {
 
                     // Skip zero-delta events
                     if delta_input == 0 && delta_output == 0 && delta_cached == 0 {
-                        continue;
+                        return;
                     }
 
                     // Normalize: input_tokens = non-cached only (Claude convention)
@@ -250,13 +259,27 @@ impl CLIParser for CodexParser {
                         request_id: None,
                         source: Some("codex".into()),
                         provider: None,
+                        cost_estimated: false,
+                        project_path: None,
+                        git_branch: None,
                     });
                 }
             }
+        });
+
+        if truncated {
+            self.truncated_lines.fetch_add(1, Ordering::Relaxed);
         }
 
         Ok(entries)
     }
+
+    fn parse_stats(&self) -> ParseStats {
+        ParseStats {
+            truncated_lines: self.truncated_lines.load(Ordering::Relaxed),
+            ..ParseStats::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,224 @@
+//! Continue.dev JSONL parser
+
+use crate::types::{ParseStats, Result, ToktrackError, UsageEntry};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{read_complete_lines, CLIParser, JsonBackend};
+
+/// One completion telemetry line
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContinueJsonLine<'a> {
+    timestamp: &'a str,
+    #[serde(default)]
+    completion_options: Option<ContinueCompletionOptions>,
+    /// Null for context-only requests that never reached an LLM
+    #[serde(default)]
+    usage: Option<ContinueUsage>,
+}
+
+#[derive(Deserialize)]
+struct ContinueCompletionOptions {
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    provider: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContinueUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+    #[serde(default)]
+    cost: Option<f64>,
+}
+
+/// Parser for Continue.dev usage data
+pub struct ContinueParser {
+    data_dir: PathBuf,
+    /// Truncated trailing lines skipped by `parse_file` (files still being written)
+    truncated_lines: AtomicU64,
+    json: JsonBackend,
+}
+
+impl ContinueParser {
+    /// Create a new parser with default data directory (~/.continue/logs/)
+    pub fn new() -> Self {
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
+        let home = directories::BaseDirs::new()
+            .map(|d| d.home_dir().to_path_buf())
+            .unwrap_or_else(|| {
+                eprintln!("[toktrack] Warning: Could not determine home directory");
+                PathBuf::from(".")
+            });
+        home.join(".continue").join("logs")
+    }
+
+    /// Create a parser with a custom data directory (for testing)
+    pub fn with_data_dir(data_dir: PathBuf) -> Self {
+        Self {
+            data_dir,
+            truncated_lines: AtomicU64::new(0),
+            json: JsonBackend::default(),
+        }
+    }
+
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Parse a single JSONL line; `None` for invalid lines and null usage
+    fn parse_line(&self, line: &mut [u8]) -> Option<UsageEntry> {
+        if line.is_empty() {
+            return None;
+        }
+
+        let data: ContinueJsonLine = self.json.decode(line).ok()?;
+        let usage = data.usage?;
+
+        let timestamp = match DateTime::parse_from_rfc3339(data.timestamp) {
+            Ok(dt) => dt.with_timezone(&Utc),
+            Err(_) => {
+                eprintln!(
+                    "[toktrack] Warning: Invalid timestamp '{}', skipping entry",
+                    data.timestamp
+                );
+                return None;
+            }
+        };
+
+        let (model, provider) = data
+            .completion_options
+            .map(|o| (o.model, o.provider))
+            .unwrap_or_default();
+
+        Some(UsageEntry {
+            timestamp,
+            model,
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            thinking_tokens: 0,
+            cost_usd: usage.cost,
+            message_id: None,
+            request_id: None,
+            source: Some("continue".into()),
+            provider,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
+        })
+    }
+}
+
+impl Default for ContinueParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CLIParser for ContinueParser {
+    fn name(&self) -> &str {
+        "continue"
+    }
+
+    fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
+    fn file_pattern(&self) -> &str {
+        "**/*.jsonl"
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
+        let file = File::open(path).map_err(ToktrackError::Io)?;
+        let reader = BufReader::new(file);
+        let mut entries = Vec::new();
+
+        let truncated = read_complete_lines(reader, |line| {
+            if let Some(entry) = self.parse_line(line) {
+                entries.push(entry);
+            }
+        });
+
+        if truncated {
+            self.truncated_lines.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(entries)
+    }
+
+    fn parse_stats(&self) -> ParseStats {
+        ParseStats {
+            truncated_lines: self.truncated_lines.load(Ordering::Relaxed),
+            ..ParseStats::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser() -> ContinueParser {
+        ContinueParser::with_data_dir(PathBuf::from("tests/data/continue"))
+    }
+
+    #[test]
+    fn test_parse_all_skips_null_usage() {
+        let entries = parser().parse_all().unwrap();
+        // 4 lines, one with null usage
+        assert_eq!(entries.len(), 3);
+        for entry in &entries {
+            assert_eq!(entry.source, Some("continue".into()));
+        }
+    }
+
+    #[test]
+    fn test_parse_usage_cost_and_provider() {
+        let entries = parser()
+            .parse_file(Path::new("tests/data/continue/sample.jsonl"))
+            .unwrap();
+
+        let e1 = &entries[0];
+        assert_eq!(e1.model, Some("claude-sonnet-4-5".to_string()));
+        assert_eq!(e1.provider, Some("anthropic".to_string()));
+        assert_eq!(e1.input_tokens, 2400);
+        assert_eq!(e1.output_tokens, 380);
+        assert_eq!(e1.cost_usd, Some(0.0129));
+
+        // No cost → left for pricing
+        let e2 = &entries[1];
+        assert_eq!(e2.provider, Some("openai".to_string()));
+        assert_eq!(e2.cost_usd, None);
+
+        // No completionOptions.provider
+        assert_eq!(entries[2].provider, None);
+    }
+
+    #[test]
+    fn test_parser_name_and_pattern() {
+        let parser = ContinueParser::new();
+        assert_eq!(parser.name(), "continue");
+        assert_eq!(parser.file_pattern(), "**/*.jsonl");
+        assert!(parser.default_data_dir().ends_with(".continue/logs"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Cursor IDE JSONL parser
+
+use crate::types::{ParseStats, Result, ToktrackError, UsageEntry};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{read_complete_lines, CLIParser, JsonBackend};
+
+/// One log line in the OpenAI-compatible envelope Cursor writes
+#[derive(Deserialize)]
+struct CursorJsonLine<'a> {
+    #[serde(default)]
+    id: Option<&'a str>,
+    /// Unix seconds (OpenAI `created`)
+    #[serde(default)]
+    created: Option<i64>,
+    #[serde(default)]
+    timestamp: Option<&'a str>,
+    #[serde(default)]
+    model: Option<&'a str>,
+    usage: Option<CursorUsage>,
+}
+
+#[derive(Deserialize)]
+struct CursorUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+    #[serde(default)]
+    cost: Option<f64>,
+}
+
+/// Parser for Cursor IDE usage data
+pub struct CursorParser {
+    data_dir: PathBuf,
+    /// Truncated trailing lines skipped by `parse_file` (files still being written)
+    truncated_lines: AtomicU64,
+    json: JsonBackend,
+}
+
+impl CursorParser {
+    /// Create a new parser with default data directory (~/.cursor/logs/)
+    pub fn new() -> Self {
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
+        let home = directories::BaseDirs::new()
+            .map(|d| d.home_dir().to_path_buf())
+            .unwrap_or_else(|| {
+                eprintln!("[toktrack] Warning: Could not determine home directory");
+                PathBuf::from(".")
+            });
+        home.join(".cursor").join("logs")
+    }
+
+    /// Create a parser with a custom data directory (for testing)
+    pub fn with_data_dir(data_dir: PathBuf) -> Self {
+        Self {
+            data_dir,
+            truncated_lines: AtomicU64::new(0),
+            json: JsonBackend::default(),
+        }
+    }
+
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Parse a single JSONL line.
+    /// Cursor writes partial lines during active sessions, so undecodable lines are skipped silently.
+    fn parse_line(&self, line: &mut [u8]) -> Option<UsageEntry> {
+        if line.is_empty() {
+            return None;
+        }
+
+        let data: CursorJsonLine = self.json.decode(line).ok()?;
+        let usage = data.usage?;
+
+        let timestamp = match (data.timestamp, data.created) {
+            (Some(ts), _) => DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc)),
+            (None, Some(secs)) => DateTime::from_timestamp(secs, 0),
+            (None, None) => None,
+        };
+        let Some(timestamp) = timestamp else {
+            eprintln!("[toktrack] Warning: Cursor entry without a valid timestamp, skipping");
+            return None;
+        };
+
+        Some(UsageEntry {
+            timestamp,
+            model: data.model.map(str::to_string),
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            thinking_tokens: 0,
+            cost_usd: usage.cost,
+            message_id: data.id.map(str::to_string),
+            request_id: None,
+            source: Some("cursor".into()),
+            provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
+        })
+    }
+}
+
+impl Default for CursorParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CLIParser for CursorParser {
+    fn name(&self) -> &str {
+        "cursor"
+    }
+
+    fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
+    fn file_pattern(&self) -> &str {
+        "**/*.jsonl"
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
+        let file = File::open(path).map_err(ToktrackError::Io)?;
+        let reader = BufReader::new(file);
+        let mut entries = Vec::new();
+
+        let truncated = read_complete_lines(reader, |line| {
+            if let Some(entry) = self.parse_line(line) {
+                entries.push(entry);
+            }
+        });
+
+        if truncated {
+            self.truncated_lines.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(entries)
+    }
+
+    fn parse_stats(&self) -> ParseStats {
+        ParseStats {
+            truncated_lines: self.truncated_lines.load(Ordering::Relaxed),
+            ..ParseStats::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("data")
+            .join("cursor")
+            .join(name)
+    }
+
+    fn parse_sample() -> Vec<UsageEntry> {
+        let parser = CursorParser::with_data_dir(PathBuf::from("tests/data/cursor"));
+        parser
+            .parse_file(&fixture_path("cursor-sample.jsonl"))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_maps_openai_usage() {
+        let entries = parse_sample();
+        assert_eq!(entries.len(), 3);
+
+        let e1 = &entries[0];
+        assert_eq!(e1.model, Some("gpt-4.1".to_string()));
+        assert_eq!(e1.input_tokens, 1200);
+        assert_eq!(e1.output_tokens, 340);
+        assert_eq!(e1.cost_usd, Some(0.0051));
+        assert_eq!(e1.message_id, Some("chatcmpl-001".to_string()));
+        assert_eq!(e1.timestamp.to_rfc3339(), "2026-01-15T10:00:00+00:00");
+
+        // No usage.cost → left for pricing
+        assert_eq!(entries[1].cost_usd, None);
+        assert_eq!(entries[2].model, Some("claude-sonnet-4-5".to_string()));
+    }
+
+    #[test]
+    fn test_partial_lines_skipped() {
+        // Partial JSON mid-file and an unterminated tail are dropped, not errors
+        let parser = CursorParser::with_data_dir(PathBuf::from("tests/data/cursor"));
+        let entries = parser
+            .parse_file(&fixture_path("cursor-sample.jsonl"))
+            .unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(parser.parse_stats().truncated_lines, 1);
+    }
+
+    #[test]
+    fn test_source_is_cursor() {
+        for entry in parse_sample() {
+            assert_eq!(entry.source, Some("cursor".into()));
+        }
+    }
+
+    #[test]
+    fn test_parser_name_and_pattern() {
+        let parser = CursorParser::new();
+        assert_eq!(parser.name(), "cursor");
+        assert_eq!(parser.file_pattern(), "**/*.jsonl");
+        assert!(parser.default_data_dir().ends_with(".cursor/logs"));
+    }
+
+    #[test]
+    fn test_parse_nonexistent_file() {
+        let parser = CursorParser::new();
+        let result = parser.parse_file(Path::new("/nonexistent/file.jsonl"));
+        assert!(result.is_err());
+    }
+}
@@ -6,9 +6,7 @@ use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-// Using simd_json for consistency with other parsers
-
-use super::CLIParser;
+use super::{CLIParser, JsonBackend};
 
 /// Gemini session JSON structure
 #[derive(Deserialize)]
@@ -27,6 +25,9 @@ struct GeminiMessage {
     timestamp: String,
     #[serde(default)]
     tokens: Option<GeminiTokens>,
+    /// Raw Gemini API counts, logged instead of `tokens` by some CLI versions
+    #[serde(default, rename = "usageMetadata")]
+    usage_metadata: Option<GeminiUsageMetadata>,
     model: Option<String>,
 }
 
@@ -40,29 +41,67 @@ struct GeminiTokens {
     thoughts: u64,
 }
 
+/// Gemini API `usageMetadata` block (`promptTokenCount` includes cached tokens,
+/// like `tokens.input`)
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+    #[serde(default)]
+    cached_content_token_count: u64,
+    #[serde(default)]
+    thoughts_token_count: u64,
+}
+
+impl From<GeminiUsageMetadata> for GeminiTokens {
+    fn from(usage: GeminiUsageMetadata) -> Self {
+        Self {
+            input: usage.prompt_token_count,
+            output: usage.candidates_token_count,
+            cached: usage.cached_content_token_count,
+            thoughts: usage.thoughts_token_count,
+        }
+    }
+}
+
 /// Parser for Gemini CLI usage data
 pub struct GeminiParser {
     data_dir: PathBuf,
+    json: JsonBackend,
 }
 
 impl GeminiParser {
     /// Create a new parser with default data directory (~/.gemini/tmp/)
     pub fn new() -> Self {
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
         let home = directories::BaseDirs::new()
             .map(|d| d.home_dir().to_path_buf())
             .unwrap_or_else(|| {
                 eprintln!("[toktrack] Warning: Could not determine home directory");
                 PathBuf::from(".")
             });
-        Self {
-            data_dir: home.join(".gemini").join("tmp"),
-        }
+        home.join(".gemini").join("tmp")
     }
 
     /// Create a parser with a custom data directory (for testing)
     #[allow(dead_code)]
     pub fn with_data_dir(data_dir: PathBuf) -> Self {
-        Self { data_dir }
+        Self {
+            data_dir,
+            json: JsonBackend::default(),
+        }
+    }
+
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
     }
 }
 
@@ -81,16 +120,20 @@ impl CLIParser for GeminiParser {
         &self.data_dir
     }
 
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
     fn file_pattern(&self) -> &str {
         "*/chats/session-*.json"
     }
 
     fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
-        let mut content = fs::read_to_string(path).map_err(ToktrackError::Io)?;
-        // SAFETY: `content` is exclusively owned and not aliased; safe for simd_json in-place mutation
-        let session: GeminiSession = unsafe {
-            simd_json::from_str(&mut content).map_err(|e| ToktrackError::Parse(e.to_string()))?
-        };
+        let mut content = fs::read(path).map_err(ToktrackError::Io)?;
+        let session: GeminiSession = self
+            .json
+            .decode(&mut content)
+            .map_err(ToktrackError::Parse)?;
 
         let mut entries = Vec::new();
 
@@ -100,7 +143,7 @@ impl CLIParser for GeminiParser {
                 continue;
             }
 
-            let tokens = match msg.tokens {
+            let tokens = match msg.tokens.or_else(|| msg.usage_metadata.map(Into::into)) {
                 Some(t) => t,
                 None => continue,
             };
@@ -129,6 +172,9 @@ impl CLIParser for GeminiParser {
                 request_id: Some(session.session_id.clone()),
                 source: Some("gemini".into()),
                 provider: None,
+                cost_estimated: false,
+                project_path: None,
+                git_branch: None,
             });
         }
 
@@ -259,4 +305,23 @@ mod tests {
             .iter()
             .all(|e| e.model.is_some() && e.model.as_deref() != Some("unknown")));
     }
+
+    #[test]
+    fn test_parse_usage_metadata_field_names() {
+        let parser = GeminiParser::with_data_dir(PathBuf::from("tests/fixtures/gemini"));
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/gemini/tmp789/chats/session-usage-metadata.json");
+        let entries = parser.parse_file(&path).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.model, Some("gemini-2.5-pro".to_string()));
+        assert_eq!(entry.input_tokens, 1200);
+        assert_eq!(entry.output_tokens, 300);
+        assert_eq!(entry.cache_read_tokens, 800);
+        assert_eq!(entry.cache_creation_tokens, 0);
+        assert_eq!(entry.thinking_tokens, 150);
+        assert_eq!(entry.message_id, Some("msg-102".to_string()));
+        assert_eq!(entry.request_id, Some("meta789".to_string()));
+    }
 }
@@ -1,37 +1,104 @@
 //! Parser traits and implementations for AI CLI tools
 
+mod aider;
 mod claude;
+mod cline;
 mod codex;
+mod continue_parser;
+mod cursor;
 mod gemini;
 mod opencode;
 
+pub use aider::AiderParser;
 pub use claude::ClaudeCodeParser;
+pub use cline::ClineParser;
 pub use codex::CodexParser;
+pub use continue_parser::ContinueParser;
+pub use cursor::CursorParser;
 pub use gemini::GeminiParser;
 pub use opencode::OpenCodeParser;
 
-use crate::types::{Result, UsageEntry};
+use crate::config::Config;
+use crate::types::{ParseProgress, ParseStats, Result, UsageEntry};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::io::BufRead;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 /// Trait for parsing usage data from AI CLI tools
 pub trait CLIParser: Send + Sync {
     /// Parser name (e.g., "claude-code")
-    #[allow(dead_code)] // Part of trait API, used in tests
     fn name(&self) -> &str;
 
     /// Data directory to scan for usage files
     fn data_dir(&self) -> &Path;
 
+    /// Whether the data directory exists (i.e. the CLI is installed and has run)
+    fn data_dir_exists(&self) -> bool {
+        self.data_dir().exists()
+    }
+
+    /// Data directory used when no custom directory is configured
+    fn default_data_dir(&self) -> PathBuf;
+
+    /// Key for this parser's cache files.
+    ///
+    /// Plain parser name for the default data directory (keeps existing cache
+    /// files valid); otherwise the name plus a short hash of the resolved
+    /// directory, so different data roots never share a cache.
+    fn cache_key(&self) -> String {
+        let data_dir = self.data_dir();
+        if data_dir == self.default_data_dir() {
+            return self.name().to_string();
+        }
+        let resolved = std::fs::canonicalize(data_dir).unwrap_or_else(|_| data_dir.to_path_buf());
+        format!("{}-{:08x}", self.name(), path_hash(&resolved))
+    }
+
     /// Glob pattern for finding usage files (e.g., "**/*.jsonl")
     fn file_pattern(&self) -> &str;
 
     /// Parse a single file and return usage entries
     fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>>;
 
+    /// Model names marking synthetic entries (no real API call behind them).
+    /// These are dropped by `parse_and_dedup*` unless synthetic entries are requested.
+    fn synthetic_models(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Whether `entry` is a synthetic response according to `synthetic_models`
+    fn is_synthetic(&self, entry: &UsageEntry) -> bool {
+        entry
+            .model
+            .as_deref()
+            .is_some_and(|model| self.synthetic_models().contains(&model))
+    }
+
+    /// Entries from the lines after byte `offset` of `path`, and the offset just
+    /// past the last complete line. None when the parser only reads whole files.
+    fn parse_file_from(
+        &self,
+        _path: &Path,
+        _offset: u64,
+    ) -> Option<Result<(Vec<UsageEntry>, u64)>> {
+        None
+    }
+
+    /// This parser as the Claude Code parser, for its session and project data
+    fn as_claude(&self) -> Option<&ClaudeCodeParser> {
+        None
+    }
+
+    /// Statistics accumulated across all `parse_file` calls on this parser
+    fn parse_stats(&self) -> ParseStats {
+        ParseStats::default()
+    }
+
     /// Parse all files in parallel using rayon, with deduplication
+    #[allow(dead_code)] // Part of trait API, used in benches and tests
     fn parse_all(&self) -> Result<Vec<UsageEntry>> {
         let files = self.collect_files();
         Self::parse_and_dedup(self, &files)
@@ -39,16 +106,12 @@ pub trait CLIParser: Send + Sync {
 
     /// Parse only files modified since `since`, with deduplication.
     /// Falls back to including files whose mtime cannot be read.
+    #[allow(dead_code)] // Part of trait API, used in benches and tests
     fn parse_recent_files(&self, since: SystemTime) -> Result<Vec<UsageEntry>> {
         let all_files = self.collect_files();
         let recent: Vec<PathBuf> = all_files
             .into_iter()
-            .filter(|f| {
-                f.metadata()
-                    .and_then(|m| m.modified())
-                    .map(|mtime| mtime >= since)
-                    .unwrap_or(true) // include on mtime failure (safe direction)
-            })
+            .filter(|f| modified_since(f, since))
             .collect();
         Self::parse_and_dedup(self, &recent)
     }
@@ -62,35 +125,187 @@ pub trait CLIParser: Send + Sync {
     }
 
     /// Parse files in parallel and deduplicate
+    #[allow(dead_code)] // Part of trait API, used in benches and tests
     fn parse_and_dedup(&self, files: &[PathBuf]) -> Result<Vec<UsageEntry>> {
+        self.parse_and_dedup_with_progress(files, None, false)
+    }
+
+    /// Parse files in parallel and deduplicate, advancing `progress` once per file.
+    /// Synthetic entries are dropped unless `include_synthetic` is set.
+    fn parse_and_dedup_with_progress(
+        &self,
+        files: &[PathBuf],
+        progress: Option<&ParseProgress>,
+        include_synthetic: bool,
+    ) -> Result<Vec<UsageEntry>> {
         let all_entries: Vec<UsageEntry> = files
             .par_iter()
-            .flat_map(|f| match self.parse_file(f) {
-                Ok(entries) => entries,
-                Err(e) => {
-                    eprintln!("[toktrack] Warning: Failed to parse {:?}: {}", f, e);
-                    Vec::new()
+            .flat_map(|f| {
+                let entries = match self.parse_file(f) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        eprintln!("[toktrack] Warning: Failed to parse {:?}: {}", f, e);
+                        Vec::new()
+                    }
+                };
+                if let Some(progress) = progress {
+                    progress.advance();
                 }
+                entries
             })
+            .filter(|entry| include_synthetic || !self.is_synthetic(entry))
             .collect();
 
-        // Deduplicate by message_id:request_id (same as ccusage)
-        let mut seen: HashSet<String> = HashSet::new();
-        let mut deduped: Vec<UsageEntry> = Vec::with_capacity(all_entries.len());
+        Ok(dedup_entries(all_entries))
+    }
+}
+
+/// How far a file has been read by `parse_file_from`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileBookmark {
+    pub path: PathBuf,
+    /// Inode when the bookmark was taken; a different one means the file was replaced
+    pub inode: u64,
+    /// End of the last complete line read
+    pub byte_offset: u64,
+}
+
+impl FileBookmark {
+    /// Bookmark at `byte_offset` of `path`, whose metadata is `meta`
+    pub fn new(path: &Path, meta: &std::fs::Metadata, byte_offset: u64) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            inode: file_inode(meta),
+            byte_offset,
+        }
+    }
+
+    /// Where to resume reading `path`; None when the bookmark is stale
+    /// (another file, or this one replaced or truncated)
+    pub fn resume_offset(&self, path: &Path, meta: &std::fs::Metadata) -> Option<u64> {
+        (self.path == path && self.inode == file_inode(meta) && self.byte_offset <= meta.len())
+            .then_some(self.byte_offset)
+    }
+}
+
+#[cfg(unix)]
+fn file_inode(meta: &std::fs::Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::ino(meta)
+}
+
+/// No inode outside Unix: replaced files are only caught when they shrink
+#[cfg(not(unix))]
+fn file_inode(_meta: &std::fs::Metadata) -> u64 {
+    0
+}
+
+/// Deduplicate by message_id:request_id (same as ccusage).
+/// Entries without a hash (missing message_id or request_id) are kept.
+pub(crate) fn dedup_entries(entries: Vec<UsageEntry>) -> Vec<UsageEntry> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut deduped: Vec<UsageEntry> = Vec::with_capacity(entries.len());
 
-        for entry in all_entries {
-            if let Some(hash) = entry.dedup_hash() {
+    for entry in entries {
+        match entry.dedup_hash() {
+            Some(hash) => {
                 if seen.insert(hash) {
                     deduped.push(entry);
                 }
-                // Skip duplicate (hash already in set)
-            } else {
-                // No hash (missing message_id or request_id) - keep entry
-                deduped.push(entry);
             }
+            None => deduped.push(entry),
+        }
+    }
+
+    deduped
+}
+
+/// Whether `path` was modified at or after `since`.
+/// Files whose mtime cannot be read are included (safe direction).
+pub(crate) fn modified_since(path: &Path, since: SystemTime) -> bool {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .map(|mtime| mtime >= since)
+        .unwrap_or(true)
+}
+
+/// Stream newline-terminated lines from `reader`, passing each line (without
+/// its terminator) to `on_line`.
+///
+/// A final line with no trailing newline is still being written by the CLI,
+/// so it is skipped instead of parsed. Otherwise it would be dropped as invalid
+/// JSON on one run and counted on the next, making today's totals jump.
+/// A line hit by a read error is skipped like an invalid one; an error that
+/// makes no progress ends the stream. Returns `true` if a truncated line was skipped.
+pub(crate) fn read_complete_lines<R: BufRead>(reader: R, on_line: impl FnMut(&mut [u8])) -> bool {
+    read_complete_lines_counted(reader, on_line).0
+}
+
+/// Like `read_complete_lines`, but also returns the bytes taken up by the
+/// complete lines (terminators included): where reading should resume.
+pub(crate) fn read_complete_lines_counted<R: BufRead>(
+    mut reader: R,
+    mut on_line: impl FnMut(&mut [u8]),
+) -> (bool, u64) {
+    let mut buf = Vec::new();
+    let mut consumed = 0u64;
+    let mut failed = false;
+    loop {
+        // On error, `read_until` keeps what it read; the rest of the line follows
+        let before = buf.len();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(_) => {}
+            Err(_) if buf.len() > before => {
+                failed = true;
+                continue;
+            }
+            Err(_) => return (false, consumed),
+        }
+        if buf.last() != Some(&b'\n') {
+            return (!buf.is_empty(), consumed);
         }
+        consumed += buf.len() as u64;
+        if !std::mem::take(&mut failed) {
+            buf.pop();
+            if buf.last() == Some(&b'\r') {
+                buf.pop();
+            }
+            on_line(&mut buf);
+        }
+        buf.clear();
+    }
+}
+
+/// Stable 32-bit FNV-1a hash of a path, used for cache file names.
+/// (`DefaultHasher` output is not guaranteed to be stable across Rust releases.)
+fn path_hash(path: &Path) -> u32 {
+    path.to_string_lossy()
+        .bytes()
+        .fold(0x811c_9dc5, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+        })
+}
 
-        Ok(deduped)
+/// JSON decoder used by the parsers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonBackend {
+    /// simd-json: fastest, decodes in place (default)
+    #[default]
+    Simd,
+    /// serde_json: slower but more forgiving (`--safe-parse`)
+    Serde,
+}
+
+impl JsonBackend {
+    /// Deserialize one JSON document from `bytes`.
+    /// simd-json may rewrite `bytes` in place while decoding.
+    pub(crate) fn decode<'a, T: serde::Deserialize<'a>>(
+        self,
+        bytes: &'a mut [u8],
+    ) -> std::result::Result<T, String> {
+        match self {
+            Self::Simd => simd_json::from_slice(bytes).map_err(|e| e.to_string()),
+            Self::Serde => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+        }
     }
 }
 
@@ -100,36 +315,103 @@ pub struct ParserRegistry {
 }
 
 impl ParserRegistry {
-    /// Create a new registry with default parsers
-    pub fn new() -> Self {
+    /// Create a new registry with default parsers.
+    /// `config.data_dirs` replaces a parser's data directory by name.
+    pub fn new(config: Option<&Config>) -> Self {
+        Self::with_json_backend(JsonBackend::default(), config)
+    }
+
+    /// Create a registry whose parsers decode JSON with `json`
+    pub fn with_json_backend(json: JsonBackend, config: Option<&Config>) -> Self {
+        let dir = |name: &str| config.and_then(|c| c.data_dir_for(name));
         Self {
             parsers: vec![
-                Box::new(ClaudeCodeParser::new()),
-                Box::new(CodexParser::new()),
-                Box::new(GeminiParser::new()),
-                Box::new(OpenCodeParser::new()),
+                Box::new(
+                    dir("claude-code")
+                        .map_or_else(ClaudeCodeParser::new, ClaudeCodeParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
+                Box::new(
+                    dir("codex")
+                        .map_or_else(CodexParser::new, CodexParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
+                Box::new(
+                    dir("gemini")
+                        .map_or_else(GeminiParser::new, GeminiParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
+                Box::new(
+                    dir("opencode")
+                        .map_or_else(OpenCodeParser::new, OpenCodeParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
+                Box::new(
+                    dir("cursor")
+                        .map_or_else(CursorParser::new, CursorParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
+                Box::new(
+                    dir("aider")
+                        .map_or_else(AiderParser::new, AiderParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
+                Box::new(
+                    dir("continue")
+                        .map_or_else(ContinueParser::new, ContinueParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
+                Box::new(
+                    dir("cline")
+                        .map_or_else(ClineParser::new, ClineParser::with_data_dir)
+                        .with_json_backend(json),
+                ),
             ],
         }
     }
 
+    /// Create a registry from explicit parsers (for testing)
+    #[cfg(test)]
+    pub(crate) fn from_parsers(parsers: Vec<Box<dyn CLIParser>>) -> Self {
+        Self { parsers }
+    }
+
     /// Get all registered parsers
     pub fn parsers(&self) -> &[Box<dyn CLIParser>] {
         &self.parsers
     }
 
+    /// Registered parsers whose data directory exists on disk
+    pub fn detect_active(&self) -> Vec<&dyn CLIParser> {
+        self.parsers
+            .iter()
+            .map(|p| p.as_ref())
+            .filter(|p| p.data_dir_exists())
+            .collect()
+    }
+
+    /// Take ownership of the registered parsers
+    pub fn into_parsers(self) -> Vec<Box<dyn CLIParser>> {
+        self.parsers
+    }
+
     /// Find a parser by name
-    #[allow(dead_code)] // Used in tests and future features
     pub fn get(&self, name: &str) -> Option<&dyn CLIParser> {
         self.parsers
             .iter()
             .find(|p| p.name() == name)
             .map(|p| p.as_ref())
     }
+
+    /// The registered Claude Code parser, with its configured directory and JSON backend
+    pub fn claude(&self) -> Option<&ClaudeCodeParser> {
+        self.parsers.iter().find_map(|p| p.as_claude())
+    }
 }
 
 impl Default for ParserRegistry {
     fn default() -> Self {
-        Self::new()
+        Self::new(None)
     }
 }
 
@@ -137,19 +419,121 @@ impl Default for ParserRegistry {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_registry_config_overrides_data_dir() {
+        let config = Config {
+            data_dirs: std::collections::HashMap::from([(
+                "codex".to_string(),
+                PathBuf::from("/tmp/codex-archive"),
+            )]),
+            ..Config::default()
+        };
+        let registry = ParserRegistry::new(Some(&config));
+        let codex = registry.get("codex").unwrap();
+        assert_eq!(codex.data_dir(), Path::new("/tmp/codex-archive"));
+        let claude = registry.get("claude-code").unwrap();
+        assert_eq!(claude.data_dir(), claude.default_data_dir());
+    }
+
+    #[test]
+    fn test_detect_active_skips_missing_data_dirs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            data_dirs: std::collections::HashMap::from([
+                ("codex".to_string(), dir.path().to_path_buf()),
+                ("gemini".to_string(), dir.path().join("missing")),
+            ]),
+            ..Config::default()
+        };
+        let registry = ParserRegistry::new(Some(&config));
+        assert!(!registry.get("gemini").unwrap().data_dir_exists());
+        let active: Vec<&str> = registry.detect_active().iter().map(|p| p.name()).collect();
+        assert!(active.contains(&"codex"));
+        assert!(!active.contains(&"gemini"));
+    }
+
     #[test]
     fn test_registry_default_parsers() {
-        let registry = ParserRegistry::new();
-        assert_eq!(registry.parsers().len(), 4);
+        let registry = ParserRegistry::new(None);
+        assert_eq!(registry.parsers().len(), 8);
         assert!(registry.get("claude-code").is_some());
         assert!(registry.get("codex").is_some());
         assert!(registry.get("gemini").is_some());
         assert!(registry.get("opencode").is_some());
+        assert!(registry.get("cursor").is_some());
+        assert!(registry.get("aider").is_some());
+        assert!(registry.get("continue").is_some());
+        assert!(registry.get("cline").is_some());
+    }
+
+    /// Parse every fixture directory with `json` and return all entries in file order
+    fn parse_fixtures(json: JsonBackend) -> Vec<UsageEntry> {
+        let parsers: Vec<Box<dyn CLIParser>> = vec![
+            Box::new(
+                ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"))
+                    .with_json_backend(json),
+            ),
+            Box::new(
+                CodexParser::with_data_dir(PathBuf::from("tests/fixtures/codex"))
+                    .with_json_backend(json),
+            ),
+            Box::new(
+                GeminiParser::with_data_dir(PathBuf::from("tests/fixtures/gemini"))
+                    .with_json_backend(json),
+            ),
+            Box::new(
+                OpenCodeParser::with_data_dir(PathBuf::from("tests/fixtures/opencode/storage"))
+                    .with_json_backend(json),
+            ),
+            Box::new(
+                CursorParser::with_data_dir(PathBuf::from("tests/data/cursor"))
+                    .with_json_backend(json),
+            ),
+            Box::new(
+                AiderParser::with_data_dir(PathBuf::from("tests/data/aider"))
+                    .with_json_backend(json),
+            ),
+            Box::new(
+                ContinueParser::with_data_dir(PathBuf::from("tests/data/continue"))
+                    .with_json_backend(json),
+            ),
+            Box::new(
+                ClineParser::with_data_dir(PathBuf::from("tests/data/cline"))
+                    .with_json_backend(json),
+            ),
+        ];
+        parsers
+            .iter()
+            .flat_map(|parser| {
+                let mut files = parser.collect_files();
+                files.sort();
+                files
+                    .iter()
+                    .flat_map(|f| parser.parse_file(f).unwrap_or_default())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_json_backends_produce_identical_entries() {
+        let simd = parse_fixtures(JsonBackend::Simd);
+        let serde = parse_fixtures(JsonBackend::Serde);
+        assert!(!simd.is_empty());
+        for source in [
+            "claude", "codex", "gemini", "opencode", "cursor", "aider", "continue", "cline",
+        ] {
+            assert!(
+                simd.iter().any(|e| e.source.as_deref() == Some(source)),
+                "no {source} entries in fixtures"
+            );
+        }
+        assert_eq!(simd, serde);
     }
 
     #[test]
     fn test_registry_get_unknown() {
-        let registry = ParserRegistry::new();
+        let registry = ParserRegistry::new(None);
         assert!(registry.get("unknown-parser").is_none());
     }
 
@@ -221,4 +605,110 @@ mod tests {
         // claude-sample.jsonl, empty.jsonl, multi/file1.jsonl, multi/file2.jsonl, codex/sample-session.jsonl, codex/multi-turn-session.jsonl
         assert_eq!(files.len(), 6);
     }
+
+    #[test]
+    fn test_parse_progress_reaches_file_count() {
+        let parser = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
+        let files = parser.collect_files();
+        let progress = ParseProgress::default();
+        progress.start(files.len());
+
+        parser
+            .parse_and_dedup_with_progress(&files, Some(&progress), false)
+            .unwrap();
+        assert_eq!(progress.snapshot(), (files.len(), files.len()));
+    }
+
+    #[test]
+    fn test_read_complete_lines_skips_unterminated_tail() {
+        let input: &[u8] = b"first\r\nsecond\nthird-partial";
+        let mut lines = Vec::new();
+        let truncated = read_complete_lines(input, |line| lines.push(line.to_vec()));
+        assert!(truncated);
+        assert_eq!(lines, vec![b"first".to_vec(), b"second".to_vec()]);
+    }
+
+    #[test]
+    fn test_read_complete_lines_all_terminated() {
+        let input: &[u8] = b"first\nsecond\n";
+        let mut count = 0;
+        let truncated = read_complete_lines(input, |_| count += 1);
+        assert!(!truncated);
+        assert_eq!(count, 2);
+    }
+
+    /// Yields `data`, failing once on reaching each `fail_at` offset
+    struct FlakyReader {
+        data: &'static [u8],
+        pos: usize,
+        fail_at: Vec<usize>,
+    }
+
+    impl std::io::Read for FlakyReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.fail_at.first() == Some(&self.pos) {
+                self.fail_at.remove(0);
+                return Err(std::io::Error::other("flaky"));
+            }
+            let end = self
+                .fail_at
+                .first()
+                .copied()
+                .unwrap_or(self.data.len())
+                .min(self.pos + buf.len());
+            let n = end - self.pos;
+            buf[..n].copy_from_slice(&self.data[self.pos..end]);
+            self.pos = end;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_read_complete_lines_skips_line_with_read_error() {
+        let data: &[u8] = b"first\nsecond\nthird\n";
+        let reader = std::io::BufReader::new(FlakyReader {
+            data,
+            pos: 0,
+            fail_at: vec![9],
+        });
+        let mut lines = Vec::new();
+        let (truncated, consumed) =
+            read_complete_lines_counted(reader, |line| lines.push(line.to_vec()));
+        assert!(!truncated);
+        assert_eq!(consumed, data.len() as u64);
+        assert_eq!(lines, vec![b"first".to_vec(), b"third".to_vec()]);
+
+        // Failing at a line start makes no progress: the lines so far are kept
+        let reader = std::io::BufReader::new(FlakyReader {
+            data,
+            pos: 0,
+            fail_at: vec![6],
+        });
+        let mut count = 0;
+        assert_eq!(
+            read_complete_lines_counted(reader, |_| count += 1),
+            (false, 6)
+        );
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_cache_key_default_dir_is_plain_name() {
+        let parser = ClaudeCodeParser::new();
+        assert_eq!(parser.cache_key(), "claude-code");
+        assert_eq!(CodexParser::new().cache_key(), "codex");
+    }
+
+    #[test]
+    fn test_cache_key_custom_dir_is_hashed() {
+        let a = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures"));
+        let b = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/fixtures/multi"));
+
+        let key_a = a.cache_key();
+        assert!(key_a.starts_with("claude-code-"));
+        assert_eq!(key_a.len(), "claude-code-".len() + 8);
+        assert_ne!(key_a, b.cache_key());
+        // Stable across calls
+        assert_eq!(key_a, a.cache_key());
+    }
 }
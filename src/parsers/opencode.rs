@@ -6,7 +6,7 @@ use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::CLIParser;
+use super::{CLIParser, JsonBackend};
 
 /// OpenCode message JSON structure
 #[derive(Deserialize)]
@@ -47,13 +47,18 @@ struct OpenCodeCache {
 /// Parser for OpenCode CLI usage data
 pub struct OpenCodeParser {
     data_dir: PathBuf,
+    json: JsonBackend,
 }
 
 impl OpenCodeParser {
     /// Create a new parser with default data directory (~/.local/share/opencode/storage/message)
     /// OpenCode uses XDG standard, so we use ~/.local/share on all platforms
     pub fn new() -> Self {
-        let data_dir = directories::BaseDirs::new()
+        Self::with_data_dir(Self::default_dir())
+    }
+
+    fn default_dir() -> PathBuf {
+        directories::BaseDirs::new()
             .map(|d| d.home_dir().join(".local").join("share"))
             .unwrap_or_else(|| {
                 eprintln!("[toktrack] Warning: Could not determine home directory");
@@ -61,14 +66,22 @@ impl OpenCodeParser {
             })
             .join("opencode")
             .join("storage")
-            .join("message");
-        Self { data_dir }
+            .join("message")
     }
 
     /// Create a parser with a custom data directory (for testing)
     #[allow(dead_code)]
     pub fn with_data_dir(data_dir: PathBuf) -> Self {
-        Self { data_dir }
+        Self {
+            data_dir,
+            json: JsonBackend::default(),
+        }
+    }
+
+    /// Decode JSON with `json` instead of the default simd-json backend
+    pub fn with_json_backend(mut self, json: JsonBackend) -> Self {
+        self.json = json;
+        self
     }
 }
 
@@ -87,16 +100,20 @@ impl CLIParser for OpenCodeParser {
         &self.data_dir
     }
 
+    fn default_data_dir(&self) -> PathBuf {
+        Self::default_dir()
+    }
+
     fn file_pattern(&self) -> &str {
         "**/msg_*.json"
     }
 
     fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
-        let mut content = fs::read_to_string(path).map_err(ToktrackError::Io)?;
-        // SAFETY: `content` is exclusively owned and not aliased; safe for simd_json in-place mutation
-        let message: OpenCodeMessage = unsafe {
-            simd_json::from_str(&mut content).map_err(|e| ToktrackError::Parse(e.to_string()))?
-        };
+        let mut content = fs::read(path).map_err(ToktrackError::Io)?;
+        let message: OpenCodeMessage = self
+            .json
+            .decode(&mut content)
+            .map_err(ToktrackError::Parse)?;
 
         // Skip messages without token data
         let tokens = match message.tokens {
@@ -136,6 +153,9 @@ impl CLIParser for OpenCodeParser {
             request_id: Some(message.session_id),
             source: Some("opencode".into()),
             provider: message.provider_id,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
 
         Ok(vec![entry])
@@ -1,12 +1,36 @@
 //! Aggregator service for computing usage statistics
 
-use super::normalize_model_name;
-use crate::types::{DailySummary, ModelUsage, SourceUsage, TotalSummary, UsageEntry};
-use chrono::Datelike;
+use super::{normalize_model_name, provider_for_model};
+use crate::config::WeekStart;
+use crate::types::{
+    BranchUsage, DailySummary, DayOfWeekStats, HourlySummary, ModelUsage, ProjectSummary,
+    ProviderSummary, SessionInfo, SourceModelBreakdown, SourceUsage, TotalSummary, UsageEntry,
+};
+use chrono::{Datelike, Local, NaiveDate, TimeZone, Timelike, Weekday};
 use std::collections::{HashMap, HashSet};
 
 pub struct Aggregator;
 
+/// Last path segment of a project directory (`/home/me/webapp` → `webapp`)
+fn project_name(path: &str) -> &str {
+    match path
+        .trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+    {
+        Some(name) if !name.is_empty() => name,
+        _ => path,
+    }
+}
+
+/// Branch to group usage under; missing, empty and `HEAD` (detached) become "unknown"
+fn branch_name(branch: Option<&str>) -> &str {
+    match branch.map(str::trim) {
+        None | Some("" | "HEAD") => "unknown",
+        Some(branch) => branch,
+    }
+}
+
 /// Accumulate token fields and cost from `source` into `target`
 fn accumulate_summary(target: &mut DailySummary, source: &DailySummary) {
     target.total_input_tokens = target
@@ -25,6 +49,7 @@ fn accumulate_summary(target: &mut DailySummary, source: &DailySummary) {
         .total_thinking_tokens
         .saturating_add(source.total_thinking_tokens);
     target.total_cost_usd += source.total_cost_usd;
+    target.cost_estimated |= source.cost_estimated;
 
     for (model_name, model_usage) in &source.models {
         let t = target.models.entry(model_name.clone()).or_default();
@@ -32,6 +57,41 @@ fn accumulate_summary(target: &mut DailySummary, source: &DailySummary) {
     }
 }
 
+/// Add one source's usage to `provider`'s summary
+fn add_provider_usage(
+    providers: &mut HashMap<String, ProviderSummary>,
+    provider: &str,
+    source: &str,
+    tokens: u64,
+    cost: f64,
+) {
+    let summary = providers
+        .entry(provider.to_string())
+        .or_insert_with(|| ProviderSummary {
+            provider: provider.to_string(),
+            ..ProviderSummary::default()
+        });
+    summary.total_tokens = summary.total_tokens.saturating_add(tokens);
+    summary.total_cost_usd += cost;
+    if !summary.sources.iter().any(|s| s == source) {
+        summary.sources.push(source.to_string());
+    }
+}
+
+/// Providers by cost descending, each with its sources sorted by name
+fn sorted_providers(providers: HashMap<String, ProviderSummary>) -> Vec<ProviderSummary> {
+    let mut providers: Vec<ProviderSummary> = providers.into_values().collect();
+    for summary in &mut providers {
+        summary.sources.sort();
+    }
+    providers.sort_by(|a, b| {
+        b.total_cost_usd
+            .total_cmp(&a.total_cost_usd)
+            .then_with(|| a.provider.cmp(&b.provider))
+    });
+    providers
+}
+
 /// Merge model usage fields from `source` into `target`
 fn merge_model_usage(target: &mut ModelUsage, source: &ModelUsage) {
     target.input_tokens = target.input_tokens.saturating_add(source.input_tokens);
@@ -50,7 +110,13 @@ fn merge_model_usage(target: &mut ModelUsage, source: &ModelUsage) {
 }
 
 impl Aggregator {
+    /// Daily summaries grouped by the local calendar date
     pub fn daily(entries: &[UsageEntry]) -> Vec<DailySummary> {
+        Self::daily_in(entries, &Local)
+    }
+
+    /// Daily summaries grouped by calendar date in `tz`
+    pub(crate) fn daily_in<Tz: TimeZone>(entries: &[UsageEntry], tz: &Tz) -> Vec<DailySummary> {
         if entries.is_empty() {
             return Vec::new();
         }
@@ -59,8 +125,8 @@ impl Aggregator {
         let mut daily_map: HashMap<chrono::NaiveDate, DailySummary> = HashMap::new();
 
         for entry in entries {
-            let date = entry.local_date();
-            let cost = entry.cost_usd.unwrap_or(0.0);
+            let date = entry.date_in(tz);
+            let cost = entry.cost_or_zero();
             let model_name = normalize_model_name(entry.model.as_deref().unwrap_or("unknown"));
 
             let summary = daily_map.entry(date).or_insert_with(|| DailySummary {
@@ -72,6 +138,7 @@ impl Aggregator {
                 total_thinking_tokens: 0,
                 total_cost_usd: 0.0,
                 models: HashMap::new(),
+                cost_estimated: false,
             });
 
             summary.total_input_tokens = summary
@@ -90,6 +157,7 @@ impl Aggregator {
                 .total_thinking_tokens
                 .saturating_add(entry.thinking_tokens);
             summary.total_cost_usd += cost;
+            summary.cost_estimated |= entry.cost_estimated;
 
             // Update model breakdown
             let model_usage = summary.models.entry(model_name).or_default();
@@ -102,8 +170,200 @@ impl Aggregator {
         result
     }
 
+    /// Usage per local hour of day; index 0 is midnight–1 AM
+    #[allow(dead_code)]
+    pub fn by_hour(entries: &[UsageEntry]) -> [ModelUsage; 24] {
+        Self::by_hour_in(entries, &Local)
+    }
+
+    /// Usage per hour of day in `tz`
+    pub(crate) fn by_hour_in<Tz: TimeZone>(entries: &[UsageEntry], tz: &Tz) -> [ModelUsage; 24] {
+        let mut hours: [ModelUsage; 24] = std::array::from_fn(|_| ModelUsage::default());
+        for entry in entries {
+            let hour = entry.timestamp.with_timezone(tz).hour() as usize;
+            hours[hour].add(entry, entry.cost_or_zero());
+        }
+        hours
+    }
+
+    /// One summary per local hour of day (all 24), with a per-model breakdown
+    pub fn hourly(entries: &[UsageEntry]) -> Vec<HourlySummary> {
+        Self::hourly_in(entries, &Local)
+    }
+
+    /// One summary per hour of day in `tz`
+    pub(crate) fn hourly_in<Tz: TimeZone>(entries: &[UsageEntry], tz: &Tz) -> Vec<HourlySummary> {
+        let mut models: [HashMap<String, ModelUsage>; 24] = std::array::from_fn(|_| HashMap::new());
+        for entry in entries {
+            let hour = entry.timestamp.with_timezone(tz).hour() as usize;
+            let model_name = normalize_model_name(entry.model.as_deref().unwrap_or("unknown"));
+            models[hour]
+                .entry(model_name)
+                .or_default()
+                .add(entry, entry.cost_or_zero());
+        }
+        Self::by_hour_in(entries, tz)
+            .into_iter()
+            .zip(models)
+            .enumerate()
+            .map(|(hour, (usage, models))| HourlySummary {
+                hour: hour as u8,
+                total_input_tokens: usage.input_tokens,
+                total_output_tokens: usage.output_tokens,
+                total_cost_usd: usage.cost_usd,
+                models,
+            })
+            .collect()
+    }
+
+    /// Moving average of `total_cost_usd` over `window` days centered on each
+    /// day, sorted ascending by date. Edge days average over the part of the
+    /// window that exists (no zero padding).
+    pub fn moving_average(
+        summaries: &[DailySummary],
+        window: usize,
+    ) -> Vec<(chrono::NaiveDate, f64)> {
+        let mut days: Vec<(chrono::NaiveDate, f64)> = summaries
+            .iter()
+            .map(|s| (s.date, s.total_cost_usd))
+            .collect();
+        days.sort_by_key(|(date, _)| *date);
+
+        let window = window.max(1);
+        let before = (window - 1) / 2;
+        let after = window / 2;
+        (0..days.len())
+            .map(|i| {
+                let span = &days[i.saturating_sub(before)..=(i + after).min(days.len() - 1)];
+                let sum: f64 = span.iter().map(|(_, cost)| cost).sum();
+                (days[i].0, sum / span.len() as f64)
+            })
+            .collect()
+    }
+
+    /// Running total of `total_cost_usd` per day. `summaries` must be sorted
+    /// ascending by date; the last sum is the lifetime cost.
+    pub fn cumulative(summaries: &[DailySummary]) -> Vec<(chrono::NaiveDate, f64)> {
+        summaries
+            .iter()
+            .scan(0.0, |total, s| {
+                *total += s.total_cost_usd;
+                Some((s.date, *total))
+            })
+            .collect()
+    }
+
+    /// The `p`-th percentile (0.0..=1.0) of daily `total_cost_usd`, linearly
+    /// interpolated between the nearest days. 0.0 with no days.
+    pub fn percentile_cost(summaries: &[DailySummary], p: f64) -> f64 {
+        let mut costs: Vec<f64> = summaries.iter().map(|s| s.total_cost_usd).collect();
+        if costs.is_empty() {
+            return 0.0;
+        }
+        costs.sort_by(f64::total_cmp);
+
+        let rank = p.clamp(0.0, 1.0) * (costs.len() - 1) as f64;
+        let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+        costs[lower] + (costs[upper] - costs[lower]) * (rank - lower as f64)
+    }
+
+    /// Population variance of daily `total_cost_usd`. 0.0 with no days.
+    pub fn variance_cost(summaries: &[DailySummary]) -> f64 {
+        if summaries.is_empty() {
+            return 0.0;
+        }
+        let n = summaries.len() as f64;
+        let mean = summaries.iter().map(|s| s.total_cost_usd).sum::<f64>() / n;
+        summaries
+            .iter()
+            .map(|s| (s.total_cost_usd - mean).powi(2))
+            .sum::<f64>()
+            / n
+    }
+
+    /// Consecutive days with usage ending today; 0 without usage today.
+    /// `summaries` must be sorted ascending by date (as from `daily`).
+    pub fn streak(summaries: &[DailySummary]) -> u32 {
+        Self::streak_on(summaries, Local::now().date_naive())
+    }
+
+    /// `streak` as of `today`
+    pub fn streak_on(summaries: &[DailySummary], today: NaiveDate) -> u32 {
+        let mut expected = Some(today);
+        let mut streak = 0;
+        for summary in summaries
+            .iter()
+            .rev()
+            .filter(|s| s.date <= today && s.total_tokens() > 0)
+        {
+            if Some(summary.date) != expected {
+                break;
+            }
+            streak += 1;
+            expected = summary.date.pred_opt();
+        }
+        streak
+    }
+
+    /// Longest run of consecutive days with usage and the day it ended; the
+    /// earliest run wins a tie. `summaries` must be sorted ascending by date.
+    pub fn longest_streak(summaries: &[DailySummary]) -> (u32, Option<NaiveDate>) {
+        let mut longest = (0, None);
+        let mut current = 0;
+        let mut prev: Option<NaiveDate> = None;
+        for summary in summaries.iter().filter(|s| s.total_tokens() > 0) {
+            current = match prev {
+                Some(p) if p.succ_opt() == Some(summary.date) => current + 1,
+                Some(p) if p == summary.date => current,
+                _ => 1,
+            };
+            if current > longest.0 {
+                longest = (current, Some(summary.date));
+            }
+            prev = Some(summary.date);
+        }
+        longest
+    }
+
+    /// Usage per weekday; index 0 is Monday
+    pub fn by_day_of_week(summaries: &[DailySummary]) -> [DayOfWeekStats; 7] {
+        let mut weekday = Weekday::Mon;
+        let mut days: [DayOfWeekStats; 7] = std::array::from_fn(|_| {
+            let day = weekday;
+            weekday = weekday.succ();
+            DayOfWeekStats {
+                day,
+                total_cost_usd: 0.0,
+                total_tokens: 0,
+                avg_cost_usd: 0.0,
+                day_count: 0,
+            }
+        });
+        for summary in summaries {
+            let day = &mut days[summary.date.weekday().num_days_from_monday() as usize];
+            day.total_cost_usd += summary.total_cost_usd;
+            day.total_tokens = day.total_tokens.saturating_add(summary.total_tokens());
+            day.day_count += 1;
+        }
+        for day in &mut days {
+            if day.day_count > 0 {
+                day.avg_cost_usd = day.total_cost_usd / day.day_count as f64;
+            }
+        }
+        days
+    }
+
     /// Aggregate daily summaries into weekly summaries (Sunday-start weeks)
+    #[allow(dead_code)]
     pub fn weekly(daily_summaries: &[DailySummary]) -> Vec<DailySummary> {
+        Self::weekly_starting(daily_summaries, WeekStart::Sunday)
+    }
+
+    /// Aggregate daily summaries into weekly summaries, weeks starting on `first_day`
+    pub fn weekly_starting(
+        daily_summaries: &[DailySummary],
+        first_day: WeekStart,
+    ) -> Vec<DailySummary> {
         if daily_summaries.is_empty() {
             return Vec::new();
         }
@@ -111,11 +371,15 @@ impl Aggregator {
         let mut week_map: HashMap<chrono::NaiveDate, DailySummary> = HashMap::new();
 
         for summary in daily_summaries {
-            // Calculate the Sunday that starts this week
-            let days_from_sunday = summary.date.weekday().num_days_from_sunday();
+            // Calculate the first day of this week
+            let weekday = summary.date.weekday();
+            let days_into_week = match first_day {
+                WeekStart::Sunday => weekday.num_days_from_sunday(),
+                WeekStart::Monday => weekday.num_days_from_monday(),
+            };
             let week_start = summary
                 .date
-                .checked_sub_signed(chrono::Duration::days(days_from_sunday as i64))
+                .checked_sub_signed(chrono::Duration::days(days_into_week as i64))
                 .unwrap_or(summary.date);
 
             let week_summary = week_map.entry(week_start).or_insert_with(|| DailySummary {
@@ -127,6 +391,7 @@ impl Aggregator {
                 total_thinking_tokens: 0,
                 total_cost_usd: 0.0,
                 models: HashMap::new(),
+                cost_estimated: false,
             });
 
             accumulate_summary(week_summary, summary);
@@ -159,6 +424,7 @@ impl Aggregator {
                 total_thinking_tokens: 0,
                 total_cost_usd: 0.0,
                 models: HashMap::new(),
+                cost_estimated: false,
             });
 
             accumulate_summary(month_summary, summary);
@@ -169,13 +435,47 @@ impl Aggregator {
         result
     }
 
+    /// Aggregate daily summaries into calendar quarters (Q1 = Jan–Mar);
+    /// each summary is dated the first day of its quarter
+    pub fn quarterly(daily_summaries: &[DailySummary]) -> Vec<DailySummary> {
+        if daily_summaries.is_empty() {
+            return Vec::new();
+        }
+
+        let mut quarter_map: HashMap<(i32, u32), DailySummary> = HashMap::new();
+
+        for summary in daily_summaries {
+            let key = (summary.date.year(), summary.date.month0() / 3);
+            let quarter_start =
+                chrono::NaiveDate::from_ymd_opt(key.0, key.1 * 3 + 1, 1).unwrap_or(summary.date);
+
+            let quarter_summary = quarter_map.entry(key).or_insert_with(|| DailySummary {
+                date: quarter_start,
+                total_input_tokens: 0,
+                total_output_tokens: 0,
+                total_cache_read_tokens: 0,
+                total_cache_creation_tokens: 0,
+                total_thinking_tokens: 0,
+                total_cost_usd: 0.0,
+                models: HashMap::new(),
+                cost_estimated: false,
+            });
+
+            accumulate_summary(quarter_summary, summary);
+        }
+
+        let mut result: Vec<DailySummary> = quarter_map.into_values().collect();
+        result.sort_by_key(|s| s.date);
+        result
+    }
+
     #[allow(dead_code)]
     pub fn by_model(entries: &[UsageEntry]) -> HashMap<String, ModelUsage> {
         let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
 
         for entry in entries {
             let model_name = normalize_model_name(entry.model.as_deref().unwrap_or("unknown"));
-            let cost = entry.cost_usd.unwrap_or(0.0);
+            let cost = entry.cost_or_zero();
 
             let usage = model_map.entry(model_name).or_default();
             usage.add(entry, cost);
@@ -258,7 +558,7 @@ impl Aggregator {
             summary.total_thinking_tokens = summary
                 .total_thinking_tokens
                 .saturating_add(entry.thinking_tokens);
-            summary.total_cost_usd += entry.cost_usd.unwrap_or(0.0);
+            summary.total_cost_usd += entry.cost_or_zero();
             summary.entry_count = summary.entry_count.saturating_add(1);
 
             dates.insert(entry.local_date());
@@ -275,12 +575,8 @@ impl Aggregator {
 
         for entry in entries {
             let source = entry.source.as_deref().unwrap_or("unknown").to_string();
-            let total_tokens = entry.input_tokens
-                + entry.output_tokens
-                + entry.cache_read_tokens
-                + entry.cache_creation_tokens
-                + entry.thinking_tokens;
-            let cost = entry.cost_usd.unwrap_or(0.0);
+            let total_tokens = entry.total_tokens();
+            let cost = entry.cost_or_zero();
 
             let entry_stats = source_map.entry(source).or_insert((0, 0.0));
             entry_stats.0 = entry_stats.0.saturating_add(total_tokens);
@@ -293,6 +589,7 @@ impl Aggregator {
                 source,
                 total_tokens,
                 total_cost_usd,
+                by_model: Vec::new(),
             })
             .collect();
 
@@ -301,6 +598,206 @@ impl Aggregator {
         result
     }
 
+    /// Aggregate entries by `provider` ("unknown" when unset), costliest first
+    #[allow(dead_code)]
+    pub fn by_provider(entries: &[UsageEntry]) -> Vec<ProviderSummary> {
+        let mut providers: HashMap<String, ProviderSummary> = HashMap::new();
+        for entry in entries {
+            let provider = entry.provider.as_deref().unwrap_or("unknown");
+            let source = entry.source.as_deref().unwrap_or("unknown");
+            add_provider_usage(
+                &mut providers,
+                provider,
+                source,
+                entry.total_tokens(),
+                entry.cost_or_zero(),
+            );
+        }
+        sorted_providers(providers)
+    }
+
+    /// `by_provider` for per-source daily summaries, which keep only model names,
+    /// so the provider comes from `provider_for_model`
+    pub fn by_provider_from_daily(
+        source_summaries: &HashMap<String, Vec<DailySummary>>,
+    ) -> Vec<ProviderSummary> {
+        let mut providers: HashMap<String, ProviderSummary> = HashMap::new();
+        for (source, summaries) in source_summaries {
+            for (model, usage) in Self::by_model_from_daily(summaries) {
+                add_provider_usage(
+                    &mut providers,
+                    provider_for_model(&model),
+                    source,
+                    usage.total_tokens(),
+                    usage.cost_usd,
+                );
+            }
+        }
+        sorted_providers(providers)
+    }
+
+    /// Usage per source, then per normalized model name
+    #[allow(dead_code)]
+    pub fn by_source_and_model(
+        entries: &[UsageEntry],
+    ) -> HashMap<String, HashMap<String, ModelUsage>> {
+        let mut result: HashMap<String, HashMap<String, ModelUsage>> = HashMap::new();
+        for entry in entries {
+            let source = entry.source.as_deref().unwrap_or("unknown").to_string();
+            let model = normalize_model_name(entry.model.as_deref().unwrap_or("unknown"));
+            result
+                .entry(source)
+                .or_default()
+                .entry(model)
+                .or_default()
+                .add(entry, entry.cost_or_zero());
+        }
+        result
+    }
+
+    /// Models sorted by cost descending, ties by name
+    pub fn models_by_cost(models: HashMap<String, ModelUsage>) -> Vec<(String, ModelUsage)> {
+        let mut models: Vec<(String, ModelUsage)> = models.into_iter().collect();
+        models.sort_by(|(a_name, a), (b_name, b)| {
+            b.cost_usd
+                .total_cmp(&a.cost_usd)
+                .then_with(|| a_name.cmp(b_name))
+        });
+        models
+    }
+
+    /// `by_source_and_model` output as a list, costliest source and model first
+    pub fn source_model_breakdown(
+        by_source: HashMap<String, HashMap<String, ModelUsage>>,
+    ) -> Vec<SourceModelBreakdown> {
+        let mut result: Vec<SourceModelBreakdown> = by_source
+            .into_iter()
+            .map(|(source, models)| SourceModelBreakdown {
+                source,
+                models: Self::models_by_cost(models),
+            })
+            .collect();
+        let cost = |b: &SourceModelBreakdown| b.models.iter().map(|(_, u)| u.cost_usd).sum::<f64>();
+        result.sort_by(|a, b| {
+            cost(b)
+                .total_cmp(&cost(a))
+                .then_with(|| a.source.cmp(&b.source))
+        });
+        result
+    }
+
+    /// Aggregate session cost and tokens by git branch, costliest first.
+    /// Empty and `HEAD` (detached) branches are grouped as "unknown".
+    pub fn by_branch(sessions: &[SessionInfo]) -> Vec<BranchUsage> {
+        let mut branch_map: HashMap<&str, BranchUsage> = HashMap::new();
+
+        for session in sessions {
+            let branch = branch_name(Some(&session.git_branch));
+            let usage = branch_map.entry(branch).or_insert_with(|| BranchUsage {
+                branch: branch.to_string(),
+                ..BranchUsage::default()
+            });
+            *usage.session_count.get_or_insert(0) += 1;
+            usage.total_tokens = usage.total_tokens.saturating_add(session.total_tokens);
+            usage.total_cost_usd += session.total_cost_usd;
+        }
+
+        let mut result: Vec<BranchUsage> = branch_map.into_values().collect();
+        result.sort_by(|a, b| {
+            b.total_cost_usd
+                .total_cmp(&a.total_cost_usd)
+                .then_with(|| a.branch.cmp(&b.branch))
+        });
+        result
+    }
+
+    /// Usage per git branch of individual entries, so sessions that switch
+    /// branches are split. Missing, empty and `HEAD` branches are "unknown".
+    pub fn usage_by_branch(entries: &[UsageEntry]) -> HashMap<String, ModelUsage> {
+        let mut branch_map: HashMap<String, ModelUsage> = HashMap::new();
+        for entry in entries {
+            branch_map
+                .entry(branch_name(entry.git_branch.as_deref()).to_string())
+                .or_default()
+                .add(entry, entry.cost_or_zero());
+        }
+        branch_map
+    }
+
+    /// One row per branch (see `usage_by_branch`), costliest first
+    pub fn branch_summaries(entries: &[UsageEntry]) -> Vec<BranchUsage> {
+        let mut result: Vec<BranchUsage> = Self::usage_by_branch(entries)
+            .into_iter()
+            .map(|(branch, usage)| BranchUsage {
+                branch,
+                session_count: None,
+                total_tokens: usage.total_tokens(),
+                total_cost_usd: usage.cost_usd,
+            })
+            .collect();
+        result.sort_by(|a, b| {
+            b.total_cost_usd
+                .total_cmp(&a.total_cost_usd)
+                .then_with(|| a.branch.cmp(&b.branch))
+        });
+        result
+    }
+
+    /// Daily summaries per project, keyed by the basename of `project_path`.
+    /// Entries without a project path are skipped.
+    pub fn by_project(entries: &[UsageEntry]) -> HashMap<String, Vec<DailySummary>> {
+        let mut project_map: HashMap<String, Vec<UsageEntry>> = HashMap::new();
+        for entry in entries {
+            if let Some(path) = entry.project_path.as_deref() {
+                project_map
+                    .entry(project_name(path).to_string())
+                    .or_default()
+                    .push(entry.clone());
+            }
+        }
+        project_map
+            .into_iter()
+            .map(|(name, entries)| (name, Self::daily(&entries)))
+            .collect()
+    }
+
+    /// One summary per project (see `by_project`), costliest first
+    pub fn project_summaries(entries: &[UsageEntry]) -> Vec<ProjectSummary> {
+        // Latest full path per basename, so the summary points at the current checkout
+        let mut latest_paths: HashMap<&str, (chrono::DateTime<chrono::Utc>, &str)> = HashMap::new();
+        for entry in entries {
+            if let Some(path) = entry.project_path.as_deref() {
+                let latest = latest_paths
+                    .entry(project_name(path))
+                    .or_insert((entry.timestamp, path));
+                if entry.timestamp >= latest.0 {
+                    *latest = (entry.timestamp, path);
+                }
+            }
+        }
+
+        let mut result: Vec<ProjectSummary> = Self::by_project(entries)
+            .into_iter()
+            .map(|(project_name, daily)| ProjectSummary {
+                project_path: latest_paths
+                    .get(project_name.as_str())
+                    .map(|(_, path)| path.to_string())
+                    .unwrap_or_default(),
+                total_cost_usd: daily.iter().map(|d| d.total_cost_usd).sum(),
+                total_tokens: daily.iter().map(DailySummary::total_tokens).sum(),
+                day_count: daily.len(),
+                project_name,
+                daily,
+            })
+            .collect();
+        result.sort_by(|a, b| {
+            b.total_cost_usd
+                .total_cmp(&a.total_cost_usd)
+                .then_with(|| a.project_name.cmp(&b.project_name))
+        });
+        result
+    }
+
     /// Merge DailySummaries with the same date.
     /// Useful when combining summaries from multiple CLI sources.
     pub fn merge_by_date(summaries: Vec<DailySummary>) -> Vec<DailySummary> {
@@ -322,6 +819,7 @@ impl Aggregator {
                     total_thinking_tokens: 0,
                     total_cost_usd: 0.0,
                     models: HashMap::new(),
+                    cost_estimated: false,
                 });
             accumulate_summary(target, &summary);
         }
@@ -330,12 +828,35 @@ impl Aggregator {
         result.sort_by_key(|s| s.date);
         result
     }
+
+    /// Combined daily summaries of all sources (see `merge_by_date`) and each
+    /// source's totals, most tokens first. Sources without summaries are left out.
+    pub fn merge_sources(
+        source_summaries: &HashMap<String, Vec<DailySummary>>,
+    ) -> (Vec<DailySummary>, Vec<SourceUsage>) {
+        let merged = Self::merge_by_date(source_summaries.values().flatten().cloned().collect());
+
+        let mut source_usage: Vec<SourceUsage> = source_summaries
+            .iter()
+            .filter(|(_, summaries)| !summaries.is_empty())
+            .map(|(source, summaries)| SourceUsage {
+                source: source.clone(),
+                total_tokens: summaries
+                    .iter()
+                    .fold(0u64, |sum, s| sum.saturating_add(s.total_tokens())),
+                total_cost_usd: summaries.iter().map(|s| s.total_cost_usd).sum(),
+                by_model: Vec::new(),
+            })
+            .collect();
+        source_usage.sort_by(|a, b| b.total_tokens.cmp(&a.total_tokens));
+        (merged, source_usage)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{TimeZone, Utc};
+    use chrono::{FixedOffset, NaiveDate, Utc};
 
     fn make_entry(
         year: i32,
@@ -359,6 +880,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }
     }
 
@@ -387,6 +911,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }
     }
 
@@ -434,6 +961,23 @@ mod tests {
         assert_eq!(result[2].date.to_string(), "2024-01-20");
     }
 
+    #[test]
+    fn test_daily_invalid_cost_keeps_total_finite() {
+        let entries = vec![
+            make_entry(2024, 1, 15, Some("claude-sonnet"), 100, 50, Some(f64::NAN)),
+            make_entry(2024, 1, 15, Some("claude-sonnet"), 100, 50, Some(-1.0)),
+            make_entry(2024, 1, 15, Some("claude-sonnet"), 100, 50, Some(0.02)),
+        ];
+        assert!(entries[0].has_invalid_cost());
+        assert!(entries[1].has_invalid_cost());
+        assert!(!entries[2].has_invalid_cost());
+
+        let result = Aggregator::daily(&entries);
+        assert!(result[0].total_cost_usd.is_finite());
+        assert!((result[0].total_cost_usd - 0.02).abs() < f64::EPSILON);
+        assert!(Aggregator::total(&entries).total_cost_usd.is_finite());
+    }
+
     #[test]
     fn test_daily_same_day_aggregation() {
         let entries = vec![
@@ -453,6 +997,37 @@ mod tests {
         assert_eq!(result[0].models.len(), 2);
     }
 
+    #[test]
+    fn test_thinking_tokens_accumulate_through_rollups() {
+        let mut a = make_entry(2024, 1, 15, Some("claude-sonnet-4"), 100, 50, Some(0.01));
+        a.thinking_tokens = 30;
+        let mut b = make_entry(2024, 1, 15, Some("claude-sonnet-4"), 100, 50, Some(0.01));
+        b.thinking_tokens = 20;
+        let mut c = make_entry(2024, 1, 16, Some("claude-sonnet-4"), 100, 50, Some(0.01));
+        c.thinking_tokens = 5;
+        let entries = [a, b, c];
+
+        let daily = Aggregator::daily(&entries);
+        assert_eq!(daily[0].total_thinking_tokens, 50);
+        assert_eq!(daily[0].models["claude-sonnet-4"].thinking_tokens, 50);
+        assert_eq!(daily[1].total_thinking_tokens, 5);
+
+        let weekly = Aggregator::weekly(&daily);
+        assert_eq!(weekly.len(), 1);
+        assert_eq!(weekly[0].total_thinking_tokens, 55);
+        assert_eq!(weekly[0].models["claude-sonnet-4"].thinking_tokens, 55);
+
+        let monthly = Aggregator::monthly(&daily);
+        assert_eq!(monthly[0].total_thinking_tokens, 55);
+        assert_eq!(monthly[0].models["claude-sonnet-4"].thinking_tokens, 55);
+
+        assert_eq!(Aggregator::total(&entries).total_thinking_tokens, 55);
+        assert_eq!(
+            Aggregator::total_from_daily(&daily).total_thinking_tokens,
+            55
+        );
+    }
+
     #[test]
     fn test_by_model_empty() {
         let result = Aggregator::by_model(&[]);
@@ -657,6 +1232,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: cost,
             models: HashMap::new(),
+            cost_estimated: false,
         }
     }
 
@@ -678,6 +1254,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: cost,
             models,
+            cost_estimated: false,
         }
     }
 
@@ -751,6 +1328,23 @@ mod tests {
         assert_eq!(result[0].date.to_string(), "2025-01-12");
     }
 
+    #[test]
+    fn test_weekly_starting_monday() {
+        // 2025-01-19 (Sun) closes the Monday-start week of 2025-01-13;
+        // 2025-01-20 (Mon) opens the next one
+        let summaries = vec![
+            make_daily_summary(2025, 1, 13, 100, 50, 0.01),
+            make_daily_summary(2025, 1, 19, 200, 100, 0.02),
+            make_daily_summary(2025, 1, 20, 300, 150, 0.03),
+        ];
+        let result = Aggregator::weekly_starting(&summaries, WeekStart::Monday);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].date.to_string(), "2025-01-13");
+        assert_eq!(result[0].total_input_tokens, 300);
+        assert_eq!(result[1].date.to_string(), "2025-01-20");
+    }
+
     #[test]
     fn test_weekly_models_merged() {
         let mut models_a = HashMap::new();
@@ -892,6 +1486,48 @@ mod tests {
         assert_eq!(result[2].date.to_string(), "2025-03-01");
     }
 
+    // ========== Quarterly aggregation tests ==========
+
+    #[test]
+    fn test_quarterly_empty() {
+        assert!(Aggregator::quarterly(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_quarterly_groups_by_calendar_quarter() {
+        let summaries = vec![
+            make_daily_summary(2025, 1, 5, 100, 50, 0.01),
+            make_daily_summary(2025, 3, 31, 200, 100, 0.02),
+            make_daily_summary(2025, 4, 1, 300, 150, 0.03),
+            make_daily_summary(2025, 9, 30, 400, 200, 0.04),
+        ];
+        let result = Aggregator::quarterly(&summaries);
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].date.to_string(), "2025-01-01");
+        assert_eq!(result[0].total_input_tokens, 300);
+        assert!((result[0].total_cost_usd - 0.03).abs() < f64::EPSILON);
+        assert_eq!(result[1].date.to_string(), "2025-04-01");
+        assert_eq!(result[2].date.to_string(), "2025-07-01");
+        assert_eq!(result[2].total_input_tokens, 400);
+    }
+
+    #[test]
+    fn test_quarterly_year_boundary() {
+        let summaries = vec![
+            make_daily_summary(2025, 1, 2, 200, 100, 0.02),
+            make_daily_summary(2024, 12, 31, 100, 50, 0.01),
+            make_daily_summary(2024, 10, 1, 50, 25, 0.005),
+        ];
+        let result = Aggregator::quarterly(&summaries);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].date.to_string(), "2024-10-01");
+        assert_eq!(result[0].total_input_tokens, 150);
+        assert_eq!(result[1].date.to_string(), "2025-01-01");
+        assert_eq!(result[1].total_input_tokens, 200);
+    }
+
     // ========== total_from_daily tests ==========
 
     #[test]
@@ -1043,6 +1679,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.01,
             models: HashMap::new(),
+            cost_estimated: false,
         };
         let source = DailySummary {
             date: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
@@ -1053,6 +1690,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.02,
             models: HashMap::new(),
+            cost_estimated: false,
         };
 
         accumulate_summary(&mut target, &source);
@@ -1151,6 +1789,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.01,
             models: models_target,
+            cost_estimated: false,
         };
 
         let mut models_source = HashMap::new();
@@ -1183,6 +1822,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.025,
             models: models_source,
+            cost_estimated: false,
         };
 
         accumulate_summary(&mut target, &source);
@@ -1223,11 +1863,281 @@ mod tests {
             request_id: None,
             source: source.map(String::from),
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }
     }
 
+    // ========== Project tests ==========
+
+    fn project_entry(day: u32, cost: f64, project: Option<&str>) -> UsageEntry {
+        UsageEntry {
+            project_path: project.map(String::from),
+            git_branch: None,
+            ..make_entry(2026, 2, day, Some("claude-sonnet-4-5"), 100, 50, Some(cost))
+        }
+    }
+
+    #[test]
+    fn test_by_project_groups_by_basename() {
+        let entries = vec![
+            project_entry(1, 1.0, Some("/home/user/webapp")),
+            project_entry(2, 2.0, Some("/tmp/checkout/webapp/")),
+            project_entry(2, 0.5, Some("/home/user/api")),
+            project_entry(3, 9.0, None),
+        ];
+        let projects = Aggregator::by_project(&entries);
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects["webapp"].len(), 2);
+        assert_eq!(projects["api"].len(), 1);
+        assert!((projects["api"][0].total_cost_usd - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_project_summaries_costliest_first_with_latest_path() {
+        let entries = vec![
+            project_entry(1, 1.0, Some("/home/user/webapp")),
+            project_entry(2, 2.0, Some("/tmp/checkout/webapp")),
+            project_entry(2, 0.5, Some("/home/user/api")),
+        ];
+        let summaries = Aggregator::project_summaries(&entries);
+        assert_eq!(summaries.len(), 2);
+
+        let webapp = &summaries[0];
+        assert_eq!(webapp.project_name, "webapp");
+        assert_eq!(webapp.project_path, "/tmp/checkout/webapp");
+        assert!((webapp.total_cost_usd - 3.0).abs() < 1e-9);
+        assert_eq!(webapp.total_tokens, 300);
+        assert_eq!(webapp.day_count, 2);
+        assert_eq!(summaries[1].project_name, "api");
+    }
+
+    #[test]
+    fn test_project_name_basename() {
+        assert_eq!(project_name("/home/user/webapp"), "webapp");
+        assert_eq!(project_name("C:\\code\\webapp\\"), "webapp");
+        assert_eq!(project_name("/"), "/");
+    }
+
+    // ========== Moving average tests ==========
+
+    fn cost_day(day: u32, cost: f64) -> DailySummary {
+        let mut summary = Aggregator::daily(&[make_entry(2026, 1, day, None, 1, 1, Some(cost))])
+            .pop()
+            .unwrap();
+        summary.date = NaiveDate::from_ymd_opt(2026, 1, day).unwrap();
+        summary
+    }
+
+    #[test]
+    fn test_moving_average_centered_without_padding() {
+        // Unsorted input; a 3-day window averages each day with its neighbours
+        let summaries = vec![
+            cost_day(3, 9.0),
+            cost_day(1, 3.0),
+            cost_day(2, 6.0),
+            cost_day(4, 0.0),
+        ];
+        let avg = Aggregator::moving_average(&summaries, 3);
+        let dates: Vec<u32> = avg.iter().map(|(d, _)| d.day()).collect();
+        assert_eq!(dates, vec![1, 2, 3, 4]);
+        let values: Vec<f64> = avg.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![4.5, 6.0, 5.0, 4.5]);
+    }
+
+    #[test]
+    fn test_moving_average_window_larger_than_data() {
+        let summaries = vec![cost_day(1, 1.0), cost_day(2, 3.0)];
+        let avg = Aggregator::moving_average(&summaries, 7);
+        assert_eq!(avg.len(), 2);
+        assert!(avg.iter().all(|(_, v)| (*v - 2.0).abs() < f64::EPSILON));
+        assert!(Aggregator::moving_average(&[], 7).is_empty());
+    }
+
+    #[test]
+    fn test_cumulative_running_total() {
+        let summaries = vec![cost_day(1, 0.45), cost_day(2, 0.0), cost_day(3, 1.5)];
+        let totals = Aggregator::cumulative(&summaries);
+        let dates: Vec<u32> = totals.iter().map(|(d, _)| d.day()).collect();
+        assert_eq!(dates, vec![1, 2, 3]);
+        let values: Vec<f64> = totals.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![0.45, 0.45, 1.95]);
+        assert!(Aggregator::cumulative(&[]).is_empty());
+    }
+
+    // ========== Cost distribution tests ==========
+
+    #[test]
+    fn test_percentile_cost_interpolates() {
+        // Unsorted input: sorted costs are 1, 2, 3, 4, 10
+        let summaries: Vec<DailySummary> = [4.0, 1.0, 10.0, 3.0, 2.0]
+            .iter()
+            .enumerate()
+            .map(|(i, cost)| cost_day(i as u32 + 1, *cost))
+            .collect();
+        let p = |q| Aggregator::percentile_cost(&summaries, q);
+        assert_eq!(p(0.0), 1.0);
+        assert_eq!(p(0.25), 2.0);
+        assert_eq!(p(0.5), 3.0);
+        assert_eq!(p(1.0), 10.0);
+        // rank 3.8 → 4 + 0.8 × (10 − 4)
+        assert!((p(0.95) - 8.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_percentile_cost_edge_cases() {
+        assert_eq!(Aggregator::percentile_cost(&[], 0.5), 0.0);
+        let one = vec![cost_day(1, 2.5)];
+        assert_eq!(Aggregator::percentile_cost(&one, 0.95), 2.5);
+        // Out-of-range p is clamped
+        assert_eq!(Aggregator::percentile_cost(&one, 1.5), 2.5);
+    }
+
+    #[test]
+    fn test_variance_cost() {
+        let summaries = vec![cost_day(1, 2.0), cost_day(2, 4.0), cost_day(3, 6.0)];
+        // mean 4, squared deviations 4 + 0 + 4
+        assert!((Aggregator::variance_cost(&summaries) - 8.0 / 3.0).abs() < 1e-9);
+        assert_eq!(Aggregator::variance_cost(&[]), 0.0);
+        assert_eq!(Aggregator::variance_cost(&summaries[..1]), 0.0);
+    }
+
+    // ========== Streak tests ==========
+
+    #[test]
+    fn test_streak_counts_back_from_today() {
+        let today = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        let summaries = vec![
+            cost_day(1, 1.0),
+            cost_day(3, 1.0),
+            cost_day(4, 1.0),
+            cost_day(5, 1.0),
+        ];
+        assert_eq!(Aggregator::streak_on(&summaries, today), 3);
+        // No usage today breaks the streak
+        assert_eq!(Aggregator::streak_on(&summaries[..3], today), 0);
+        assert_eq!(Aggregator::streak_on(&[], today), 0);
+
+        // Zero-token days do not count
+        let mut idle_today = summaries.clone();
+        idle_today[3] = DailySummary {
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            ..idle_today[3].clone()
+        };
+        assert_eq!(Aggregator::streak_on(&idle_today, today), 0);
+    }
+
+    #[test]
+    fn test_longest_streak_keeps_earliest_run() {
+        assert_eq!(Aggregator::longest_streak(&[]), (0, None));
+        let summaries = vec![
+            cost_day(1, 1.0),
+            cost_day(2, 1.0),
+            cost_day(4, 1.0),
+            cost_day(5, 1.0),
+            cost_day(9, 1.0),
+        ];
+        assert_eq!(
+            Aggregator::longest_streak(&summaries),
+            (2, NaiveDate::from_ymd_opt(2026, 1, 2))
+        );
+        // Runs span month boundaries
+        let mut boundary = summaries.clone();
+        boundary.insert(0, cost_day(31, 1.0));
+        boundary[0].date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        assert_eq!(
+            Aggregator::longest_streak(&boundary),
+            (3, NaiveDate::from_ymd_opt(2026, 1, 2))
+        );
+    }
+
+    #[test]
+    fn test_by_day_of_week() {
+        // 2026-01-05 and 01-12 are Mondays
+        let summaries = vec![
+            cost_day(5, 1.0),
+            cost_day(6, 4.0),
+            cost_day(10, 0.5),
+            cost_day(12, 3.0),
+        ];
+        let days = Aggregator::by_day_of_week(&summaries);
+        assert_eq!(days[0].day, Weekday::Mon);
+        assert_eq!(days[6].day, Weekday::Sun);
+        assert_eq!(days[0].day_count, 2);
+        assert_eq!(days[0].total_tokens, 4);
+        assert!((days[0].total_cost_usd - 4.0).abs() < 1e-9);
+        assert!((days[0].avg_cost_usd - 2.0).abs() < 1e-9);
+        assert_eq!(days[2].day_count, 0);
+        assert_eq!(days[2].avg_cost_usd, 0.0);
+
+        assert_eq!(
+            DayOfWeekStats::busiest_and_quietest(&days),
+            (Some(Weekday::Tue), Some(Weekday::Sat))
+        );
+        assert_eq!(
+            DayOfWeekStats::busiest_and_quietest(&Aggregator::by_day_of_week(&[])),
+            (None, None)
+        );
+    }
+
+    // ========== Hourly tests ==========
+
+    #[test]
+    fn test_by_hour_buckets_local_hours() {
+        let tz = FixedOffset::east_opt(9 * 3600).unwrap();
+        let mut a = make_entry(2026, 1, 15, Some("claude"), 100, 50, Some(0.01));
+        a.timestamp = Utc.with_ymd_and_hms(2026, 1, 15, 15, 10, 0).unwrap(); // 00:10 local
+        let mut b = a.clone();
+        b.timestamp = Utc.with_ymd_and_hms(2026, 1, 15, 15, 50, 0).unwrap(); // 00:50 local
+        let mut c = a.clone();
+        c.timestamp = Utc.with_ymd_and_hms(2026, 1, 15, 4, 30, 0).unwrap(); // 13:30 local
+
+        let hours = Aggregator::by_hour_in(&[a, b, c], &tz);
+        assert_eq!(hours[0].input_tokens, 200);
+        assert_eq!(hours[0].count, 2);
+        assert_eq!(hours[13].input_tokens, 100);
+        assert_eq!(hours.iter().map(|h| h.count).sum::<u64>(), 3);
+    }
+
+    #[test]
+    fn test_hourly_summaries_include_models() {
+        let mut a = make_entry(2026, 1, 15, Some("claude-sonnet-4-5"), 100, 50, Some(0.5));
+        a.timestamp = Utc.with_ymd_and_hms(2026, 1, 15, 9, 0, 0).unwrap();
+        let mut b = make_entry(2026, 1, 15, Some("gpt-4.1"), 10, 5, Some(0.25));
+        b.timestamp = Utc.with_ymd_and_hms(2026, 1, 15, 9, 30, 0).unwrap();
+
+        let hourly = Aggregator::hourly_in(&[a, b], &Utc);
+        assert_eq!(hourly.len(), 24);
+        assert_eq!(hourly[9].hour, 9);
+        assert_eq!(hourly[9].total_input_tokens, 110);
+        assert_eq!(hourly[9].total_cost_usd, 0.75);
+        assert_eq!(hourly[9].models.len(), 2);
+        assert!(hourly[8].models.is_empty());
+    }
+
     // ========== Timezone boundary tests ==========
 
+    #[test]
+    fn test_daily_groups_by_local_date_west_of_utc() {
+        // 23:30 UTC on Jan 15 is 15:30 on Jan 15 in UTC-8; 07:30 UTC on
+        // Jan 16 is 23:30 on Jan 15 there, so both land on Jan 15.
+        let pacific = FixedOffset::west_opt(8 * 3600).unwrap();
+        let mut late = make_entry(2026, 1, 15, Some("claude"), 100, 50, Some(0.01));
+        late.timestamp = Utc.with_ymd_and_hms(2026, 1, 15, 23, 30, 0).unwrap();
+        let mut next_utc_day = late.clone();
+        next_utc_day.timestamp = Utc.with_ymd_and_hms(2026, 1, 16, 7, 30, 0).unwrap();
+
+        let result = Aggregator::daily_in(&[late, next_utc_day], &pacific);
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].date,
+            NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()
+        );
+        assert_eq!(result[0].total_input_tokens, 200);
+    }
+
     #[test]
     fn test_daily_groups_by_local_date_not_utc() {
         // Simulate entries around midnight UTC.
@@ -1249,6 +2159,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         let entry_early = UsageEntry {
             timestamp: early_utc,
@@ -1263,6 +2176,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
 
         let result = Aggregator::daily(&[entry_late.clone(), entry_early.clone()]);
@@ -1313,6 +2229,9 @@ mod tests {
                 request_id: None,
                 source: None,
                 provider: None,
+                cost_estimated: false,
+                project_path: None,
+                git_branch: None,
             },
             UsageEntry {
                 timestamp: ts2,
@@ -1327,6 +2246,9 @@ mod tests {
                 request_id: None,
                 source: None,
                 provider: None,
+                cost_estimated: false,
+                project_path: None,
+                git_branch: None,
             },
         ];
 
@@ -1345,6 +2267,79 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_by_source_and_model() {
+        let entries = vec![
+            make_entry_with_source(2024, 1, 15, Some("opus"), 10, 5, Some(3.0), Some("claude")),
+            make_entry_with_source(
+                2024,
+                1,
+                16,
+                Some("sonnet"),
+                10,
+                5,
+                Some(1.0),
+                Some("claude"),
+            ),
+            make_entry_with_source(2024, 1, 16, Some("opus"), 10, 5, Some(2.0), Some("claude")),
+            make_entry_with_source(2024, 1, 16, Some("gpt-4o"), 10, 5, Some(0.5), Some("codex")),
+        ];
+        let nested = Aggregator::by_source_and_model(&entries);
+        assert_eq!(nested["claude"]["opus"].count, 2);
+        assert!((nested["claude"]["opus"].cost_usd - 5.0).abs() < 1e-9);
+        assert_eq!(nested["codex"].len(), 1);
+
+        let breakdown = Aggregator::source_model_breakdown(nested);
+        let sources: Vec<&str> = breakdown.iter().map(|b| b.source.as_str()).collect();
+        assert_eq!(sources, vec!["claude", "codex"]);
+        let models: Vec<&str> = breakdown[0]
+            .models
+            .iter()
+            .map(|(m, _)| m.as_str())
+            .collect();
+        assert_eq!(models, vec!["opus", "sonnet"]);
+    }
+
+    #[test]
+    fn test_by_provider_groups_and_sorts_by_cost() {
+        let with_provider = |cost: f64, source: &str, provider: Option<&str>| UsageEntry {
+            provider: provider.map(String::from),
+            ..make_entry_with_source(2024, 1, 15, Some("m"), 10, 5, Some(cost), Some(source))
+        };
+        let entries = vec![
+            with_provider(1.0, "opencode", Some("anthropic")),
+            with_provider(2.0, "aider", Some("anthropic")),
+            with_provider(4.0, "opencode", Some("openai")),
+            with_provider(0.5, "claude", None),
+            with_provider(1.0, "opencode", Some("anthropic")),
+        ];
+        let providers = Aggregator::by_provider(&entries);
+        let names: Vec<&str> = providers.iter().map(|p| p.provider.as_str()).collect();
+        assert_eq!(names, vec!["anthropic", "openai", "unknown"]);
+        assert!((providers[0].total_cost_usd - 4.0).abs() < 1e-9);
+        assert_eq!(providers[0].total_tokens, 45);
+        assert_eq!(providers[0].sources, vec!["aider", "opencode"]);
+        assert_eq!(providers[2].sources, vec!["claude"]);
+        assert!(Aggregator::by_provider(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_by_provider_from_daily_uses_model_vendor() {
+        let entries = |model: &str, cost: f64| {
+            Aggregator::daily(&[make_entry(2024, 1, 15, Some(model), 10, 5, Some(cost))])
+        };
+        let source_summaries = HashMap::from([
+            ("claude-code".to_string(), entries("claude-sonnet-4", 3.0)),
+            ("codex".to_string(), entries("gpt-4o", 1.0)),
+            ("opencode".to_string(), entries("claude-opus-4", 2.0)),
+        ]);
+        let providers = Aggregator::by_provider_from_daily(&source_summaries);
+        let names: Vec<&str> = providers.iter().map(|p| p.provider.as_str()).collect();
+        assert_eq!(names, vec!["Anthropic", "OpenAI"]);
+        assert!((providers[0].total_cost_usd - 5.0).abs() < 1e-9);
+        assert_eq!(providers[0].sources, vec!["claude-code", "opencode"]);
+    }
+
     #[test]
     fn test_by_source_single_source() {
         let entries = vec![
@@ -1493,6 +2488,52 @@ mod tests {
         assert_eq!(result[2].date.to_string(), "2025-01-20");
     }
 
+    #[test]
+    fn test_merge_sources_empty() {
+        let (merged, usage) = Aggregator::merge_sources(&HashMap::new());
+        assert!(merged.is_empty());
+        assert!(usage.is_empty());
+    }
+
+    #[test]
+    fn test_merge_sources_matches_manual_merge() {
+        let sources = HashMap::from([
+            (
+                "claude".to_string(),
+                vec![
+                    make_daily_summary(2025, 1, 10, 300, 100, 0.03),
+                    make_daily_summary(2025, 1, 15, 100, 50, 0.01),
+                ],
+            ),
+            (
+                "opencode".to_string(),
+                vec![make_daily_summary(2025, 1, 15, 1500, 500, 0.10)],
+            ),
+            (
+                "gemini".to_string(),
+                vec![make_daily_summary(2025, 1, 20, 800, 200, 0.05)],
+            ),
+            ("codex".to_string(), Vec::new()),
+        ]);
+
+        let (merged, usage) = Aggregator::merge_sources(&sources);
+
+        let manual = Aggregator::merge_by_date(sources.values().flatten().cloned().collect());
+        assert_eq!(merged, manual);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[1].total_input_tokens, 1600);
+
+        let totals: Vec<(&str, u64)> = usage
+            .iter()
+            .map(|u| (u.source.as_str(), u.total_tokens))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![("opencode", 2000), ("gemini", 1000), ("claude", 550)]
+        );
+        assert!((usage[2].total_cost_usd - 0.04).abs() < 1e-9);
+    }
+
     #[test]
     fn test_merge_by_date_merges_models() {
         let mut models_a = HashMap::new();
@@ -1530,4 +2571,66 @@ mod tests {
         assert!(result[0].models.contains_key("claude"));
         assert!(result[0].models.contains_key("gpt-4"));
     }
+
+    #[test]
+    fn test_by_branch_multi_branch_sessions() {
+        use crate::parsers::ClaudeCodeParser;
+        use std::path::PathBuf;
+
+        let sessions = ClaudeCodeParser::with_data_dir(PathBuf::from("tests/data/branches"))
+            .parse_sessions_index(None, false);
+        assert_eq!(sessions.len(), 5);
+
+        let branches = Aggregator::by_branch(&sessions);
+        let summary: Vec<(&str, u64, u64)> = branches
+            .iter()
+            .map(|b| (b.branch.as_str(), b.session_count.unwrap(), b.total_tokens))
+            .collect();
+        // HEAD and missing branches are grouped as "unknown"
+        assert_eq!(
+            summary,
+            vec![
+                ("feat/login", 2, 1600),
+                ("main", 1, 600),
+                ("unknown", 2, 210)
+            ]
+        );
+        assert!((branches[0].total_cost_usd - 0.50).abs() < 1e-9);
+        assert!((branches[2].total_cost_usd - 0.07).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_by_branch_empty() {
+        assert!(Aggregator::by_branch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_branch_summaries_from_entries() {
+        let entry = |branch: Option<&str>, input: u64, cost: f64| UsageEntry {
+            git_branch: branch.map(String::from),
+            ..make_entry(2026, 3, 1, Some("claude-sonnet-4"), input, 0, Some(cost))
+        };
+        let entries = vec![
+            entry(Some("main"), 100, 0.10),
+            entry(Some("feat/login"), 300, 0.40),
+            entry(Some("main"), 50, 0.05),
+            entry(Some("HEAD"), 10, 0.01),
+            entry(None, 20, 0.02),
+        ];
+
+        let usage = Aggregator::usage_by_branch(&entries);
+        assert_eq!(usage["main"].count, 2);
+        assert_eq!(usage["unknown"].input_tokens, 30);
+
+        let branches = Aggregator::branch_summaries(&entries);
+        let summary: Vec<(&str, u64)> = branches
+            .iter()
+            .map(|b| (b.branch.as_str(), b.total_tokens))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("feat/login", 300), ("main", 150), ("unknown", 30)]
+        );
+        assert!(branches.iter().all(|b| b.session_count.is_none()));
+    }
 }
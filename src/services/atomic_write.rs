@@ -0,0 +1,86 @@
+//! Atomic file replacement for toktrack's caches
+//!
+//! Writes go to a temp file beside the target, named after the process and a
+//! per-process counter, then are renamed over it. Concurrent writers never
+//! share a temp file, and readers see either the old or the new content.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::types::{Result, ToktrackError};
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Replace `path` with `bytes` via a uniquely named temp file and rename
+pub fn atomic_write(path: &Path, bytes: &[u8]) -> Result<()> {
+    let temp_path = temp_path_for(path);
+    let result = write_synced(&temp_path, bytes).and_then(|()| {
+        fs::rename(&temp_path, path)
+            .map_err(|e| ToktrackError::Cache(format!("Failed to rename temp file: {}", e)))
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_synced(temp_path: &Path, bytes: &[u8]) -> Result<()> {
+    let mut file = File::create(temp_path)
+        .map_err(|e| ToktrackError::Cache(format!("Failed to create temp file: {}", e)))?;
+    file.write_all(bytes)
+        .map_err(|e| ToktrackError::Cache(format!("Failed to write temp file: {}", e)))?;
+    file.sync_all()
+        .map_err(|e| ToktrackError::Cache(format!("Failed to sync temp file: {}", e)))
+}
+
+/// `<name>.<pid>.<n>.tmp` beside `path`
+fn temp_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_atomic_write_replaces_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cache.json");
+        atomic_write(&path, b"old").unwrap();
+        atomic_write(&path, b"new").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_temp_paths_are_unique() {
+        let path = Path::new("/cache/claude_daily.json");
+        let (a, b) = (temp_path_for(path), temp_path_for(path));
+        assert_ne!(a, b);
+        assert_eq!(a.parent(), path.parent());
+        assert!(a
+            .to_string_lossy()
+            .contains(&format!("claude_daily.json.{}.", std::process::id())));
+    }
+
+    #[test]
+    fn test_atomic_write_failure_leaves_no_temp_file() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("taken");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("inner"), "x").unwrap();
+
+        assert!(atomic_write(&target, b"data").is_err());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
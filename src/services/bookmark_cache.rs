@@ -0,0 +1,264 @@
+//! Bookmarked entries of recently modified files, for the warm path
+//!
+//! Stores each recent file's parsed entries together with how far it was read,
+//! keyed by parser cache key under `<cache_dir>/<cli>_bookmarks.json`. A later
+//! load reads only the lines appended since. The store always holds whole-file
+//! results, so processes sharing it never consume each other's lines.
+//! Entries are cached before synthetic filtering, dedup and pricing.
+
+use crate::parsers::{dedup_entries, CLIParser, FileBookmark};
+use crate::services::atomic_write::atomic_write;
+use crate::types::{ParseProgress, Result, ToktrackError, UsageEntry};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bump when `UsageEntry` or parser output changes.
+/// Mismatched version → the store is ignored and rebuilt.
+const BOOKMARK_CACHE_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarkCache {
+    #[serde(default)]
+    version: u32,
+    files: HashMap<PathBuf, BookmarkedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BookmarkedFile {
+    bookmark: FileBookmark,
+    /// Every entry up to `bookmark`, not just the last appended ones
+    entries: Vec<UsageEntry>,
+}
+
+/// Outcome of reading one file through the store
+enum FileEntries {
+    /// Read from a bookmark (or from the start); the file is kept in the store
+    Bookmarked(BookmarkedFile),
+    /// The parser can't resume mid-file, so it was read in full and not stored
+    Whole(Vec<UsageEntry>),
+    Failed,
+}
+
+pub struct BookmarkCacheService {
+    cache_dir: PathBuf,
+}
+
+impl BookmarkCacheService {
+    pub fn with_cache_dir(cache_dir: PathBuf) -> Self {
+        Self { cache_dir }
+    }
+
+    pub fn cache_path(&self, cli: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}_bookmarks.json", cli))
+    }
+
+    /// Same as `CLIParser::parse_and_dedup_with_progress`, but files read by an
+    /// earlier call are only read from their bookmark on. Files not in `files`
+    /// are dropped from the store.
+    pub fn parse_and_dedup(
+        &self,
+        parser: &dyn CLIParser,
+        files: &[PathBuf],
+        progress: Option<&ParseProgress>,
+        include_synthetic: bool,
+    ) -> Result<Vec<UsageEntry>> {
+        let cli = parser.cache_key();
+        let cache = self.load(&cli);
+
+        let results: Vec<FileEntries> = files
+            .par_iter()
+            .map(|f| {
+                let result = read_file(parser, f, cache.files.get(f));
+                if let Some(progress) = progress {
+                    progress.advance();
+                }
+                result
+            })
+            .collect();
+
+        let mut dirty = false;
+        let mut files_read = HashMap::new();
+        let mut all_entries = Vec::new();
+        for (path, result) in files.iter().zip(results) {
+            match result {
+                FileEntries::Bookmarked(file) => {
+                    dirty |= cache.files.get(path).map(|c| &c.bookmark) != Some(&file.bookmark);
+                    all_entries.extend(file.entries.iter().cloned());
+                    files_read.insert(path.clone(), file);
+                }
+                FileEntries::Whole(entries) => all_entries.extend(entries),
+                FileEntries::Failed => {}
+            }
+        }
+        dirty |= cache.files.len() != files_read.len();
+
+        if dirty {
+            let cache = BookmarkCache {
+                version: BOOKMARK_CACHE_VERSION,
+                files: files_read,
+            };
+            if let Err(e) = self.save(&cli, &cache) {
+                eprintln!("[toktrack] Warning: bookmarks for {} failed: {}", cli, e);
+            }
+        }
+
+        all_entries.retain(|entry| include_synthetic || !parser.is_synthetic(entry));
+        Ok(dedup_entries(all_entries))
+    }
+
+    /// Load the store for `cli`; missing, corrupt or outdated stores start empty
+    fn load(&self, cli: &str) -> BookmarkCache {
+        fs::read(self.cache_path(cli))
+            .ok()
+            .and_then(|content| serde_json::from_slice::<BookmarkCache>(&content).ok())
+            .filter(|cache| cache.version == BOOKMARK_CACHE_VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Save using atomic write (temp file + rename)
+    fn save(&self, cli: &str, cache: &BookmarkCache) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir)?;
+        let content = serde_json::to_vec(cache)
+            .map_err(|e| ToktrackError::Cache(format!("Serialization failed: {}", e)))?;
+        atomic_write(&self.cache_path(cli), &content)
+    }
+}
+
+/// Read `path` from its stored bookmark when still valid, else from the start
+fn read_file(parser: &dyn CLIParser, path: &Path, cached: Option<&BookmarkedFile>) -> FileEntries {
+    let warn = |e: &dyn std::fmt::Display| {
+        eprintln!("[toktrack] Warning: Failed to parse {:?}: {}", path, e);
+        FileEntries::Failed
+    };
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(e) => return warn(&e),
+    };
+    let resumed = cached.and_then(|c| {
+        c.bookmark
+            .resume_offset(path, &meta)
+            .map(|offset| (offset, c))
+    });
+    let offset = resumed.map_or(0, |(offset, _)| offset);
+
+    match parser.parse_file_from(path, offset) {
+        Some(Ok((appended, end))) => {
+            let mut entries = resumed.map_or_else(Vec::new, |(_, c)| c.entries.clone());
+            entries.extend(appended);
+            FileEntries::Bookmarked(BookmarkedFile {
+                bookmark: FileBookmark::new(path, &meta, end),
+                entries,
+            })
+        }
+        Some(Err(e)) => warn(&e),
+        None => match parser.parse_file(path) {
+            Ok(entries) => FileEntries::Whole(entries),
+            Err(e) => warn(&e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ClaudeCodeParser;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn line(id: &str, model: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"2026-01-15T10:00:00.000Z","requestId":"req-{id}","message":{{"id":"{id}","model":"{model}","usage":{{"input_tokens":100,"output_tokens":50}}}}}}"#
+        ) + "\n"
+    }
+
+    fn append(path: &Path, content: &str) {
+        let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn ids(entries: &[UsageEntry]) -> Vec<String> {
+        entries
+            .iter()
+            .filter_map(|e| e.message_id.clone())
+            .collect()
+    }
+
+    struct Setup {
+        data: TempDir,
+        state: TempDir,
+        parser: ClaudeCodeParser,
+    }
+
+    impl Setup {
+        fn new() -> Self {
+            let data = TempDir::new().unwrap();
+            let parser = ClaudeCodeParser::with_data_dir(data.path().to_path_buf());
+            Self {
+                data,
+                state: TempDir::new().unwrap(),
+                parser,
+            }
+        }
+
+        fn service(&self) -> BookmarkCacheService {
+            BookmarkCacheService::with_cache_dir(self.state.path().to_path_buf())
+        }
+
+        fn read(&self, files: &[PathBuf], include_synthetic: bool) -> Vec<String> {
+            ids(&self
+                .service()
+                .parse_and_dedup(&self.parser, files, None, include_synthetic)
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_reads_appended_lines_and_returns_whole_file() {
+        let setup = Setup::new();
+        let path = setup.data.path().join("session.jsonl");
+        fs::write(&path, line("a", "claude-sonnet-4")).unwrap();
+        let files = vec![path.clone()];
+        assert_eq!(setup.read(&files, false), vec!["a"]);
+
+        // Same size, so a bookmarked read never sees the rewritten first line
+        let first = fs::read_to_string(&path).unwrap();
+        fs::write(&path, " ".repeat(first.len() - 1) + "\n").unwrap();
+        append(&path, &line("b", "claude-sonnet-4"));
+        assert_eq!(setup.read(&files, false), vec!["a", "b"]);
+
+        // Another reader of the same store still gets every line
+        assert_eq!(setup.read(&files, false), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_appended_duplicates_and_synthetic_entries() {
+        let setup = Setup::new();
+        let path = setup.data.path().join("session.jsonl");
+        fs::write(&path, line("a", "claude-sonnet-4")).unwrap();
+        let files = vec![path.clone()];
+        setup.read(&files, false);
+
+        append(&path, &line("a", "claude-sonnet-4"));
+        append(&path, &line("s", "<synthetic>"));
+        assert_eq!(setup.read(&files, false), vec!["a"]);
+        assert_eq!(setup.read(&files, true), vec!["a", "s"]);
+    }
+
+    #[test]
+    fn test_files_no_longer_read_are_pruned() {
+        let setup = Setup::new();
+        let kept = setup.data.path().join("kept.jsonl");
+        let gone = setup.data.path().join("gone.jsonl");
+        fs::write(&kept, line("a", "claude-sonnet-4")).unwrap();
+        fs::write(&gone, line("b", "claude-sonnet-4")).unwrap();
+        setup.read(&[kept.clone(), gone.clone()], false);
+
+        fs::remove_file(&gone).unwrap();
+        setup.read(std::slice::from_ref(&kept), false);
+        let cache = setup.service().load(&setup.parser.cache_key());
+        assert_eq!(cache.files.keys().collect::<Vec<_>>(), vec![&kept]);
+    }
+}
@@ -0,0 +1,282 @@
+//! Spending limits service
+//!
+//! Daily, weekly and monthly cost limits stored in `~/.toktrack/budget.toml`,
+//! checked against the spend of the current day, week and month.
+
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::config::WeekStart;
+use crate::services::data_dir::data_dir;
+use crate::services::Aggregator;
+use crate::types::{DailySummary, Result, ToktrackError};
+
+/// Limits from `budget.toml`; unset periods are not checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BudgetConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_limit_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weekly_limit_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monthly_limit_usd: Option<f64>,
+}
+
+/// Period a limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Daily => "Daily",
+            Self::Weekly => "Weekly",
+            Self::Monthly => "Monthly",
+        }
+    }
+}
+
+/// Spend of the current period against its limit
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetStatus {
+    pub period: BudgetPeriod,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    /// Share of the limit spent, in percent (0 for a zero limit with no spend)
+    pub percent_used: f64,
+    pub over_budget: bool,
+}
+
+/// Service for reading, writing and checking spending limits
+pub struct BudgetService {
+    path: PathBuf,
+}
+
+impl BudgetService {
+    /// Create a new service using the default path (`~/.toktrack/budget.toml`)
+    pub fn new() -> Result<Self> {
+        let dir = data_dir()
+            .ok_or_else(|| ToktrackError::Config("Cannot determine home directory".into()))?;
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            path: dir.join("budget.toml"),
+        })
+    }
+
+    /// Create a service with a custom file path (for testing)
+    #[cfg(test)]
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Load the limits. A missing file means no limits; a malformed one is an error.
+    pub fn load(&self) -> Result<BudgetConfig> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(BudgetConfig::default())
+            }
+            Err(e) => return Err(e.into()),
+        };
+        toml::from_str(&content)
+            .map_err(|e| ToktrackError::Config(format!("{}: {}", self.path.display(), e)))
+    }
+
+    /// Replace the given limits, keeping the others. Returns the saved config.
+    pub fn set(
+        &self,
+        daily: Option<f64>,
+        weekly: Option<f64>,
+        monthly: Option<f64>,
+    ) -> Result<BudgetConfig> {
+        if let Some(limit) = [daily, weekly, monthly]
+            .into_iter()
+            .flatten()
+            .find(|limit| !limit.is_finite() || *limit < 0.0)
+        {
+            return Err(ToktrackError::Config(format!(
+                "Budget limit must be a non-negative amount, got {}",
+                limit
+            )));
+        }
+        let mut config = self.load()?;
+        config.daily_limit_usd = daily.or(config.daily_limit_usd);
+        config.weekly_limit_usd = weekly.or(config.weekly_limit_usd);
+        config.monthly_limit_usd = monthly.or(config.monthly_limit_usd);
+        let content = toml::to_string(&config)
+            .map_err(|e| ToktrackError::Config(format!("Failed to serialize budget: {}", e)))?;
+        fs::write(&self.path, content)?;
+        Ok(config)
+    }
+
+    /// One status per configured limit, for the day, week and month containing `today`
+    pub fn status(
+        config: &BudgetConfig,
+        summaries: &[DailySummary],
+        today: NaiveDate,
+        week_start: WeekStart,
+    ) -> Vec<BudgetStatus> {
+        let spent_in = |rows: &[DailySummary], contains: &dyn Fn(NaiveDate) -> bool| {
+            rows.iter()
+                .filter(|s| contains(s.date))
+                .fold(0.0, |spent, s| spent + s.total_cost_usd)
+        };
+        let limits = [
+            (BudgetPeriod::Daily, config.daily_limit_usd),
+            (BudgetPeriod::Weekly, config.weekly_limit_usd),
+            (BudgetPeriod::Monthly, config.monthly_limit_usd),
+        ];
+        limits
+            .into_iter()
+            .filter_map(|(period, limit)| {
+                let limit = limit?;
+                let spent = match period {
+                    BudgetPeriod::Daily => spent_in(summaries, &|d| d == today),
+                    BudgetPeriod::Weekly => {
+                        spent_in(&Aggregator::weekly_starting(summaries, week_start), &|d| {
+                            d <= today && today < d + Duration::days(7)
+                        })
+                    }
+                    BudgetPeriod::Monthly => spent_in(&Aggregator::monthly(summaries), &|d| {
+                        (d.year(), d.month()) == (today.year(), today.month())
+                    }),
+                };
+                let percent_used = if limit > 0.0 {
+                    spent / limit * 100.0
+                } else if spent > 0.0 {
+                    100.0
+                } else {
+                    0.0
+                };
+                Some(BudgetStatus {
+                    period,
+                    limit_usd: limit,
+                    spent_usd: spent,
+                    percent_used,
+                    over_budget: spent > limit,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    fn day(date: NaiveDate, cost: f64) -> DailySummary {
+        DailySummary {
+            date,
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::new(),
+            cost_estimated: false,
+        }
+    }
+
+    #[test]
+    fn test_load_missing_file_has_no_limits() {
+        let tmp = TempDir::new().unwrap();
+        let svc = BudgetService::with_path(tmp.path().join("budget.toml"));
+        assert_eq!(svc.load().unwrap(), BudgetConfig::default());
+    }
+
+    #[test]
+    fn test_set_keeps_other_limits() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("budget.toml");
+        let svc = BudgetService::with_path(path.clone());
+        svc.set(Some(10.0), None, Some(200.0)).unwrap();
+        svc.set(None, Some(50.0), Some(150.0)).unwrap();
+
+        assert_eq!(
+            svc.load().unwrap(),
+            BudgetConfig {
+                daily_limit_usd: Some(10.0),
+                weekly_limit_usd: Some(50.0),
+                monthly_limit_usd: Some(150.0),
+            }
+        );
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("daily_limit_usd = 10.0"));
+        assert!(svc.set(Some(-1.0), None, None).is_err());
+    }
+
+    #[test]
+    fn test_malformed_file_is_error() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("budget.toml");
+        fs::write(&path, "daily_limit = 5").unwrap();
+        assert!(matches!(
+            BudgetService::with_path(path).load(),
+            Err(ToktrackError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_status_for_current_periods() {
+        // Wednesday 2026-03-18; the Monday week starts on the 16th
+        let summaries = vec![
+            day(date(2), 40.0),
+            day(date(15), 7.0),
+            day(date(16), 3.0),
+            day(date(18), 12.0),
+        ];
+        let config = BudgetConfig {
+            daily_limit_usd: Some(10.0),
+            weekly_limit_usd: Some(20.0),
+            monthly_limit_usd: Some(0.0),
+        };
+
+        let status = BudgetService::status(&config, &summaries, date(18), WeekStart::Monday);
+        let spent: Vec<(BudgetPeriod, f64, bool)> = status
+            .iter()
+            .map(|s| (s.period, s.spent_usd, s.over_budget))
+            .collect();
+        assert_eq!(
+            spent,
+            vec![
+                (BudgetPeriod::Daily, 12.0, true),
+                (BudgetPeriod::Weekly, 15.0, false),
+                (BudgetPeriod::Monthly, 62.0, true),
+            ]
+        );
+        assert!((status[1].percent_used - 75.0).abs() < 1e-9);
+        assert_eq!(status[2].percent_used, 100.0);
+
+        let sunday = BudgetService::status(&config, &summaries, date(18), WeekStart::Sunday);
+        assert_eq!(sunday[1].spent_usd, 22.0);
+    }
+
+    #[test]
+    fn test_status_skips_unset_limits() {
+        let config = BudgetConfig {
+            weekly_limit_usd: Some(5.0),
+            ..BudgetConfig::default()
+        };
+        let status = BudgetService::status(&config, &[], date(18), WeekStart::Sunday);
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].spent_usd, 0.0);
+        assert!(!status[0].over_budget);
+    }
+}
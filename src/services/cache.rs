@@ -3,16 +3,21 @@
 //! Caches daily summaries to preserve historical data even after
 //! original JSONL files are deleted.
 
+use crate::config::Config;
+use crate::services::atomic_write::atomic_write;
+use crate::services::data_dir::data_dir;
 use crate::services::{normalize_model_name, Aggregator};
 use crate::types::{CacheWarning, DailySummary, ModelUsage, Result, ToktrackError, UsageEntry};
 use chrono::{Local, NaiveDate};
-use directories::BaseDirs;
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
 /// Normalize model name keys in a HashMap, merging duplicates.
 fn normalize_model_keys(models: HashMap<String, ModelUsage>) -> HashMap<String, ModelUsage> {
@@ -41,9 +46,25 @@ fn normalize_model_keys(models: HashMap<String, ModelUsage>) -> HashMap<String,
     normalized
 }
 
-/// Bump when aggregation logic changes (e.g., timezone fix).
-/// Mismatched version → full cache invalidation.
-const CACHE_VERSION: u32 = 7;
+/// Bump when aggregation logic or the summary format changes (e.g., timezone fix).
+/// Older caches are upgraded with `DailySummaryCacheService::migrate`, so only bump
+/// once that can carry old data forward; dates with entries are recomputed anyway.
+pub const CACHE_VERSION: u32 = 7;
+
+/// Prefix of the zip comment of a cache backup; the cache version follows
+const BACKUP_COMMENT_PREFIX: &str = "toktrack cache v";
+
+/// How long cache writes wait for the exclusive lock before going ahead without it
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pause between write-lock attempts
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Cache saves tried before giving up and keeping the old cache
+const SAVE_ATTEMPTS: u32 = 5;
+
+/// Wait after the first failed save; doubles after each further failure
+const SAVE_RETRY_DELAY: Duration = Duration::from_millis(100);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DailySummaryCache {
@@ -54,22 +75,64 @@ pub struct DailySummaryCache {
     pub summaries: Vec<DailySummary>,
 }
 
+/// What `toktrack cache inspect` reports about one cache file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheMetadata {
+    pub cli: String,
+    pub version: u32,
+    pub entries: usize,
+    pub earliest: Option<NaiveDate>,
+    pub latest: Option<NaiveDate>,
+    pub size_bytes: u64,
+    /// Unix timestamp of the last write
+    pub updated_at: i64,
+}
+
 pub struct DailySummaryCacheService {
     cache_dir: PathBuf,
+    lock_timeout: Duration,
+    save_retry_delay: Duration,
+    max_age_days: Option<u32>,
 }
 
 impl DailySummaryCacheService {
-    pub fn new() -> Result<Self> {
-        let base_dirs = BaseDirs::new()
-            .ok_or_else(|| ToktrackError::Cache("Cannot determine home directory".into()))?;
-        let cache_dir = base_dirs.home_dir().join(".toktrack").join("cache");
+    /// Cache in `config.cache_dir`, else `cache/` in the data directory
+    pub fn new(config: Option<&Config>) -> Result<Self> {
+        let cache_dir = match config.and_then(|c| c.cache_dir.clone()) {
+            Some(dir) => dir,
+            None => data_dir()
+                .ok_or_else(|| ToktrackError::Cache("Cannot determine home directory".into()))?
+                .join("cache"),
+        };
         fs::create_dir_all(&cache_dir)?;
-        Ok(Self { cache_dir })
+        Ok(Self::with_cache_dir(cache_dir))
     }
 
-    #[allow(dead_code)]
     pub fn with_cache_dir(cache_dir: PathBuf) -> Self {
-        Self { cache_dir }
+        Self {
+            cache_dir,
+            lock_timeout: LOCK_TIMEOUT,
+            save_retry_delay: SAVE_RETRY_DELAY,
+            max_age_days: None,
+        }
+    }
+
+    /// Drop summaries older than `max_age_days` from the cache on every load.
+    /// Usage in pruned days is lost once its source files are gone.
+    pub fn with_max_age_days(mut self, max_age_days: Option<u32>) -> Self {
+        self.max_age_days = max_age_days;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_save_retry_delay(mut self, save_retry_delay: Duration) -> Self {
+        self.save_retry_delay = save_retry_delay;
+        self
+    }
+
+    /// Directory holding the cache files
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
     }
 
     pub fn cache_path(&self, cli: &str) -> PathBuf {
@@ -133,18 +196,26 @@ impl DailySummaryCacheService {
             .filter(|s| !new_dates.contains(&s.date))
             .collect();
         result.extend(new_summaries);
-        result.sort_by_key(|s| s.date);
+        if let Some(days) = self.max_age_days {
+            let cutoff = today - chrono::Duration::days(i64::from(days));
+            result.retain(|s| s.date >= cutoff);
+        }
+        // Also merges duplicate dates left by an older buggy write, so the cache self-heals
+        let result = Aggregator::merge_by_date(result);
 
         self.save_cache(cli, &result)?;
 
         Ok((result, warning))
     }
 
-    #[allow(dead_code)]
     pub fn clear(&self, cli: &str) -> Result<()> {
         let path = self.cache_path(cli);
         if path.exists() {
+            let (lock_file, locked) = self.lock_exclusive(cli)?;
             fs::remove_file(&path)?;
+            if locked {
+                let _ = lock_file.unlock();
+            }
         }
         let lock = self.lock_path(cli);
         if lock.exists() {
@@ -153,6 +224,167 @@ impl DailySummaryCacheService {
         Ok(())
     }
 
+    /// Remove every `*_daily.json` cache (and its lock), including caches of
+    /// other data dirs. Returns the number of caches removed.
+    pub fn clear_all(&self) -> Result<usize> {
+        let clis = self.cached_clis()?;
+        for cli in &clis {
+            self.clear(cli)?;
+        }
+        Ok(clis.len())
+    }
+
+    /// Names of every `*_daily.json` cache in the cache directory, sorted
+    pub fn cached_clis(&self) -> Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.cache_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut clis: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                name.to_string_lossy()
+                    .strip_suffix("_daily.json")
+                    .map(String::from)
+            })
+            .collect();
+        clis.sort();
+        Ok(clis)
+    }
+
+    /// Version, size and date range of the `cli` cache; `None` if there is none
+    pub fn inspect(&self, cli: &str) -> Result<Option<CacheMetadata>> {
+        let path = self.cache_path(cli);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let (lock_file, locked) = self.lock_exclusive(cli)?;
+        let result = self.read_cache(cli).and_then(|cache| {
+            let size_bytes = fs::metadata(&path)?.len();
+            Ok(CacheMetadata {
+                cli: cli.to_string(),
+                version: cache.version,
+                entries: cache.summaries.len(),
+                earliest: cache.summaries.iter().map(|s| s.date).min(),
+                latest: cache.summaries.iter().map(|s| s.date).max(),
+                size_bytes,
+                updated_at: cache.updated_at,
+            })
+        });
+        if locked {
+            let _ = lock_file.unlock();
+        }
+        result.map(Some)
+    }
+
+    /// Drop cached days before `before`, keeping the cache version. Returns the
+    /// number of days removed (or that would be, with `dry_run`).
+    pub fn prune(&self, cli: &str, before: NaiveDate, dry_run: bool) -> Result<usize> {
+        if !self.cache_path(cli).exists() {
+            return Ok(0);
+        }
+        let (lock_file, locked) = self.lock_exclusive(cli)?;
+        let result = self.read_cache(cli).and_then(|mut cache| {
+            let count = cache.summaries.len();
+            cache.summaries.retain(|s| s.date >= before);
+            let removed = count - cache.summaries.len();
+            if removed > 0 && !dry_run {
+                self.write_cache(cli, &cache)?;
+            }
+            Ok(removed)
+        });
+        if locked {
+            let _ = lock_file.unlock();
+        }
+        result
+    }
+
+    /// Zip every file in the cache directory (lock files excepted) into `dest`,
+    /// or a timestamped archive in `backups/` beside the cache directory
+    /// (`~/.toktrack/backups/` by default). Returns the archive path.
+    pub fn backup(&self, dest: Option<&Path>) -> Result<PathBuf> {
+        let path = match dest {
+            Some(dest) => dest.to_path_buf(),
+            None => self.next_backup_path()?,
+        };
+        let mut files = Vec::new();
+        collect_files(&self.cache_dir, &mut files)?;
+
+        let zip_err = |e: zip::result::ZipError| {
+            ToktrackError::Cache(format!("Failed to write backup {}: {}", path.display(), e))
+        };
+        let mut zip = ZipWriter::new(File::create(&path)?);
+        zip.set_comment(format!("{}{}", BACKUP_COMMENT_PREFIX, CACHE_VERSION));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for file in files {
+            let name = file
+                .strip_prefix(&self.cache_dir)
+                .unwrap_or(&file)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            zip.start_file(name, options).map_err(zip_err)?;
+            zip.write_all(&fs::read(&file)?)?;
+        }
+        zip.finish().map_err(zip_err)?;
+        Ok(path)
+    }
+
+    /// Back up the current caches, then extract `backup` into the cache directory.
+    /// Archives from a newer toktrack (higher cache version) are rejected.
+    pub fn restore(&self, backup: &Path) -> Result<()> {
+        let zip_err = |e: zip::result::ZipError| {
+            ToktrackError::Cache(format!("Failed to read backup {}: {}", backup.display(), e))
+        };
+        let mut archive = ZipArchive::new(File::open(backup)?).map_err(zip_err)?;
+        let version = std::str::from_utf8(archive.comment())
+            .ok()
+            .and_then(|c| c.strip_prefix(BACKUP_COMMENT_PREFIX))
+            .and_then(|v| v.parse::<u32>().ok())
+            .ok_or_else(|| {
+                ToktrackError::Cache(format!("{} is not a toktrack backup", backup.display()))
+            })?;
+        if version > CACHE_VERSION {
+            return Err(ToktrackError::Cache(format!(
+                "Backup has cache v{}, newer than this toktrack's v{}",
+                version, CACHE_VERSION
+            )));
+        }
+
+        self.backup(None)?;
+        archive.extract(&self.cache_dir).map_err(zip_err)
+    }
+
+    /// `toktrack-backup-<timestamp>.zip` in the backups directory, with a
+    /// numeric suffix when a backup was already taken this second
+    fn next_backup_path(&self) -> Result<PathBuf> {
+        let dir = self
+            .cache_dir
+            .parent()
+            .unwrap_or(&self.cache_dir)
+            .join("backups");
+        fs::create_dir_all(&dir)?;
+        let stem = format!("toktrack-backup-{}", Local::now().format("%Y%m%d-%H%M%S"));
+        let mut path = dir.join(format!("{}.zip", stem));
+        let mut n = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.zip", stem, n));
+            n += 1;
+        }
+        Ok(path)
+    }
+
+    fn read_cache(&self, cli: &str) -> Result<DailySummaryCache> {
+        let path = self.cache_path(cli);
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            ToktrackError::Cache(format!("Corrupted cache file {}: {}", path.display(), e))
+        })
+    }
+
     /// Load cached summaries for past dates (excludes today).
     /// Uses shared file lock for concurrent read safety.
     fn load_past_summaries(
@@ -209,15 +441,6 @@ impl DailySummaryCacheService {
             }
         };
 
-        let warning = if cache.version != CACHE_VERSION {
-            Some(CacheWarning::VersionMismatch(format!(
-                "Cache version {} != {}, recomputing available dates",
-                cache.version, CACHE_VERSION
-            )))
-        } else {
-            None
-        };
-
         if let Ok(ref lf) = lock_file {
             let _ = lf.unlock();
         }
@@ -232,11 +455,55 @@ impl DailySummaryCacheService {
                 s
             })
             .collect();
+        match Self::migrate(summaries, cache.version, CACHE_VERSION) {
+            Some(summaries) => (summaries, None),
+            None => (
+                Vec::new(),
+                Some(CacheWarning::VersionMismatch(format!(
+                    "Cache version {} != {}, no migration, recomputing available dates",
+                    cache.version, CACHE_VERSION
+                ))),
+            ),
+        }
+    }
 
-        (summaries, warning)
+    /// Upgrade summaries cached under `from_version` to `to_version`, one version
+    /// at a time, so days whose source files are gone survive a version bump.
+    /// None when a step has no migration (or the cache is newer than
+    /// `to_version`); the cached days are then dropped and recomputed.
+    pub fn migrate(
+        summaries: Vec<DailySummary>,
+        from_version: u32,
+        to_version: u32,
+    ) -> Option<Vec<DailySummary>> {
+        if from_version > to_version {
+            return None;
+        }
+        (from_version..to_version).try_fold(summaries, |summaries, version| {
+            match (version, version + 1) {
+                // v6 added thinking tokens; v5 caches had no such field
+                (5, 6) => Some(
+                    summaries
+                        .into_iter()
+                        .map(|mut s| {
+                            s.total_thinking_tokens = 0;
+                            for usage in s.models.values_mut() {
+                                usage.thinking_tokens = 0;
+                            }
+                            s
+                        })
+                        .collect(),
+                ),
+                // v7 kept the v6 summary format
+                (6, 7) => Some(summaries),
+                _ => None,
+            }
+        })
     }
 
     /// Save using atomic write (temp file + rename) with exclusive lock.
+    /// Retries with exponential backoff while another process holds the lock;
+    /// if every attempt fails the old cache is kept and only a warning is printed.
     fn save_cache(&self, cli: &str, summaries: &[DailySummary]) -> Result<()> {
         fs::create_dir_all(&self.cache_dir)?;
 
@@ -247,12 +514,53 @@ impl DailySummaryCacheService {
             summaries: summaries.to_vec(),
         };
 
-        let content = serde_json::to_string_pretty(&cache)
+        let mut delay = self.save_retry_delay;
+        for attempt in 1..=SAVE_ATTEMPTS {
+            match self.try_save(cli, &cache) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt == SAVE_ATTEMPTS => {
+                    eprintln!(
+                        "[toktrack] Warning: Cache write failed after {} attempts: {e}",
+                        SAVE_ATTEMPTS
+                    );
+                }
+                Err(_) => {
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One save attempt: take the exclusive lock without waiting, then write
+    fn try_save(&self, cli: &str, cache: &DailySummaryCache) -> Result<()> {
+        let lock_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.lock_path(cli))
+            .map_err(|e| ToktrackError::Cache(format!("Failed to open lock file: {}", e)))?;
+        lock_file
+            .try_lock_exclusive()
+            .map_err(|e| ToktrackError::Cache(format!("Cache is locked: {}", e)))?;
+        let result = self.write_cache(cli, cache);
+        let _ = lock_file.unlock();
+        result
+    }
+
+    /// Atomically replace the `cli` cache file; the caller holds the lock
+    fn write_cache(&self, cli: &str, cache: &DailySummaryCache) -> Result<()> {
+        let content = serde_json::to_string_pretty(cache)
             .map_err(|e| ToktrackError::Cache(format!("Serialization failed: {}", e)))?;
 
-        let path = self.cache_path(cli);
-        let temp_path = path.with_extension("json.tmp");
+        atomic_write(&self.cache_path(cli), content.as_bytes())
+    }
 
+    /// Open the lock file and take the exclusive lock. The flag is false when the
+    /// lock timed out: a lock left behind by a crashed process must not hang the
+    /// tool, so callers go ahead without it (writes stay atomic via rename).
+    fn lock_exclusive(&self, cli: &str) -> Result<(File, bool)> {
         let lock_path = self.lock_path(cli);
         let lock_file = OpenOptions::new()
             .write(true)
@@ -260,25 +568,49 @@ impl DailySummaryCacheService {
             .truncate(false)
             .open(&lock_path)
             .map_err(|e| ToktrackError::Cache(format!("Failed to open lock file: {}", e)))?;
-        lock_file
-            .lock_exclusive()
-            .map_err(|e| ToktrackError::Cache(format!("Failed to acquire write lock: {}", e)))?;
-
-        {
-            let mut file = File::create(&temp_path)
-                .map_err(|e| ToktrackError::Cache(format!("Failed to create temp file: {}", e)))?;
-            file.write_all(content.as_bytes())
-                .map_err(|e| ToktrackError::Cache(format!("Failed to write temp file: {}", e)))?;
-            file.sync_all()
-                .map_err(|e| ToktrackError::Cache(format!("Failed to sync temp file: {}", e)))?;
+        let locked = self.try_lock_exclusive(&lock_file);
+        if !locked {
+            eprintln!(
+                "[toktrack] Warning: cache lock {} held for over {}s; continuing without it",
+                lock_path.display(),
+                self.lock_timeout.as_secs()
+            );
         }
+        Ok((lock_file, locked))
+    }
 
-        fs::rename(&temp_path, &path)
-            .map_err(|e| ToktrackError::Cache(format!("Failed to rename temp file: {}", e)))?;
+    /// Try to take the exclusive lock until `lock_timeout` elapses
+    fn try_lock_exclusive(&self, lock_file: &File) -> bool {
+        let deadline = Instant::now() + self.lock_timeout;
+        loop {
+            if lock_file.try_lock_exclusive().is_ok() {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+    }
+}
 
-        let _ = lock_file.unlock();
-        Ok(())
+/// Files under `dir`, recursively, skipping lock files; nothing if `dir` is missing
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if path.extension().is_none_or(|ext| ext != "lock") {
+            files.push(path);
+        }
     }
+    files.sort();
+    Ok(())
 }
 
 #[cfg(test)]
@@ -310,6 +642,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }
     }
 
@@ -355,6 +690,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 9.99,
             models: HashMap::new(),
+            cost_estimated: false,
         };
         let cache = DailySummaryCache {
             cli: "claude-code".to_string(),
@@ -381,6 +717,9 @@ mod tests {
                 request_id: None,
                 source: None,
                 provider: None,
+                cost_estimated: false,
+                project_path: None,
+                git_branch: None,
             },
             UsageEntry {
                 timestamp: today.and_hms_opt(12, 0, 0).unwrap().and_utc(),
@@ -395,6 +734,9 @@ mod tests {
                 request_id: None,
                 source: None,
                 provider: None,
+                cost_estimated: false,
+                project_path: None,
+                git_branch: None,
             },
         ];
 
@@ -458,6 +800,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 9.99,
             models: HashMap::new(),
+            cost_estimated: false,
         };
         let cache = DailySummaryCache {
             cli: "claude-code".to_string(),
@@ -483,6 +826,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }];
 
         let (result, _warning) = service.load_or_compute("claude-code", &entries).unwrap();
@@ -493,6 +839,41 @@ mod tests {
         assert_eq!(result[0].total_input_tokens, 100); // New value, not 999
     }
 
+    #[test]
+    fn test_duplicate_cached_dates_are_merged() {
+        let (service, _temp) = create_test_service();
+        let past = Local::now().date_naive() - chrono::Duration::days(3);
+        let duplicate = |input: u64, cost: f64| DailySummary {
+            date: past,
+            total_input_tokens: input,
+            total_output_tokens: 0,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::new(),
+            cost_estimated: false,
+        };
+        let cache = DailySummaryCache {
+            cli: "claude-code".to_string(),
+            version: CACHE_VERSION,
+            updated_at: chrono::Utc::now().timestamp(),
+            summaries: vec![duplicate(100, 0.10), duplicate(50, 0.05)],
+        };
+        let cache_path = service.cache_path("claude-code");
+        fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+        fs::write(&cache_path, serde_json::to_string(&cache).unwrap()).unwrap();
+
+        let (result, _warning) = service.load_or_compute("claude-code", &[]).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].total_input_tokens, 150);
+        assert!((result[0].total_cost_usd - 0.15).abs() < 1e-9);
+
+        let saved: DailySummaryCache =
+            serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
+        assert_eq!(saved.summaries.len(), 1);
+    }
+
     // Test 6: Results are sorted ascending by date
     #[test]
     fn test_results_sorted_ascending() {
@@ -527,6 +908,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.005,
             models: HashMap::new(),
+            cost_estimated: false,
         };
         let cache = DailySummaryCache {
             cli: "claude-code".to_string(),
@@ -552,6 +934,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }];
 
         let (result, _warning) = service.load_or_compute("claude-code", &entries).unwrap();
@@ -663,6 +1048,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.30,
             models,
+            cost_estimated: false,
         };
         let cache = DailySummaryCache {
             cli: "claude-code".to_string(),
@@ -737,6 +1123,87 @@ mod tests {
         assert_eq!(result[0].total_input_tokens, 100);
     }
 
+    #[test]
+    fn test_migrate_steps_through_versions() {
+        let day = |thinking: u64| DailySummary {
+            date: NaiveDate::from_ymd_opt(2024, 1, 10).unwrap(),
+            total_input_tokens: 500,
+            total_output_tokens: 250,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: thinking,
+            total_cost_usd: 0.50,
+            models: HashMap::from([(
+                "claude".to_string(),
+                ModelUsage {
+                    input_tokens: 500,
+                    thinking_tokens: thinking,
+                    ..ModelUsage::default()
+                },
+            )]),
+            cost_estimated: false,
+        };
+
+        let migrated = DailySummaryCacheService::migrate(vec![day(7)], 5, CACHE_VERSION).unwrap();
+        assert_eq!(migrated.len(), 1);
+        assert_eq!(migrated[0].total_input_tokens, 500);
+        assert_eq!(migrated[0].total_thinking_tokens, 0);
+        assert_eq!(migrated[0].models["claude"].thinking_tokens, 0);
+
+        // Pass-through steps keep the data untouched
+        let kept = DailySummaryCacheService::migrate(vec![day(7)], 6, CACHE_VERSION);
+        assert_eq!(kept, Some(vec![day(7)]));
+        let current = DailySummaryCacheService::migrate(vec![day(7)], CACHE_VERSION, CACHE_VERSION);
+        assert_eq!(current, Some(vec![day(7)]));
+
+        // Versions without a migration, and newer caches, are dropped
+        assert_eq!(
+            DailySummaryCacheService::migrate(vec![day(7)], 0, CACHE_VERSION),
+            None
+        );
+        assert_eq!(
+            DailySummaryCacheService::migrate(vec![day(7)], 4, CACHE_VERSION),
+            None
+        );
+        let newer =
+            DailySummaryCacheService::migrate(vec![day(7)], CACHE_VERSION + 1, CACHE_VERSION);
+        assert_eq!(newer, None);
+    }
+
+    #[test]
+    fn test_version_mismatch_migrates_and_stamps_current_version() {
+        let (service, _temp) = create_test_service();
+        let old_date = Local::now().date_naive() - chrono::Duration::days(30);
+        let json = serde_json::json!({
+            "cli": "claude-code",
+            "version": 5,
+            "updated_at": chrono::Utc::now().timestamp(),
+            "summaries": [{
+                "date": old_date.to_string(),
+                "total_input_tokens": 700,
+                "total_output_tokens": 300,
+                "total_cache_read_tokens": 0,
+                "total_cache_creation_tokens": 0,
+                "total_cost_usd": 1.5,
+                "models": {}
+            }]
+        });
+        let cache_path = service.cache_path("claude-code");
+        fs::write(&cache_path, json.to_string()).unwrap();
+
+        let (result, warning) = service.load_or_compute("claude-code", &[]).unwrap();
+        assert!(warning.is_none());
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].total_input_tokens, 700);
+        assert_eq!(result[0].total_thinking_tokens, 0);
+
+        let saved: DailySummaryCache =
+            serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
+        assert_eq!(saved.version, CACHE_VERSION);
+        assert_eq!(saved.summaries.len(), 1);
+        assert!(service.is_version_current("claude-code"));
+    }
+
     // Test 13: Matching version loads cache normally
     #[test]
     fn test_matching_version_loads_normally() {
@@ -752,6 +1219,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.50,
             models: HashMap::new(),
+            cost_estimated: false,
         };
         let cache = DailySummaryCache {
             cli: "claude-code".to_string(),
@@ -772,6 +1240,25 @@ mod tests {
         assert_eq!(result[0].total_input_tokens, 500);
     }
 
+    #[test]
+    fn test_cache_without_cost_estimated_loads_normally() {
+        let (service, _temp) = create_test_service();
+        let yesterday = Local::now().date_naive() - chrono::Duration::days(1);
+        let content = format!(
+            r#"{{"cli":"claude-code","version":{},"updated_at":0,"summaries":[{{"date":"{}","total_input_tokens":500,"total_output_tokens":250,"total_cache_read_tokens":0,"total_cache_creation_tokens":0,"total_cost_usd":0.5,"models":{{}}}}]}}"#,
+            CACHE_VERSION, yesterday
+        );
+        let cache_path = service.cache_path("claude-code");
+        fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+        fs::write(&cache_path, content).unwrap();
+
+        let (result, warning) = service.load_or_compute("claude-code", &[]).unwrap();
+
+        assert!(warning.is_none());
+        assert_eq!(result.len(), 1);
+        assert!(!result[0].cost_estimated);
+    }
+
     // Test 14: Version mismatch preserves cached dates without entries
     #[test]
     fn test_version_mismatch_preserves_old_data_without_entries() {
@@ -779,10 +1266,10 @@ mod tests {
         let old_date = Local::now().date_naive() - chrono::Duration::days(30);
         let yesterday = Local::now().date_naive() - chrono::Duration::days(1);
 
-        // Old cache with two dates: old_date (no entries) + yesterday (has entries)
+        // Migratable old cache with two dates: old_date (no entries) + yesterday (has entries)
         let json = serde_json::json!({
             "cli": "claude-code",
-            "version": 0,
+            "version": 6,
             "updated_at": chrono::Utc::now().timestamp(),
             "summaries": [
                 {
@@ -824,7 +1311,7 @@ mod tests {
 
         let (result, warning) = service.load_or_compute("claude-code", &entries).unwrap();
 
-        assert!(matches!(warning, Some(CacheWarning::VersionMismatch(_))));
+        assert!(warning.is_none());
         assert_eq!(result.len(), 2);
 
         // old_date: preserved from cache (no entries to recompute)
@@ -840,4 +1327,285 @@ mod tests {
             serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
         assert_eq!(saved.version, CACHE_VERSION);
     }
+
+    // Test 15: Distinct data roots for the same CLI get separate cache files
+    #[test]
+    fn test_distinct_data_roots_use_separate_caches() {
+        use crate::parsers::{CLIParser, ClaudeCodeParser};
+
+        let (service, _temp) = create_test_service();
+        let root_a = TempDir::new().unwrap();
+        let root_b = TempDir::new().unwrap();
+        let parser_a = ClaudeCodeParser::with_data_dir(root_a.path().to_path_buf());
+        let parser_b = ClaudeCodeParser::with_data_dir(root_b.path().to_path_buf());
+
+        let entries_a = vec![make_entry(2024, 1, 10, Some("claude"), 100, 50, Some(0.01))];
+        let entries_b = vec![make_entry(2024, 1, 10, Some("claude"), 900, 50, Some(0.09))];
+        service
+            .load_or_compute(&parser_a.cache_key(), &entries_a)
+            .unwrap();
+        service
+            .load_or_compute(&parser_b.cache_key(), &entries_b)
+            .unwrap();
+
+        let path_a = service.cache_path(&parser_a.cache_key());
+        let path_b = service.cache_path(&parser_b.cache_key());
+        assert_ne!(path_a, path_b);
+        assert!(path_a.exists());
+        assert!(path_b.exists());
+        assert_eq!(
+            fs::read_dir(service.cache_dir.as_path())
+                .unwrap()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name().to_string_lossy().ends_with("_daily.json"))
+                .count(),
+            2
+        );
+
+        let cache_a: DailySummaryCache =
+            serde_json::from_str(&fs::read_to_string(&path_a).unwrap()).unwrap();
+        assert_eq!(cache_a.summaries[0].total_input_tokens, 100);
+    }
+
+    fn hold_lock(service: &DailySummaryCacheService, cli: &str) -> File {
+        let held = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(service.lock_path(cli))
+            .unwrap();
+        held.lock_exclusive().unwrap();
+        held
+    }
+
+    #[test]
+    fn test_save_gives_up_when_lock_is_held() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = DailySummaryCacheService::with_cache_dir(temp_dir.path().to_path_buf())
+            .with_save_retry_delay(Duration::from_millis(5));
+
+        // Simulate another (stuck) process holding the write lock
+        let held = hold_lock(&service, "claude");
+
+        let entries = vec![make_entry(2024, 1, 10, Some("claude"), 100, 50, Some(0.01))];
+        let started = Instant::now();
+        let (summaries, _) = service.load_or_compute("claude", &entries).unwrap();
+        // Backoff of 5 + 10 + 20 + 40ms between the five attempts
+        assert!(started.elapsed() >= Duration::from_millis(75));
+        assert_eq!(summaries.len(), 1);
+        assert!(!service.cache_path("claude").exists());
+
+        held.unlock().unwrap();
+    }
+
+    #[test]
+    fn test_save_retries_until_lock_is_released() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = DailySummaryCacheService::with_cache_dir(temp_dir.path().to_path_buf())
+            .with_save_retry_delay(Duration::from_millis(20));
+        let held = hold_lock(&service, "claude");
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            held.unlock().unwrap();
+        });
+
+        let entries = vec![make_entry(2024, 1, 10, Some("claude"), 100, 50, Some(0.01))];
+        service.load_or_compute("claude", &entries).unwrap();
+        releaser.join().unwrap();
+
+        let cache = service.read_cache("claude").unwrap();
+        assert_eq!(cache.summaries.len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_saves_leave_a_valid_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let handles: Vec<_> = (1..=4u32)
+            .map(|day| {
+                let dir = temp_dir.path().to_path_buf();
+                std::thread::spawn(move || {
+                    let service = DailySummaryCacheService::with_cache_dir(dir)
+                        .with_save_retry_delay(Duration::from_millis(1));
+                    let entries = vec![make_entry(
+                        2024,
+                        1,
+                        day,
+                        Some("claude"),
+                        100,
+                        50,
+                        Some(0.01),
+                    )];
+                    service.save_cache("claude", &Aggregator::daily(&entries))
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap().is_ok());
+        }
+
+        let service = DailySummaryCacheService::with_cache_dir(temp_dir.path().to_path_buf());
+        let cache = service.read_cache("claude").unwrap();
+        assert_eq!(cache.summaries.len(), 1);
+    }
+
+    #[test]
+    fn test_inspect_reports_cache_metadata() {
+        let (service, _temp) = create_test_service();
+        assert_eq!(service.inspect("claude").unwrap(), None);
+
+        let entries = vec![
+            make_entry(2024, 1, 10, Some("claude"), 100, 50, Some(0.01)),
+            make_entry(2024, 1, 12, Some("claude"), 100, 50, Some(0.01)),
+            make_entry(2024, 1, 11, Some("claude"), 100, 50, Some(0.01)),
+        ];
+        service.load_or_compute("claude", &entries).unwrap();
+
+        let meta = service.inspect("claude").unwrap().unwrap();
+        assert_eq!(meta.cli, "claude");
+        assert_eq!(meta.version, CACHE_VERSION);
+        assert_eq!(meta.entries, 3);
+        assert_eq!(meta.earliest, NaiveDate::from_ymd_opt(2024, 1, 10));
+        assert_eq!(meta.latest, NaiveDate::from_ymd_opt(2024, 1, 12));
+        assert_eq!(
+            meta.size_bytes,
+            fs::metadata(service.cache_path("claude")).unwrap().len()
+        );
+        assert_eq!(service.cached_clis().unwrap(), vec!["claude".to_string()]);
+    }
+
+    #[test]
+    fn test_prune_removes_days_before_date() {
+        let (service, _temp) = create_test_service();
+        let entries = vec![
+            make_entry(2024, 1, 10, Some("claude"), 100, 50, Some(0.01)),
+            make_entry(2024, 1, 11, Some("claude"), 100, 50, Some(0.01)),
+            make_entry(2024, 1, 12, Some("claude"), 100, 50, Some(0.01)),
+        ];
+        service.load_or_compute("claude", &entries).unwrap();
+        let before = NaiveDate::from_ymd_opt(2024, 1, 12).unwrap();
+
+        assert_eq!(service.prune("claude", before, true).unwrap(), 2);
+        assert_eq!(service.inspect("claude").unwrap().unwrap().entries, 3);
+
+        assert_eq!(service.prune("claude", before, false).unwrap(), 2);
+        let meta = service.inspect("claude").unwrap().unwrap();
+        assert_eq!(meta.entries, 1);
+        assert_eq!(meta.earliest, Some(before));
+        assert_eq!(service.prune("codex", before, false).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_max_age_days_prunes_saved_cache() {
+        let (service, _temp) = create_test_service();
+        let service = service.with_max_age_days(Some(30));
+        let today = Local::now().date_naive();
+        let entry_on = |date: NaiveDate| {
+            make_entry(
+                date.year(),
+                date.month(),
+                date.day(),
+                Some("claude"),
+                100,
+                50,
+                Some(0.01),
+            )
+        };
+        let recent = today - chrono::Duration::days(5);
+        let old = today - chrono::Duration::days(40);
+
+        let (summaries, _) = service
+            .load_or_compute("claude", &[entry_on(recent), entry_on(old)])
+            .unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].date, recent);
+
+        let content = fs::read_to_string(service.cache_path("claude")).unwrap();
+        let cache: DailySummaryCache = serde_json::from_str(&content).unwrap();
+        let dates: Vec<NaiveDate> = cache.summaries.iter().map(|s| s.date).collect();
+        assert_eq!(dates, vec![recent]);
+    }
+
+    fn nested_test_service() -> (DailySummaryCacheService, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let service = DailySummaryCacheService::with_cache_dir(temp_dir.path().join("cache"));
+        (service, temp_dir)
+    }
+
+    #[test]
+    fn test_backup_and_restore_round_trip() {
+        let (service, temp) = nested_test_service();
+        let entries = vec![make_entry(2024, 1, 10, Some("claude"), 100, 50, Some(0.01))];
+        service.load_or_compute("claude-code", &entries).unwrap();
+        fs::create_dir_all(service.cache_dir.join("raw")).unwrap();
+        fs::write(service.cache_dir.join("raw").join("a.json"), "{}").unwrap();
+        let original = fs::read_to_string(service.cache_path("claude-code")).unwrap();
+
+        let backup = service.backup(None).unwrap();
+        assert_eq!(backup.parent().unwrap(), temp.path().join("backups"));
+        let name = backup.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("toktrack-backup-") && name.ends_with(".zip"));
+
+        let archive = ZipArchive::new(File::open(&backup).unwrap()).unwrap();
+        assert_eq!(
+            archive.comment(),
+            format!("toktrack cache v{}", CACHE_VERSION).as_bytes()
+        );
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(names, vec!["claude-code_daily.json", "raw/a.json"]);
+
+        service.clear("claude-code").unwrap();
+        service.restore(&backup).unwrap();
+        assert_eq!(
+            fs::read_to_string(service.cache_path("claude-code")).unwrap(),
+            original
+        );
+        assert!(service.cache_dir.join("raw").join("a.json").exists());
+
+        // The pre-restore state got its own backup rather than replacing this one
+        assert_eq!(
+            fs::read_dir(temp.path().join("backups")).unwrap().count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_backup_to_explicit_destination() {
+        let (service, temp) = nested_test_service();
+        let dest = temp.path().join("mine.zip");
+        assert_eq!(service.backup(Some(&dest)).unwrap(), dest);
+        assert_eq!(
+            ZipArchive::new(File::open(&dest).unwrap()).unwrap().len(),
+            0
+        );
+        assert!(!temp.path().join("backups").exists());
+    }
+
+    #[test]
+    fn test_restore_rejects_foreign_or_newer_archives() {
+        let (service, temp) = nested_test_service();
+        let write_zip = |name: &str, comment: &str| {
+            let path = temp.path().join(name);
+            let mut zip = ZipWriter::new(File::create(&path).unwrap());
+            zip.set_comment(comment);
+            zip.finish().unwrap();
+            path
+        };
+
+        let foreign = write_zip("foreign.zip", "something else");
+        assert!(matches!(
+            service.restore(&foreign),
+            Err(ToktrackError::Cache(_))
+        ));
+        let newer = write_zip(
+            "newer.zip",
+            &format!("toktrack cache v{}", CACHE_VERSION + 1),
+        );
+        assert!(matches!(
+            service.restore(&newer),
+            Err(ToktrackError::Cache(_))
+        ));
+        // Rejected before touching anything
+        assert!(!temp.path().join("backups").exists());
+    }
 }
@@ -0,0 +1,80 @@
+//! Location of toktrack's own files (caches, pricing cache, state)
+//!
+//! Defaults to `~/.toktrack/`. `--portable` moves everything to a
+//! `toktrack-data/` directory beside the executable.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use directories::BaseDirs;
+
+use crate::types::{Result, ToktrackError};
+
+/// Directory created beside the executable in portable mode
+pub const PORTABLE_DIR_NAME: &str = "toktrack-data";
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Store all state under `dir` instead of `~/.toktrack/`.
+/// Call once at startup, before any service is created; later calls are ignored.
+pub fn set_data_dir(dir: PathBuf) {
+    let _ = DATA_DIR.set(dir);
+}
+
+/// Root of toktrack's files: the `set_data_dir` override or `~/.toktrack/`.
+/// None when the home directory cannot be determined.
+pub fn data_dir() -> Option<PathBuf> {
+    resolve(DATA_DIR.get())
+}
+
+fn resolve(override_dir: Option<&PathBuf>) -> Option<PathBuf> {
+    override_dir
+        .cloned()
+        .or_else(|| BaseDirs::new().map(|dirs| dirs.home_dir().join(".toktrack")))
+}
+
+/// `toktrack-data/` beside the running executable
+pub fn portable_dir() -> Result<PathBuf> {
+    portable_dir_for(&std::env::current_exe()?)
+}
+
+fn portable_dir_for(exe: &Path) -> Result<PathBuf> {
+    exe.parent()
+        .map(|dir| dir.join(PORTABLE_DIR_NAME))
+        .ok_or_else(|| {
+            ToktrackError::Config(format!("Cannot locate directory of {}", exe.display()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::DailySummaryCacheService;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_portable_dir_is_beside_executable() {
+        let dir = TempDir::new().unwrap();
+        let exe = dir.path().join("bin").join("toktrack");
+        assert_eq!(
+            portable_dir_for(&exe).unwrap(),
+            dir.path().join("bin").join(PORTABLE_DIR_NAME)
+        );
+        assert!(portable_dir_for(Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_portable_cache_reads_and_writes_beside_executable() {
+        let dir = TempDir::new().unwrap();
+        let portable = portable_dir_for(&dir.path().join("toktrack")).unwrap();
+        let root = resolve(Some(&portable)).unwrap();
+        assert_eq!(root, portable);
+        let service = DailySummaryCacheService::with_cache_dir(root.join("cache"));
+
+        service.load_or_compute("claude-code", &[]).unwrap();
+        let path = service.cache_path("claude-code");
+        assert!(path.starts_with(dir.path().join(PORTABLE_DIR_NAME)));
+        assert!(path.exists());
+        assert!(service.load_or_compute("claude-code", &[]).is_ok());
+    }
+}
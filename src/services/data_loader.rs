@@ -3,17 +3,26 @@
 //! This module provides a single `DataLoaderService` that consolidates
 //! the duplicated data loading logic from CLI and TUI.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::SystemTime;
 
-use chrono::{Local, TimeZone};
+use chrono::{Local, NaiveDate, TimeZone};
 
-use crate::parsers::{ClaudeCodeParser, ParserRegistry};
+use crate::config::Config;
+use crate::parsers::{
+    dedup_entries, modified_since, CLIParser, ClaudeCodeParser, JsonBackend, ParserRegistry,
+};
+use crate::services::bookmark_cache::BookmarkCacheService;
+use crate::services::merge_dir::{load_machine_exports, MachineSummaries};
+use crate::services::raw_cache::RawEntryCacheService;
 use crate::services::session_metadata::{extract_issue_id, SessionMetadataService};
 use crate::services::{Aggregator, DailySummaryCacheService, PricingService};
 use crate::types::{
-    AutoDetected, CacheWarning, DailySummary, Result, SessionInfo, SessionMetadata, SourceUsage,
-    ToktrackError, UsageEntry,
+    AutoDetected, CacheWarning, DailySummary, ParseProgress, ParseStats, ProviderSummary, Result,
+    SessionDetailEntry, SessionInfo, SessionMetadata, SourceUsage, ToktrackError, UsageEntry,
+    SOURCE_TOP_MODELS,
 };
 
 /// Compute the warm-path cutoff: yesterday 00:00:00 local time.
@@ -22,14 +31,18 @@ use crate::types::{
 /// "yesterday" (the most recent completed day) is always recomputed
 /// before being trusted as a complete cached date.
 fn warm_path_since() -> SystemTime {
-    let yesterday = Local::now().date_naive() - chrono::Duration::days(1);
-    let yesterday_midnight = yesterday.and_hms_opt(0, 0, 0).unwrap();
-    let utc = match Local.from_local_datetime(&yesterday_midnight) {
+    local_midnight(Local::now().date_naive() - chrono::Duration::days(1))
+}
+
+/// Start of `date` (00:00:00 local time) as a `SystemTime`
+fn local_midnight(date: NaiveDate) -> SystemTime {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+    let utc = match Local.from_local_datetime(&midnight) {
         chrono::LocalResult::Single(dt) => dt.to_utc(),
         chrono::LocalResult::Ambiguous(earlier, _) => earlier.to_utc(),
         chrono::LocalResult::None => {
             // DST spring-forward: midnight doesn't exist, use 01:00
-            let fallback = yesterday.and_hms_opt(1, 0, 0).unwrap();
+            let fallback = date.and_hms_opt(1, 0, 0).unwrap();
             Local
                 .from_local_datetime(&fallback)
                 .earliest()
@@ -40,8 +53,77 @@ fn warm_path_since() -> SystemTime {
     SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(utc.timestamp() as u64)
 }
 
+/// Resolve a data directory for overlap comparison (symlinks followed when possible)
+fn resolve_dir(dir: &Path) -> PathBuf {
+    std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Files each parser must skip because an earlier (higher-priority) parser in
+/// the registry reads the same files through an identical or nested data dir.
+///
+/// Without this, the same usage would be counted once per source, and
+/// dedup can't catch it since the entries carry different `source` tags.
+/// Warns once per overlapping pair.
+fn overlap_exclusions(parsers: &[&dyn CLIParser]) -> Vec<HashSet<PathBuf>> {
+    let dirs: Vec<Option<PathBuf>> = parsers
+        .iter()
+        .map(|p| p.data_dir().exists().then(|| resolve_dir(p.data_dir())))
+        .collect();
+    let mut exclusions = vec![HashSet::new(); parsers.len()];
+
+    for (i, lower) in parsers.iter().enumerate() {
+        let Some(lower_dir) = &dirs[i] else { continue };
+        for (j, higher) in parsers[..i].iter().enumerate() {
+            let Some(higher_dir) = &dirs[j] else { continue };
+            if !lower_dir.starts_with(higher_dir) && !higher_dir.starts_with(lower_dir) {
+                continue;
+            }
+            eprintln!(
+                "[toktrack] Warning: {} and {} data directories overlap ({} / {}); files read by {} are skipped for {}",
+                higher.name(),
+                lower.name(),
+                higher_dir.display(),
+                lower_dir.display(),
+                higher.name(),
+                lower.name()
+            );
+            let claimed: HashSet<PathBuf> = higher
+                .collect_files()
+                .iter()
+                .map(|f| resolve_dir(f))
+                .collect();
+            exclusions[i].extend(
+                lower
+                    .collect_files()
+                    .into_iter()
+                    .filter(|f| claimed.contains(&resolve_dir(f))),
+            );
+        }
+    }
+
+    exclusions
+}
+
+/// Files a parser should read: all (or only those modified since `since`),
+/// minus `excluded` files claimed by a higher-priority parser.
+fn select_files(
+    parser: &dyn CLIParser,
+    since: Option<SystemTime>,
+    excluded: &HashSet<PathBuf>,
+) -> Vec<PathBuf> {
+    parser
+        .collect_files()
+        .into_iter()
+        .filter(|f| !excluded.contains(f))
+        .filter(|f| match since {
+            Some(since) => modified_since(f, since),
+            None => true,
+        })
+        .collect()
+}
+
 /// Result of loading data from all parsers
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct LoadResult {
     /// Daily summaries from all sources, merged by date
     pub summaries: Vec<DailySummary>,
@@ -53,6 +135,29 @@ pub struct LoadResult {
     pub cache_warning: Option<CacheWarning>,
     /// Claude Code session metadata
     pub sessions: Vec<SessionInfo>,
+    /// Parse statistics summed across all parsers
+    pub parse_stats: ParseStats,
+    /// Usage per provider across all sources, costliest first
+    pub provider_usage: Vec<ProviderSummary>,
+}
+
+/// Outcome of `DataLoaderService::rebuild_caches`
+#[derive(Debug)]
+pub struct CacheRebuild {
+    /// Number of daily cache files deleted
+    pub cleared: usize,
+    /// Rebuilt day count per registered parser, in registry order
+    pub days: Vec<(String, usize)>,
+}
+
+/// Data found for one registered parser, without parsing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceStatus {
+    pub name: String,
+    /// Files matching the parser's pattern
+    pub file_count: usize,
+    /// Whether there is anything to load
+    pub active: bool,
 }
 
 /// Unified data loading service
@@ -63,41 +168,387 @@ pub struct LoadResult {
 pub struct DataLoaderService {
     registry: ParserRegistry,
     cache_service: Option<DailySummaryCacheService>,
+    /// Bookmarked entries of recent files, read incrementally by the warm path
+    bookmarks: Option<BookmarkCacheService>,
+    raw_cache: Option<RawEntryCacheService>,
     pricing: Option<PricingService>,
+    progress: Option<Arc<ParseProgress>>,
+    include_synthetic: bool,
+    exclude_today: bool,
+    show_empty_sources: bool,
+    merge_dir: Option<PathBuf>,
+    /// Only load these parsers by name (None = all)
+    sources: Option<Vec<String>>,
+    config: Config,
 }
 
 impl DataLoaderService {
-    /// Create a new data loader service
+    /// Create a new data loader service using `~/.toktrack/config.toml`
     pub fn new() -> Self {
+        Self::from_config(Config::load_or_default())
+    }
+
+    /// Create a data loader whose paths and pricing TTL come from `config`
+    pub fn from_config(config: Config) -> Self {
+        let cache_service = DailySummaryCacheService::new(Some(&config)).ok();
+        let bookmarks = cache_service
+            .as_ref()
+            .map(|cs| BookmarkCacheService::with_cache_dir(cs.cache_dir().to_path_buf()));
         Self {
-            registry: ParserRegistry::new(),
-            cache_service: DailySummaryCacheService::new().ok(),
-            pricing: PricingService::from_cache_only(),
+            registry: ParserRegistry::new(Some(&config)),
+            cache_service,
+            bookmarks,
+            raw_cache: None,
+            pricing: PricingService::from_cache_only(Some(&config)),
+            progress: None,
+            include_synthetic: false,
+            exclude_today: false,
+            show_empty_sources: false,
+            merge_dir: None,
+            sources: None,
+            config,
         }
     }
 
-    /// Load data from all parsers using cache-first strategy
-    pub fn load(&self) -> Result<LoadResult> {
-        // Load sessions independently (always from sessions-index.json + JSONL fallback)
-        let mut sessions = ClaudeCodeParser::new().parse_sessions_index(self.pricing.as_ref());
+    /// Settings this loader was created with
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
 
-        // Attach sidecar metadata to sessions
-        Self::attach_metadata(&mut sessions);
+    /// Cached pricing, if any, for costing entries outside `load()`
+    pub fn pricing(&self) -> Option<&PricingService> {
+        self.pricing.as_ref()
+    }
 
-        if self.has_valid_cache() {
-            if let Ok(mut result) = self.load_warm_path() {
-                if !result.summaries.is_empty() {
-                    result.sessions = sessions;
-                    return Ok(result);
-                }
+    /// Keep synthetic entries (e.g. Claude `<synthetic>` responses) instead of dropping them
+    pub fn with_include_synthetic(mut self, include_synthetic: bool) -> Self {
+        self.include_synthetic = include_synthetic;
+        self
+    }
+
+    /// Prune cached summaries older than `max_age_days` (`--max-age-days`)
+    pub fn with_max_age_days(mut self, max_age_days: Option<u32>) -> Self {
+        self.cache_service = self
+            .cache_service
+            .map(|cs| cs.with_max_age_days(max_age_days));
+        self
+    }
+
+    /// Drop today's in-progress usage so totals cover completed days only
+    pub fn with_exclude_today(mut self, exclude_today: bool) -> Self {
+        self.exclude_today = exclude_today;
+        self
+    }
+
+    /// Keep sources with zero tokens in `source_usage` (`--show-empty-sources`)
+    pub fn with_show_empty_sources(mut self, show_empty_sources: bool) -> Self {
+        self.show_empty_sources = show_empty_sources;
+        self
+    }
+
+    /// Add the daily exports of other machines found in `merge_dir` (`--merge-dir`)
+    pub fn with_merge_dir(mut self, merge_dir: Option<PathBuf>) -> Self {
+        self.merge_dir = merge_dir;
+        self
+    }
+
+    /// Load only the parsers named in `sources` (`--source`)
+    pub fn with_sources(mut self, sources: Option<Vec<String>>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Whether the parser named `name` passes the `--source` filter
+    fn includes(&self, name: &str) -> bool {
+        self.sources
+            .as_ref()
+            .is_none_or(|sources| sources.iter().any(|s| s == name))
+    }
+
+    /// Parsers to load: those with a data directory, narrowed by `--source`
+    fn active_parsers(&self) -> Vec<&dyn CLIParser> {
+        self.registry
+            .detect_active()
+            .into_iter()
+            .filter(|p| self.includes(p.name()))
+            .collect()
+    }
+
+    /// Every registered parser, whether or not its data directory exists
+    fn all_parsers(&self) -> Vec<&dyn CLIParser> {
+        self.registry.parsers().iter().map(|p| p.as_ref()).collect()
+    }
+
+    /// Reject `--source` names without a registered parser
+    fn check_sources(&self) -> Result<()> {
+        for source in self.sources.iter().flatten() {
+            if self.registry.get(source).is_none() {
+                return Err(ToktrackError::Parse(format!("Unknown source: {}", source)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode JSON with serde_json instead of simd-json (`--safe-parse`)
+    pub fn with_safe_parse(mut self, safe_parse: bool) -> Self {
+        if safe_parse {
+            self.registry =
+                ParserRegistry::with_json_backend(JsonBackend::Serde, Some(&self.config));
+        }
+        self
+    }
+
+    /// Reuse parsed entries of unchanged files from `raw/` in the cache directory (`--raw-cache`)
+    pub fn with_raw_cache(mut self, raw_cache: bool) -> Self {
+        if !raw_cache {
+            self.raw_cache = None;
+            return self;
+        }
+        let result = match &self.cache_service {
+            Some(cs) => RawEntryCacheService::new(cs.cache_dir()),
+            None => Err(ToktrackError::Cache("cache directory unavailable".into())),
+        };
+        self.raw_cache = match result {
+            Ok(rc) => Some(rc),
+            Err(e) => {
+                eprintln!("[toktrack] Warning: raw cache disabled: {}", e);
+                None
             }
+        };
+        self
+    }
+
+    /// Report files-parsed progress through `progress` while loading
+    pub fn with_progress(mut self, progress: Arc<ParseProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Name and data directory of every registered parser
+    pub fn data_dirs(&self) -> Vec<(String, PathBuf)> {
+        self.registry
+            .parsers()
+            .iter()
+            .map(|parser| (parser.name().to_string(), parser.data_dir().to_path_buf()))
+            .collect()
+    }
+
+    /// File counts of every registered parser passing `--source`, from a glob
+    /// of its data directory. Cheap enough to run before `load`.
+    pub fn sources(&self) -> Vec<SourceStatus> {
+        self.all_parsers()
+            .into_iter()
+            .filter(|p| self.includes(p.name()))
+            .map(|parser| {
+                let file_count = if parser.data_dir_exists() {
+                    parser.collect_files().len()
+                } else {
+                    0
+                };
+                SourceStatus {
+                    name: parser.name().to_string(),
+                    file_count,
+                    active: file_count > 0,
+                }
+            })
+            .collect()
+    }
+
+    /// Parse one parser's files, advancing the shared progress counter
+    fn parse_files(&self, parser: &dyn CLIParser, files: &[PathBuf]) -> Result<Vec<UsageEntry>> {
+        match &self.raw_cache {
+            Some(raw_cache) => raw_cache.parse_and_dedup(
+                parser,
+                files,
+                self.progress.as_deref(),
+                self.include_synthetic,
+            ),
+            None => parser.parse_and_dedup_with_progress(
+                files,
+                self.progress.as_deref(),
+                self.include_synthetic,
+            ),
+        }
+    }
+
+    /// Parse the recent files of a parser with a daily cache. Files are read
+    /// from their bookmark on, unless `--raw-cache` already skips unchanged ones.
+    fn parse_recent(&self, parser: &dyn CLIParser, files: &[PathBuf]) -> Result<Vec<UsageEntry>> {
+        match (&self.bookmarks, &self.raw_cache) {
+            (Some(bookmarks), None) => bookmarks.parse_and_dedup(
+                parser,
+                files,
+                self.progress.as_deref(),
+                self.include_synthetic,
+            ),
+            _ => self.parse_files(parser, files),
         }
+    }
+
+    /// Reset the progress counter for a pass over `file_lists`
+    fn start_progress(&self, file_lists: &[Vec<PathBuf>]) {
+        if let Some(progress) = &self.progress {
+            progress.start(file_lists.iter().map(Vec::len).sum());
+        }
+    }
 
-        let mut result = self.load_cold_path()?;
+    /// Load data from all parsers (or the `--source` ones) using cache-first strategy
+    pub fn load(&self) -> Result<LoadResult> {
+        self.check_sources()?;
+        let sessions = self.load_sessions();
+
+        let parsers = self.active_parsers();
+        let exclusions = overlap_exclusions(&parsers);
+
+        let warm = if self.has_valid_cache() {
+            self.load_warm_path(&parsers, &exclusions)
+                .ok()
+                .filter(|result| !result.summaries.is_empty())
+        } else {
+            None
+        };
+
+        let mut result = match warm {
+            Some(result) => result,
+            None => self.load_cold_path(&parsers, &exclusions)?,
+        };
         result.sessions = sessions;
+        // Other machines' exports are not split by source, so they can't be filtered
+        if let Some(dir) = self.merge_dir.as_ref().filter(|_| self.sources.is_none()) {
+            self.merge_machines(&mut result, load_machine_exports(dir)?);
+        }
+        if self.exclude_today {
+            self.exclude_date(&mut result, Local::now().date_naive());
+        }
+        self.drop_empty_sources(&mut result);
+        attach_top_models(&mut result);
+        result.provider_usage = Aggregator::by_provider_from_daily(&result.source_summaries);
         Ok(result)
     }
 
+    /// Priced entries from every parser whose local date is `date`.
+    /// Only files modified on or after that day are parsed.
+    pub fn load_entries_on(&self, date: NaiveDate) -> Result<Vec<UsageEntry>> {
+        let since = local_midnight(date);
+        let parsers = self.active_parsers();
+        let exclusions = overlap_exclusions(&parsers);
+        let mut all_entries = Vec::new();
+        for (parser, excluded) in parsers.iter().zip(&exclusions) {
+            let files = select_files(*parser, Some(since), excluded);
+            match self.parse_files(*parser, &files) {
+                Ok(entries) => all_entries.extend(
+                    entries
+                        .into_iter()
+                        .filter(|entry| entry.local_date() == date),
+                ),
+                Err(e) => eprintln!("[toktrack] Warning: {} failed: {}", parser.name(), e),
+            }
+        }
+        Ok(self.apply_pricing(all_entries))
+    }
+
+    /// Merge other machines' summaries into the totals; each machine becomes a
+    /// `machine:<name>` source
+    fn merge_machines(&self, result: &mut LoadResult, machines: Vec<MachineSummaries>) {
+        if machines.is_empty() {
+            return;
+        }
+        for machine in machines {
+            let source = format!("machine:{}", machine.machine);
+            let existing = result.source_summaries.remove(&source).unwrap_or_default();
+            let merged =
+                Aggregator::merge_by_date(existing.into_iter().chain(machine.summaries).collect());
+            result.source_summaries.insert(source, merged);
+        }
+        (result.summaries, result.source_usage) =
+            Aggregator::merge_sources(&result.source_summaries);
+    }
+
+    /// Hide sources without usage (e.g. an empty data dir) unless `show_empty_sources`
+    fn drop_empty_sources(&self, result: &mut LoadResult) {
+        if !self.show_empty_sources {
+            result.source_usage.retain(|usage| usage.total_tokens > 0);
+        }
+    }
+
+    /// Remove `date` from merged and per-source summaries and recompute source usage
+    fn exclude_date(&self, result: &mut LoadResult, date: NaiveDate) {
+        for summaries in result.source_summaries.values_mut() {
+            summaries.retain(|s| s.date != date);
+        }
+        (result.summaries, result.source_usage) =
+            Aggregator::merge_sources(&result.source_summaries);
+    }
+
+    /// Delete every daily summary cache, then rebuild caches for all registered
+    /// parsers from a full parse
+    pub fn rebuild_caches(&self) -> Result<CacheRebuild> {
+        let cache_service = self
+            .cache_service
+            .as_ref()
+            .ok_or_else(|| ToktrackError::Cache("No cache service".into()))?;
+        let cleared = cache_service.clear_all()?;
+
+        let parsers = self.all_parsers();
+        let result = self.load_cold_path(&parsers, &overlap_exclusions(&parsers))?;
+        let days = self
+            .registry
+            .parsers()
+            .iter()
+            .map(|parser| {
+                let count = result
+                    .source_summaries
+                    .get(parser.name())
+                    .map_or(0, Vec::len);
+                (parser.name().to_string(), count)
+            })
+            .collect();
+        Ok(CacheRebuild { cleared, days })
+    }
+
+    /// Claude Code entries tagged with their session's working directory and
+    /// git branch, priced
+    pub fn load_project_entries(&self) -> Vec<UsageEntry> {
+        self.registry
+            .claude()
+            .map(|parser| self.project_entries_from(parser))
+            .unwrap_or_default()
+    }
+
+    fn project_entries_from(&self, parser: &ClaudeCodeParser) -> Vec<UsageEntry> {
+        let entries: Vec<UsageEntry> = parser
+            .collect_files()
+            .iter()
+            .flat_map(|f| match parser.parse_file_with_metadata(f) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    eprintln!("[toktrack] Warning: Failed to parse {:?}: {}", f, e);
+                    Vec::new()
+                }
+            })
+            .filter(|entry| self.include_synthetic || !parser.is_synthetic(entry))
+            .collect();
+        self.apply_pricing(dedup_entries(entries))
+    }
+
+    /// Load Claude Code sessions (sessions-index.json + JSONL fallback) with sidecar metadata.
+    /// None when `--source` leaves out Claude Code.
+    pub fn load_sessions(&self) -> Vec<SessionInfo> {
+        let claude = self.registry.claude().filter(|p| self.includes(p.name()));
+        let mut sessions = claude.map_or_else(Vec::new, |parser| {
+            parser.parse_sessions_index(self.pricing.as_ref(), self.include_synthetic)
+        });
+        Self::attach_metadata(&mut sessions);
+        sessions
+    }
+
+    /// Requests of the Claude Code session logged at `jsonl_path`, oldest first
+    pub fn load_session_detail(&self, jsonl_path: &str) -> Vec<SessionDetailEntry> {
+        self.registry.claude().map_or_else(Vec::new, |parser| {
+            parser.parse_session_detail(jsonl_path, self.pricing.as_ref(), self.include_synthetic)
+        })
+    }
+
     /// Attach sidecar metadata to sessions.
     /// If no sidecar exists, try `extract_issue_id` from git_branch as virtual fallback.
     fn attach_metadata(sessions: &mut [SessionInfo]) {
@@ -150,12 +601,16 @@ impl DataLoaderService {
             self.registry
                 .parsers()
                 .iter()
-                .any(|p| cs.is_version_current(p.name()))
+                .any(|p| cs.is_version_current(&p.cache_key()))
         })
     }
 
     /// Warm path: use cached DailySummaries + parse only recent files
-    fn load_warm_path(&self) -> Result<LoadResult> {
+    fn load_warm_path(
+        &self,
+        parsers: &[&dyn CLIParser],
+        exclusions: &[HashSet<PathBuf>],
+    ) -> Result<LoadResult> {
         let cache_service = self
             .cache_service
             .as_ref()
@@ -163,45 +618,50 @@ impl DataLoaderService {
 
         let since = warm_path_since();
 
-        let mut all_summaries = Vec::new();
-        let mut source_stats: HashMap<String, (u64, f64)> = HashMap::new();
         let mut source_summaries: HashMap<String, Vec<DailySummary>> = HashMap::new();
         let mut cache_warning = None;
 
-        for parser in self.registry.parsers() {
-            let has_parser_cache = cache_service.cache_path(parser.name()).exists();
+        // Recent files only when a parser cache exists; otherwise a full parse
+        let has_parser_cache: Vec<bool> = parsers
+            .iter()
+            .map(|parser| cache_service.cache_path(&parser.cache_key()).exists())
+            .collect();
+        let file_lists: Vec<Vec<PathBuf>> = parsers
+            .iter()
+            .zip(exclusions)
+            .zip(&has_parser_cache)
+            .map(|((parser, excluded), &cached)| {
+                select_files(*parser, cached.then_some(since), excluded)
+            })
+            .collect();
+        self.start_progress(&file_lists);
 
-            let entries = if has_parser_cache {
-                match parser.parse_recent_files(since) {
-                    Ok(e) => e,
-                    Err(e) => {
-                        eprintln!("[toktrack] Warning: {} failed: {}", parser.name(), e);
-                        continue;
-                    }
-                }
+        for ((parser, files), &cached) in parsers.iter().zip(&file_lists).zip(&has_parser_cache) {
+            let cache_key = parser.cache_key();
+            let parsed = if cached {
+                self.parse_recent(*parser, files)
             } else {
-                match parser.parse_all() {
-                    Ok(e) => e,
-                    Err(e) => {
-                        eprintln!("[toktrack] Warning: {} failed: {}", parser.name(), e);
-                        continue;
-                    }
+                self.parse_files(*parser, files)
+            };
+            let entries = match parsed {
+                Ok(e) => e,
+                Err(e) => {
+                    eprintln!("[toktrack] Warning: {} failed: {}", parser.name(), e);
+                    continue;
                 }
             };
 
             let entries = self.apply_pricing(entries);
 
-            match cache_service.load_or_compute(parser.name(), &entries) {
+            match cache_service.load_or_compute(&cache_key, &entries) {
                 Ok((summaries, warning)) => {
                     if warning.is_some() && cache_warning.is_none() {
                         cache_warning = warning;
                     }
-                    self.collect_source_stats(&summaries, parser.name(), &mut source_stats);
                     source_summaries
                         .entry(parser.name().to_string())
                         .or_default()
-                        .extend(summaries.iter().cloned());
-                    all_summaries.extend(summaries);
+                        .extend(summaries);
                 }
                 Err(e) => {
                     eprintln!(
@@ -213,38 +673,48 @@ impl DataLoaderService {
             }
         }
 
-        let all_summaries = Aggregator::merge_by_date(all_summaries);
-        let source_usage = Self::build_source_usage(source_stats);
+        let (summaries, source_usage) = Aggregator::merge_sources(&source_summaries);
 
         Ok(LoadResult {
-            summaries: all_summaries,
+            summaries,
             source_usage,
             source_summaries,
             cache_warning,
             sessions: Vec::new(), // populated by load()
+            parse_stats: self.collect_parse_stats(),
+            provider_usage: Vec::new(), // populated by load()
         })
     }
 
     /// Cold path: full parse_all() per parser + build cache
-    fn load_cold_path(&self) -> Result<LoadResult> {
+    fn load_cold_path(
+        &self,
+        parsers: &[&dyn CLIParser],
+        exclusions: &[HashSet<PathBuf>],
+    ) -> Result<LoadResult> {
         // Try network pricing if cache-only failed
         let fallback_pricing;
         let pricing_ref = match &self.pricing {
             Some(p) => Some(p),
             None => {
-                fallback_pricing = PricingService::new().ok();
+                fallback_pricing = PricingService::new(Some(&self.config)).ok();
                 fallback_pricing.as_ref()
             }
         };
 
-        let mut all_summaries = Vec::new();
-        let mut source_stats: HashMap<String, (u64, f64)> = HashMap::new();
         let mut source_summaries: HashMap<String, Vec<DailySummary>> = HashMap::new();
         let mut cache_warning = None;
         let mut any_entries = false;
 
-        for parser in self.registry.parsers() {
-            let entries = match parser.parse_all() {
+        let file_lists: Vec<Vec<PathBuf>> = parsers
+            .iter()
+            .zip(exclusions)
+            .map(|(parser, excluded)| select_files(*parser, None, excluded))
+            .collect();
+        self.start_progress(&file_lists);
+
+        for (parser, files) in parsers.iter().zip(&file_lists) {
+            let entries = match self.parse_files(*parser, files) {
                 Ok(e) => e,
                 Err(e) => {
                     eprintln!("[toktrack] Warning: {} failed: {}", parser.name(), e);
@@ -261,17 +731,15 @@ impl DataLoaderService {
 
             // Try to use cache service
             if let Some(cs) = &self.cache_service {
-                match cs.load_or_compute(parser.name(), &entries) {
+                match cs.load_or_compute(&parser.cache_key(), &entries) {
                     Ok((summaries, warning)) => {
                         if warning.is_some() && cache_warning.is_none() {
                             cache_warning = warning;
                         }
-                        self.collect_source_stats(&summaries, parser.name(), &mut source_stats);
                         source_summaries
                             .entry(parser.name().to_string())
                             .or_default()
-                            .extend(summaries.iter().cloned());
-                        all_summaries.extend(summaries);
+                            .extend(summaries);
                         continue;
                     }
                     Err(e) => {
@@ -286,32 +754,38 @@ impl DataLoaderService {
 
             // Cache unavailable: compute summaries directly
             let summaries = Aggregator::daily(&entries);
-            self.collect_source_stats(&summaries, parser.name(), &mut source_stats);
             source_summaries
                 .entry(parser.name().to_string())
                 .or_default()
-                .extend(summaries.iter().cloned());
-            all_summaries.extend(summaries);
+                .extend(summaries);
         }
 
         if !any_entries {
-            return Err(ToktrackError::Parse(
-                "No usage data found from any CLI".into(),
-            ));
+            return Err(ToktrackError::NoData);
         }
 
-        let all_summaries = Aggregator::merge_by_date(all_summaries);
-        let source_usage = Self::build_source_usage(source_stats);
+        let (summaries, source_usage) = Aggregator::merge_sources(&source_summaries);
 
         Ok(LoadResult {
-            summaries: all_summaries,
+            summaries,
             source_usage,
             source_summaries,
             cache_warning,
             sessions: Vec::new(), // populated by load()
+            parse_stats: self.collect_parse_stats(),
+            provider_usage: Vec::new(), // populated by load()
         })
     }
 
+    /// Sum parse statistics from all registered parsers
+    fn collect_parse_stats(&self) -> ParseStats {
+        let mut stats = ParseStats::default();
+        for parser in self.registry.parsers() {
+            stats.add(parser.parse_stats());
+        }
+        stats
+    }
+
     /// Apply pricing to entries using cached pricing service
     fn apply_pricing(&self, entries: Vec<UsageEntry>) -> Vec<UsageEntry> {
         self.apply_pricing_with_ref(entries, self.pricing.as_ref())
@@ -332,46 +806,13 @@ impl DataLoaderService {
                 } else if entry.cost_usd.is_none() {
                     if let Some(p) = pricing {
                         entry.cost_usd = Some(p.calculate_cost(&entry));
+                        entry.cost_estimated = true;
                     }
                 }
                 entry
             })
             .collect()
     }
-
-    /// Collect source statistics from summaries
-    fn collect_source_stats(
-        &self,
-        summaries: &[DailySummary],
-        source_name: &str,
-        stats: &mut HashMap<String, (u64, f64)>,
-    ) {
-        for s in summaries {
-            let tokens = s.total_input_tokens
-                + s.total_output_tokens
-                + s.total_cache_read_tokens
-                + s.total_cache_creation_tokens
-                + s.total_thinking_tokens;
-            let stat = stats.entry(source_name.to_string()).or_default();
-            stat.0 = stat.0.saturating_add(tokens);
-            stat.1 += s.total_cost_usd;
-        }
-    }
-
-    /// Convert source stats map to sorted SourceUsage vector
-    fn build_source_usage(source_stats: HashMap<String, (u64, f64)>) -> Vec<SourceUsage> {
-        let mut result: Vec<SourceUsage> = source_stats
-            .into_iter()
-            .map(|(source, (total_tokens, total_cost_usd))| SourceUsage {
-                source,
-                total_tokens,
-                total_cost_usd,
-            })
-            .collect();
-        // Sort by total_tokens descending
-        result.sort_by(|a, b| b.total_tokens.cmp(&a.total_tokens));
-        result
-    }
 }
 
 impl Default for DataLoaderService {
@@ -380,6 +821,18 @@ impl Default for DataLoaderService {
     }
 }
 
+/// Fill each source's `by_model` with its costliest models
+fn attach_top_models(result: &mut LoadResult) {
+    for usage in &mut result.source_usage {
+        if let Some(summaries) = result.source_summaries.get(&usage.source) {
+            usage.by_model = Aggregator::models_by_cost(Aggregator::by_model_from_daily(summaries))
+                .into_iter()
+                .take(SOURCE_TOP_MODELS)
+                .collect();
+        }
+    }
+}
+
 /// Check if provider is GitHub Copilot (free service)
 pub fn is_copilot_provider(provider: Option<&str>) -> bool {
     matches!(
@@ -424,46 +877,6 @@ mod tests {
         assert!(!is_copilot_provider(Some("")));
     }
 
-    // ========== build_source_usage tests ==========
-
-    #[test]
-    fn test_build_source_usage_empty() {
-        let stats = HashMap::new();
-        let result = DataLoaderService::build_source_usage(stats);
-        assert!(result.is_empty());
-    }
-
-    #[test]
-    fn test_build_source_usage_single_source() {
-        let mut stats = HashMap::new();
-        stats.insert("claude".to_string(), (1000u64, 0.05f64));
-
-        let result = DataLoaderService::build_source_usage(stats);
-
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].source, "claude");
-        assert_eq!(result[0].total_tokens, 1000);
-        assert!((result[0].total_cost_usd - 0.05).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_build_source_usage_sorted_by_tokens_descending() {
-        let mut stats = HashMap::new();
-        stats.insert("claude".to_string(), (500u64, 0.03f64));
-        stats.insert("opencode".to_string(), (2000u64, 0.10f64));
-        stats.insert("gemini".to_string(), (1000u64, 0.05f64));
-
-        let result = DataLoaderService::build_source_usage(stats);
-
-        assert_eq!(result.len(), 3);
-        assert_eq!(result[0].source, "opencode");
-        assert_eq!(result[0].total_tokens, 2000);
-        assert_eq!(result[1].source, "gemini");
-        assert_eq!(result[1].total_tokens, 1000);
-        assert_eq!(result[2].source, "claude");
-        assert_eq!(result[2].total_tokens, 500);
-    }
-
     // ========== warm_path_since tests ==========
 
     use chrono::Timelike;
@@ -542,6 +955,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: provider.map(|s| s.to_string()),
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }
     }
 
@@ -579,4 +995,506 @@ mod tests {
         // Copilot should always be $0 regardless of original cost
         assert_eq!(result[0].cost_usd, Some(0.0));
     }
+
+    #[test]
+    fn test_mixed_logged_and_estimated_day_is_flagged() {
+        use crate::services::pricing::{ModelPricing, PricingCache};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache_path = dir.path().join("pricing.json");
+        let cache = PricingCache {
+            fetched_at: chrono::Utc::now().timestamp(),
+            models: HashMap::from([(
+                "claude-sonnet-4-5".to_string(),
+                ModelPricing {
+                    input_cost_per_token: Some(0.000003),
+                    output_cost_per_token: Some(0.000015),
+                    cache_read_input_token_cost: None,
+                    cache_creation_input_token_cost: None,
+                    output_cost_per_reasoning_token: None,
+                    context_window_tokens: None,
+                },
+            )]),
+            bundled_at: None,
+        };
+        std::fs::write(&cache_path, serde_json::to_string(&cache).unwrap()).unwrap();
+        let pricing = PricingService::from_cache_only_with_path(&cache_path).unwrap();
+
+        let service = DataLoaderService::new();
+        let logged = service.apply_pricing_with_ref(
+            vec![make_entry(Some(0.05), Some("anthropic"))],
+            Some(&pricing),
+        );
+        let mixed = service.apply_pricing_with_ref(
+            vec![
+                make_entry(Some(0.05), Some("anthropic")),
+                make_entry(None, Some("anthropic")),
+            ],
+            Some(&pricing),
+        );
+        assert!(!mixed[0].cost_estimated);
+        assert!(mixed[1].cost_estimated);
+
+        let logged_day = Aggregator::daily(&logged);
+        assert!(!logged_day[0].cost_estimated);
+
+        let mixed_day = Aggregator::daily(&mixed);
+        assert_eq!(mixed_day.len(), 1);
+        assert!(mixed_day[0].cost_estimated);
+
+        let json = serde_json::to_value(&mixed_day[0]).unwrap();
+        assert_eq!(json["cost_estimated"], true);
+    }
+
+    #[test]
+    fn test_exclude_date_drops_today_and_lowers_totals() {
+        let today = Local::now().date_naive();
+        let yesterday = today - chrono::Duration::days(1);
+        let service = DataLoaderService::new().with_exclude_today(true);
+        let entries = vec![
+            UsageEntry {
+                timestamp: Local::now().to_utc(),
+                ..make_entry(Some(0.50), Some("anthropic"))
+            },
+            UsageEntry {
+                timestamp: Local::now().to_utc() - chrono::Duration::days(1),
+                ..make_entry(Some(0.25), Some("anthropic"))
+            },
+        ];
+        let summaries = Aggregator::daily(&entries);
+        let total = |summaries: &[DailySummary]| -> f64 {
+            summaries.iter().map(|s| s.total_cost_usd).sum()
+        };
+        let mut result = LoadResult {
+            summaries: summaries.clone(),
+            source_usage: Vec::new(),
+            source_summaries: HashMap::from([("claude".to_string(), summaries)]),
+            cache_warning: None,
+            sessions: Vec::new(),
+            parse_stats: ParseStats::default(),
+            provider_usage: Vec::new(),
+        };
+        assert!((total(&result.summaries) - 0.75).abs() < 1e-9);
+
+        service.exclude_date(&mut result, today);
+        let dates: Vec<NaiveDate> = result.summaries.iter().map(|s| s.date).collect();
+        assert_eq!(dates, vec![yesterday]);
+        assert!((total(&result.summaries) - 0.25).abs() < 1e-9);
+        assert_eq!(result.source_summaries["claude"].len(), 1);
+        assert_eq!(result.source_usage.len(), 1);
+        assert!((result.source_usage[0].total_cost_usd - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_sources_hidden_unless_requested() {
+        let usage = |source: &str, total_tokens: u64| SourceUsage {
+            source: source.to_string(),
+            total_tokens,
+            total_cost_usd: 0.0,
+            by_model: Vec::new(),
+        };
+        let make_result = || LoadResult {
+            summaries: Vec::new(),
+            source_usage: vec![usage("claude-code", 1500), usage("gemini", 0)],
+            source_summaries: HashMap::new(),
+            cache_warning: None,
+            sessions: Vec::new(),
+            parse_stats: ParseStats::default(),
+            provider_usage: Vec::new(),
+        };
+        let sources = |result: &LoadResult| -> Vec<String> {
+            result
+                .source_usage
+                .iter()
+                .map(|u| u.source.clone())
+                .collect()
+        };
+
+        let mut result = make_result();
+        DataLoaderService::new().drop_empty_sources(&mut result);
+        assert_eq!(sources(&result), vec!["claude-code"]);
+
+        let mut result = make_result();
+        DataLoaderService::new()
+            .with_show_empty_sources(true)
+            .drop_empty_sources(&mut result);
+        assert_eq!(sources(&result), vec!["claude-code", "gemini"]);
+    }
+
+    #[test]
+    fn test_merge_machines_adds_exports_from_merge_dir() {
+        use chrono::Datelike;
+
+        let day = |d: u32, cost: f64| DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 3, d).unwrap(),
+            total_input_tokens: 100,
+            total_output_tokens: 0,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::new(),
+            cost_estimated: false,
+        };
+        let dir = tempfile::TempDir::new().unwrap();
+        let tagged = serde_json::json!({"machine": "desktop", "summaries": [day(1, 2.0)]});
+        std::fs::write(dir.path().join("desktop.json"), tagged.to_string()).unwrap();
+        let bare = serde_json::to_string(&vec![day(1, 0.5), day(2, 0.25)]).unwrap();
+        std::fs::write(dir.path().join("laptop.json"), bare).unwrap();
+
+        let local = vec![day(1, 1.0)];
+        let mut result = LoadResult {
+            summaries: local.clone(),
+            source_usage: Vec::new(),
+            source_summaries: HashMap::from([("claude-code".to_string(), local)]),
+            cache_warning: None,
+            sessions: Vec::new(),
+            parse_stats: ParseStats::default(),
+            provider_usage: Vec::new(),
+        };
+        let service = DataLoaderService::new();
+        service.merge_machines(&mut result, load_machine_exports(dir.path()).unwrap());
+
+        let days: Vec<(u32, f64)> = result
+            .summaries
+            .iter()
+            .map(|s| (s.date.day(), s.total_cost_usd))
+            .collect();
+        assert_eq!(days, vec![(1, 3.5), (2, 0.25)]);
+        assert_eq!(result.summaries[0].total_input_tokens, 300);
+
+        let mut sources: Vec<&str> = result
+            .source_usage
+            .iter()
+            .map(|u| u.source.as_str())
+            .collect();
+        sources.sort_unstable();
+        assert_eq!(
+            sources,
+            vec!["claude-code", "machine:desktop", "machine:laptop"]
+        );
+        assert_eq!(result.source_summaries["machine:laptop"].len(), 2);
+    }
+
+    #[test]
+    fn test_rebuild_caches_matches_fresh_load() {
+        use crate::parsers::CodexParser;
+        use crate::services::pricing::PricingCache;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let pricing_path = dir.path().join("pricing.json");
+        let pricing = PricingCache {
+            fetched_at: chrono::Utc::now().timestamp(),
+            models: HashMap::new(),
+            bundled_at: None,
+        };
+        std::fs::write(&pricing_path, serde_json::to_string(&pricing).unwrap()).unwrap();
+
+        let cache_dir = dir.path().join("cache");
+        std::fs::create_dir(&cache_dir).unwrap();
+        let make_service = || {
+            let registry = ParserRegistry::from_parsers(vec![
+                Box::new(ClaudeCodeParser::with_data_dir(fixtures("multi"))),
+                Box::new(CodexParser::with_data_dir(fixtures("codex"))),
+            ]);
+            DataLoaderService {
+                registry,
+                cache_service: Some(DailySummaryCacheService::with_cache_dir(cache_dir.clone())),
+                bookmarks: Some(BookmarkCacheService::with_cache_dir(cache_dir.clone())),
+                raw_cache: None,
+                pricing: PricingService::from_cache_only_with_path(&pricing_path),
+                progress: None,
+                include_synthetic: false,
+                exclude_today: false,
+                show_empty_sources: false,
+                merge_dir: None,
+                sources: None,
+                config: Config::default(),
+            }
+        };
+
+        // Fresh load into an empty cache dir is the reference
+        let service = make_service();
+        let fresh = service
+            .load_cold_path(
+                &service.all_parsers(),
+                &overlap_exclusions(&service.all_parsers()),
+            )
+            .unwrap();
+        let cache_service = DailySummaryCacheService::with_cache_dir(cache_dir.clone());
+        let cached = |cli: &str| std::fs::read_to_string(cache_service.cache_path(cli)).unwrap();
+        let summaries_of = |content: String| {
+            serde_json::from_str::<crate::services::cache::DailySummaryCache>(&content)
+                .unwrap()
+                .summaries
+        };
+        let keys: Vec<String> = service
+            .registry
+            .parsers()
+            .iter()
+            .map(|p| p.cache_key())
+            .collect();
+        let reference: Vec<Vec<DailySummary>> =
+            keys.iter().map(|k| summaries_of(cached(k))).collect();
+
+        // Corrupt one cache and leave a stale cache from another data dir
+        std::fs::write(cache_service.cache_path(&keys[0]), "{not json").unwrap();
+        std::fs::write(cache_service.cache_path("gemini-deadbeef"), "{}").unwrap();
+
+        let rebuild = make_service().rebuild_caches().unwrap();
+        assert_eq!(rebuild.cleared, 3);
+        assert!(!cache_service.cache_path("gemini-deadbeef").exists());
+        let rebuilt: Vec<Vec<DailySummary>> =
+            keys.iter().map(|k| summaries_of(cached(k))).collect();
+        assert_eq!(rebuilt, reference);
+
+        let expected: Vec<(String, usize)> = ["claude-code", "codex"]
+            .iter()
+            .map(|name| (name.to_string(), fresh.source_summaries[*name].len()))
+            .collect();
+        assert_eq!(rebuild.days, expected);
+        assert!(rebuild.days.iter().all(|(_, days)| *days > 0));
+    }
+
+    #[test]
+    fn test_source_filter_loads_a_subset() {
+        use crate::parsers::CodexParser;
+        use crate::services::pricing::PricingCache;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let pricing_path = dir.path().join("pricing.json");
+        let pricing = PricingCache {
+            fetched_at: chrono::Utc::now().timestamp(),
+            models: HashMap::new(),
+            bundled_at: None,
+        };
+        std::fs::write(&pricing_path, serde_json::to_string(&pricing).unwrap()).unwrap();
+        let make_service = |sources: Option<&[&str]>| DataLoaderService {
+            registry: ParserRegistry::from_parsers(vec![
+                Box::new(ClaudeCodeParser::with_data_dir(fixtures("multi"))),
+                Box::new(CodexParser::with_data_dir(fixtures("codex"))),
+            ]),
+            cache_service: None,
+            bookmarks: None,
+            raw_cache: None,
+            pricing: PricingService::from_cache_only_with_path(&pricing_path),
+            progress: None,
+            include_synthetic: false,
+            exclude_today: false,
+            show_empty_sources: false,
+            merge_dir: None,
+            sources: sources.map(|s| s.iter().map(|name| name.to_string()).collect()),
+            config: Config::default(),
+        };
+        // Cold path only: `load()` would also read the real Claude sessions index
+        let load = |service: DataLoaderService| {
+            let parsers = service.active_parsers();
+            service
+                .load_cold_path(&parsers, &overlap_exclusions(&parsers))
+                .unwrap()
+        };
+        let total_tokens = |result: &LoadResult| -> u64 {
+            result.summaries.iter().map(|s| s.total_tokens()).sum()
+        };
+
+        let full = load(make_service(None));
+        let codex = load(make_service(Some(&["codex"])));
+        let claude = load(make_service(Some(&["claude-code"])));
+
+        assert_eq!(
+            codex.source_summaries.keys().collect::<Vec<_>>(),
+            vec!["codex"]
+        );
+        assert_eq!(
+            codex.source_summaries["codex"],
+            full.source_summaries["codex"]
+        );
+        assert!(total_tokens(&codex) < total_tokens(&full));
+        for day in &codex.summaries {
+            let full_day = full.summaries.iter().find(|s| s.date == day.date).unwrap();
+            assert!(day.total_tokens() <= full_day.total_tokens());
+        }
+        // The sources partition the full result
+        assert_eq!(
+            total_tokens(&codex) + total_tokens(&claude),
+            total_tokens(&full)
+        );
+
+        // Both sources merged by date equal the unfiltered load
+        let both = load(make_service(Some(&["claude-code", "codex"])));
+        assert_eq!(both.summaries, full.summaries);
+    }
+
+    #[test]
+    fn test_sources_counts_files_without_parsing() {
+        let service = DataLoaderService {
+            registry: ParserRegistry::from_parsers(vec![
+                Box::new(ClaudeCodeParser::with_data_dir(fixtures("multi"))),
+                Box::new(crate::parsers::CodexParser::with_data_dir(fixtures(
+                    "does-not-exist",
+                ))),
+            ]),
+            ..DataLoaderService::from_config(Config::default())
+        };
+        let sources = service.sources();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].name, "claude-code");
+        assert!(sources[0].active);
+        assert!(sources[0].file_count > 0);
+        assert_eq!(
+            sources[1],
+            SourceStatus {
+                name: "codex".to_string(),
+                file_count: 0,
+                active: false,
+            }
+        );
+
+        let filtered = DataLoaderService {
+            sources: Some(vec!["codex".to_string()]),
+            ..service
+        };
+        assert_eq!(filtered.sources().len(), 1);
+    }
+
+    #[test]
+    fn test_claude_sessions_and_projects_use_configured_dir() {
+        let loader = |dir: &str| {
+            DataLoaderService::from_config(Config {
+                data_dirs: HashMap::from([(
+                    "claude-code".to_string(),
+                    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                        .join("tests/data")
+                        .join(dir),
+                )]),
+                ..Config::default()
+            })
+        };
+        assert_eq!(loader("sessions").load_sessions().len(), 3);
+        let codex_only = loader("sessions").with_sources(Some(vec!["codex".to_string()]));
+        assert!(codex_only.load_sessions().is_empty());
+        let entries = loader("projects").load_project_entries();
+        assert!(!entries.is_empty());
+        assert!(entries.iter().any(|e| e.project_path.is_some()));
+    }
+
+    #[test]
+    fn test_raw_cache_lives_in_configured_cache_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let loader = DataLoaderService::from_config(Config {
+            cache_dir: Some(dir.path().to_path_buf()),
+            ..Config::default()
+        })
+        .with_raw_cache(true);
+        assert!(loader.raw_cache.is_some());
+        assert!(dir.path().join("raw").is_dir());
+    }
+
+    #[test]
+    fn test_unknown_source_is_parse_error() {
+        let service = DataLoaderService::new().with_sources(Some(vec!["claude".to_string()]));
+        match service.load() {
+            Err(ToktrackError::Parse(message)) => assert_eq!(message, "Unknown source: claude"),
+            other => panic!("expected unknown source error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_load_entries_on_keeps_only_that_local_date() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let now = chrono::Utc::now();
+        let line = |id: &str, ts: chrono::DateTime<chrono::Utc>| {
+            format!(
+                r#"{{"type":"assistant","timestamp":"{}","requestId":"req-{id}","message":{{"id":"msg-{id}","model":"claude-sonnet-4-5","usage":{{"input_tokens":10,"output_tokens":5}}}},"costUSD":0.01}}"#,
+                ts.to_rfc3339()
+            ) + "\n"
+        };
+        let content = line("today", now) + &line("old", now - chrono::Duration::days(3));
+        std::fs::write(dir.path().join("s.jsonl"), content).unwrap();
+
+        let service = DataLoaderService {
+            registry: ParserRegistry::from_parsers(vec![Box::new(
+                ClaudeCodeParser::with_data_dir(dir.path().to_path_buf()),
+            )]),
+            cache_service: None,
+            bookmarks: None,
+            raw_cache: None,
+            pricing: None,
+            progress: None,
+            include_synthetic: false,
+            exclude_today: false,
+            show_empty_sources: false,
+            merge_dir: None,
+            sources: None,
+            config: Config::default(),
+        };
+        let today = now.with_timezone(&Local).date_naive();
+        let entries = service.load_entries_on(today).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message_id.as_deref(), Some("msg-today"));
+    }
+
+    // ========== overlapping data dir tests ==========
+
+    fn fixtures(sub: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(sub)
+    }
+
+    fn refs(parsers: &[Box<dyn CLIParser>]) -> Vec<&dyn CLIParser> {
+        parsers.iter().map(|p| p.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_overlap_exclusions_same_dir() {
+        use crate::parsers::CodexParser;
+
+        let parsers: Vec<Box<dyn CLIParser>> = vec![
+            Box::new(ClaudeCodeParser::with_data_dir(fixtures(""))),
+            Box::new(CodexParser::with_data_dir(fixtures(""))),
+        ];
+        let exclusions = overlap_exclusions(&refs(&parsers));
+
+        // Higher-priority parser keeps everything; the other skips all shared files
+        assert!(exclusions[0].is_empty());
+        assert_eq!(exclusions[1].len(), parsers[1].collect_files().len());
+        assert!(select_files(parsers[1].as_ref(), None, &exclusions[1]).is_empty());
+    }
+
+    #[test]
+    fn test_overlap_exclusions_nested_dir_prevents_double_count() {
+        let parsers: Vec<Box<dyn CLIParser>> = vec![
+            Box::new(ClaudeCodeParser::with_data_dir(fixtures(""))),
+            Box::new(ClaudeCodeParser::with_data_dir(fixtures("multi"))),
+        ];
+        let no_exclusions = HashSet::new();
+        assert_eq!(
+            select_files(parsers[1].as_ref(), None, &no_exclusions).len(),
+            2
+        );
+
+        let exclusions = overlap_exclusions(&refs(&parsers));
+        assert_eq!(exclusions[1].len(), 2);
+        assert!(select_files(parsers[1].as_ref(), None, &exclusions[1]).is_empty());
+        // Recent-files path honours exclusions too
+        assert!(select_files(
+            parsers[1].as_ref(),
+            Some(SystemTime::UNIX_EPOCH),
+            &exclusions[1]
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_overlap_exclusions_disjoint_or_missing_dirs() {
+        let parsers: Vec<Box<dyn CLIParser>> = vec![
+            Box::new(ClaudeCodeParser::with_data_dir(fixtures("multi"))),
+            Box::new(ClaudeCodeParser::with_data_dir(fixtures("codex"))),
+            Box::new(ClaudeCodeParser::with_data_dir(fixtures("nonexistent"))),
+        ];
+        let exclusions = overlap_exclusions(&refs(&parsers));
+        assert!(exclusions.iter().all(|e| e.is_empty()));
+    }
 }
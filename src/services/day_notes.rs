@@ -0,0 +1,128 @@
+//! Day notes service
+//!
+//! Free-form notes attached to calendar dates ("big refactor", "prod incident"),
+//! stored in `~/.toktrack/notes.json` as a `{ "YYYY-MM-DD": "text" }` map.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::services::data_dir::data_dir;
+use chrono::NaiveDate;
+
+use crate::types::{Result, ToktrackError};
+
+/// Service for managing per-day notes
+pub struct DayNotesService {
+    path: PathBuf,
+}
+
+impl DayNotesService {
+    /// Create a new service using the default path (`~/.toktrack/notes.json`)
+    pub fn new() -> Result<Self> {
+        let dir = data_dir()
+            .ok_or_else(|| ToktrackError::Config("Cannot determine home directory".into()))?;
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            path: dir.join("notes.json"),
+        })
+    }
+
+    /// Create a service with a custom file path (for testing)
+    #[cfg(test)]
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Load all notes. Missing or unreadable files yield an empty map.
+    pub fn load(&self) -> BTreeMap<NaiveDate, String> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Set the note for `date`, replacing any existing one
+    pub fn set(&self, date: NaiveDate, text: String) -> Result<()> {
+        let mut notes = self.load();
+        notes.insert(date, text);
+        self.save(&notes)
+    }
+
+    /// Remove the note for `date`. Returns whether a note existed.
+    pub fn clear(&self, date: NaiveDate) -> Result<bool> {
+        let mut notes = self.load();
+        let existed = notes.remove(&date).is_some();
+        if existed {
+            self.save(&notes)?;
+        }
+        Ok(existed)
+    }
+
+    fn save(&self, notes: &BTreeMap<NaiveDate, String>) -> Result<()> {
+        let content = serde_json::to_string_pretty(notes)
+            .map_err(|e| ToktrackError::Cache(format!("Failed to serialize notes: {}", e)))?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_service() -> (TempDir, DayNotesService) {
+        let tmp = TempDir::new().unwrap();
+        let svc = DayNotesService::with_path(tmp.path().join("notes.json"));
+        (tmp, svc)
+    }
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 2, d).unwrap()
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let (_tmp, svc) = make_service();
+        assert!(svc.load().is_empty());
+    }
+
+    #[test]
+    fn test_set_and_load() {
+        let (_tmp, svc) = make_service();
+        svc.set(date(3), "big refactor".to_string()).unwrap();
+        svc.set(date(5), "prod incident".to_string()).unwrap();
+
+        let notes = svc.load();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[&date(3)], "big refactor");
+        assert_eq!(notes[&date(5)], "prod incident");
+    }
+
+    #[test]
+    fn test_set_replaces_existing() {
+        let (_tmp, svc) = make_service();
+        svc.set(date(3), "first".to_string()).unwrap();
+        svc.set(date(3), "second".to_string()).unwrap();
+        assert_eq!(svc.load()[&date(3)], "second");
+    }
+
+    #[test]
+    fn test_clear() {
+        let (_tmp, svc) = make_service();
+        svc.set(date(3), "note".to_string()).unwrap();
+
+        assert!(svc.clear(date(3)).unwrap());
+        assert!(!svc.clear(date(3)).unwrap());
+        assert!(svc.load().is_empty());
+    }
+
+    #[test]
+    fn test_file_format_uses_iso_date_keys() {
+        let (tmp, svc) = make_service();
+        svc.set(date(3), "note".to_string()).unwrap();
+        let content = fs::read_to_string(tmp.path().join("notes.json")).unwrap();
+        assert!(content.contains("\"2026-02-03\": \"note\""));
+    }
+}
@@ -0,0 +1,118 @@
+//! Daily summaries exported by other machines (`--merge-dir`)
+//!
+//! Each `*.json` file in the directory is either the output of
+//! `toktrack daily --json` (machine named after the file stem) or an object
+//! `{"machine": "...", "summaries": [...]}`.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::types::{DailySummary, Result, ToktrackError};
+
+/// Daily summaries exported by one machine
+#[derive(Debug, Clone, PartialEq)]
+pub struct MachineSummaries {
+    pub machine: String,
+    pub summaries: Vec<DailySummary>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MachineExport {
+    Tagged {
+        machine: String,
+        summaries: Vec<DailySummary>,
+    },
+    Bare(Vec<DailySummary>),
+}
+
+/// Read every `*.json` export in `dir`, sorted by file name.
+/// Unreadable or malformed files are skipped with a warning.
+pub fn load_machine_exports(dir: &Path) -> Result<Vec<MachineSummaries>> {
+    if !dir.is_dir() {
+        return Err(ToktrackError::Config(format!(
+            "merge dir {} is not a directory",
+            dir.display()
+        )));
+    }
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut machines = Vec::new();
+    for path in paths {
+        let export = fs::read_to_string(&path)
+            .map_err(ToktrackError::from)
+            .and_then(|content| {
+                serde_json::from_str::<MachineExport>(&content)
+                    .map_err(|e| ToktrackError::Parse(e.to_string()))
+            });
+        match export {
+            Ok(MachineExport::Tagged { machine, summaries }) => {
+                machines.push(MachineSummaries { machine, summaries })
+            }
+            Ok(MachineExport::Bare(summaries)) => {
+                let machine = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                machines.push(MachineSummaries { machine, summaries })
+            }
+            Err(e) => {
+                eprintln!("[toktrack] Warning: Skipping export {:?}: {}", path, e);
+            }
+        }
+    }
+    Ok(machines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn summary(day: u32, cost: f64) -> DailySummary {
+        DailySummary {
+            date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            total_input_tokens: 100,
+            total_output_tokens: 50,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: cost,
+            models: HashMap::new(),
+            cost_estimated: false,
+        }
+    }
+
+    #[test]
+    fn test_load_tagged_and_bare_exports() {
+        let dir = TempDir::new().unwrap();
+        let tagged = serde_json::json!({"machine": "desktop", "summaries": [summary(1, 1.0)]});
+        fs::write(dir.path().join("a.json"), tagged.to_string()).unwrap();
+        fs::write(
+            dir.path().join("laptop.json"),
+            serde_json::to_string(&vec![summary(2, 2.0)]).unwrap(),
+        )
+        .unwrap();
+        fs::write(dir.path().join("broken.json"), "not json").unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let machines = load_machine_exports(dir.path()).unwrap();
+        let names: Vec<&str> = machines.iter().map(|m| m.machine.as_str()).collect();
+        assert_eq!(names, vec!["desktop", "laptop"]);
+        assert_eq!(machines[1].summaries, vec![summary(2, 2.0)]);
+    }
+
+    #[test]
+    fn test_missing_dir_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_machine_exports(&dir.path().join("missing")).is_err());
+    }
+}
@@ -1,15 +1,24 @@
 //! Services for data aggregation and processing
 
 pub mod aggregator;
+pub mod atomic_write;
+pub mod bookmark_cache;
+pub mod budget;
 pub mod cache;
+pub mod data_dir;
 pub mod data_loader;
+pub mod day_notes;
+pub mod merge_dir;
 pub mod normalizer;
 pub mod pricing;
+pub mod raw_cache;
+pub mod run_state;
 pub mod session_metadata;
 pub mod update_checker;
+pub mod watcher;
 
 pub use aggregator::Aggregator;
 pub use cache::DailySummaryCacheService;
 pub use data_loader::DataLoaderService;
-pub use normalizer::{display_name, normalize_model_name};
+pub use normalizer::{display_name, normalize_model_name, provider_for_model};
 pub use pricing::PricingService;
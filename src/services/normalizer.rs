@@ -3,26 +3,67 @@
 //! Normalizes model names to a canonical form for consistent pricing lookup
 //! and aggregation across different data sources.
 
+/// Built-in display names for models the pattern rules below label poorly
+/// (legacy naming schemes, vendor-specific casing). Checked before the rules.
+const DISPLAY_NAMES: &[(&str, &str)] = &[
+    ("<synthetic>", "Synthetic"),
+    ("claude-3-opus", "Claude Opus 3"),
+    ("claude-3-sonnet", "Claude Sonnet 3"),
+    ("claude-3-haiku", "Claude Haiku 3"),
+    ("claude-3-5-sonnet", "Claude Sonnet 3.5"),
+    ("claude-3-5-haiku", "Claude Haiku 3.5"),
+    ("claude-3-7-sonnet", "Claude Sonnet 3.7"),
+    ("gpt-oss-20b", "GPT-OSS 20B"),
+    ("gpt-oss-120b", "GPT-OSS 120B"),
+    ("deepseek-r1", "DeepSeek R1"),
+    ("deepseek-v3", "DeepSeek V3"),
+    ("glm-4-6", "GLM-4.6"),
+    ("mistral-nemo", "Mistral NeMo"),
+    ("open-mistral-nemo", "Mistral NeMo"),
+    ("open-mixtral-8x7b", "Mixtral 8x7B"),
+    ("open-mixtral-8x22b", "Mixtral 8x22B"),
+    ("kimi-k2", "Kimi K2"),
+    ("qwen3-coder", "Qwen3 Coder"),
+];
+
+/// First word of Mistral model ids
+const MISTRAL_FAMILIES: &[&str] = &[
+    "mistral",
+    "codestral",
+    "devstral",
+    "magistral",
+    "ministral",
+    "pixtral",
+];
+
 /// Convert normalized model name to human-readable display name.
-/// Uses dynamic pattern parsing for automatic support of new models.
+/// Looks up [`DISPLAY_NAMES`] first, then uses dynamic pattern parsing for
+/// automatic support of new models. Unknown models are title-cased.
 ///
 /// # Examples
-/// - "claude-opus-4-5" → "Opus 4.5"
-/// - "claude-sonnet-4" → "Sonnet 4"
-/// - "claude-haiku-4-5" → "Haiku 4.5"
+/// - "claude-opus-4-5" → "Claude Opus 4.5"
+/// - "claude-sonnet-4" → "Claude Sonnet 4"
+/// - "claude-haiku-4-5" → "Claude Haiku 4.5"
+/// - "claude-3-5-sonnet" → "Claude Sonnet 3.5"
 /// - "gpt-4o" → "GPT-4o"
 /// - "gpt-4o-mini" → "GPT-4o Mini"
 /// - "gpt-4-1" → "GPT-4.1"
 /// - "gpt-4-1-mini" → "GPT-4.1 Mini"
 /// - "gemini-2-5-pro" → "Gemini 2.5 Pro"
-/// - "o1" → "o1", "o4-mini" → "o4 Mini"
+/// - "o1" → "OpenAI o1", "o4-mini" → "OpenAI o4 Mini"
 /// - "codex-mini-latest" → "Codex Mini"
+/// - "mistral-large-latest" → "Mistral Large", "mistral-small-3-1" → "Mistral Small 3.1"
+/// - "some-new_model" → "Some New Model"
 pub fn display_name(normalized: &str) -> String {
     if normalized.is_empty() {
         return String::new();
     }
 
-    // Claude: claude-{family}-{version} → {Family} {version}
+    if let Some((_, name)) = DISPLAY_NAMES.iter().find(|(id, _)| *id == normalized) {
+        return (*name).to_string();
+    }
+
+    // Claude: claude-{family}-{version} → Claude {Family} {version}
     if let Some(rest) = normalized.strip_prefix("claude-") {
         return parse_claude_name(rest);
     }
@@ -49,11 +90,38 @@ pub fn display_name(normalized: &str) -> String {
         }
     }
 
-    // Fallback: return as-is
-    normalized.to_string()
+    // Mistral: {family}-{tier}-{version} → {Family} {Tier} {version}
+    let first = normalized.split('-').next().unwrap_or(normalized);
+    if MISTRAL_FAMILIES.contains(&first) {
+        return parse_mistral_name(normalized);
+    }
+
+    // Fallback: title-case the id ("some-new_model" → "Some New Model")
+    title_case(normalized)
+}
+
+/// Provider (vendor) of a normalized model name, for per-provider totals.
+///
+/// # Examples
+/// - "claude-opus-4-5" → "Anthropic"
+/// - "gpt-4o", "o4-mini", "codex-mini" → "OpenAI"
+/// - "gemini-2-5-pro" → "Google"
+pub fn provider_for_model(normalized: &str) -> &'static str {
+    let is_o_series = normalized
+        .strip_prefix('o')
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()));
+    if normalized.starts_with("claude-") {
+        "Anthropic"
+    } else if normalized.starts_with("gpt-") || normalized.starts_with("codex-") || is_o_series {
+        "OpenAI"
+    } else if normalized.starts_with("gemini-") {
+        "Google"
+    } else {
+        "Other"
+    }
 }
 
-/// Parse Claude model name: {family}-{version} → {Family} {version}
+/// Parse Claude model name: {family}-{version} → Claude {Family} {version}
 fn parse_claude_name(rest: &str) -> String {
     // Split into family and version parts
     // e.g., "opus-4-5" → family="opus", version="4-5"
@@ -66,7 +134,7 @@ fn parse_claude_name(rest: &str) -> String {
     let family = capitalize(parts[0]);
     let version = format_version(parts[1]);
 
-    format!("{} {}", family, version)
+    format!("Claude {} {}", family, version)
 }
 
 /// Parse GPT model name with minor version support:
@@ -144,17 +212,41 @@ fn parse_codex_name(rest: &str) -> String {
 
 /// Parse OpenAI o-series: o{N}, o{N}-mini, o{N}-pro, etc.
 fn parse_o_series(name: &str) -> String {
-    // e.g., "o1" → "o1"
-    // e.g., "o1-mini" → "o1 Mini"
+    // e.g., "o1" → "OpenAI o1"
+    // e.g., "o1-mini" → "OpenAI o1 Mini"
     if let Some(pos) = name.find('-') {
         let base = &name[..pos];
         let suffix = &name[pos + 1..];
-        format!("{} {}", base, capitalize(suffix))
+        format!("OpenAI {} {}", base, capitalize(suffix))
     } else {
-        name.to_string()
+        format!("OpenAI {}", name)
     }
 }
 
+/// Parse Mistral model name: words capitalized, version parts joined with dots,
+/// sizes upper-cased and `latest` dropped
+fn parse_mistral_name(name: &str) -> String {
+    // e.g., "mistral-small-3-1" → "Mistral Small 3.1"
+    // e.g., "ministral-8b-latest" → "Ministral 8B"
+    let is_number = |p: &str| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit());
+    let mut words: Vec<String> = Vec::new();
+    let mut prev_numeric = false;
+    for part in name.split('-').filter(|p| *p != "latest") {
+        let numeric = is_number(part);
+        match words.last_mut() {
+            // 4-digit snapshot ids (YYMM) are not version parts
+            Some(last) if numeric && prev_numeric && part.len() <= 2 => {
+                last.push('.');
+                last.push_str(part);
+            }
+            _ if part.strip_suffix('b').is_some_and(is_number) => words.push(part.to_uppercase()),
+            _ => words.push(capitalize(part)),
+        }
+        prev_numeric = numeric;
+    }
+    words.join(" ")
+}
+
 /// Capitalize first letter of a string
 fn capitalize(s: &str) -> String {
     let mut chars = s.chars();
@@ -164,16 +256,32 @@ fn capitalize(s: &str) -> String {
     }
 }
 
+/// Title-case a model id, treating '-' and '_' as word separators
+fn title_case(s: &str) -> String {
+    s.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(capitalize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Format version string: "4-5" → "4.5", "4" → "4"
 fn format_version(version: &str) -> String {
     version.replace('-', ".")
 }
 
+/// Claude model families, for ordering family and version in Claude names
+const CLAUDE_FAMILIES: &[&str] = &["opus", "sonnet", "haiku"];
+
 /// Normalize a model name to canonical form.
 ///
 /// Transformations:
+/// - Provider prefix: "anthropic/claude-sonnet-4" → "claude-sonnet-4"
 /// - Dots to hyphens: "claude-opus-4.5" → "claude-opus-4-5"
-/// - Remove date suffix: "claude-opus-4-5-20251101" → "claude-opus-4-5"
+/// - Remove date suffix: "claude-opus-4-5-20251101" → "claude-opus-4-5",
+///   "o1-2024-12-17" → "o1"
+/// - Claude family order: version first before Claude 4 ("claude-sonnet-3-5" →
+///   "claude-3-5-sonnet"), family first since ("claude-4-opus" → "claude-opus-4")
 ///
 /// # Examples
 /// ```
@@ -181,24 +289,86 @@ fn format_version(version: &str) -> String {
 ///
 /// assert_eq!(normalize_model_name("claude-opus-4-5-20251101"), "claude-opus-4-5");
 /// assert_eq!(normalize_model_name("claude-opus-4.5"), "claude-opus-4-5");
+/// assert_eq!(normalize_model_name("anthropic/claude-3.5-sonnet"), "claude-3-5-sonnet");
 /// ```
 pub fn normalize_model_name(model: &str) -> String {
-    // Step 1: Replace dots with hyphens
+    // Step 1: Strip provider prefixes ("openrouter/anthropic/...")
+    let model = model.rsplit('/').next().unwrap_or(model);
+
+    // Step 2: Replace dots with hyphens
     let normalized = model.replace('.', "-");
 
-    // Step 2: Remove 8-digit date suffix at end (e.g., -20251101)
+    // Step 3: Remove date suffix at end (-YYYYMMDD or -YYYY-MM-DD)
+    let normalized = strip_date_suffix(&normalized);
+
+    // Step 4: Put Claude family and version in the order Anthropic uses
+    match normalized.strip_prefix("claude-") {
+        Some(rest) => format!("claude-{}", canonical_claude_order(rest)),
+        None => normalized.to_string(),
+    }
+}
+
+/// `name` without a trailing `-YYYYMMDD` or `-YYYY-MM-DD` date (years 20xx)
+fn strip_date_suffix(name: &str) -> &str {
+    let is_digits = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+
     // Pattern: ends with -YYYYMMDD where YYYYMMDD is 8 digits starting with 20
-    if let Some(suffix_start) = normalized.rfind('-') {
-        let suffix = &normalized[suffix_start + 1..];
-        if suffix.len() == 8
-            && suffix.starts_with("20")
-            && suffix.chars().all(|c| c.is_ascii_digit())
+    if let Some((head, suffix)) = name.rsplit_once('-') {
+        if is_digits(suffix, 8) && suffix.starts_with("20") {
+            return head;
+        }
+    }
+
+    // Pattern: ends with -YYYY-MM-DD (OpenAI snapshots)
+    let parts: Vec<&str> = name.rsplitn(4, '-').collect();
+    if let [day, month, year, head] = parts[..] {
+        if is_digits(year, 4) && year.starts_with("20") && is_digits(month, 2) && is_digits(day, 2)
         {
-            return normalized[..suffix_start].to_string();
+            return head;
         }
     }
 
-    normalized
+    name
+}
+
+/// Claude 3.x and older put the version first ("3-5-sonnet"), Claude 4 and
+/// later the family ("sonnet-4-5"). Names without a known family are kept.
+fn canonical_claude_order(rest: &str) -> String {
+    let parts: Vec<&str> = rest.split('-').collect();
+    let Some(family_idx) = parts.iter().position(|p| CLAUDE_FAMILIES.contains(p)) else {
+        return rest.to_string();
+    };
+    let is_version = |p: &&str| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit());
+
+    let (version, tail) = if family_idx == 0 {
+        // family-first: sonnet-3-5[-tail]
+        let len = parts[1..]
+            .iter()
+            .take(2)
+            .take_while(|p| is_version(p))
+            .count();
+        (&parts[1..1 + len], &parts[1 + len..])
+    } else if family_idx <= 2 && parts[..family_idx].iter().all(is_version) {
+        // version-first: 3-5-sonnet[-tail]
+        (&parts[..family_idx], &parts[family_idx + 1..])
+    } else {
+        return rest.to_string();
+    };
+    let Some(major) = version.first().and_then(|v| v.parse::<u32>().ok()) else {
+        return rest.to_string();
+    };
+
+    let family = parts[family_idx];
+    let version = version.join("-");
+    let head = if major < 4 {
+        format!("{}-{}", version, family)
+    } else {
+        format!("{}-{}", family, version)
+    };
+    std::iter::once(head.as_str())
+        .chain(tail.iter().copied())
+        .collect::<Vec<_>>()
+        .join("-")
 }
 
 #[cfg(test)]
@@ -209,22 +379,22 @@ mod tests {
 
     #[test]
     fn test_display_name_claude_opus_4_5() {
-        assert_eq!(display_name("claude-opus-4-5"), "Opus 4.5");
+        assert_eq!(display_name("claude-opus-4-5"), "Claude Opus 4.5");
     }
 
     #[test]
     fn test_display_name_claude_sonnet_4() {
-        assert_eq!(display_name("claude-sonnet-4"), "Sonnet 4");
+        assert_eq!(display_name("claude-sonnet-4"), "Claude Sonnet 4");
     }
 
     #[test]
     fn test_display_name_claude_haiku_4_5() {
-        assert_eq!(display_name("claude-haiku-4-5"), "Haiku 4.5");
+        assert_eq!(display_name("claude-haiku-4-5"), "Claude Haiku 4.5");
     }
 
     #[test]
     fn test_display_name_claude_sonnet_3_5() {
-        assert_eq!(display_name("claude-sonnet-3-5"), "Sonnet 3.5");
+        assert_eq!(display_name("claude-sonnet-3-5"), "Claude Sonnet 3.5");
     }
 
     #[test]
@@ -254,17 +424,17 @@ mod tests {
 
     #[test]
     fn test_display_name_o1() {
-        assert_eq!(display_name("o1"), "o1");
+        assert_eq!(display_name("o1"), "OpenAI o1");
     }
 
     #[test]
     fn test_display_name_o1_mini() {
-        assert_eq!(display_name("o1-mini"), "o1 Mini");
+        assert_eq!(display_name("o1-mini"), "OpenAI o1 Mini");
     }
 
     #[test]
     fn test_display_name_o3_mini() {
-        assert_eq!(display_name("o3-mini"), "o3 Mini");
+        assert_eq!(display_name("o3-mini"), "OpenAI o3 Mini");
     }
 
     #[test]
@@ -284,12 +454,12 @@ mod tests {
 
     #[test]
     fn test_display_name_o4_mini() {
-        assert_eq!(display_name("o4-mini"), "o4 Mini");
+        assert_eq!(display_name("o4-mini"), "OpenAI o4 Mini");
     }
 
     #[test]
     fn test_display_name_o4() {
-        assert_eq!(display_name("o4"), "o4");
+        assert_eq!(display_name("o4"), "OpenAI o4");
     }
 
     #[test]
@@ -303,8 +473,91 @@ mod tests {
     }
 
     #[test]
-    fn test_display_name_unknown_model() {
-        assert_eq!(display_name("unknown-model"), "unknown-model");
+    fn test_display_name_unknown_model_title_cased() {
+        assert_eq!(display_name("unknown-model"), "Unknown Model");
+        assert_eq!(display_name("acme_coder-2"), "Acme Coder 2");
+    }
+
+    #[test]
+    fn test_display_name_builtin_table() {
+        assert_eq!(display_name("claude-3-5-sonnet"), "Claude Sonnet 3.5");
+        assert_eq!(display_name("gpt-oss-120b"), "GPT-OSS 120B");
+        assert_eq!(display_name("deepseek-r1"), "DeepSeek R1");
+    }
+
+    fn assert_display_names(cases: &[(&str, &str)]) {
+        for (id, expected) in cases {
+            assert_eq!(display_name(id), *expected, "display_name({:?})", id);
+        }
+    }
+
+    #[test]
+    fn test_display_name_claude_family() {
+        assert_display_names(&[
+            ("claude-3-opus", "Claude Opus 3"),
+            ("claude-3-sonnet", "Claude Sonnet 3"),
+            ("claude-3-haiku", "Claude Haiku 3"),
+            ("claude-3-5-sonnet", "Claude Sonnet 3.5"),
+            ("claude-3-5-haiku", "Claude Haiku 3.5"),
+            ("claude-haiku-3-5", "Claude Haiku 3.5"),
+            ("claude-3-7-sonnet", "Claude Sonnet 3.7"),
+            ("claude-sonnet-4", "Claude Sonnet 4"),
+            ("claude-opus-4", "Claude Opus 4"),
+            ("claude-opus-4-1", "Claude Opus 4.1"),
+            ("claude-sonnet-4-5", "Claude Sonnet 4.5"),
+            ("claude-haiku-4-5", "Claude Haiku 4.5"),
+            ("claude-opus-4-5", "Claude Opus 4.5"),
+        ]);
+    }
+
+    #[test]
+    fn test_display_name_openai_family() {
+        assert_display_names(&[
+            ("gpt-3-5-turbo", "GPT-3.5 Turbo"),
+            ("gpt-4", "GPT-4"),
+            ("gpt-4-turbo", "GPT-4 Turbo"),
+            ("gpt-4o", "GPT-4o"),
+            ("gpt-4o-mini", "GPT-4o Mini"),
+            ("gpt-4-1", "GPT-4.1"),
+            ("gpt-4-1-nano", "GPT-4.1 Nano"),
+            ("gpt-5", "GPT-5"),
+            ("gpt-5-codex", "GPT-5 Codex"),
+            ("gpt-oss-120b", "GPT-OSS 120B"),
+            ("o1", "OpenAI o1"),
+            ("o1-pro", "OpenAI o1 Pro"),
+            ("o3", "OpenAI o3"),
+            ("o4-mini", "OpenAI o4 Mini"),
+            ("codex-mini-latest", "Codex Mini"),
+        ]);
+    }
+
+    #[test]
+    fn test_display_name_google_family() {
+        assert_display_names(&[
+            ("gemini-1-5-pro", "Gemini 1.5 Pro"),
+            ("gemini-1-5-flash", "Gemini 1.5 Flash"),
+            ("gemini-2-0-flash", "Gemini 2.0 Flash"),
+            ("gemini-2-5-pro", "Gemini 2.5 Pro"),
+            ("gemini-2-5-flash-lite", "Gemini 2.5 Flash Lite"),
+        ]);
+    }
+
+    #[test]
+    fn test_display_name_mistral_family() {
+        assert_display_names(&[
+            ("mistral-large", "Mistral Large"),
+            ("mistral-large-latest", "Mistral Large"),
+            ("mistral-large-2411", "Mistral Large 2411"),
+            ("mistral-medium-3", "Mistral Medium 3"),
+            ("mistral-small-3-1", "Mistral Small 3.1"),
+            ("codestral-2501", "Codestral 2501"),
+            ("devstral-medium", "Devstral Medium"),
+            ("magistral-small", "Magistral Small"),
+            ("ministral-8b-latest", "Ministral 8B"),
+            ("pixtral-large", "Pixtral Large"),
+            ("mistral-nemo", "Mistral NeMo"),
+            ("open-mixtral-8x22b", "Mixtral 8x22B"),
+        ]);
     }
 
     #[test]
@@ -312,6 +565,17 @@ mod tests {
         assert_eq!(display_name(""), "");
     }
 
+    #[test]
+    fn test_provider_for_model() {
+        assert_eq!(provider_for_model("claude-opus-4-5"), "Anthropic");
+        assert_eq!(provider_for_model("gpt-4o"), "OpenAI");
+        assert_eq!(provider_for_model("o4-mini"), "OpenAI");
+        assert_eq!(provider_for_model("codex-mini-latest"), "OpenAI");
+        assert_eq!(provider_for_model("gemini-2-5-pro"), "Google");
+        assert_eq!(provider_for_model("deepseek-r1"), "Other");
+        assert_eq!(provider_for_model("opus"), "Other");
+    }
+
     // ========== Dot to hyphen conversion ==========
 
     #[test]
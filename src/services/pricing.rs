@@ -4,8 +4,10 @@
 //! Supports auto mode: uses pre-calculated cost_usd when available,
 //! falls back to token-based calculation otherwise.
 
-use crate::types::{Result, ToktrackError, UsageEntry};
-use serde::{Deserialize, Serialize};
+use crate::config::Config;
+use crate::services::data_dir::data_dir;
+use crate::types::{CostBreakdown, CostRates, Result, ToktrackError, UsageEntry};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
@@ -15,23 +17,91 @@ use std::time::{SystemTime, UNIX_EPOCH};
 const LITELLM_PRICING_URL: &str =
     "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
 
-/// Cache TTL in seconds (1 hour)
-const CACHE_TTL_SECS: i64 = 3600;
+/// Default cache TTL in seconds (1 hour); `pricing_ttl_secs` in the config overrides it
+pub const DEFAULT_PRICING_TTL_SECS: u64 = 3600;
 
 /// HTTP request timeout in seconds
 const REQUEST_TIMEOUT_SECS: u64 = 10;
 
+/// Prices of the most common models, shipped in the binary for when there is
+/// neither a pricing cache nor a network connection
+const BUILTIN_PRICES: &str = include_str!("builtin_prices.json");
+
 /// Pricing information for a model
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(from = "RawModelPricing")]
 pub struct ModelPricing {
-    #[serde(default)]
     pub input_cost_per_token: Option<f64>,
-    #[serde(default)]
     pub output_cost_per_token: Option<f64>,
-    #[serde(default)]
     pub cache_read_input_token_cost: Option<f64>,
-    #[serde(default)]
     pub cache_creation_input_token_cost: Option<f64>,
+    /// Thinking/reasoning tokens, when priced apart from output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_cost_per_reasoning_token: Option<f64>,
+    /// Context window size in tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_window_tokens: Option<u64>,
+}
+
+/// `ModelPricing` as written by LiteLLM, the pricing cache or config overrides.
+/// LiteLLM has no `context_window_tokens`; it is taken from `max_input_tokens`,
+/// `context_window` or `max_tokens`, in that order.
+#[derive(Deserialize)]
+struct RawModelPricing {
+    #[serde(default)]
+    input_cost_per_token: Option<f64>,
+    #[serde(default)]
+    output_cost_per_token: Option<f64>,
+    #[serde(default)]
+    cache_read_input_token_cost: Option<f64>,
+    #[serde(default)]
+    cache_creation_input_token_cost: Option<f64>,
+    #[serde(default)]
+    output_cost_per_reasoning_token: Option<f64>,
+    #[serde(default, deserialize_with = "token_count")]
+    context_window_tokens: Option<u64>,
+    #[serde(default, deserialize_with = "token_count")]
+    max_input_tokens: Option<u64>,
+    #[serde(default, deserialize_with = "token_count")]
+    context_window: Option<u64>,
+    #[serde(default, deserialize_with = "token_count")]
+    max_tokens: Option<u64>,
+}
+
+impl From<RawModelPricing> for ModelPricing {
+    fn from(raw: RawModelPricing) -> Self {
+        Self {
+            input_cost_per_token: raw.input_cost_per_token,
+            output_cost_per_token: raw.output_cost_per_token,
+            cache_read_input_token_cost: raw.cache_read_input_token_cost,
+            cache_creation_input_token_cost: raw.cache_creation_input_token_cost,
+            output_cost_per_reasoning_token: raw.output_cost_per_reasoning_token,
+            context_window_tokens: raw
+                .context_window_tokens
+                .or(raw.max_input_tokens)
+                .or(raw.context_window)
+                .or(raw.max_tokens),
+        }
+    }
+}
+
+/// A token count, or `None` for anything else (LiteLLM's `sample_spec`
+/// describes its fields with strings)
+fn token_count<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<u64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Count {
+        Int(u64),
+        Float(f64),
+        Other(serde::de::IgnoredAny),
+    }
+    Ok(match Count::deserialize(deserializer)? {
+        Count::Int(n) => Some(n),
+        Count::Float(f) if f >= 0.0 => Some(f as u64),
+        Count::Float(_) | Count::Other(_) => None,
+    })
 }
 
 /// Cached pricing data
@@ -41,68 +111,123 @@ pub struct PricingCache {
     pub fetched_at: i64,
     /// Model pricing data
     pub models: HashMap<String, ModelPricing>,
+    /// Date of the built-in prices, when this is the bundled fallback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundled_at: Option<String>,
 }
 
 impl PricingCache {
-    /// Check if the cache has expired
-    pub fn is_expired(&self) -> bool {
+    /// Check if the cache is older than `ttl_secs`
+    pub fn is_expired(&self, ttl_secs: u64) -> bool {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_secs() as i64)
             .unwrap_or(0);
-        now - self.fetched_at > CACHE_TTL_SECS
+        now - self.fetched_at > i64::try_from(ttl_secs).unwrap_or(i64::MAX)
+    }
+
+    /// The prices bundled into the binary. `fetched_at` is 0, so they always
+    /// count as expired and are replaced as soon as a fetch succeeds.
+    pub fn builtin() -> Self {
+        #[derive(Deserialize)]
+        struct BuiltinPrices {
+            bundled_at: String,
+            models: HashMap<String, ModelPricing>,
+        }
+        let builtin: BuiltinPrices =
+            serde_json::from_str(BUILTIN_PRICES).expect("builtin_prices.json is valid");
+        Self {
+            fetched_at: 0,
+            models: builtin.models,
+            bundled_at: Some(builtin.bundled_at),
+        }
     }
 }
 
+/// Pricing cache TTL from `config`, or the default
+fn ttl_secs(config: Option<&Config>) -> u64 {
+    config.map_or(DEFAULT_PRICING_TTL_SECS, |c| c.pricing_ttl_secs)
+}
+
+/// User pricing from `[pricing.overrides]` in `config`
+fn overrides(config: Option<&Config>) -> HashMap<String, ModelPricing> {
+    config
+        .map(|c| c.pricing.overrides.clone())
+        .unwrap_or_default()
+}
+
 /// Pricing service for calculating token costs
 pub struct PricingService {
     cache: PricingCache,
     #[allow(dead_code)]
     cache_path: PathBuf,
+    /// User pricing layered over the LiteLLM data (kept to re-apply after a refresh)
+    overrides: HashMap<String, ModelPricing>,
 }
 
 impl PricingService {
-    /// Create a new PricingService, loading from cache or fetching fresh data
-    pub fn new() -> Result<Self> {
+    /// Create a new PricingService, loading from cache or fetching fresh data.
+    /// `config` sets how long a cached copy stays fresh.
+    pub fn new(config: Option<&Config>) -> Result<Self> {
         let cache_path = Self::default_cache_path()?;
-        Self::with_cache_path(cache_path)
+        let cache = Self::load_or_fetch_cache(&cache_path, ttl_secs(config))?;
+        Ok(Self::build(cache, cache_path, overrides(config)))
     }
 
-    /// Create a new PricingService with a custom cache path
-    pub fn with_cache_path(cache_path: PathBuf) -> Result<Self> {
-        let cache = Self::load_or_fetch_cache(&cache_path)?;
-        Ok(Self { cache, cache_path })
+    /// Create a new PricingService with a custom cache path.
+    /// `overrides` take precedence over the LiteLLM data for the same model name.
+    #[allow(dead_code)]
+    pub fn with_cache_path(
+        cache_path: PathBuf,
+        overrides: HashMap<String, ModelPricing>,
+    ) -> Result<Self> {
+        let cache = Self::load_or_fetch_cache(&cache_path, DEFAULT_PRICING_TTL_SECS)?;
+        Ok(Self::build(cache, cache_path, overrides))
+    }
+
+    /// Merge `overrides` into the loaded cache, replacing same-named models
+    fn build(
+        mut cache: PricingCache,
+        cache_path: PathBuf,
+        overrides: HashMap<String, ModelPricing>,
+    ) -> Self {
+        cache.models.extend(overrides.clone());
+        Self {
+            cache,
+            cache_path,
+            overrides,
+        }
     }
 
     /// Create a PricingService, preferring cache but refreshing if expired or corrupt.
     /// Returns None only if no cache exists AND network fetch fails.
-    pub fn from_cache_only() -> Option<Self> {
+    pub fn from_cache_only(config: Option<&Config>) -> Option<Self> {
         let cache_path = Self::default_cache_path().ok()?;
 
         match Self::load_cache(&cache_path) {
-            Ok(cache) if !cache.is_expired() => Some(Self { cache, cache_path }),
+            Ok(cache) if !cache.is_expired(ttl_secs(config)) => {
+                Some(Self::build(cache, cache_path, overrides(config)))
+            }
             Ok(cache) => {
                 // Expired → try refresh, fallback to expired cache
                 if let Ok(fresh) = Self::fetch_pricing() {
                     let _ = Self::save_cache(&cache_path, &fresh);
-                    Some(Self {
-                        cache: fresh,
-                        cache_path,
-                    })
+                    Some(Self::build(fresh, cache_path, overrides(config)))
                 } else {
-                    Some(Self { cache, cache_path })
+                    Some(Self::build(cache, cache_path, overrides(config)))
                 }
             }
             Err(_) => {
-                // Corrupt or unreadable → try fresh fetch to recover
+                // Corrupt or unreadable → try fresh fetch to recover, else the built-in prices
                 if let Ok(fresh) = Self::fetch_pricing() {
                     let _ = Self::save_cache(&cache_path, &fresh);
-                    Some(Self {
-                        cache: fresh,
-                        cache_path,
-                    })
+                    Some(Self::build(fresh, cache_path, overrides(config)))
                 } else {
-                    None
+                    Some(Self::build(
+                        PricingCache::builtin(),
+                        cache_path,
+                        overrides(config),
+                    ))
                 }
             }
         }
@@ -112,30 +237,33 @@ impl PricingService {
     #[allow(dead_code)]
     pub fn from_cache_only_with_path(cache_path: &PathBuf) -> Option<Self> {
         let cache = Self::load_cache(cache_path).ok()?;
-        Some(Self {
-            cache,
-            cache_path: cache_path.clone(),
-        })
+        Some(Self::build(cache, cache_path.clone(), HashMap::new()))
     }
 
     /// Get the default cache path (~/.toktrack/pricing.json)
-    fn default_cache_path() -> Result<PathBuf> {
-        let home = directories::UserDirs::new()
-            .ok_or_else(|| ToktrackError::Pricing("Failed to get home directory".into()))?
-            .home_dir()
-            .to_path_buf();
-        Ok(home.join(".toktrack").join("pricing.json"))
+    pub fn default_cache_path() -> Result<PathBuf> {
+        let dir = data_dir()
+            .ok_or_else(|| ToktrackError::Pricing("Failed to get home directory".into()))?;
+        Ok(dir.join("pricing.json"))
     }
 
     /// Load cache from disk or fetch fresh data
-    fn load_or_fetch_cache(cache_path: &PathBuf) -> Result<PricingCache> {
+    fn load_or_fetch_cache(cache_path: &PathBuf, ttl_secs: u64) -> Result<PricingCache> {
+        Self::load_or_fetch_cache_from(cache_path, LITELLM_PRICING_URL, ttl_secs)
+    }
+
+    fn load_or_fetch_cache_from(
+        cache_path: &PathBuf,
+        url: &str,
+        ttl_secs: u64,
+    ) -> Result<PricingCache> {
         // Try loading existing cache
         if let Ok(cache) = Self::load_cache(cache_path) {
-            if !cache.is_expired() {
+            if !cache.is_expired(ttl_secs) {
                 return Ok(cache);
             }
             // Cache expired, try to refresh
-            if let Ok(fresh_cache) = Self::fetch_pricing() {
+            if let Ok(fresh_cache) = Self::fetch_pricing_from(url) {
                 let _ = Self::save_cache(cache_path, &fresh_cache);
                 return Ok(fresh_cache);
             }
@@ -143,11 +271,14 @@ impl PricingService {
             return Ok(cache);
         }
 
-        // No cache exists, must fetch
-        let cache = Self::fetch_pricing()
-            .map_err(|e| ToktrackError::Pricing(format!("Failed to fetch pricing data: {}", e)))?;
-        let _ = Self::save_cache(cache_path, &cache);
-        Ok(cache)
+        // No cache exists, must fetch; offline, fall back to the built-in prices
+        match Self::fetch_pricing_from(url) {
+            Ok(cache) => {
+                let _ = Self::save_cache(cache_path, &cache);
+                Ok(cache)
+            }
+            Err(_) => Ok(PricingCache::builtin()),
+        }
     }
 
     /// Load cache from disk
@@ -171,26 +302,40 @@ impl PricingService {
 
     /// Fetch pricing data from LiteLLM
     fn fetch_pricing() -> std::result::Result<PricingCache, String> {
+        Self::fetch_pricing_from(LITELLM_PRICING_URL)
+    }
+
+    fn fetch_pricing_from(url: &str) -> std::result::Result<PricingCache, String> {
         let client = reqwest::blocking::Client::builder()
             .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()
             .map_err(|e| format!("HTTP client error: {}", e))?;
 
         let response = client
-            .get(LITELLM_PRICING_URL)
+            .get(url)
             .send()
             .map_err(|e| format!("HTTP request failed: {}", e))?;
 
-        let models: HashMap<String, ModelPricing> = response
-            .json()
-            .map_err(|e| format!("JSON parse error: {}", e))?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = response
+            .text()
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+        let models = parse_pricing_body(content_type.as_deref(), &body)?;
 
         let fetched_at = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_secs() as i64)
             .unwrap_or(0);
 
-        Ok(PricingCache { fetched_at, models })
+        Ok(PricingCache {
+            fetched_at,
+            models,
+            bundled_at: None,
+        })
     }
 
     /// Get cost, using pre-calculated cost_usd if available (auto mode)
@@ -204,25 +349,30 @@ impl PricingService {
 
     /// Calculate cost from tokens (always calculates, ignores cost_usd)
     pub fn calculate_cost(&self, entry: &UsageEntry) -> f64 {
-        let model = match &entry.model {
-            Some(m) => m,
-            None => return 0.0,
-        };
-
-        let pricing = match self.get_pricing(model) {
-            Some(p) => p,
-            None => return 0.0,
-        };
+        self.rates_for(entry).map_or(0.0, |rates| rates.cost(entry))
+    }
 
-        let input_cost = pricing.input_cost_per_token.unwrap_or(0.0);
-        let output_cost = pricing.output_cost_per_token.unwrap_or(0.0);
-        let cache_read_cost = pricing.cache_read_input_token_cost.unwrap_or(0.0);
-        let cache_creation_cost = pricing.cache_creation_input_token_cost.unwrap_or(0.0);
+    /// Cost of each token type of `entry`, all zero if its model has no pricing
+    #[allow(dead_code)]
+    pub fn calculate_cost_breakdown(&self, entry: &UsageEntry) -> CostBreakdown {
+        self.rates_for(entry)
+            .map(|rates| rates.breakdown(entry))
+            .unwrap_or_default()
+    }
 
-        (entry.input_tokens as f64 * input_cost)
-            + (entry.cache_read_tokens as f64 * cache_read_cost)
-            + (entry.cache_creation_tokens as f64 * cache_creation_cost)
-            + (entry.output_tokens as f64 * output_cost)
+    /// Per-token rates `calculate_cost` uses for `entry` (None if its model has no pricing)
+    pub fn rates_for(&self, entry: &UsageEntry) -> Option<CostRates> {
+        let pricing = self.get_pricing(entry.model.as_deref()?)?;
+        Some(CostRates {
+            input: pricing.input_cost_per_token.unwrap_or(0.0),
+            output: pricing.output_cost_per_token.unwrap_or(0.0),
+            cache_read: pricing.cache_read_input_token_cost.unwrap_or(0.0),
+            cache_creation: pricing.cache_creation_input_token_cost.unwrap_or(0.0),
+            thinking: pricing
+                .output_cost_per_reasoning_token
+                .or(pricing.output_cost_per_token)
+                .unwrap_or(0.0),
+        })
     }
 
     /// Get pricing for a model (exact → normalized → fuzzy substring)
@@ -266,9 +416,16 @@ impl PricingService {
             .map_err(|e| ToktrackError::Pricing(format!("Refresh failed: {}", e)))?;
         let _ = Self::save_cache(&self.cache_path, &cache);
         self.cache = cache;
+        self.cache.models.extend(self.overrides.clone());
         Ok(())
     }
 
+    /// Date of the built-in prices if no LiteLLM data could be loaded
+    #[allow(dead_code)]
+    pub fn bundled_at(&self) -> Option<&str> {
+        self.cache.bundled_at.as_deref()
+    }
+
     /// Get the number of models in the cache
     #[allow(dead_code)]
     pub fn model_count(&self) -> usize {
@@ -276,6 +433,35 @@ impl PricingService {
     }
 }
 
+/// Parse a pricing response body, rejecting HTML (e.g. a captive portal
+/// login page) as a network failure rather than a JSON error
+fn parse_pricing_body(
+    content_type: Option<&str>,
+    body: &str,
+) -> std::result::Result<HashMap<String, ModelPricing>, String> {
+    let is_html = content_type.is_some_and(|ct| ct.to_ascii_lowercase().contains("text/html"))
+        || looks_like_html(body);
+    if is_html {
+        return Err(
+            "HTTP request failed: received HTML instead of pricing data \
+                    (captive portal?)"
+                .to_string(),
+        );
+    }
+    serde_json::from_str(body).map_err(|e| format!("JSON parse error: {}", e))
+}
+
+fn looks_like_html(body: &str) -> bool {
+    let head: String = body
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .chars()
+        .take(16)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.starts_with("<html") || head.starts_with("<head")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,6 +490,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         }
     }
 
@@ -320,6 +509,8 @@ mod tests {
                 output_cost_per_token: Some(0.000015),        // $15 per 1M tokens
                 cache_read_input_token_cost: Some(0.0000003), // $0.30 per 1M tokens
                 cache_creation_input_token_cost: Some(0.00000375), // $3.75 per 1M tokens
+                output_cost_per_reasoning_token: None,
+                context_window_tokens: None,
             },
         );
         models.insert(
@@ -329,6 +520,8 @@ mod tests {
                 output_cost_per_token: Some(0.000075), // $75 per 1M tokens
                 cache_read_input_token_cost: Some(0.0000015), // $1.50 per 1M tokens
                 cache_creation_input_token_cost: Some(0.00001875), // $18.75 per 1M tokens
+                output_cost_per_reasoning_token: None,
+                context_window_tokens: None,
             },
         );
 
@@ -340,6 +533,7 @@ mod tests {
         let cache = PricingCache {
             fetched_at: now,
             models,
+            bundled_at: None,
         };
 
         // Save mock cache
@@ -347,7 +541,7 @@ mod tests {
         fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
         fs::write(&cache_path, content).unwrap();
 
-        let service = PricingService::with_cache_path(cache_path).unwrap();
+        let service = PricingService::with_cache_path(cache_path, HashMap::new()).unwrap();
         (service, temp_dir)
     }
 
@@ -443,6 +637,29 @@ mod tests {
         assert!((cost - 0.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_calculate_cost_breakdown() {
+        let (service, _temp) = create_test_service();
+        let mut entry = make_entry(Some("claude-sonnet-4"), 1000, 500, 200, 100, None);
+        entry.thinking_tokens = 100;
+
+        let breakdown = service.calculate_cost_breakdown(&entry);
+
+        assert!((breakdown.input_cost - 0.003).abs() < 1e-10);
+        assert!((breakdown.output_cost - 0.0075).abs() < 1e-10);
+        assert!((breakdown.cache_read_cost - 0.00006).abs() < 1e-10);
+        assert!((breakdown.cache_creation_cost - 0.000375).abs() < 1e-10);
+        // No reasoning price: thinking is billed at the output rate
+        assert!((breakdown.thinking_cost - 0.0015).abs() < 1e-10);
+        assert!((breakdown.total - 0.012435).abs() < 1e-10);
+
+        let unknown = make_entry(Some("unknown-model-xyz"), 1000, 500, 0, 0, None);
+        assert_eq!(
+            service.calculate_cost_breakdown(&unknown),
+            CostBreakdown::default()
+        );
+    }
+
     #[test]
     fn test_input_tokens_not_double_deducted() {
         let (service, _temp) = create_test_service();
@@ -475,6 +692,48 @@ mod tests {
         assert!((p.input_cost_per_token.unwrap() - 0.000003).abs() < 1e-10);
     }
 
+    // ========== Override tests ==========
+
+    fn override_pricing(input: f64, output: f64) -> ModelPricing {
+        ModelPricing {
+            input_cost_per_token: Some(input),
+            output_cost_per_token: Some(output),
+            ..ModelPricing::default()
+        }
+    }
+
+    #[test]
+    fn test_override_takes_precedence_over_litellm() {
+        let (_, temp) = create_test_service();
+        let overrides = HashMap::from([
+            ("claude-sonnet-4".to_string(), override_pricing(1e-6, 2e-6)),
+            ("my-custom-model".to_string(), override_pricing(3e-6, 15e-6)),
+        ]);
+        let service =
+            PricingService::with_cache_path(temp.path().join("pricing.json"), overrides).unwrap();
+
+        let sonnet = service.get_pricing("claude-sonnet-4").unwrap();
+        assert_eq!(sonnet.input_cost_per_token, Some(1e-6));
+        assert_eq!(sonnet.cache_read_input_token_cost, None);
+        let custom = service.get_pricing("my-custom-model").unwrap();
+        assert_eq!(custom.output_cost_per_token, Some(15e-6));
+        // Models without an override keep the LiteLLM pricing
+        let opus = service.get_pricing("claude-opus-4").unwrap();
+        assert_eq!(opus.input_cost_per_token, Some(0.000015));
+    }
+
+    #[test]
+    fn test_overrides_are_not_written_to_cache() {
+        let (_, temp) = create_test_service();
+        let cache_path = temp.path().join("pricing.json");
+        let overrides =
+            HashMap::from([("my-custom-model".to_string(), override_pricing(1.0, 1.0))]);
+        let _service = PricingService::with_cache_path(cache_path.clone(), overrides).unwrap();
+
+        let cache = PricingService::load_cache(&cache_path).unwrap();
+        assert!(!cache.models.contains_key("my-custom-model"));
+    }
+
     #[test]
     fn test_get_pricing_not_found() {
         let (service, _temp) = create_test_service();
@@ -518,6 +777,8 @@ mod tests {
                 output_cost_per_token: Some(0.00003),
                 cache_read_input_token_cost: None,
                 cache_creation_input_token_cost: None,
+                output_cost_per_reasoning_token: None,
+                context_window_tokens: None,
             },
         );
         models.insert(
@@ -527,6 +788,8 @@ mod tests {
                 output_cost_per_token: Some(0.000015),
                 cache_read_input_token_cost: None,
                 cache_creation_input_token_cost: None,
+                output_cost_per_reasoning_token: None,
+                context_window_tokens: None,
             },
         );
         models.insert(
@@ -536,6 +799,8 @@ mod tests {
                 output_cost_per_token: Some(0.000004),
                 cache_read_input_token_cost: None,
                 cache_creation_input_token_cost: None,
+                output_cost_per_reasoning_token: None,
+                context_window_tokens: None,
             },
         );
         models.insert(
@@ -545,6 +810,8 @@ mod tests {
                 output_cost_per_token: Some(0.00006),
                 cache_read_input_token_cost: None,
                 cache_creation_input_token_cost: None,
+                output_cost_per_reasoning_token: None,
+                context_window_tokens: None,
             },
         );
 
@@ -556,13 +823,14 @@ mod tests {
         let cache = PricingCache {
             fetched_at: now,
             models,
+            bundled_at: None,
         };
 
         let content = serde_json::to_string_pretty(&cache).unwrap();
         fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
         fs::write(&cache_path, content).unwrap();
 
-        let service = PricingService::with_cache_path(cache_path).unwrap();
+        let service = PricingService::with_cache_path(cache_path, HashMap::new()).unwrap();
         (service, temp_dir)
     }
 
@@ -613,9 +881,10 @@ mod tests {
         let cache = PricingCache {
             fetched_at: old_timestamp,
             models: HashMap::new(),
+            bundled_at: None,
         };
 
-        assert!(cache.is_expired());
+        assert!(cache.is_expired(DEFAULT_PRICING_TTL_SECS));
     }
 
     #[test]
@@ -629,9 +898,11 @@ mod tests {
         let cache = PricingCache {
             fetched_at: recent_timestamp,
             models: HashMap::new(),
+            bundled_at: None,
         };
 
-        assert!(!cache.is_expired());
+        assert!(!cache.is_expired(DEFAULT_PRICING_TTL_SECS));
+        assert!(cache.is_expired(600));
     }
 
     #[test]
@@ -647,12 +918,15 @@ mod tests {
                 output_cost_per_token: Some(0.002),
                 cache_read_input_token_cost: None,
                 cache_creation_input_token_cost: None,
+                output_cost_per_reasoning_token: None,
+                context_window_tokens: None,
             },
         );
 
         let cache = PricingCache {
             fetched_at: 12345,
             models,
+            bundled_at: None,
         };
 
         // Save
@@ -705,6 +979,7 @@ mod tests {
         let cache = PricingCache {
             fetched_at: 0,
             models,
+            bundled_at: None,
         };
         let content = serde_json::to_string_pretty(&cache).unwrap();
         fs::write(&cache_path, content).unwrap();
@@ -726,4 +1001,131 @@ mod tests {
         let service = PricingService::from_cache_only_with_path(&cache_path);
         assert!(service.is_none());
     }
+
+    /// Serve one canned HTTP response on a local port, returning its URL
+    fn serve_once(content_type: &'static str, body: &'static str) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    content_type,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        format!("http://{}/pricing.json", addr)
+    }
+
+    #[test]
+    fn test_parse_pricing_body_rejects_html() {
+        let html = "<!DOCTYPE html><html><body>Sign in to Wi-Fi</body></html>";
+        // Sniffed from the body even when the content type is misleading
+        let err = parse_pricing_body(Some("text/plain"), html).unwrap_err();
+        assert!(err.contains("captive portal"));
+        let err = parse_pricing_body(Some("text/html; charset=utf-8"), "{}").unwrap_err();
+        assert!(err.contains("HTML"));
+
+        let models = parse_pricing_body(
+            Some("text/plain; charset=utf-8"),
+            r#"{"m": {"input_cost_per_token": 0.001}}"#,
+        )
+        .unwrap();
+        assert_eq!(models["m"].input_cost_per_token, Some(0.001));
+    }
+
+    #[test]
+    fn test_context_window_from_litellm_fields() {
+        let models = parse_pricing_body(
+            None,
+            r#"{
+                "sample_spec": {"max_tokens": "LEGACY parameter", "input_cost_per_token": 0.0},
+                "both": {"max_tokens": 8192, "max_input_tokens": 200000},
+                "legacy": {"max_tokens": 4096},
+                "none": {"input_cost_per_token": 0.001}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(models["sample_spec"].context_window_tokens, None);
+        assert_eq!(models["both"].context_window_tokens, Some(200000));
+        assert_eq!(models["legacy"].context_window_tokens, Some(4096));
+        assert_eq!(models["none"].context_window_tokens, None);
+
+        // Round-trips through the pricing cache under its own name
+        let json = serde_json::to_string(&models["both"]).unwrap();
+        assert!(json.contains("\"context_window_tokens\":200000"));
+        let parsed: ModelPricing = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, models["both"]);
+    }
+
+    #[test]
+    fn test_builtin_prices_cover_common_models() {
+        let builtin = PricingCache::builtin();
+        assert_eq!(builtin.models.len(), 25);
+        assert!(builtin.is_expired(DEFAULT_PRICING_TTL_SECS));
+        assert!(builtin.bundled_at.is_some());
+
+        let service = PricingService::build(builtin, PathBuf::new(), HashMap::new());
+        for model in [
+            "claude-3-5-sonnet-20241022",
+            "claude-opus-4-5-20251101",
+            "gpt-4o-mini",
+            "o3",
+            "gemini-2.0-flash",
+        ] {
+            assert!(service.get_pricing(model).is_some(), "{model}");
+        }
+        let sonnet = service.get_pricing("claude-sonnet-4-5-20250929").unwrap();
+        assert_eq!(sonnet.input_cost_per_token, Some(3e-6));
+        assert_eq!(service.bundled_at(), Some("2025-11-24"));
+    }
+
+    #[test]
+    fn test_offline_without_cache_uses_builtin_prices() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("pricing.json");
+        // Nothing listens on port 9 (discard), so the fetch fails fast
+        let cache = PricingService::load_or_fetch_cache_from(
+            &cache_path,
+            "http://127.0.0.1:9/prices.json",
+            DEFAULT_PRICING_TTL_SECS,
+        )
+        .unwrap();
+        assert!(cache.bundled_at.is_some());
+        assert!(cache.models.contains_key("gpt-4o"));
+        // Built-in prices are never written out as a cache
+        assert!(!cache_path.exists());
+    }
+
+    #[test]
+    fn test_html_response_falls_back_to_expired_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("pricing.json");
+        let mut models = HashMap::new();
+        models.insert("test-model".to_string(), ModelPricing::default());
+        let cache = PricingCache {
+            fetched_at: 0,
+            models,
+            bundled_at: None,
+        };
+        fs::write(&cache_path, serde_json::to_string(&cache).unwrap()).unwrap();
+
+        let url = serve_once(
+            "text/html",
+            "<html><head><title>Login</title></head><body>Accept terms</body></html>",
+        );
+        let cache =
+            PricingService::load_or_fetch_cache_from(&cache_path, &url, DEFAULT_PRICING_TTL_SECS)
+                .unwrap();
+        assert!(cache.models.contains_key("test-model"));
+        // The expired cache on disk is left untouched
+        let on_disk = PricingService::load_cache(&cache_path).unwrap();
+        assert_eq!(on_disk.fetched_at, 0);
+    }
 }
@@ -0,0 +1,341 @@
+//! Raw UsageEntry caching for faster re-analysis
+//!
+//! Stores each parsed file's entries keyed by path, size and mtime under
+//! `raw/` in the cache directory, so unchanged files are not parsed again.
+//! Entries are cached before synthetic filtering, dedup and pricing.
+
+use crate::parsers::{dedup_entries, CLIParser};
+use crate::services::atomic_write::atomic_write;
+use crate::types::{ParseProgress, Result, ToktrackError, UsageEntry};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Bump when `UsageEntry` or parser output changes.
+/// Mismatched version → the raw cache is ignored and rebuilt.
+const RAW_CACHE_VERSION: u32 = 1;
+
+/// Most entries kept per parser; files with the oldest mtime are evicted first
+const DEFAULT_MAX_ENTRIES: usize = 200_000;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RawEntryCache {
+    #[serde(default)]
+    version: u32,
+    files: HashMap<PathBuf, CachedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFile {
+    size: u64,
+    modified: SystemTime,
+    entries: Vec<UsageEntry>,
+}
+
+/// Outcome of reading one file through the cache
+enum FileEntries {
+    Hit(Vec<UsageEntry>),
+    Parsed(Option<(u64, SystemTime)>, Vec<UsageEntry>),
+    Failed,
+}
+
+pub struct RawEntryCacheService {
+    cache_dir: PathBuf,
+    max_entries: usize,
+}
+
+impl RawEntryCacheService {
+    /// Cache in `raw/` under `cache_dir`, the summary cache directory
+    pub fn new(cache_dir: &Path) -> Result<Self> {
+        let cache_dir = cache_dir.join("raw");
+        fs::create_dir_all(&cache_dir)?;
+        Ok(Self::with_cache_dir(cache_dir))
+    }
+
+    pub fn with_cache_dir(cache_dir: PathBuf) -> Self {
+        Self {
+            cache_dir,
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    pub fn cache_path(&self, cli: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}_raw.json", cli))
+    }
+
+    /// Same as `CLIParser::parse_and_dedup_with_progress`, but files whose size
+    /// and mtime match the cache are served from it instead of being parsed.
+    pub fn parse_and_dedup(
+        &self,
+        parser: &dyn CLIParser,
+        files: &[PathBuf],
+        progress: Option<&ParseProgress>,
+        include_synthetic: bool,
+    ) -> Result<Vec<UsageEntry>> {
+        let cli = parser.cache_key();
+        let mut cache = self.load(&cli);
+
+        let results: Vec<FileEntries> = files
+            .par_iter()
+            .map(|f| {
+                let stamp = file_stamp(f);
+                let hit = stamp.and_then(|(size, modified)| {
+                    cache
+                        .files
+                        .get(f)
+                        .filter(|c| c.size == size && c.modified == modified)
+                });
+                let result = match hit {
+                    Some(cached) => FileEntries::Hit(cached.entries.clone()),
+                    None => match parser.parse_file(f) {
+                        Ok(entries) => FileEntries::Parsed(stamp, entries),
+                        Err(e) => {
+                            eprintln!("[toktrack] Warning: Failed to parse {:?}: {}", f, e);
+                            FileEntries::Failed
+                        }
+                    },
+                };
+                if let Some(progress) = progress {
+                    progress.advance();
+                }
+                result
+            })
+            .collect();
+
+        let mut dirty = false;
+        let mut all_entries = Vec::new();
+        for (path, result) in files.iter().zip(results) {
+            match result {
+                FileEntries::Hit(entries) => all_entries.extend(entries),
+                FileEntries::Parsed(stamp, entries) => {
+                    if let Some((size, modified)) = stamp {
+                        cache.files.insert(
+                            path.clone(),
+                            CachedFile {
+                                size,
+                                modified,
+                                entries: entries.clone(),
+                            },
+                        );
+                        dirty = true;
+                    }
+                    all_entries.extend(entries);
+                }
+                FileEntries::Failed => {}
+            }
+        }
+
+        let before = cache.files.len();
+        cache.files.retain(|path, _| path.exists());
+        dirty |= cache.files.len() != before;
+        dirty |= self.enforce_cap(&mut cache);
+
+        if dirty {
+            if let Err(e) = self.save(&cli, &cache) {
+                eprintln!("[toktrack] Warning: raw cache for {} failed: {}", cli, e);
+            }
+        }
+
+        all_entries.retain(|entry| include_synthetic || !parser.is_synthetic(entry));
+        Ok(dedup_entries(all_entries))
+    }
+
+    /// Evict the oldest files until at most `max_entries` remain.
+    /// Returns whether anything was evicted.
+    fn enforce_cap(&self, cache: &mut RawEntryCache) -> bool {
+        let mut total: usize = cache.files.values().map(|c| c.entries.len()).sum();
+        if total <= self.max_entries {
+            return false;
+        }
+        let mut by_age: Vec<(SystemTime, PathBuf)> = cache
+            .files
+            .iter()
+            .map(|(path, c)| (c.modified, path.clone()))
+            .collect();
+        by_age.sort();
+        for (_, path) in by_age {
+            if total <= self.max_entries {
+                break;
+            }
+            if let Some(evicted) = cache.files.remove(&path) {
+                total -= evicted.entries.len();
+            }
+        }
+        true
+    }
+
+    /// Load the cache for `cli`; missing, corrupt or outdated caches start empty
+    fn load(&self, cli: &str) -> RawEntryCache {
+        fs::read(self.cache_path(cli))
+            .ok()
+            .and_then(|content| serde_json::from_slice::<RawEntryCache>(&content).ok())
+            .filter(|cache| cache.version == RAW_CACHE_VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Save using atomic write (temp file + rename)
+    fn save(&self, cli: &str, cache: &RawEntryCache) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir)?;
+        let content = serde_json::to_vec(&RawEntryCacheRef {
+            version: RAW_CACHE_VERSION,
+            files: &cache.files,
+        })
+        .map_err(|e| ToktrackError::Cache(format!("Serialization failed: {}", e)))?;
+
+        atomic_write(&self.cache_path(cli), &content)
+    }
+}
+
+/// Borrowed form of `RawEntryCache` for saving without cloning entries
+#[derive(Serialize)]
+struct RawEntryCacheRef<'a> {
+    version: u32,
+    files: &'a HashMap<PathBuf, CachedFile>,
+}
+
+/// Size and mtime used to detect changed files
+fn file_stamp(path: &Path) -> Option<(u64, SystemTime)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.len(), meta.modified().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ClaudeCodeParser;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Claude parser that counts `parse_file` calls
+    struct CountingParser {
+        inner: ClaudeCodeParser,
+        parsed: AtomicUsize,
+    }
+
+    impl CLIParser for CountingParser {
+        fn name(&self) -> &str {
+            self.inner.name()
+        }
+        fn data_dir(&self) -> &Path {
+            self.inner.data_dir()
+        }
+        fn default_data_dir(&self) -> PathBuf {
+            self.inner.default_data_dir()
+        }
+        fn file_pattern(&self) -> &str {
+            self.inner.file_pattern()
+        }
+        fn parse_file(&self, path: &Path) -> Result<Vec<UsageEntry>> {
+            self.parsed.fetch_add(1, Ordering::Relaxed);
+            self.inner.parse_file(path)
+        }
+    }
+
+    fn assistant_line(id: &str, input: u64) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"2026-03-02T10:00:00Z","requestId":"req-{id}","message":{{"id":"msg-{id}","model":"claude-sonnet-4-5","usage":{{"input_tokens":{input},"output_tokens":5}}}}}}"#
+        ) + "\n"
+    }
+
+    fn setup() -> (TempDir, TempDir, CountingParser) {
+        let data = TempDir::new().unwrap();
+        fs::write(data.path().join("a.jsonl"), assistant_line("a", 10)).unwrap();
+        fs::write(data.path().join("b.jsonl"), assistant_line("b", 20)).unwrap();
+        let parser = CountingParser {
+            inner: ClaudeCodeParser::with_data_dir(data.path().to_path_buf()),
+            parsed: AtomicUsize::new(0),
+        };
+        (data, TempDir::new().unwrap(), parser)
+    }
+
+    fn total_input(entries: &[UsageEntry]) -> u64 {
+        entries.iter().map(|e| e.input_tokens).sum()
+    }
+
+    #[test]
+    fn test_unchanged_files_served_from_raw_cache() {
+        let (_data, cache_dir, parser) = setup();
+        let service = RawEntryCacheService::with_cache_dir(cache_dir.path().to_path_buf());
+        let files = parser.collect_files();
+
+        let first = service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+        assert_eq!(parser.parsed.load(Ordering::Relaxed), 2);
+        assert!(service.cache_path(&parser.cache_key()).exists());
+
+        let second = service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+        assert_eq!(parser.parsed.load(Ordering::Relaxed), 2);
+        assert_eq!(first, second);
+        assert_eq!(total_input(&second), 30);
+    }
+
+    #[test]
+    fn test_changed_file_is_reparsed() {
+        let (data, cache_dir, parser) = setup();
+        let service = RawEntryCacheService::with_cache_dir(cache_dir.path().to_path_buf());
+        let files = parser.collect_files();
+        service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+
+        let grown = assistant_line("a", 10) + &assistant_line("c", 100);
+        fs::write(data.path().join("a.jsonl"), grown).unwrap();
+        let entries = service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+        assert_eq!(parser.parsed.load(Ordering::Relaxed), 3);
+        assert_eq!(total_input(&entries), 130);
+    }
+
+    #[test]
+    fn test_cap_evicts_files_beyond_max_entries() {
+        let (_data, cache_dir, parser) = setup();
+        let service = RawEntryCacheService::with_cache_dir(cache_dir.path().to_path_buf())
+            .with_max_entries(1);
+        let files = parser.collect_files();
+        service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+
+        assert_eq!(service.load(&parser.cache_key()).files.len(), 1);
+        // Output is unaffected by the cap
+        let entries = service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+        assert_eq!(total_input(&entries), 30);
+        assert_eq!(parser.parsed.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_version_mismatch_ignores_cache() {
+        let (_data, cache_dir, parser) = setup();
+        let service = RawEntryCacheService::with_cache_dir(cache_dir.path().to_path_buf());
+        let files = parser.collect_files();
+        service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+
+        let path = service.cache_path(&parser.cache_key());
+        let mut value: serde_json::Value =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        value["version"] = serde_json::json!(0);
+        fs::write(&path, value.to_string()).unwrap();
+
+        service
+            .parse_and_dedup(&parser, &files, None, false)
+            .unwrap();
+        assert_eq!(parser.parsed.load(Ordering::Relaxed), 4);
+    }
+}
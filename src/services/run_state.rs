@@ -0,0 +1,152 @@
+//! Persistent run state service
+//!
+//! Stores small bits of state between runs in `~/.toktrack/state.json`,
+//! such as the time of the last successful `--since-last-run` report.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::services::data_dir::data_dir;
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DailySummary, Result, ToktrackError};
+
+/// State persisted between runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunState {
+    /// Time of the last successful `--since-last-run` report
+    #[serde(default)]
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+/// Service for loading and saving `state.json`
+pub struct RunStateService {
+    path: PathBuf,
+}
+
+impl RunStateService {
+    /// Create a new service using the default path (`~/.toktrack/state.json`)
+    pub fn new() -> Result<Self> {
+        let dir = data_dir()
+            .ok_or_else(|| ToktrackError::Config("Cannot determine home directory".into()))?;
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            path: dir.join("state.json"),
+        })
+    }
+
+    /// Create a service with a custom file path (for testing)
+    #[cfg(test)]
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Load the stored state. Missing or unreadable files yield the default state.
+    pub fn load(&self) -> RunState {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save state to disk
+    pub fn save(&self, state: &RunState) -> Result<()> {
+        let content = serde_json::to_string_pretty(state)
+            .map_err(|e| ToktrackError::Cache(format!("Failed to serialize state: {}", e)))?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+/// Keep summaries dated on or after the local date of `since`.
+/// `None` keeps everything (first run).
+pub fn filter_since(
+    summaries: Vec<DailySummary>,
+    since: Option<DateTime<Utc>>,
+) -> Vec<DailySummary> {
+    match since {
+        Some(ts) => {
+            let since_date = ts.with_timezone(&Local).date_naive();
+            summaries
+                .into_iter()
+                .filter(|s| s.date >= since_date)
+                .collect()
+        }
+        None => summaries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn make_summary(date: NaiveDate) -> DailySummary {
+        DailySummary {
+            date,
+            total_input_tokens: 100,
+            total_output_tokens: 50,
+            total_cache_read_tokens: 0,
+            total_cache_creation_tokens: 0,
+            total_thinking_tokens: 0,
+            total_cost_usd: 0.01,
+            models: HashMap::new(),
+            cost_estimated: false,
+        }
+    }
+
+    #[test]
+    fn test_load_missing_file_is_default() {
+        let dir = TempDir::new().unwrap();
+        let svc = RunStateService::with_path(dir.path().join("state.json"));
+        assert_eq!(svc.load(), RunState::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let svc = RunStateService::with_path(dir.path().join("state.json"));
+        let state = RunState {
+            last_run: Some(Utc.with_ymd_and_hms(2026, 1, 15, 10, 0, 0).unwrap()),
+        };
+        svc.save(&state).unwrap();
+        assert_eq!(svc.load(), state);
+    }
+
+    #[test]
+    fn test_load_corrupted_file_is_default() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        fs::write(&path, "not json").unwrap();
+        let svc = RunStateService::with_path(path);
+        assert_eq!(svc.load(), RunState::default());
+    }
+
+    #[test]
+    fn test_filter_since_none_keeps_all() {
+        let d = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        let result = filter_since(vec![make_summary(d)], None);
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_filter_since_keeps_same_day_and_later() {
+        let d1 = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2026, 1, 11).unwrap();
+        let d3 = NaiveDate::from_ymd_opt(2026, 1, 12).unwrap();
+        let since = Local
+            .from_local_datetime(&d2.and_hms_opt(15, 30, 0).unwrap())
+            .unwrap()
+            .to_utc();
+
+        let result = filter_since(
+            vec![make_summary(d1), make_summary(d2), make_summary(d3)],
+            Some(since),
+        );
+        let dates: Vec<NaiveDate> = result.iter().map(|s| s.date).collect();
+        assert_eq!(dates, vec![d2, d3]);
+    }
+}
@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-use directories::BaseDirs;
+use crate::services::data_dir::data_dir;
 use regex::Regex;
 
 use crate::types::{Result, SessionMetadata, ToktrackError};
@@ -20,9 +20,9 @@ pub struct SessionMetadataService {
 impl SessionMetadataService {
     /// Create a new service using the default sidecar directory (`~/.toktrack/sessions/`)
     pub fn new() -> Result<Self> {
-        let base_dirs = BaseDirs::new()
-            .ok_or_else(|| ToktrackError::Config("Cannot determine home directory".into()))?;
-        let sessions_dir = base_dirs.home_dir().join(".toktrack").join("sessions");
+        let sessions_dir = data_dir()
+            .ok_or_else(|| ToktrackError::Config("Cannot determine home directory".into()))?
+            .join("sessions");
         fs::create_dir_all(&sessions_dir)?;
         Ok(Self { sessions_dir })
     }
@@ -0,0 +1,112 @@
+//! File change detection for headless `--watch` mode
+//!
+//! Fingerprints every parser's data files (path, size, mtime) and reports a
+//! change once the fingerprint differs from the last one seen and has stayed
+//! stable for the debounce window, so a burst of writes yields one change.
+//! Only file metadata is read.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::parsers::CLIParser;
+
+/// Quiet period required after a change before it is reported
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Size and mtime of every data file, keyed by path
+type Fingerprint = BTreeMap<PathBuf, (u64, Option<SystemTime>)>;
+
+/// Watches parser data files for changes, checked on each `changed` call
+pub struct FileWatcher {
+    parsers: Vec<Box<dyn CLIParser>>,
+    last: Fingerprint,
+    debounce: Duration,
+}
+
+impl FileWatcher {
+    /// Start watching the files of `parsers`; the current state is the baseline
+    pub fn new(parsers: Vec<Box<dyn CLIParser>>) -> Self {
+        let last = fingerprint(&parsers);
+        Self {
+            parsers,
+            last,
+            debounce: DEFAULT_DEBOUNCE,
+        }
+    }
+
+    /// Override the debounce window (for testing)
+    #[cfg(test)]
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Whether files changed since the last reported change (debounced)
+    pub fn changed(&mut self) -> bool {
+        let mut current = fingerprint(&self.parsers);
+        if current == self.last {
+            return false;
+        }
+        loop {
+            thread::sleep(self.debounce);
+            let next = fingerprint(&self.parsers);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        self.last = current;
+        true
+    }
+}
+
+fn fingerprint(parsers: &[Box<dyn CLIParser>]) -> Fingerprint {
+    parsers
+        .iter()
+        .flat_map(|parser| parser.collect_files())
+        .map(|path| {
+            let meta = fs::metadata(&path).ok();
+            let size = meta.as_ref().map_or(0, |m| m.len());
+            let modified = meta.and_then(|m| m.modified().ok());
+            (path, (size, modified))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ClaudeCodeParser;
+    use tempfile::TempDir;
+
+    fn make_watcher(dir: &TempDir) -> FileWatcher {
+        let parser = ClaudeCodeParser::with_data_dir(dir.path().to_path_buf());
+        FileWatcher::new(vec![Box::new(parser)]).with_debounce(Duration::ZERO)
+    }
+
+    #[test]
+    fn test_unchanged_files_report_no_change() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.jsonl"), "{}\n").unwrap();
+        let mut watcher = make_watcher(&dir);
+        assert!(!watcher.changed());
+    }
+
+    #[test]
+    fn test_new_and_grown_files_report_change_once() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.jsonl");
+        fs::write(&file, "{}\n").unwrap();
+        let mut watcher = make_watcher(&dir);
+
+        fs::write(&file, "{}\n{}\n").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::write(dir.path().join("b.jsonl"), "{}\n").unwrap();
+        assert!(watcher.changed());
+    }
+}
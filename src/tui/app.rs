@@ -1,37 +1,57 @@
 //! Application state and event loop
 
+use std::cell::Cell;
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::{Local, NaiveDate};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+    MouseButton, MouseEventKind,
+};
 use ratatui::{
-    buffer::Buffer, layout::Rect, style::Style, widgets::Widget, DefaultTerminal, Frame,
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::Span,
+    widgets::Widget,
+    DefaultTerminal, Frame,
 };
 
 use super::theme::Theme;
 
+use crate::config::{Config, WeekStart};
+use crate::services::data_loader::{LoadResult, SourceStatus};
+use crate::services::day_notes::DayNotesService;
 use crate::services::update_checker::{check_for_update, execute_update, UpdateCheckResult};
 use crate::services::{Aggregator, DataLoaderService};
 use crate::types::{
-    CacheWarning, DailySummary, SessionDetailEntry, SessionInfo, SourceUsage, StatsData,
-    TotalSummary,
+    CacheWarning, DailySummary, DetailGranularity, ParseProgress, ProviderSummary,
+    SessionDetailEntry, SessionInfo, SourceUsage, StatsData, ToktrackError, TotalSummary,
 };
 
 use super::widgets::{
-    daily::{DailyData, DailyView, DailyViewMode},
+    daily::{
+        compute_visible_rows, quarter_label, DailyData, DailyView, DailyViewMode, DisplayMetric,
+        DEFAULT_SPARKLINE_WIDTH, MIN_TABLE_WIDTH,
+    },
+    date_range::{DateRangeInput, DateRangeSelector},
     help::HelpPopup,
-    model_breakdown::{ModelBreakdownPopup, ModelBreakdownState},
+    model_breakdown::{
+        ModelBreakdownPopup, ModelBreakdownState, DEFAULT_COST_PRECISION, MAX_VISIBLE_MODELS,
+    },
     models::ModelsData,
-    overview::{Overview, OverviewData},
+    overview::{Overview, OverviewData, MOVING_AVG_DAYS},
     quit_confirm::{QuitConfirmPopup, QuitConfirmState},
     session_detail::SessionDetailView,
     sessions::{SessionSort, SessionsView},
     source_detail::SourceDetailView,
     spinner::{LoadingStage, Spinner},
     stats::StatsView,
+    status_bar::{StatusBar, STATUS_BAR_HEIGHT, WARNING_DISPLAY_DURATION},
     tabs::Tab,
     update_popup::{DimOverlay, UpdateMessagePopup, UpdatePopup},
 };
@@ -55,8 +75,46 @@ impl Default for ViewMode {
 pub struct TuiConfig {
     pub initial_view_mode: DailyViewMode,
     pub initial_tab: Option<Tab>,
+    /// Collapse models below this percent of total cost into an "other" row (0 = off)
+    pub other_threshold: f64,
+    /// Daily table sparkline width (None = derived from the Usage column)
+    pub sparkline_width: Option<usize>,
+    /// Scale weekly sparklines to the trailing N weeks (None = all history)
+    pub weekly_scale_weeks: Option<usize>,
+    /// Below this width the dashboard is replaced by a resize message (None = daily table minimum)
+    pub min_cols: Option<u16>,
+    /// Below this height the dashboard is replaced by a resize message (None = `DEFAULT_MIN_ROWS`)
+    pub min_rows: Option<u16>,
+    /// Timestamp bucketing for the session detail view
+    pub detail_granularity: DetailGranularity,
+    /// Keep synthetic entries instead of dropping them while loading
+    pub include_synthetic: bool,
+    /// Parse with serde_json instead of simd-json
+    pub safe_parse: bool,
+    /// Reuse cached parsed entries for unchanged files
+    pub raw_cache: bool,
+    /// Decimal places for model breakdown costs (None = `DEFAULT_COST_PRECISION`)
+    pub cost_precision: Option<usize>,
+    /// Leave out today's in-progress usage
+    pub exclude_today: bool,
+    /// Prune cached days older than this many days
+    pub max_age_days: Option<u32>,
+    /// Directory of other machines' daily exports to merge in
+    pub merge_dir: Option<PathBuf>,
+    /// Keep zero-usage sources in the overview list
+    pub show_empty_sources: bool,
+    /// Only load these sources (None = all)
+    pub sources: Option<Vec<String>>,
+    /// Settings from `config.toml`
+    pub config: Config,
 }
 
+/// Default minimum terminal height for the dashboard (tabs, table header and a few rows)
+pub const DEFAULT_MIN_ROWS: u16 = 10;
+
+/// Two clicks on the same source within this interval count as a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
 /// Application state
 pub enum AppState {
     /// Loading data with spinner animation
@@ -74,11 +132,17 @@ pub enum AppState {
 pub struct AppData {
     pub total: TotalSummary,
     pub daily_tokens: Vec<(NaiveDate, u64)>,
+    /// Daily cost smoothed over `MOVING_AVG_DAYS`, oldest first
+    pub daily_moving_avg: Vec<(NaiveDate, f64)>,
+    /// Consecutive days with usage ending today
+    pub streak: u32,
     pub models_data: ModelsData,
     pub daily_data: DailyData,
     pub stats_data: StatsData,
     /// Usage breakdown by source CLI
     pub source_usage: Vec<SourceUsage>,
+    /// Usage breakdown by provider, costliest first
+    pub provider_usage: Vec<ProviderSummary>,
     /// Per-source daily data
     pub source_daily_data: HashMap<String, DailyData>,
     /// Per-source models data
@@ -86,11 +150,30 @@ pub struct AppData {
     pub source_models_data: HashMap<String, ModelsData>,
     /// Per-source stats data
     pub source_stats_data: HashMap<String, StatsData>,
-    /// Cache warning indicator for display in TUI
-    #[allow(dead_code)] // Reserved for warning indicator feature
+    /// Cache warning shown in the status bar
     pub cache_warning: Option<CacheWarning>,
     /// Claude Code session metadata
     pub sessions: Vec<SessionInfo>,
+    /// Parser name and data directory, listed by the empty state
+    pub data_dirs: Vec<(String, PathBuf)>,
+    /// Screen row of the first Overview source bar, set on each draw
+    pub source_list_y: Cell<Option<u16>>,
+}
+
+impl AppData {
+    /// No usage at all, e.g. on first run
+    fn is_empty(&self) -> bool {
+        self.daily_data.daily_summaries.is_empty() && self.source_usage.is_empty()
+    }
+}
+
+/// Background parse of the open session's requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SessionDetailLoad {
+    /// Waiting for the event loop to start parsing this JSONL path
+    Requested(String),
+    /// Parsing this JSONL path on a background thread
+    Running(String),
 }
 
 /// Update overlay status
@@ -132,10 +215,15 @@ pub struct App {
     daily_scroll: usize,
     weekly_scroll: usize,
     monthly_scroll: usize,
+    quarterly_scroll: usize,
     daily_selected: Option<usize>,
     weekly_selected: Option<usize>,
     monthly_selected: Option<usize>,
+    quarterly_selected: Option<usize>,
     daily_view_mode: DailyViewMode,
+    display_metric: DisplayMetric,
+    /// Source detail Cost column shows the running total
+    cumulative_cost: bool,
     show_help: bool,
     update_status: UpdateStatus,
     update_selection: u8, // 0 = Update now, 1 = Skip
@@ -143,12 +231,32 @@ pub struct App {
     theme: Theme,
     quit_confirm: Option<QuitConfirmState>,
     model_breakdown: Option<ModelBreakdownState>,
+    /// Inclusive date range the source detail table is narrowed to
+    date_filter: Option<(NaiveDate, NaiveDate)>,
+    /// The open source's daily data with `date_filter` applied
+    filtered_daily_data: Option<DailyData>,
+    /// Date range selector being edited (`f` in source detail)
+    date_range_input: Option<DateRangeInput>,
     terminal_height: u16,
     sessions_scroll: usize,
     sessions_selected: Option<usize>,
     sessions_sort: SessionSort,
+    model_filter: String,
+    model_filter_editing: bool,
     session_detail_entries: Vec<SessionDetailEntry>,
     session_detail_scroll: usize,
+    session_detail_load: Option<SessionDetailLoad>,
+    sparkline_width: usize,
+    weekly_scale_weeks: Option<usize>,
+    min_cols: u16,
+    min_rows: u16,
+    cost_precision: usize,
+    detail_granularity: DetailGranularity,
+    config: Config,
+    /// Time and source index of the last left click on the Overview source list
+    last_source_click: Option<(Instant, usize)>,
+    /// When the loaded data's cache warning first appeared in the status bar
+    warning_shown_at: Option<Instant>,
 }
 
 impl App {
@@ -157,7 +265,7 @@ impl App {
         Self {
             state: AppState::Loading {
                 spinner_frame: 0,
-                stage: LoadingStage::Scanning,
+                stage: LoadingStage::scanning(),
             },
             should_quit: false,
             view_mode: ViewMode::Dashboard {
@@ -167,10 +275,14 @@ impl App {
             daily_scroll: 0,
             weekly_scroll: 0,
             monthly_scroll: 0,
+            quarterly_scroll: 0,
             daily_selected: None,
             weekly_selected: None,
             monthly_selected: None,
+            quarterly_selected: None,
             daily_view_mode: config.initial_view_mode,
+            display_metric: DisplayMetric::default(),
+            cumulative_cost: false,
             show_help: false,
             update_status: UpdateStatus::Checking,
             update_selection: 0,
@@ -178,25 +290,50 @@ impl App {
             theme,
             quit_confirm: None,
             model_breakdown: None,
+            date_filter: None,
+            filtered_daily_data: None,
+            date_range_input: None,
             terminal_height: 24,
             sessions_scroll: 0,
             sessions_selected: None,
             sessions_sort: SessionSort::default(),
+            model_filter: String::new(),
+            model_filter_editing: false,
             session_detail_entries: Vec::new(),
             session_detail_scroll: 0,
+            session_detail_load: None,
+            sparkline_width: config.sparkline_width.unwrap_or(DEFAULT_SPARKLINE_WIDTH),
+            weekly_scale_weeks: config.weekly_scale_weeks,
+            min_cols: config.min_cols.unwrap_or(MIN_TABLE_WIDTH),
+            min_rows: config.min_rows.unwrap_or(DEFAULT_MIN_ROWS),
+            cost_precision: config.cost_precision.unwrap_or(DEFAULT_COST_PRECISION),
+            detail_granularity: config.detail_granularity,
+            config: config.config,
+            last_source_click: None,
+            warning_shown_at: None,
         }
     }
 
-    /// Calculate the effective number of visible rows based on the current view and terminal height.
-    /// SourceDetail fixed overhead: padding(1) + header(1) + stats(1) + sep(1) + mode(1) + header(1) + sep(1) + keybindings(1) = 8
-    /// Dashboard fixed overhead: padding(1) + tabs(1) + sep(1) + mode(1) + header(1) + sep(1) + keybindings(1) = 7
-    fn effective_visible_rows(&self) -> usize {
-        let overhead: u16 = match &self.view_mode {
-            ViewMode::SourceDetail { .. } => 8,
-            ViewMode::SessionDetail { .. } => 8,
-            ViewMode::Dashboard { .. } => 7,
+    /// Terminal height left for views once the status bar row is reserved
+    fn content_height(&self) -> u16 {
+        self.terminal_height.saturating_sub(STATUS_BAR_HEIGHT)
+    }
+
+    /// Cache warning to show in the status bar, until `WARNING_DISPLAY_DURATION` has passed
+    fn active_cache_warning(&self, now: Instant) -> Option<&CacheWarning> {
+        let AppState::Ready { data } = &self.state else {
+            return None;
         };
-        self.terminal_height.saturating_sub(overhead) as usize
+        let shown_at = self.warning_shown_at?;
+        if now.duration_since(shown_at) >= WARNING_DISPLAY_DURATION {
+            return None;
+        }
+        data.cache_warning.as_ref()
+    }
+
+    /// Daily table rows visible in the current content height
+    fn effective_visible_rows(&self) -> usize {
+        compute_visible_rows(self.content_height())
     }
 
     /// Get scroll offset for the current daily view mode
@@ -205,6 +342,7 @@ impl App {
             DailyViewMode::Daily => self.daily_scroll,
             DailyViewMode::Weekly => self.weekly_scroll,
             DailyViewMode::Monthly => self.monthly_scroll,
+            DailyViewMode::Quarterly => self.quarterly_scroll,
         }
     }
 
@@ -214,6 +352,7 @@ impl App {
             DailyViewMode::Daily => &mut self.daily_scroll,
             DailyViewMode::Weekly => &mut self.weekly_scroll,
             DailyViewMode::Monthly => &mut self.monthly_scroll,
+            DailyViewMode::Quarterly => &mut self.quarterly_scroll,
         }
     }
 
@@ -223,6 +362,7 @@ impl App {
             DailyViewMode::Daily => self.daily_selected,
             DailyViewMode::Weekly => self.weekly_selected,
             DailyViewMode::Monthly => self.monthly_selected,
+            DailyViewMode::Quarterly => self.quarterly_selected,
         }
     }
 
@@ -232,15 +372,22 @@ impl App {
             DailyViewMode::Daily => &mut self.daily_selected,
             DailyViewMode::Weekly => &mut self.weekly_selected,
             DailyViewMode::Monthly => &mut self.monthly_selected,
+            DailyViewMode::Quarterly => &mut self.quarterly_selected,
         }
     }
 
-    /// Handle keyboard and resize events
+    /// Handle keyboard, mouse and resize events
     pub fn handle_event(&mut self, event: Event) {
         if let Event::Resize(_, h) = event {
             self.terminal_height = h;
             return;
         }
+        if let Event::Mouse(mouse) = event {
+            if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                self.handle_left_click(mouse.row, Instant::now());
+            }
+            return;
+        }
         if let Event::Key(key) = event {
             if key.kind == KeyEventKind::Press {
                 // Ctrl+C shows quit confirmation
@@ -258,6 +405,88 @@ impl App {
         }
     }
 
+    /// Open SourceDetail for the selected Overview source
+    fn open_selected_source(&mut self) {
+        if let AppState::Ready { data } = &self.state {
+            if let Some(source) = data.source_usage.get(self.source_selected) {
+                self.view_mode = ViewMode::SourceDetail {
+                    source: source.source.clone(),
+                };
+                self.date_filter = None;
+                self.filtered_daily_data = None;
+                self.reset_daily_position();
+            }
+        }
+    }
+
+    /// Clear selections and scroll every mode of the active daily table to its newest rows
+    fn reset_daily_position(&mut self) {
+        self.daily_selected = None;
+        self.weekly_selected = None;
+        self.monthly_selected = None;
+        self.quarterly_selected = None;
+        let vr = self.effective_visible_rows();
+        let AppState::Ready { data } = &self.state else {
+            return;
+        };
+        let daily_data = self.active_daily_data(data);
+        let offset = |mode| DailyView::max_scroll_offset(daily_data, mode, vr);
+        let scrolls = [
+            offset(DailyViewMode::Daily),
+            offset(DailyViewMode::Weekly),
+            offset(DailyViewMode::Monthly),
+            offset(DailyViewMode::Quarterly),
+        ];
+        [
+            self.daily_scroll,
+            self.weekly_scroll,
+            self.monthly_scroll,
+            self.quarterly_scroll,
+        ] = scrolls;
+    }
+
+    /// Narrow the open source's table to `range` (`None` shows every day)
+    fn set_date_filter(&mut self, range: Option<(NaiveDate, NaiveDate)>) {
+        self.date_filter = None;
+        self.filtered_daily_data = None;
+        if let (Some((from, to)), AppState::Ready { data }) = (range, &self.state) {
+            let filtered = self.active_daily_data(data).filter(from, to);
+            self.date_filter = range;
+            self.filtered_daily_data = Some(filtered);
+        }
+        self.reset_daily_position();
+    }
+
+    /// Left click on the Overview source list selects that source; a second
+    /// click on it within `DOUBLE_CLICK_INTERVAL` opens it like Enter
+    fn handle_left_click(&mut self, row: u16, now: Instant) {
+        if self.view_mode != (ViewMode::Dashboard { tab: Tab::Overview }) {
+            return;
+        }
+        let AppState::Ready { data } = &self.state else {
+            return;
+        };
+        let Some(start_y) = data.source_list_y.get() else {
+            return;
+        };
+        let index = match row.checked_sub(start_y) {
+            Some(offset) if (offset as usize) < data.source_usage.len().min(4) => offset as usize,
+            _ => return,
+        };
+
+        self.source_selected = index;
+        let is_double = matches!(
+            self.last_source_click,
+            Some((at, last)) if last == index && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+        );
+        if is_double {
+            self.last_source_click = None;
+            self.open_selected_source();
+        } else {
+            self.last_source_click = Some((now, index));
+        }
+    }
+
     /// Get the current dashboard tab
     fn current_tab(&self) -> Tab {
         match &self.view_mode {
@@ -273,11 +502,19 @@ impl App {
 
     /// Handle keyboard events in Dashboard mode
     fn handle_dashboard_event(&mut self, code: KeyCode) {
+        // The Models filter captures every key while it is being typed
+        if self.model_filter_editing && self.current_tab() == Tab::Models {
+            self.handle_model_filter_event(code);
+            return;
+        }
+
         // Common keys for all tabs
         match code {
             KeyCode::Esc => {
                 if self.show_help {
                     self.show_help = false;
+                } else if self.current_tab() == Tab::Models {
+                    self.model_filter.clear();
                 }
                 return;
             }
@@ -319,6 +556,10 @@ impl App {
                 self.show_help = !self.show_help;
                 return;
             }
+            KeyCode::Char('$') => {
+                self.display_metric = self.display_metric.toggle();
+                return;
+            }
             _ => {}
         }
 
@@ -338,41 +579,7 @@ impl App {
                         }
                     }
                 }
-                KeyCode::Enter => {
-                    if let AppState::Ready { data } = &self.state {
-                        if let Some(source) = data.source_usage.get(self.source_selected) {
-                            self.view_mode = ViewMode::SourceDetail {
-                                source: source.source.clone(),
-                            };
-                            // Reset scroll/selection for source detail
-                            self.daily_scroll = 0;
-                            self.weekly_scroll = 0;
-                            self.monthly_scroll = 0;
-                            self.daily_selected = None;
-                            self.weekly_selected = None;
-                            self.monthly_selected = None;
-                            // Set scroll to bottom for the source's daily data
-                            if let Some(source_daily) = data.source_daily_data.get(&source.source) {
-                                let vr = self.effective_visible_rows();
-                                self.daily_scroll = DailyView::max_scroll_offset(
-                                    source_daily,
-                                    DailyViewMode::Daily,
-                                    vr,
-                                );
-                                self.weekly_scroll = DailyView::max_scroll_offset(
-                                    source_daily,
-                                    DailyViewMode::Weekly,
-                                    vr,
-                                );
-                                self.monthly_scroll = DailyView::max_scroll_offset(
-                                    source_daily,
-                                    DailyViewMode::Monthly,
-                                    vr,
-                                );
-                            }
-                        }
-                    }
-                }
+                KeyCode::Enter => self.open_selected_source(),
                 _ => {}
             },
             Tab::Sessions => match code {
@@ -413,13 +620,10 @@ impl App {
                     if let Some(idx) = self.sessions_selected {
                         if let AppState::Ready { data } = &self.state {
                             if let Some(session) = data.sessions.get(idx) {
-                                let pricing = crate::services::PricingService::from_cache_only();
-                                let entries =
-                                    crate::parsers::ClaudeCodeParser::parse_session_detail(
-                                        &session.jsonl_path,
-                                        pricing.as_ref(),
-                                    );
-                                self.session_detail_entries = entries;
+                                // Parsed by the event loop on a background thread
+                                self.session_detail_load =
+                                    Some(SessionDetailLoad::Requested(session.jsonl_path.clone()));
+                                self.session_detail_entries.clear();
                                 self.session_detail_scroll = 0;
                                 self.view_mode = ViewMode::SessionDetail { session_index: idx };
                             }
@@ -428,28 +632,77 @@ impl App {
                 }
                 _ => {}
             },
-            Tab::Stats | Tab::Models => {
-                // Stats/Models tabs have no additional keys beyond common ones
+            Tab::Models => {
+                if code == KeyCode::Char('/') {
+                    self.model_filter_editing = true;
+                }
+            }
+            Tab::Stats => {
+                // Stats tab has no additional keys beyond common ones
             }
         }
     }
 
+    /// Edit the Models tab filter: Enter keeps the query, Esc clears it
+    fn handle_model_filter_event(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.model_filter.push(c),
+            KeyCode::Backspace => {
+                self.model_filter.pop();
+            }
+            KeyCode::Enter => self.model_filter_editing = false,
+            KeyCode::Esc => {
+                self.model_filter.clear();
+                self.model_filter_editing = false;
+            }
+            _ => {}
+        }
+    }
+
     /// Handle keyboard events in SourceDetail mode
     fn handle_source_detail_event(&mut self, code: KeyCode) {
+        if self.date_range_input.is_some() {
+            self.handle_date_range_event(code);
+            return;
+        }
         match code {
             KeyCode::Esc => {
                 if self.show_help {
                     self.show_help = false;
                 } else {
+                    self.date_filter = None;
+                    self.filtered_daily_data = None;
                     self.view_mode = ViewMode::Dashboard { tab: Tab::Overview };
                 }
             }
+            KeyCode::Char('f') => {
+                self.date_range_input = Some(DateRangeInput::new(self.date_filter));
+            }
+            KeyCode::Char('c') => {
+                self.cumulative_cost = !self.cumulative_cost;
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.select_prev();
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 self.select_next();
             }
+            KeyCode::PageUp => {
+                let page = self.effective_visible_rows();
+                self.jump_selection(|current, last| current.unwrap_or(last).saturating_sub(page));
+            }
+            KeyCode::PageDown => {
+                let page = self.effective_visible_rows();
+                self.jump_selection(|current, last| {
+                    current.map_or(last, |idx| idx + page).min(last)
+                });
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.jump_selection(|_, _| 0);
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.jump_selection(|_, last| last);
+            }
             KeyCode::Enter => {
                 self.open_model_breakdown();
             }
@@ -462,6 +715,12 @@ impl App {
             KeyCode::Char('m') => {
                 self.daily_view_mode = DailyViewMode::Monthly;
             }
+            KeyCode::Char('q') => {
+                self.daily_view_mode = DailyViewMode::Quarterly;
+            }
+            KeyCode::Char('$') => {
+                self.display_metric = self.display_metric.toggle();
+            }
             KeyCode::Char('?') => {
                 self.show_help = !self.show_help;
             }
@@ -469,6 +728,27 @@ impl App {
         }
     }
 
+    /// Typing into the date range selector; Enter applies, Esc cancels
+    fn handle_date_range_event(&mut self, code: KeyCode) {
+        let Some(input) = self.date_range_input.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => input.pop(),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => input.toggle_field(),
+            KeyCode::Enter => match input.parse() {
+                Ok(range) => {
+                    self.date_range_input = None;
+                    self.set_date_filter(range);
+                }
+                Err(message) => input.error = Some(message),
+            },
+            KeyCode::Esc => self.date_range_input = None,
+            _ => {}
+        }
+    }
+
     /// Handle keyboard events in SessionDetail mode
     fn handle_session_detail_event(&mut self, code: KeyCode) {
         match code {
@@ -478,6 +758,7 @@ impl App {
                 } else {
                     self.session_detail_entries.clear();
                     self.session_detail_scroll = 0;
+                    self.session_detail_load = None;
                     self.view_mode = ViewMode::Dashboard { tab: Tab::Sessions };
                 }
             }
@@ -489,7 +770,7 @@ impl App {
             KeyCode::Down | KeyCode::Char('j') => {
                 let count = self.session_detail_entries.len();
                 let visible = super::widgets::session_detail::session_detail_visible_rows(
-                    self.terminal_height,
+                    self.content_height(),
                 );
                 let max = count.saturating_sub(visible);
                 if self.session_detail_scroll < max {
@@ -503,9 +784,18 @@ impl App {
         }
     }
 
+    /// Show the parsed requests of `jsonl_path` if that session is still being loaded
+    fn apply_session_detail(&mut self, jsonl_path: &str, entries: Vec<SessionDetailEntry>) {
+        if self.session_detail_load != Some(SessionDetailLoad::Running(jsonl_path.to_string())) {
+            return;
+        }
+        self.session_detail_entries = self.detail_granularity.apply(entries);
+        self.session_detail_load = None;
+    }
+
     /// Adjust scroll offset to keep the sessions selection visible
     fn adjust_sessions_scroll(&mut self) {
-        let visible = super::widgets::sessions::sessions_visible_rows(self.terminal_height);
+        let visible = super::widgets::sessions::sessions_visible_rows(self.content_height());
         if let Some(selected) = self.sessions_selected {
             if selected < self.sessions_scroll {
                 self.sessions_scroll = selected;
@@ -559,6 +849,26 @@ impl App {
                     KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
                         self.model_breakdown = None;
                     }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        if let Some(state) = &mut self.model_breakdown {
+                            state.scroll_up(1);
+                        }
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        if let Some(state) = &mut self.model_breakdown {
+                            state.scroll_down(1);
+                        }
+                    }
+                    KeyCode::PageUp => {
+                        if let Some(state) = &mut self.model_breakdown {
+                            state.scroll_up(MAX_VISIBLE_MODELS);
+                        }
+                    }
+                    KeyCode::PageDown => {
+                        if let Some(state) = &mut self.model_breakdown {
+                            state.scroll_down(MAX_VISIBLE_MODELS);
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -620,18 +930,23 @@ impl App {
                     DailyView::max_scroll_offset(&data.daily_data, DailyViewMode::Weekly, vr);
                 self.monthly_scroll =
                     DailyView::max_scroll_offset(&data.daily_data, DailyViewMode::Monthly, vr);
+                self.quarterly_scroll =
+                    DailyView::max_scroll_offset(&data.daily_data, DailyViewMode::Quarterly, vr);
+                self.warning_shown_at = data.cache_warning.as_ref().map(|_| Instant::now());
                 self.state = AppState::Ready { data };
             }
             Err(message) => self.state = AppState::Error { message },
         }
     }
 
-    /// Get the active DailyData depending on the current view mode
-    fn active_daily_data<'a>(&self, data: &'a AppData) -> &'a DailyData {
+    /// Get the active DailyData depending on the current view mode,
+    /// narrowed to `date_filter` in SourceDetail
+    fn active_daily_data<'a>(&'a self, data: &'a AppData) -> &'a DailyData {
         match &self.view_mode {
-            ViewMode::SourceDetail { source } => data
-                .source_daily_data
-                .get(source)
+            ViewMode::SourceDetail { source } => self
+                .filtered_daily_data
+                .as_ref()
+                .or_else(|| data.source_daily_data.get(source))
                 .unwrap_or(&data.daily_data),
             ViewMode::Dashboard { .. } | ViewMode::SessionDetail { .. } => &data.daily_data,
         }
@@ -699,6 +1014,31 @@ impl App {
         self.adjust_scroll_for_selection();
     }
 
+    /// Move the SourceDetail selection to `target(current, last_idx)`
+    fn jump_selection(&mut self, target: impl FnOnce(Option<usize>, usize) -> usize) {
+        if !matches!(self.view_mode, ViewMode::SourceDetail { .. }) {
+            return;
+        }
+
+        let count = match &self.state {
+            AppState::Ready { data } => {
+                let daily_data = self.active_daily_data(data);
+                let (summaries, _) = daily_data.for_mode(self.daily_view_mode);
+                summaries.len()
+            }
+            _ => return,
+        };
+
+        if count == 0 {
+            return;
+        }
+
+        let new_idx = target(self.active_selected(), count - 1);
+        *self.active_selected_mut() = Some(new_idx);
+
+        self.adjust_scroll_for_selection();
+    }
+
     /// Adjust scroll offset to keep the current selection visible
     fn adjust_scroll_for_selection(&mut self) {
         let visible_rows = self.effective_visible_rows();
@@ -708,13 +1048,21 @@ impl App {
             None => return,
         };
 
-        let scroll = self.active_scroll();
-
-        if selected < scroll {
-            *self.active_scroll_mut() = selected;
-        } else if selected >= scroll + visible_rows {
-            *self.active_scroll_mut() = selected.saturating_sub(visible_rows - 1);
+        let AppState::Ready { data } = &self.state else {
+            return;
+        };
+        let daily_data = self.active_daily_data(data);
+        let mode = self.daily_view_mode;
+
+        let mut scroll = self
+            .active_scroll()
+            .min(selected)
+            .max(selected.saturating_sub(visible_rows.saturating_sub(1)));
+        // Month headers take lines too, so step down until the selection fits
+        while selected >= scroll + daily_data.data_rows_in(mode, scroll, visible_rows).max(1) {
+            scroll += 1;
         }
+        *self.active_scroll_mut() = scroll;
     }
 
     /// Open model breakdown popup for the currently selected row
@@ -736,6 +1084,7 @@ impl App {
                         summary.date.format("%Y-%m-%d").to_string()
                     }
                     DailyViewMode::Monthly => summary.date.format("%Y-%m").to_string(),
+                    DailyViewMode::Quarterly => quarter_label(summary.date),
                 };
 
                 let models: Vec<_> = summary
@@ -751,15 +1100,32 @@ impl App {
 
     /// Update spinner animation
     pub fn tick(&mut self) {
+        if let AppState::Loading { spinner_frame, .. } = &mut self.state {
+            *spinner_frame = Spinner::next_frame(*spinner_frame);
+        }
+    }
+
+    /// List the sources that have data while still scanning
+    fn set_found_sources(&mut self, sources: &[SourceStatus]) {
         if let AppState::Loading {
-            spinner_frame,
-            stage,
-        } = &self.state
+            stage: LoadingStage::Scanning { found_sources },
+            ..
+        } = &mut self.state
         {
-            self.state = AppState::Loading {
-                spinner_frame: Spinner::next_frame(*spinner_frame),
-                stage: *stage,
-            };
+            *found_sources = sources
+                .iter()
+                .filter(|s| s.active)
+                .map(|s| format!("{} ({} files)", s.name, s.file_count))
+                .collect();
+        }
+    }
+
+    /// Reflect files-parsed progress in the loading stage
+    fn set_parse_progress(&mut self, parsed: usize, total: usize) {
+        if let AppState::Loading { stage, .. } = &mut self.state {
+            if total > 0 {
+                *stage = LoadingStage::Parsing { parsed, total };
+            }
         }
     }
 
@@ -772,6 +1138,108 @@ impl App {
     pub fn draw(&self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
     }
+
+    /// Render the current state off-screen and return the text grid,
+    /// one line per row with trailing spaces trimmed.
+    /// Used for snapshot tests and for pasting a reproducible view into issues.
+    pub fn render_to_string(&self, width: u16, height: u16) -> String {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        self.render(area, &mut buf);
+
+        let mut out = String::with_capacity((width as usize + 1) * height as usize);
+        for y in 0..height {
+            let mut line = String::with_capacity(width as usize);
+            // Cells hidden behind a wide grapheme are skipped
+            let mut skip = 0;
+            for x in 0..width {
+                let symbol = buf[(x, y)].symbol();
+                if skip == 0 {
+                    line.push_str(symbol);
+                }
+                skip = skip.max(Span::raw(symbol).width()).saturating_sub(1);
+            }
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl App {
+    /// Replace the dashboard with a resize hint when the terminal is below the minimum size
+    fn render_too_small(&self, area: Rect, buf: &mut Buffer) {
+        let lines = [
+            (
+                "Terminal too small".to_string(),
+                Style::default().fg(self.theme.error()),
+            ),
+            (
+                format!(
+                    "{}x{}, need at least {}x{}",
+                    area.width, area.height, self.min_cols, self.min_rows
+                ),
+                Style::default(),
+            ),
+        ];
+        let top = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+        for (i, (text, style)) in lines.iter().enumerate() {
+            let y = top + i as u16;
+            if y >= area.y + area.height {
+                break;
+            }
+            let x = area.x + area.width.saturating_sub(text.len() as u16) / 2;
+            buf.set_stringn(x, y, text, area.width as usize, *style);
+        }
+    }
+
+    /// First-run guidance: where logs are read from and what to do next
+    fn render_empty_state(&self, area: Rect, buf: &mut Buffer, data_dirs: &[(String, PathBuf)]) {
+        let name_width = data_dirs
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![
+            (
+                "No usage data found".to_string(),
+                Style::default().fg(self.theme.accent()),
+            ),
+            (String::new(), Style::default()),
+            (
+                "toktrack reads logs from:".to_string(),
+                Style::default().fg(self.theme.text()),
+            ),
+        ];
+        lines.extend(data_dirs.iter().map(|(name, dir)| {
+            (
+                format!("{:<width$}  {}", name, dir.display(), width = name_width),
+                Style::default().fg(self.theme.muted()),
+            )
+        }));
+        lines.extend([
+            (String::new(), Style::default()),
+            (
+                "Use one of these CLIs, then restart toktrack.".to_string(),
+                Style::default().fg(self.theme.text()),
+            ),
+            (
+                "Add other machines' `toktrack daily --json` exports with --merge-dir DIR."
+                    .to_string(),
+                Style::default().fg(self.theme.text()),
+            ),
+        ]);
+
+        let top = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+        for (i, (text, style)) in lines.iter().enumerate() {
+            let y = top + i as u16;
+            if y >= area.y + area.height {
+                break;
+            }
+            let x = area.x + area.width.saturating_sub(text.chars().count() as u16) / 2;
+            buf.set_stringn(x, y, text, area.width as usize, *style);
+        }
+    }
 }
 
 impl Default for App {
@@ -787,10 +1255,22 @@ impl Widget for &App {
                 spinner_frame,
                 stage,
             } => {
-                let spinner = Spinner::new(*spinner_frame, *stage, self.theme);
+                let spinner = Spinner::new(*spinner_frame, stage.clone(), self.theme);
                 spinner.render(area, buf);
             }
+            AppState::Ready { .. } if area.width < self.min_cols || area.height < self.min_rows => {
+                self.render_too_small(area, buf);
+            }
+            AppState::Ready { data } if data.is_empty() => {
+                self.render_empty_state(area, buf, &data.data_dirs);
+            }
             AppState::Ready { data } => {
+                let [content, status] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(STATUS_BAR_HEIGHT)])
+                        .areas(area);
+                StatusBar::new(self.active_cache_warning(Instant::now()), self.theme)
+                    .render(status, buf);
+                let area = content;
                 match &self.view_mode {
                     ViewMode::Dashboard { tab } => match tab {
                         Tab::Overview => {
@@ -798,16 +1278,21 @@ impl Widget for &App {
                             let overview_data = OverviewData {
                                 total: &data.total,
                                 daily_tokens: &data.daily_tokens,
+                                daily_moving_avg: &data.daily_moving_avg,
                                 source_usage: &data.source_usage,
                                 selected_source: Some(self.source_selected),
                                 selected_tab: *tab,
+                                streak: data.streak,
                             };
-                            let overview = Overview::new(overview_data, today, self.theme);
+                            let overview = Overview::new(overview_data, today, self.theme)
+                                .with_metric(self.display_metric);
+                            data.source_list_y.set(overview.source_list_y(area));
                             overview.render(area, buf);
                         }
                         Tab::Stats => {
-                            let stats_view =
-                                StatsView::new(&data.stats_data, self.theme).with_tab(*tab);
+                            let stats_view = StatsView::new(&data.stats_data, self.theme)
+                                .with_tab(*tab)
+                                .with_providers(&data.provider_usage);
                             stats_view.render(area, buf);
                         }
                         Tab::Models => {
@@ -815,7 +1300,8 @@ impl Widget for &App {
                                 &data.models_data,
                                 self.theme,
                             )
-                            .with_tab(*tab);
+                            .with_tab(*tab)
+                            .with_filter(&self.model_filter, self.model_filter_editing);
                             models_view.render(area, buf);
                         }
                         Tab::Sessions => {
@@ -831,10 +1317,7 @@ impl Widget for &App {
                         }
                     },
                     ViewMode::SourceDetail { source } => {
-                        let daily_data = data
-                            .source_daily_data
-                            .get(source)
-                            .unwrap_or(&data.daily_data);
+                        let daily_data = self.active_daily_data(data);
                         let stats_data = data
                             .source_stats_data
                             .get(source)
@@ -847,7 +1330,12 @@ impl Widget for &App {
                             self.daily_view_mode,
                             self.active_selected(),
                             self.theme,
-                        );
+                        )
+                        .with_sparkline_width(self.sparkline_width)
+                        .with_weekly_scale_weeks(self.weekly_scale_weeks)
+                        .with_metric(self.display_metric)
+                        .with_date_filter(self.date_filter)
+                        .with_cumulative(self.cumulative_cost);
                         source_detail.render(area, buf);
                     }
                     ViewMode::SessionDetail { session_index } => {
@@ -857,7 +1345,8 @@ impl Widget for &App {
                                 &self.session_detail_entries,
                                 self.session_detail_scroll,
                                 self.theme,
-                            );
+                            )
+                            .with_loading(self.session_detail_load.is_some());
                             detail_view.render(area, buf);
                         }
                     }
@@ -869,11 +1358,21 @@ impl Widget for &App {
                     HelpPopup::new(self.theme).render(popup_area, buf);
                 }
 
+                // Render date range selector if active
+                if let Some(ref input) = self.date_range_input {
+                    DimOverlay.render(area, buf);
+                    let popup_area = DateRangeSelector::centered_area(area);
+                    DateRangeSelector::new(input, self.theme).render(popup_area, buf);
+                }
+
                 // Render model breakdown popup if active
                 if let Some(ref state) = self.model_breakdown {
                     DimOverlay.render(area, buf);
-                    let popup_area = ModelBreakdownPopup::centered_area(area, state.models.len());
-                    ModelBreakdownPopup::new(state, self.theme).render(popup_area, buf);
+                    let popup_area =
+                        ModelBreakdownPopup::centered_area(area, state.visible_count());
+                    ModelBreakdownPopup::new(state, self.theme)
+                        .with_cost_precision(self.cost_precision)
+                        .render(popup_area, buf);
                 }
             }
             AppState::Error { message } => {
@@ -923,55 +1422,89 @@ impl Widget for &App {
 /// Run the TUI application with the given configuration
 pub fn run(config: TuiConfig) -> anyhow::Result<()> {
     // Detect theme before entering raw mode (escape-sequence detection needs normal stdin)
-    let theme = Theme::detect();
+    let theme = match config.config.theme.as_deref() {
+        Some("dark") => Theme::Dark,
+        Some("light") => Theme::Light,
+        _ => Theme::detect(),
+    };
     let mut terminal = ratatui::init();
+    crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
     let result = run_app(&mut terminal, config, theme);
+    let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
     result
 }
 
 /// Load data synchronously (extracted for background thread).
-/// Uses cache-first strategy via DataLoaderService.
-fn load_data_sync() -> Result<Box<AppData>, String> {
-    let result = DataLoaderService::new().load().map_err(|e| e.to_string())?;
-
-    build_app_data_from_summaries(
+/// Uses cache-first strategy via DataLoaderService; the sources found
+/// by a quick scan are sent on `scan_tx` before parsing starts.
+fn load_data_sync(
+    loader: DataLoaderService,
+    other_threshold: f64,
+    scan_tx: &mpsc::Sender<Vec<SourceStatus>>,
+) -> Result<Box<AppData>, String> {
+    let _ = scan_tx.send(loader.sources());
+    let data_dirs = loader.data_dirs();
+    let week_start = loader.config().week_start;
+    let result = match loader.load() {
+        Ok(result) => result,
+        // Nothing logged yet: show the empty state rather than an error
+        Err(ToktrackError::NoData) => LoadResult::default(),
+        Err(e) => return Err(e.to_string()),
+    };
+    let notes = DayNotesService::new()
+        .map(|service| service.load().into_iter().collect())
+        .unwrap_or_default();
+
+    let mut data = build_app_data_from_summaries(
         result.summaries,
         result.source_usage,
+        result.provider_usage,
         result.source_summaries,
         result.cache_warning,
         result.sessions,
-    )
+        notes,
+        other_threshold,
+        week_start,
+    )?;
+    if let Some(pricing) = loader.pricing() {
+        data.models_data.fill_context_windows(|model| {
+            pricing
+                .get_pricing(model)
+                .and_then(|p| p.context_window_tokens)
+        });
+    }
+    data.data_dirs = data_dirs;
+    Ok(data)
 }
 
 /// Build AppData from DailySummary list (no raw entries needed).
+#[allow(clippy::too_many_arguments)]
 fn build_app_data_from_summaries(
     summaries: Vec<DailySummary>,
     source_usage: Vec<SourceUsage>,
+    provider_usage: Vec<ProviderSummary>,
     source_summaries: HashMap<String, Vec<DailySummary>>,
     cache_warning: Option<CacheWarning>,
     sessions: Vec<SessionInfo>,
+    notes: HashMap<NaiveDate, String>,
+    other_threshold: f64,
+    week_start: WeekStart,
 ) -> Result<Box<AppData>, String> {
     let total = Aggregator::total_from_daily(&summaries);
 
     let daily_tokens: Vec<(NaiveDate, u64)> = summaries
         .iter()
-        .map(|d| {
-            (
-                d.date,
-                d.total_input_tokens
-                    + d.total_output_tokens
-                    + d.total_cache_read_tokens
-                    + d.total_cache_creation_tokens
-                    + d.total_thinking_tokens,
-            )
-        })
+        .map(|d| (d.date, d.total_tokens()))
         .collect();
+    let daily_moving_avg = Aggregator::moving_average(&summaries, MOVING_AVG_DAYS);
+    let streak = Aggregator::streak(&summaries);
 
     let model_map = Aggregator::by_model_from_daily(&summaries);
-    let models_data = ModelsData::from_model_usage(&model_map);
+    let models_data = ModelsData::from_model_usage(&model_map, other_threshold);
     let stats_data = StatsData::from_daily_summaries(&summaries);
-    let daily_data = DailyData::from_daily_summaries(summaries);
+    let daily_data =
+        DailyData::from_daily_summaries_starting(summaries, week_start).with_notes(notes.clone());
 
     // Build per-source data
     let mut source_daily_data = HashMap::new();
@@ -982,11 +1515,12 @@ fn build_app_data_from_summaries(
         let src_model_map = Aggregator::by_model_from_daily(src_summaries);
         source_daily_data.insert(
             source_name.clone(),
-            DailyData::from_daily_summaries(src_summaries.clone()),
+            DailyData::from_daily_summaries_starting(src_summaries.clone(), week_start)
+                .with_notes(notes.clone()),
         );
         source_models_data.insert(
             source_name.clone(),
-            ModelsData::from_model_usage(&src_model_map),
+            ModelsData::from_model_usage(&src_model_map, other_threshold),
         );
         source_stats_data.insert(
             source_name.clone(),
@@ -997,39 +1531,70 @@ fn build_app_data_from_summaries(
     Ok(Box::new(AppData {
         total,
         daily_tokens,
+        daily_moving_avg,
+        streak,
         models_data,
         daily_data,
         stats_data,
         source_usage,
+        provider_usage,
         source_daily_data,
         source_models_data,
         source_stats_data,
         cache_warning,
         sessions,
+        data_dirs: Vec::new(),
+        source_list_y: Cell::new(None),
     }))
 }
 
 fn run_app(terminal: &mut DefaultTerminal, config: TuiConfig, theme: Theme) -> anyhow::Result<()> {
+    let other_threshold = config.other_threshold;
+    let update_check_enabled = config.config.update_check_enabled;
+    let (include_synthetic, safe_parse) = (config.include_synthetic, config.safe_parse);
+    let loader = DataLoaderService::from_config(config.config.clone())
+        .with_include_synthetic(config.include_synthetic)
+        .with_safe_parse(config.safe_parse)
+        .with_raw_cache(config.raw_cache)
+        .with_exclude_today(config.exclude_today)
+        .with_max_age_days(config.max_age_days)
+        .with_merge_dir(config.merge_dir.clone())
+        .with_show_empty_sources(config.show_empty_sources)
+        .with_sources(config.sources.clone());
     let mut app = App::new(config, theme);
     app.terminal_height = terminal.size()?.height;
 
     // Spawn background thread for data loading
     let (data_tx, data_rx) = mpsc::channel();
+    let (scan_tx, scan_rx) = mpsc::channel();
+    let progress = Arc::new(ParseProgress::default());
+    let loader_progress = Arc::clone(&progress);
     thread::spawn(move || {
-        let result = load_data_sync();
+        let result = load_data_sync(
+            loader.with_progress(loader_progress),
+            other_threshold,
+            &scan_tx,
+        );
         let _ = data_tx.send(result);
     });
 
     // Spawn background thread for update check
     let (update_tx, update_rx) = mpsc::channel();
-    thread::spawn(move || {
-        let result = check_for_update();
-        let _ = update_tx.send(result);
-    });
+    if update_check_enabled {
+        thread::spawn(move || {
+            let result = check_for_update();
+            let _ = update_tx.send(result);
+        });
+    } else {
+        let _ = update_tx.send(UpdateCheckResult::UpToDate);
+    }
 
     // Channel for async execute_update result
     let (execute_tx, execute_rx) = mpsc::channel();
 
+    // Channel for session detail parses, tagged with the session's JSONL path
+    let (detail_tx, detail_rx) = mpsc::channel();
+
     loop {
         terminal.draw(|frame| app.draw(frame))?;
 
@@ -1039,6 +1604,11 @@ fn run_app(terminal: &mut DefaultTerminal, config: TuiConfig, theme: Theme) -> a
 
         // Check for data loading completion (non-blocking)
         if matches!(app.state, AppState::Loading { .. }) {
+            if let Ok(sources) = scan_rx.try_recv() {
+                app.set_found_sources(&sources);
+            }
+            let (parsed, total) = progress.snapshot();
+            app.set_parse_progress(parsed, total);
             if let Ok(result) = data_rx.try_recv() {
                 if app.update_status.shows_overlay() {
                     // Overlay is active, store data for later
@@ -1049,6 +1619,23 @@ fn run_app(terminal: &mut DefaultTerminal, config: TuiConfig, theme: Theme) -> a
             }
         }
 
+        // Parse a newly opened session's requests in the background
+        if let Some(SessionDetailLoad::Requested(path)) = app.session_detail_load.clone() {
+            app.session_detail_load = Some(SessionDetailLoad::Running(path.clone()));
+            let config = app.config.clone();
+            let tx = detail_tx.clone();
+            thread::spawn(move || {
+                let entries = DataLoaderService::from_config(config)
+                    .with_include_synthetic(include_synthetic)
+                    .with_safe_parse(safe_parse)
+                    .load_session_detail(&path);
+                let _ = tx.send((path, entries));
+            });
+        }
+        if let Ok((path, entries)) = detail_rx.try_recv() {
+            app.apply_session_detail(&path, entries);
+        }
+
         // Check for update check completion (non-blocking)
         if app.update_status == UpdateStatus::Checking {
             if let Ok(result) = update_rx.try_recv() {
@@ -1135,6 +1722,7 @@ mod tests {
                 total_thinking_tokens: 0,
                 total_cost_usd: 0.01,
                 models: HashMap::new(),
+                cost_estimated: false,
             })
             .collect();
 
@@ -1142,7 +1730,7 @@ mod tests {
 
         let daily_data = DailyData::from_daily_summaries(summaries.clone());
         let stats_data = crate::types::StatsData::from_daily_summaries(&summaries);
-        let models_data = super::ModelsData::from_model_usage(&HashMap::new());
+        let models_data = super::ModelsData::from_model_usage(&HashMap::new(), 0.0);
 
         let mut app = App::default();
         let vr = app.effective_visible_rows();
@@ -1154,6 +1742,8 @@ mod tests {
             data: Box::new(AppData {
                 total: crate::types::TotalSummary::default(),
                 daily_tokens,
+                daily_moving_avg: vec![],
+                streak: 0,
                 models_data,
                 daily_data,
                 stats_data,
@@ -1161,12 +1751,16 @@ mod tests {
                     source: "claude".to_string(),
                     total_tokens: 3000,
                     total_cost_usd: 0.20,
+                    by_model: Vec::new(),
                 }],
+                provider_usage: vec![],
                 source_daily_data: HashMap::new(),
                 source_models_data: HashMap::new(),
                 source_stats_data: HashMap::new(),
                 cache_warning: None,
                 sessions: vec![],
+                data_dirs: vec![],
+                source_list_y: Cell::new(None),
             }),
         };
         app.daily_scroll = daily_scroll;
@@ -1182,7 +1776,7 @@ mod tests {
             app.state,
             AppState::Loading {
                 spinner_frame: 0,
-                stage: LoadingStage::Scanning
+                stage: LoadingStage::Scanning { .. }
             }
         ));
         assert!(!app.should_quit());
@@ -1229,6 +1823,64 @@ mod tests {
         assert!(!app.should_quit());
     }
 
+    #[test]
+    fn test_found_sources_shown_while_scanning() {
+        let mut app = App::default();
+        let status = |name: &str, file_count: usize| SourceStatus {
+            name: name.to_string(),
+            file_count,
+            active: file_count > 0,
+        };
+        app.set_found_sources(&[
+            status("claude-code", 47),
+            status("codex", 0),
+            status("opencode", 12),
+        ]);
+        let AppState::Loading { stage, .. } = &app.state else {
+            panic!("expected loading state");
+        };
+        assert_eq!(
+            stage.message(),
+            "Scanning... found claude-code (47 files), opencode (12 files)"
+        );
+
+        // Parsing has started: the scan result no longer applies
+        app.set_parse_progress(1, 59);
+        app.set_found_sources(&[status("claude-code", 47)]);
+        assert!(matches!(
+            app.state,
+            AppState::Loading {
+                stage: LoadingStage::Parsing { .. },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_progress_updates_loading_stage() {
+        let mut app = App::default();
+        app.set_parse_progress(0, 0);
+        assert!(matches!(
+            app.state,
+            AppState::Loading {
+                stage: LoadingStage::Scanning { .. },
+                ..
+            }
+        ));
+
+        app.set_parse_progress(42, 310);
+        assert!(matches!(
+            app.state,
+            AppState::Loading {
+                stage: LoadingStage::Parsing {
+                    parsed: 42,
+                    total: 310
+                },
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_app_tick_updates_spinner() {
         let mut app = App::default();
@@ -1293,6 +1945,7 @@ mod tests {
                 source: "opencode".to_string(),
                 total_tokens: 1000,
                 total_cost_usd: 0.05,
+                by_model: Vec::new(),
             });
         }
 
@@ -1315,6 +1968,81 @@ mod tests {
         assert_eq!(app.source_selected, 0);
     }
 
+    #[test]
+    fn test_cache_warning_shows_in_status_bar_then_dismisses() {
+        let mut app = make_ready_app();
+        let placeholder = AppState::Error {
+            message: String::new(),
+        };
+        let AppState::Ready { mut data } = std::mem::replace(&mut app.state, placeholder) else {
+            unreachable!()
+        };
+        data.cache_warning = Some(CacheWarning::VersionMismatch("v1".to_string()));
+        app.apply_data_result(Ok(data));
+
+        let shown_at = app.warning_shown_at.unwrap();
+        assert!(app.active_cache_warning(shown_at).is_some());
+        assert!(app
+            .render_to_string(120, 40)
+            .contains("Cache rebuilt (version mismatch)"));
+
+        let later = shown_at + WARNING_DISPLAY_DURATION;
+        assert!(app.active_cache_warning(later).is_none());
+    }
+
+    /// Ready app with two sources, drawn once so the source list position is known
+    fn make_clickable_app() -> (App, u16) {
+        let mut app = make_ready_app();
+        if let AppState::Ready { data } = &mut app.state {
+            data.source_usage.push(SourceUsage {
+                source: "opencode".to_string(),
+                total_tokens: 1000,
+                total_cost_usd: 0.05,
+                by_model: Vec::new(),
+            });
+        }
+        app.render_to_string(120, 40);
+        let start_y = match &app.state {
+            AppState::Ready { data } => data.source_list_y.get().unwrap(),
+            _ => unreachable!(),
+        };
+        (app, start_y)
+    }
+
+    #[test]
+    fn test_click_selects_source() {
+        let (mut app, start_y) = make_clickable_app();
+        let now = Instant::now();
+
+        app.handle_left_click(start_y + 1, now);
+        assert_eq!(app.source_selected, 1);
+        assert!(matches!(app.view_mode, ViewMode::Dashboard { .. }));
+
+        // Rows outside the list are ignored
+        app.handle_left_click(start_y + 2, now);
+        app.handle_left_click(start_y - 1, now);
+        assert_eq!(app.source_selected, 1);
+    }
+
+    #[test]
+    fn test_double_click_opens_source_detail() {
+        let (mut app, start_y) = make_clickable_app();
+        let now = Instant::now();
+
+        // Too slow for a double-click
+        app.handle_left_click(start_y + 1, now);
+        app.handle_left_click(start_y + 1, now + Duration::from_secs(1));
+        assert!(matches!(app.view_mode, ViewMode::Dashboard { .. }));
+
+        app.handle_left_click(start_y + 1, now + Duration::from_millis(1200));
+        assert_eq!(
+            app.view_mode,
+            ViewMode::SourceDetail {
+                source: "opencode".to_string()
+            }
+        );
+    }
+
     #[test]
     fn test_app_help_toggle() {
         let mut app = App::default();
@@ -1348,6 +2076,12 @@ mod tests {
         )));
         assert_eq!(app.daily_view_mode, DailyViewMode::Monthly);
 
+        app.handle_event(Event::Key(KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::NONE,
+        )));
+        assert_eq!(app.daily_view_mode, DailyViewMode::Quarterly);
+
         app.handle_event(Event::Key(KeyEvent::new(
             KeyCode::Char('d'),
             KeyModifiers::NONE,
@@ -1454,24 +2188,30 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: 0.01,
             models: HashMap::new(),
+            cost_estimated: false,
         }];
         let daily_tokens: Vec<(NaiveDate, u64)> = vec![(summaries[0].date, 150)];
         let daily_data = DailyData::from_daily_summaries(summaries.clone());
         let stats_data = crate::types::StatsData::from_daily_summaries(&summaries);
-        let models_data = ModelsData::from_model_usage(&HashMap::new());
+        let models_data = ModelsData::from_model_usage(&HashMap::new(), 0.0);
 
         app.pending_data = Some(Ok(Box::new(AppData {
             total: crate::types::TotalSummary::default(),
             daily_tokens,
+            daily_moving_avg: vec![],
+            streak: 0,
             models_data,
             daily_data,
             stats_data,
             source_usage: vec![],
+            provider_usage: vec![],
             source_daily_data: HashMap::new(),
             source_models_data: HashMap::new(),
             source_stats_data: HashMap::new(),
             cache_warning: None,
             sessions: vec![],
+            data_dirs: vec![],
+            source_list_y: Cell::new(None),
         })));
 
         let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
@@ -1547,18 +2287,35 @@ mod tests {
         let config = TuiConfig {
             initial_view_mode: DailyViewMode::Weekly,
             initial_tab: None,
+            other_threshold: 0.0,
+            sparkline_width: Some(30),
+            weekly_scale_weeks: None,
+            min_cols: None,
+            min_rows: None,
+            detail_granularity: DetailGranularity::Exact,
+            include_synthetic: false,
+            safe_parse: false,
+            raw_cache: false,
+            exclude_today: false,
+            max_age_days: None,
+            merge_dir: None,
+            show_empty_sources: false,
+            cost_precision: None,
+            sources: None,
+            config: Config::default(),
         };
         let app = App::new(config, Theme::Dark);
 
         assert!(matches!(app.view_mode, ViewMode::Dashboard { .. }));
         assert_eq!(app.daily_view_mode, DailyViewMode::Weekly);
+        assert_eq!(app.sparkline_width, 30);
 
         assert!(!app.should_quit);
         assert!(matches!(
             app.state,
             AppState::Loading {
                 spinner_frame: 0,
-                stage: LoadingStage::Scanning
+                stage: LoadingStage::Scanning { .. }
             }
         ));
         assert_eq!(app.update_status, UpdateStatus::Checking);
@@ -1801,6 +2558,46 @@ mod tests {
         assert!(app.model_breakdown.is_none());
     }
 
+    #[test]
+    fn test_model_breakdown_scroll_keys_clamp() {
+        let models = (0..30)
+            .map(|i| {
+                (
+                    format!("model-{}", i),
+                    crate::types::ModelUsage {
+                        input_tokens: 100,
+                        cost_usd: 1.0,
+                        count: 1,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        let mut app = App {
+            model_breakdown: Some(ModelBreakdownState::new("2026-02-05".to_string(), models)),
+            ..App::default()
+        };
+        let press = |app: &mut App, code| {
+            app.handle_model_breakdown_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+        };
+        let scroll = |app: &App| app.model_breakdown.as_ref().unwrap().scroll;
+
+        press(&mut app, KeyCode::Up);
+        assert_eq!(scroll(&app), 0);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(scroll(&app), 1);
+        press(&mut app, KeyCode::PageDown);
+        press(&mut app, KeyCode::PageDown);
+        press(&mut app, KeyCode::PageDown);
+        assert_eq!(scroll(&app), 30 - MAX_VISIBLE_MODELS);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(scroll(&app), 30 - MAX_VISIBLE_MODELS);
+        press(&mut app, KeyCode::PageUp);
+        press(&mut app, KeyCode::PageUp);
+        assert_eq!(scroll(&app), 0);
+        assert!(app.model_breakdown.is_some());
+    }
+
     #[test]
     fn test_selection_adjusts_scroll() {
         let mut app = make_ready_app();
@@ -1814,6 +2611,121 @@ mod tests {
         assert_eq!(app.daily_scroll, 5);
     }
 
+    #[test]
+    fn test_c_toggles_cumulative_cost_in_source_detail() {
+        let mut app = make_ready_app();
+        let key = |app: &mut App| {
+            app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::NONE,
+            )))
+        };
+        key(&mut app);
+        assert!(!app.cumulative_cost, "only toggles in source detail");
+
+        app.view_mode = ViewMode::SourceDetail {
+            source: "claude".to_string(),
+        };
+        key(&mut app);
+        assert!(app.cumulative_cost);
+        key(&mut app);
+        assert!(!app.cumulative_cost);
+    }
+
+    #[test]
+    fn test_date_range_filter_in_source_detail() {
+        let mut app = make_ready_app();
+        app.view_mode = ViewMode::SourceDetail {
+            source: "claude".to_string(),
+        };
+        let key = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+        };
+        let type_str = |app: &mut App, s: &str| s.chars().for_each(|c| key(app, KeyCode::Char(c)));
+        let rows = |app: &App| match &app.state {
+            AppState::Ready { data } => app.active_daily_data(data).daily_summaries.len(),
+            _ => unreachable!(),
+        };
+
+        key(&mut app, KeyCode::Char('f'));
+        assert!(app.date_range_input.is_some());
+        // Keys go to the input, not the view mode switcher
+        type_str(&mut app, "2025-01-05");
+        assert_eq!(app.daily_view_mode, DailyViewMode::Daily);
+        key(&mut app, KeyCode::Tab);
+        type_str(&mut app, "2025-01-02");
+        key(&mut app, KeyCode::Enter);
+        assert!(app.date_range_input.as_ref().unwrap().error.is_some());
+
+        for _ in 0..2 {
+            key(&mut app, KeyCode::Backspace);
+        }
+        type_str(&mut app, "11");
+        key(&mut app, KeyCode::Enter);
+        assert!(app.date_range_input.is_none());
+        let from = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 1, 11).unwrap();
+        assert_eq!(app.date_filter, Some((from, to)));
+        assert_eq!(rows(&app), 7);
+        assert_eq!(app.daily_selected, None);
+
+        // Empty fields clear the filter
+        key(&mut app, KeyCode::Char('f'));
+        for _ in 0..10 {
+            key(&mut app, KeyCode::Backspace);
+        }
+        key(&mut app, KeyCode::Tab);
+        for _ in 0..10 {
+            key(&mut app, KeyCode::Backspace);
+        }
+        key(&mut app, KeyCode::Enter);
+        assert_eq!(app.date_filter, None);
+        assert_eq!(rows(&app), 20);
+
+        // Leaving the source drops the filter
+        app.set_date_filter(Some((from, to)));
+        key(&mut app, KeyCode::Esc);
+        assert_eq!(app.date_filter, None);
+        assert!(app.filtered_daily_data.is_none());
+    }
+
+    #[test]
+    fn test_page_and_home_end_keys_in_source_detail() {
+        let mut app = make_ready_app();
+        app.view_mode = ViewMode::SourceDetail {
+            source: "claude".to_string(),
+        };
+        let page = app.effective_visible_rows();
+        let key = |app: &mut App, code| {
+            app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+        };
+
+        key(&mut app, KeyCode::Home);
+        assert_eq!(app.daily_selected, Some(0));
+        assert_eq!(app.daily_scroll, 0);
+
+        key(&mut app, KeyCode::PageDown);
+        assert_eq!(app.daily_selected, Some(page));
+
+        key(&mut app, KeyCode::End);
+        assert_eq!(app.daily_selected, Some(19));
+        // One line of the page goes to the January header
+        assert_eq!(app.daily_scroll, 21usize.saturating_sub(page));
+
+        key(&mut app, KeyCode::PageDown);
+        assert_eq!(app.daily_selected, Some(19));
+
+        key(&mut app, KeyCode::PageUp);
+        assert_eq!(app.daily_selected, Some(19 - page));
+
+        key(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.daily_selected, Some(0));
+        assert_eq!(app.daily_scroll, 0);
+
+        key(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.daily_selected, Some(19));
+    }
+
     // ========== Tab switching tests ==========
 
     #[test]
@@ -1849,6 +2761,163 @@ mod tests {
         ));
     }
 
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn test_session_detail_loads_in_background() {
+        use chrono::Utc;
+
+        let mut app = make_ready_app();
+        if let AppState::Ready { data } = &mut app.state {
+            data.sessions.push(SessionInfo {
+                session_id: "abc".to_string(),
+                project: "toktrack".to_string(),
+                project_path: "/src/toktrack".to_string(),
+                summary: String::new(),
+                first_prompt: String::new(),
+                message_count: 2,
+                created: Utc::now(),
+                modified: Utc::now(),
+                git_branch: String::new(),
+                jsonl_path: "/tmp/abc.jsonl".to_string(),
+                total_cost_usd: 0.0,
+                total_tokens: 0,
+                primary_model: String::new(),
+                mixed_utc_offsets: vec![],
+                metadata: None,
+            });
+        }
+        app.set_tab(Tab::Sessions);
+        app.sessions_selected = Some(0);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.view_mode, ViewMode::SessionDetail { session_index: 0 });
+        assert_eq!(
+            app.session_detail_load,
+            Some(SessionDetailLoad::Requested("/tmp/abc.jsonl".to_string()))
+        );
+
+        let entry = SessionDetailEntry {
+            timestamp: chrono::Utc::now(),
+            model: "Sonnet 4.5".to_string(),
+            input_tokens: 10,
+            output_tokens: 5,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cost_usd: 0.01,
+            cost_source: crate::types::CostSource::Pricing,
+            rates: None,
+            cost_breakdown: None,
+        };
+        app.session_detail_load = Some(SessionDetailLoad::Running("/tmp/abc.jsonl".to_string()));
+        // Results for a session that is no longer open are dropped
+        app.apply_session_detail("/tmp/other.jsonl", vec![entry.clone()]);
+        assert!(app.session_detail_entries.is_empty());
+        app.apply_session_detail("/tmp/abc.jsonl", vec![entry]);
+        assert_eq!(app.session_detail_entries.len(), 1);
+        assert_eq!(app.session_detail_load, None);
+    }
+
+    #[test]
+    fn test_empty_data_renders_guidance() {
+        let mut data = build_app_data_from_summaries(
+            vec![],
+            vec![],
+            vec![],
+            HashMap::new(),
+            None,
+            vec![],
+            HashMap::new(),
+            0.0,
+            WeekStart::default(),
+        )
+        .unwrap();
+        data.data_dirs = vec![
+            (
+                "claude-code".to_string(),
+                PathBuf::from("/home/u/.claude/projects"),
+            ),
+            (
+                "codex".to_string(),
+                PathBuf::from("/home/u/.codex/sessions"),
+            ),
+        ];
+        let mut app = App::default();
+        app.apply_data_result(Ok(data));
+
+        let area = Rect::new(0, 0, 120, 30);
+        let mut buf = Buffer::empty(area);
+        (&app).render(area, &mut buf);
+        let content: String = (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert!(content.contains("No usage data found"));
+        assert!(content.contains("/home/u/.claude/projects"));
+        assert!(content.contains("/home/u/.codex/sessions"));
+        assert!(content.contains("--merge-dir"));
+        // Dashboard chrome is not drawn
+        assert!(!content.contains("Overview"));
+    }
+
+    #[test]
+    fn test_dollar_toggles_display_metric() {
+        let mut app = make_ready_app();
+        assert_eq!(app.display_metric, DisplayMetric::Tokens);
+        press(&mut app, KeyCode::Char('$'));
+        assert_eq!(app.display_metric, DisplayMetric::Cost);
+
+        // Shared with the source detail tables
+        press(&mut app, KeyCode::Enter);
+        assert!(matches!(app.view_mode, ViewMode::SourceDetail { .. }));
+        press(&mut app, KeyCode::Char('$'));
+        assert_eq!(app.display_metric, DisplayMetric::Tokens);
+    }
+
+    #[test]
+    fn test_models_filter_captures_input_and_esc_clears() {
+        let mut app = make_ready_app();
+        app.set_tab(Tab::Models);
+
+        press(&mut app, KeyCode::Char('/'));
+        assert!(app.model_filter_editing);
+        // Digits and '?' are typed into the filter instead of switching tabs/help
+        for c in "op1?".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.model_filter, "op1");
+        assert_eq!(app.current_tab(), Tab::Models);
+        assert!(!app.show_help);
+
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.model_filter_editing);
+        assert_eq!(app.model_filter, "op1");
+
+        // Keys act normally again once the query is applied
+        press(&mut app, KeyCode::Esc);
+        assert!(app.model_filter.is_empty());
+        press(&mut app, KeyCode::Char('1'));
+        assert_eq!(app.current_tab(), Tab::Overview);
+    }
+
+    #[test]
+    fn test_models_filter_esc_while_editing_clears() {
+        let mut app = make_ready_app();
+        app.set_tab(Tab::Models);
+        press(&mut app, KeyCode::Char('/'));
+        press(&mut app, KeyCode::Char('g'));
+        press(&mut app, KeyCode::Esc);
+        assert!(app.model_filter.is_empty());
+        assert!(!app.model_filter_editing);
+    }
+
     #[test]
     fn test_backtab_switches_tab() {
         let mut app = App::default();
@@ -1900,6 +2969,22 @@ mod tests {
         let config = TuiConfig {
             initial_view_mode: DailyViewMode::Daily,
             initial_tab: Some(Tab::Stats),
+            other_threshold: 0.0,
+            sparkline_width: None,
+            weekly_scale_weeks: None,
+            min_cols: None,
+            min_rows: None,
+            detail_granularity: DetailGranularity::Exact,
+            include_synthetic: false,
+            safe_parse: false,
+            raw_cache: false,
+            exclude_today: false,
+            max_age_days: None,
+            merge_dir: None,
+            show_empty_sources: false,
+            cost_precision: None,
+            sources: None,
+            config: Config::default(),
         };
         let app = App::new(config, Theme::Dark);
         assert!(matches!(
@@ -1907,4 +2992,55 @@ mod tests {
             ViewMode::Dashboard { tab: Tab::Stats }
         ));
     }
+
+    // ========== minimum size tests ==========
+
+    #[test]
+    fn test_min_size_threshold_shows_resize_message() {
+        let mut app = make_ready_app();
+        app.min_cols = 80;
+        app.min_rows = 20;
+
+        let narrow = app.render_to_string(79, 30);
+        assert!(narrow.contains("Terminal too small"));
+        assert!(narrow.contains("79x30, need at least 80x20"));
+
+        let short = app.render_to_string(100, 19);
+        assert!(short.contains("Terminal too small"));
+
+        let exact = app.render_to_string(80, 20);
+        assert!(!exact.contains("Terminal too small"));
+        assert!(exact.contains("[Overview]"));
+    }
+
+    #[test]
+    fn test_min_size_defaults_to_daily_table_width() {
+        let app = App::new(TuiConfig::default(), Theme::Dark);
+        assert_eq!(app.min_cols, 69);
+        assert_eq!(app.min_rows, DEFAULT_MIN_ROWS);
+
+        let app = App::new(
+            TuiConfig {
+                min_cols: Some(40),
+                min_rows: Some(5),
+                ..TuiConfig::default()
+            },
+            Theme::Dark,
+        );
+        assert_eq!((app.min_cols, app.min_rows), (40, 5));
+    }
+
+    // ========== render_to_string tests ==========
+
+    #[test]
+    fn test_render_to_string_ready_app() {
+        let app = make_ready_app();
+        let text = app.render_to_string(120, 40);
+
+        assert_eq!(text.lines().count(), 40);
+        assert!(text.lines().all(|l| l.chars().count() <= 120));
+        assert!(text.contains("[Overview]"));
+        assert!(text.contains("claude"));
+        assert!(text.contains("Ctrl+C: Quit"));
+    }
 }
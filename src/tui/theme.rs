@@ -107,6 +107,14 @@ impl Theme {
         }
     }
 
+    /// Non-fatal warning color (status bar notices)
+    pub fn warning(self) -> Color {
+        match self {
+            Self::Dark => Color::Yellow,
+            Self::Light => Color::Indexed(136), // dark yellow (ANSI 256)
+        }
+    }
+
     /// Spike warning color (elevated spending: 1.5x~2x daily avg)
     pub fn spike_warn(self) -> Color {
         match self {
@@ -148,6 +156,39 @@ impl Theme {
         }
     }
 
+    /// Stable per-source color: the FNV-1a hash of the name picks a palette slot,
+    /// so a source keeps its color across runs and sources
+    pub fn source_color(self, source: &str) -> Color {
+        const DARK: [Color; 8] = [
+            Color::Cyan,
+            Color::LightGreen,
+            Color::Yellow,
+            Color::Magenta,
+            Color::Green,
+            Color::LightRed,
+            Color::Blue,
+            Color::Indexed(208), // orange
+        ];
+        const LIGHT: [Color; 8] = [
+            Color::Indexed(30),  // teal
+            Color::Indexed(28),  // green
+            Color::Indexed(136), // dark yellow
+            Color::Indexed(90),  // dark magenta
+            Color::Indexed(22),  // dark green
+            Color::Indexed(124), // dark red
+            Color::Indexed(25),  // dark blue
+            Color::Indexed(166), // dark orange
+        ];
+        let hash = source.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+        });
+        let palette = match self {
+            Self::Dark => &DARK,
+            Self::Light => &LIGHT,
+        };
+        palette[hash as usize % palette.len()]
+    }
+
     /// Heatmap intensity color
     pub fn heatmap_color(self, level: HeatmapLevel) -> Color {
         match self {
@@ -183,6 +224,7 @@ mod tests {
         assert_eq!(t.cost(), Color::Magenta);
         assert_eq!(t.bar(), Color::Green);
         assert_eq!(t.error(), Color::Red);
+        assert_eq!(t.warning(), Color::Yellow);
         assert_eq!(t.stat_blue(), Color::Blue);
         assert_eq!(t.stat_warm(), Color::LightRed);
         assert_eq!(t.spike_warn(), Color::Indexed(208));
@@ -199,6 +241,7 @@ mod tests {
         assert_eq!(t.cost(), Color::Indexed(90));
         assert_eq!(t.bar(), Color::Indexed(22));
         assert_eq!(t.error(), Color::Indexed(124));
+        assert_eq!(t.warning(), Color::Indexed(136));
         assert_eq!(t.stat_blue(), Color::Indexed(25));
         assert_eq!(t.stat_warm(), Color::Red);
         assert_eq!(t.spike_warn(), Color::Indexed(166));
@@ -230,6 +273,20 @@ mod tests {
         assert_eq!(t.heatmap_color(HeatmapLevel::Max), Color::Indexed(28));
     }
 
+    #[test]
+    fn test_source_color_is_stable() {
+        // Pinned values: the hash must not change between runs or releases
+        assert_eq!(Theme::Dark.source_color("claude"), Color::Indexed(208));
+        assert_eq!(Theme::Dark.source_color("codex"), Color::Green);
+        assert_eq!(Theme::Dark.source_color("gemini"), Color::Blue);
+        assert_eq!(Theme::Dark.source_color("opencode"), Color::Cyan);
+        assert_eq!(Theme::Light.source_color("claude"), Color::Indexed(166));
+        assert_eq!(
+            Theme::Dark.source_color("my-tool"),
+            Theme::Dark.source_color("my-tool")
+        );
+    }
+
     // ========== Spike level tests ==========
 
     #[test]
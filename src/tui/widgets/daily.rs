@@ -8,7 +8,12 @@ use ratatui::{
     widgets::{Paragraph, Widget},
 };
 
-use super::overview::format_number;
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use super::overview::{format_number, format_tokens_human};
+use crate::config::WeekStart;
 use crate::services::{display_name, Aggregator};
 use crate::tui::theme::{spike_level, Theme};
 use crate::types::DailySummary;
@@ -20,6 +25,7 @@ pub enum DailyViewMode {
     Daily,
     Weekly,
     Monthly,
+    Quarterly,
 }
 
 impl DailyViewMode {
@@ -28,6 +34,7 @@ impl DailyViewMode {
             Self::Daily => "Daily",
             Self::Weekly => "Weekly",
             Self::Monthly => "Monthly",
+            Self::Quarterly => "Quarterly",
         }
     }
 
@@ -36,6 +43,31 @@ impl DailyViewMode {
             Self::Daily => "Date",
             Self::Weekly => "Week",
             Self::Monthly => "Month",
+            Self::Quarterly => "Quarter",
+        }
+    }
+}
+
+/// Metric the tables and overview emphasize, toggled with `$`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayMetric {
+    #[default]
+    Tokens,
+    Cost,
+}
+
+impl DisplayMetric {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Tokens => Self::Cost,
+            Self::Cost => Self::Tokens,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Tokens => "Tokens",
+            Self::Cost => "Cost",
         }
     }
 }
@@ -43,17 +75,32 @@ impl DailyViewMode {
 /// Format a sparkline bar based on token ratio
 /// Example: tokens=500, max=1000, width=8 → "████░░░░"
 pub fn format_sparkline(tokens: u64, max: u64, width: usize) -> String {
-    if max == 0 || width == 0 {
+    format_sparkline_scaled(tokens as f64, max as f64, width)
+}
+
+/// Same as `format_sparkline` for fractional values such as cost
+pub fn format_sparkline_scaled(value: f64, max: f64, width: usize) -> String {
+    if max <= 0.0 || width == 0 {
         return "░".repeat(width);
     }
-    let ratio = tokens as f64 / max as f64;
+    let ratio = value / max;
     let filled = (ratio * width as f64).round() as usize;
     let filled = filled.min(width); // Clamp to prevent overflow when ratio > 1.0
     let empty = width.saturating_sub(filled);
     format!("{}{}", "▓".repeat(filled), "░".repeat(empty))
 }
 
-/// Data for the daily view (holds daily, weekly, and monthly aggregations)
+/// Largest per-row token total
+fn max_tokens<'a>(summaries: impl Iterator<Item = &'a DailySummary>) -> u64 {
+    summaries.map(DailySummary::total_tokens).max().unwrap_or(0)
+}
+
+/// `YYYY-Qn` label for the quarter starting at `date`
+pub fn quarter_label(date: NaiveDate) -> String {
+    format!("{}-Q{}", date.year(), date.month0() / 3 + 1)
+}
+
+/// Data for the daily view (holds daily, weekly, monthly and quarterly aggregations)
 #[derive(Debug)]
 pub struct DailyData {
     /// Daily summaries sorted by date ascending (oldest first)
@@ -63,31 +110,37 @@ pub struct DailyData {
     pub weekly_max_tokens: u64,
     pub monthly_summaries: Vec<DailySummary>,
     pub monthly_max_tokens: u64,
+    pub quarterly_summaries: Vec<DailySummary>,
+    pub quarterly_max_tokens: u64,
+    /// Day notes keyed by date (shown in the Notes column of the daily mode)
+    pub notes: HashMap<NaiveDate, String>,
+    /// First day of the week used for `weekly_summaries`
+    pub week_start: WeekStart,
 }
 
 impl DailyData {
     /// Create DailyData from aggregated daily summaries
     /// Expects summaries in ascending order (from Aggregator::daily)
+    #[allow(dead_code)]
     pub fn from_daily_summaries(summaries: Vec<DailySummary>) -> Self {
-        let calc_max = |s: &[DailySummary]| -> u64 {
-            s.iter()
-                .map(|d| {
-                    d.total_input_tokens
-                        + d.total_output_tokens
-                        + d.total_cache_read_tokens
-                        + d.total_cache_creation_tokens
-                        + d.total_thinking_tokens
-                })
-                .max()
-                .unwrap_or(0)
-        };
+        Self::from_daily_summaries_starting(summaries, WeekStart::default())
+    }
 
-        let weekly_summaries = Aggregator::weekly(&summaries);
+    /// Like `from_daily_summaries`, with weeks beginning on `week_start`
+    pub fn from_daily_summaries_starting(
+        summaries: Vec<DailySummary>,
+        week_start: WeekStart,
+    ) -> Self {
+        let calc_max = |s: &[DailySummary]| max_tokens(s.iter());
+
+        let weekly_summaries = Aggregator::weekly_starting(&summaries, week_start);
         let monthly_summaries = Aggregator::monthly(&summaries);
+        let quarterly_summaries = Aggregator::quarterly(&summaries);
 
         let daily_max_tokens = calc_max(&summaries);
         let weekly_max_tokens = calc_max(&weekly_summaries);
         let monthly_max_tokens = calc_max(&monthly_summaries);
+        let quarterly_max_tokens = calc_max(&quarterly_summaries);
 
         Self {
             daily_summaries: summaries,
@@ -96,15 +149,78 @@ impl DailyData {
             weekly_max_tokens,
             monthly_summaries,
             monthly_max_tokens,
+            quarterly_summaries,
+            quarterly_max_tokens,
+            notes: HashMap::new(),
+            week_start,
         }
     }
 
+    /// Only the days in `[from, to]`, with weekly, monthly and quarterly rows
+    /// re-aggregated from those days
+    pub fn filter(&self, from: NaiveDate, to: NaiveDate) -> DailyData {
+        let days = self
+            .daily_summaries
+            .iter()
+            .filter(|s| (from..=to).contains(&s.date))
+            .cloned()
+            .collect();
+        Self::from_daily_summaries_starting(days, self.week_start).with_notes(self.notes.clone())
+    }
+
+    /// Attach day notes
+    pub fn with_notes(mut self, notes: HashMap<NaiveDate, String>) -> Self {
+        self.notes = notes;
+        self
+    }
+
     /// Get summaries and max_tokens for the given view mode
     pub fn for_mode(&self, mode: DailyViewMode) -> (&[DailySummary], u64) {
         match mode {
             DailyViewMode::Daily => (&self.daily_summaries, self.daily_max_tokens),
             DailyViewMode::Weekly => (&self.weekly_summaries, self.weekly_max_tokens),
             DailyViewMode::Monthly => (&self.monthly_summaries, self.monthly_max_tokens),
+            DailyViewMode::Quarterly => (&self.quarterly_summaries, self.quarterly_max_tokens),
+        }
+    }
+
+    /// Sparkline scale for `mode`. With `weekly_window`, the weekly max only
+    /// covers the trailing N weeks so an old outlier cannot flatten recent bars.
+    pub fn scale_max(&self, mode: DailyViewMode, weekly_window: Option<usize>) -> u64 {
+        match (mode, weekly_window) {
+            (DailyViewMode::Weekly, Some(_)) => {
+                max_tokens(self.scale_rows(mode, weekly_window).iter())
+            }
+            _ => self.for_mode(mode).1,
+        }
+    }
+
+    /// `scale_max` for `metric`; cost is scaled in USD
+    pub fn scale_max_by(
+        &self,
+        mode: DailyViewMode,
+        weekly_window: Option<usize>,
+        metric: DisplayMetric,
+    ) -> f64 {
+        match metric {
+            DisplayMetric::Tokens => self.scale_max(mode, weekly_window) as f64,
+            DisplayMetric::Cost => self
+                .scale_rows(mode, weekly_window)
+                .iter()
+                .map(|s| s.total_cost_usd)
+                .fold(0.0, f64::max),
+        }
+    }
+
+    /// Rows the sparkline scale is taken from (trailing `weekly_window` weeks in weekly mode)
+    fn scale_rows(&self, mode: DailyViewMode, weekly_window: Option<usize>) -> &[DailySummary] {
+        let (rows, _) = self.for_mode(mode);
+        match (mode, weekly_window, rows.last()) {
+            (DailyViewMode::Weekly, Some(weeks), Some(latest)) => {
+                let cutoff = latest.date - Duration::weeks(weeks.saturating_sub(1) as i64);
+                &rows[rows.partition_point(|s| s.date < cutoff)..]
+            }
+            _ => rows,
         }
     }
 
@@ -112,15 +228,68 @@ impl DailyData {
     pub fn max_scroll_offset_for(count: usize, visible_rows: usize) -> usize {
         count.saturating_sub(visible_rows)
     }
+
+    /// Lines of the table from row `start` in `height` lines. Daily mode opens
+    /// with the month of the top row, so its header sticks while scrolling, and
+    /// adds a header where the month changes; other modes have data rows only.
+    pub fn display_rows(
+        &self,
+        mode: DailyViewMode,
+        start: usize,
+        height: usize,
+    ) -> Vec<DailyViewRow> {
+        let (summaries, _) = self.for_mode(mode);
+        let headers = mode == DailyViewMode::Daily && height > 1;
+        let mut rows = Vec::with_capacity(height);
+        let mut month = None;
+        for (index, summary) in summaries.iter().enumerate().skip(start) {
+            let key = (summary.date.year(), summary.date.month());
+            if headers && month != Some(key) {
+                // A header needs a data row below it
+                if rows.len() + 2 > height {
+                    break;
+                }
+                rows.push(DailyViewRow::Header(month_header(summary.date)));
+                month = Some(key);
+            }
+            if rows.len() >= height {
+                break;
+            }
+            rows.push(DailyViewRow::Data(index));
+        }
+        rows
+    }
+
+    /// Data rows (not headers) shown from row `start` in `height` lines
+    pub fn data_rows_in(&self, mode: DailyViewMode, start: usize, height: usize) -> usize {
+        self.display_rows(mode, start, height)
+            .iter()
+            .filter(|row| matches!(row, DailyViewRow::Data(_)))
+            .count()
+    }
+}
+
+/// One line of the daily table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DailyViewRow {
+    /// Month boundary, e.g. "── January 2025 ──"; not selectable
+    Header(String),
+    /// Index into the summaries of the current mode
+    Data(usize),
+}
+
+fn month_header(date: NaiveDate) -> String {
+    format!("── {} ──", date.format("%B %Y"))
 }
 
 /// Maximum content width for Daily view (consistent with Overview/Models)
 const MAX_CONTENT_WIDTH: u16 = 170;
 
-/// Default visible rows for scrolling tests.
-/// Actual visible rows are computed dynamically from terminal height.
-#[cfg(test)]
-const VISIBLE_ROWS: usize = 15;
+/// Rows of the daily table that fit in `area_height` of `SourceDetailView`.
+/// Overhead: padding(1) + source header(1) + stats(1) + sep(1) + mode(1) + table header(1) + sep(1) + keybindings(1) = 8
+pub fn compute_visible_rows(area_height: u16) -> usize {
+    area_height.saturating_sub(8) as usize
+}
 
 /// Column index constants for clarity
 const COL_DATE: usize = 0;
@@ -131,10 +300,12 @@ const COL_INPUT: usize = 4;
 const COL_OUTPUT: usize = 5;
 const COL_CACHE: usize = 6;
 const COL_USAGE: usize = 7;
+const COL_NOTES: usize = 8;
 
 /// Column definition: (label, width). Core columns (0-3) are never hidden.
 /// Date width includes 2 chars for selection marker (▸ )
-const COLUMNS: [(&str, u16); 8] = [
+/// Notes (8) is optional and only shown in daily mode when notes exist and fit.
+const COLUMNS: [(&str, u16); 9] = [
     ("Date", 14),   // 0: COL_DATE (12 date + 2 marker)
     ("Model", 25),  // 1: COL_MODEL
     ("Total", 18),  // 2: COL_TOTAL
@@ -143,19 +314,51 @@ const COLUMNS: [(&str, u16); 8] = [
     ("Output", 18), // 5: COL_OUTPUT
     ("Cache", 18),  // 6: COL_CACHE
     ("Usage", 18),  // 7: COL_USAGE
+    ("Notes", 24),  // 8: COL_NOTES
 ];
 
+/// Width of the core columns (Date, Model, Total, Cost) that are never hidden
+pub const MIN_TABLE_WIDTH: u16 =
+    COLUMNS[COL_DATE].1 + COLUMNS[COL_MODEL].1 + COLUMNS[COL_TOTAL].1 + COLUMNS[COL_COST].1;
+
+/// With fewer visible columns than this the layout is narrow and token counts go compact
+const COMPACT_BELOW_COLUMNS: usize = 6;
+
+/// Token formatter for a row: `1.2M` in the narrow layout, `1,234,567` otherwise
+fn token_formatter(visible: &[usize]) -> fn(u64) -> String {
+    if visible.len() < COMPACT_BELOW_COLUMNS {
+        format_tokens_human
+    } else {
+        format_number
+    }
+}
+
+/// Left padding between the Usage column edge and the sparkline
+const USAGE_PADDING: u16 = 4;
+
+/// Default sparkline width, derived from the Usage column width
+pub const DEFAULT_SPARKLINE_WIDTH: usize = (COLUMNS[COL_USAGE].1 - USAGE_PADDING) as usize;
+
+/// Width of a column; the Usage column grows or shrinks with the sparkline.
+fn column_width(col: usize, sparkline_width: usize) -> u16 {
+    if col == COL_USAGE {
+        (sparkline_width as u16).saturating_add(USAGE_PADDING)
+    } else {
+        COLUMNS[col].1
+    }
+}
+
 /// Determine which column indices are visible for a given terminal width.
 /// Columns are hidden in priority order: Input first, then Output, Cache, Usage.
 /// This prioritizes showing Usage (visual bar) in narrow views.
-pub fn visible_columns(width: u16) -> Vec<usize> {
+pub fn visible_columns(width: u16, sparkline_width: usize) -> Vec<usize> {
     // Ordered by hide priority: first element is hidden first
     const HIDE_ORDER: [usize; 4] = [COL_INPUT, COL_OUTPUT, COL_CACHE, COL_USAGE];
 
-    let mut visible: Vec<usize> = (0..COLUMNS.len()).collect();
+    let mut visible: Vec<usize> = (0..COL_NOTES).collect();
 
     for &col_idx in &HIDE_ORDER {
-        let total: u16 = visible.iter().map(|&i| COLUMNS[i].1).sum();
+        let total = table_width_for(&visible, sparkline_width);
         if total <= width {
             return visible;
         }
@@ -166,8 +369,11 @@ pub fn visible_columns(width: u16) -> Vec<usize> {
 }
 
 /// Calculate total table width for a set of visible column indices.
-fn table_width_for(visible: &[usize]) -> u16 {
-    visible.iter().map(|&i| COLUMNS[i].1).sum()
+fn table_width_for(visible: &[usize], sparkline_width: usize) -> u16 {
+    visible
+        .iter()
+        .map(|&i| column_width(i, sparkline_width))
+        .sum()
 }
 
 /// Daily view widget
@@ -178,6 +384,11 @@ pub struct DailyView<'a> {
     view_mode: DailyViewMode,
     theme: Theme,
     avg_cost: f64,
+    sparkline_width: usize,
+    weekly_scale_weeks: Option<usize>,
+    metric: DisplayMetric,
+    cumulative: bool,
+    month_headers: bool,
 }
 
 impl<'a> DailyView<'a> {
@@ -195,6 +406,11 @@ impl<'a> DailyView<'a> {
             view_mode,
             theme,
             avg_cost,
+            sparkline_width: DEFAULT_SPARKLINE_WIDTH,
+            weekly_scale_weeks: None,
+            metric: DisplayMetric::default(),
+            cumulative: false,
+            month_headers: false,
         }
     }
 
@@ -203,10 +419,70 @@ impl<'a> DailyView<'a> {
         self
     }
 
-    /// Calculate the maximum valid scroll offset for the given mode and visible rows
+    pub fn with_sparkline_width(mut self, sparkline_width: usize) -> Self {
+        self.sparkline_width = sparkline_width;
+        self
+    }
+
+    /// Scale weekly sparklines to the trailing N weeks instead of all history
+    pub fn with_weekly_scale_weeks(mut self, weeks: Option<usize>) -> Self {
+        self.weekly_scale_weeks = weeks;
+        self
+    }
+
+    /// Scale the Usage sparkline by `metric` instead of tokens
+    pub fn with_metric(mut self, metric: DisplayMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Show the running total up to each row in the Cost column
+    pub fn with_cumulative(mut self, cumulative: bool) -> Self {
+        self.cumulative = cumulative;
+        self
+    }
+
+    /// Separate months with header lines in daily mode (see `DailyData::display_rows`)
+    pub fn with_month_headers(mut self, month_headers: bool) -> Self {
+        self.month_headers = month_headers;
+        self
+    }
+
+    /// Visible columns for the given width, accounting for the sparkline width.
+    /// The Notes column is appended in daily mode when any notes exist and it fits.
+    pub fn visible_columns(&self, width: u16) -> Vec<usize> {
+        let mut visible = visible_columns(width, self.sparkline_width);
+        if self.view_mode == DailyViewMode::Daily
+            && !self.data.notes.is_empty()
+            && table_width_for(&visible, self.sparkline_width) + COLUMNS[COL_NOTES].1 <= width
+        {
+            visible.push(COL_NOTES);
+        }
+        visible
+    }
+
+    /// Calculate the maximum valid scroll offset for the given mode and visible rows,
+    /// leaving room for month headers in daily mode
     pub fn max_scroll_offset(data: &DailyData, mode: DailyViewMode, visible_rows: usize) -> usize {
-        let (summaries, _) = data.for_mode(mode);
-        DailyData::max_scroll_offset_for(summaries.len(), visible_rows)
+        let count = data.for_mode(mode).0.len();
+        let mut start = DailyData::max_scroll_offset_for(count, visible_rows);
+        while start + 1 < count && start + data.data_rows_in(mode, start, visible_rows) < count {
+            start += 1;
+        }
+        start
+    }
+
+    /// Lines from the scroll offset in `height` lines
+    fn display_rows(&self, height: usize) -> Vec<DailyViewRow> {
+        if self.month_headers {
+            return self
+                .data
+                .display_rows(self.view_mode, self.scroll_offset, height);
+        }
+        let count = self.data.for_mode(self.view_mode).0.len();
+        (self.scroll_offset.min(count)..(self.scroll_offset + height).min(count))
+            .map(DailyViewRow::Data)
+            .collect()
     }
 }
 
@@ -223,7 +499,7 @@ impl Widget for DailyView<'_> {
         };
 
         // Determine visible columns based on available width
-        let visible = visible_columns(centered_area.width);
+        let visible = self.visible_columns(centered_area.width);
 
         // Calculate layout
         let chunks = Layout::vertical([
@@ -233,6 +509,7 @@ impl Widget for DailyView<'_> {
             Constraint::Length(1), // Mode indicator
             Constraint::Length(1), // Header
             Constraint::Fill(1),   // Daily rows (fill remaining)
+            Constraint::Length(1), // Total
             Constraint::Length(1), // Separator
             Constraint::Length(1), // Keybindings
         ])
@@ -250,11 +527,19 @@ impl Widget for DailyView<'_> {
         // Render daily rows
         self.render_daily_rows(chunks[5], buf, &visible);
 
+        // Render total of the visible rows
+        let data_rows = self
+            .display_rows(chunks[5].height as usize)
+            .iter()
+            .filter(|row| matches!(row, DailyViewRow::Data(_)))
+            .count();
+        self.render_total_row(chunks[6], buf, &visible, data_rows);
+
         // Render separator
-        self.render_separator(chunks[6], buf);
+        self.render_separator(chunks[7], buf);
 
         // Render keybindings
-        self.render_keybindings(chunks[7], buf);
+        self.render_keybindings(chunks[8], buf);
     }
 }
 
@@ -279,6 +564,7 @@ impl DailyView<'_> {
             ('d', DailyViewMode::Daily),
             ('w', DailyViewMode::Weekly),
             ('m', DailyViewMode::Monthly),
+            ('q', DailyViewMode::Quarterly),
         ];
 
         let mut spans = Vec::new();
@@ -296,13 +582,18 @@ impl DailyView<'_> {
             };
             spans.push(Span::styled(format!("{}:{}", key, mode.label()), style));
         }
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("$:{}", self.metric.label()),
+            Style::default().fg(self.theme.muted()),
+        ));
 
         let indicator = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
         indicator.render(area, buf);
     }
 
     pub fn render_header(&self, area: Rect, buf: &mut Buffer, visible: &[usize]) {
-        let tw = table_width_for(visible);
+        let tw = table_width_for(visible, self.sparkline_width);
         let offset = Self::calculate_table_offset(area.width, tw);
         let date_label = self.view_mode.date_column_label();
         let header_style = Style::default()
@@ -311,10 +602,16 @@ impl DailyView<'_> {
 
         let mut spans = Vec::new();
         for &col in visible {
-            let (label, width) = COLUMNS[col];
-            let label = if col == COL_DATE { date_label } else { label };
-            let formatted = if col == COL_DATE {
-                // Add 2-space prefix to align with selection marker in rows
+            let label = COLUMNS[col].0;
+            let width = column_width(col, self.sparkline_width);
+            let label = match col {
+                COL_DATE => date_label,
+                COL_COST if self.cumulative => "Cumulative",
+                _ => label,
+            };
+            let formatted = if col == COL_DATE || col == COL_NOTES {
+                // 2-space prefix: aligns Date with the row selection marker
+                // and separates Notes from the column before it
                 format!("  {:<width$}", label, width = (width as usize) - 2)
             } else if col == COL_MODEL {
                 format!("{:<width$}", label, width = width as usize)
@@ -338,53 +635,135 @@ impl DailyView<'_> {
     }
 
     pub fn render_daily_rows(&self, area: Rect, buf: &mut Buffer, visible: &[usize]) {
-        let tw = table_width_for(visible);
+        let tw = table_width_for(visible, self.sparkline_width);
         let offset = Self::calculate_table_offset(area.width, tw);
-        let (summaries, max_tokens) = self.data.for_mode(self.view_mode);
-        let start = self.scroll_offset;
-        let end = (start + area.height as usize).min(summaries.len());
+        let (summaries, _) = self.data.for_mode(self.view_mode);
+        let scale = self
+            .data
+            .scale_max_by(self.view_mode, self.weekly_scale_weeks, self.metric);
+        let running_totals = if self.cumulative {
+            Aggregator::cumulative(summaries)
+        } else {
+            Vec::new()
+        };
 
-        for (i, summary) in summaries[start..end].iter().enumerate() {
-            let y = area.y + i as u16;
-            if y >= area.y + area.height {
-                break;
+        for (i, row) in self.display_rows(area.height as usize).iter().enumerate() {
+            let row_area = Rect {
+                x: area.x + offset,
+                y: area.y + i as u16,
+                width: tw.min(area.width),
+                height: 1,
+            };
+            match *row {
+                DailyViewRow::Header(ref label) => {
+                    buf.set_stringn(
+                        row_area.x,
+                        row_area.y,
+                        format!("  {}", label),
+                        row_area.width as usize,
+                        Style::default().fg(self.theme.muted()),
+                    );
+                }
+                DailyViewRow::Data(data_index) => self.render_daily_row(
+                    row_area,
+                    buf,
+                    &summaries[data_index],
+                    running_totals.get(data_index).map(|&(_, total)| total),
+                    scale,
+                    visible,
+                    self.selected_index == Some(data_index),
+                ),
             }
+        }
+    }
 
-            let data_index = start + i;
-            let is_selected = self.selected_index == Some(data_index);
-
-            self.render_daily_row(
-                Rect {
-                    x: area.x + offset,
-                    y,
-                    width: tw.min(area.width),
-                    height: 1,
-                },
-                buf,
-                summary,
-                max_tokens,
-                visible,
-                is_selected,
-            );
+    /// Column sums of the rows currently on screen (`visible_rows` from the scroll offset).
+    /// Not selectable; hints at scrolling when the window doesn't cover every row.
+    pub fn render_total_row(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        visible: &[usize],
+        visible_rows: usize,
+    ) {
+        let (summaries, _) = self.data.for_mode(self.view_mode);
+        let start = self.scroll_offset.min(summaries.len());
+        let end = (start + visible_rows).min(summaries.len());
+        let window = &summaries[start..end];
+        if window.is_empty() {
+            return;
         }
+        let format_tokens = token_formatter(visible);
+        let sum = |f: fn(&DailySummary) -> u64| window.iter().map(f).sum::<u64>();
+        let cost: f64 = window.iter().map(|s| s.total_cost_usd).sum();
+
+        let muted = Style::default().fg(self.theme.muted());
+        let text = Style::default().fg(self.theme.text());
+        let tokens = |n: u64| (format!("{:>18}", format_tokens(n)), text);
+        let spans: Vec<Span> = visible
+            .iter()
+            .map(|&col| {
+                let (content, style) = match col {
+                    COL_DATE => (format!("  {:<12}", "── Total ──"), muted),
+                    COL_MODEL => {
+                        let hint = if summaries.len() > visible_rows {
+                            "↑↓ scroll for more"
+                        } else {
+                            ""
+                        };
+                        (format!("{:<25}", hint), muted)
+                    }
+                    COL_TOTAL => tokens(sum(DailySummary::total_tokens)),
+                    COL_INPUT => tokens(sum(|s| s.total_input_tokens)),
+                    COL_OUTPUT => tokens(sum(|s| s.total_output_tokens)),
+                    COL_CACHE => tokens(sum(DailySummary::total_cache_tokens)),
+                    COL_COST => {
+                        let cost_color = if self.view_mode == DailyViewMode::Daily {
+                            self.theme
+                                .spike_color(spike_level(cost, window.len() as f64 * self.avg_cost))
+                        } else {
+                            self.theme.text()
+                        };
+                        (
+                            format!("{:>12}", format!("${:.2}", cost)),
+                            Style::default().fg(cost_color),
+                        )
+                    }
+                    _ => (
+                        " ".repeat(column_width(col, self.sparkline_width) as usize),
+                        text,
+                    ),
+                };
+                Span::styled(content, style)
+            })
+            .collect();
+
+        let tw = table_width_for(visible, self.sparkline_width);
+        let offset = Self::calculate_table_offset(area.width, tw);
+        Paragraph::new(Line::from(spans)).render(
+            Rect {
+                x: area.x + offset,
+                width: tw.min(area.width),
+                ..area
+            },
+            buf,
+        );
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn render_daily_row(
         &self,
         area: Rect,
         buf: &mut Buffer,
         summary: &DailySummary,
-        max_tokens: u64,
+        running_total: Option<f64>,
+        scale: f64,
         visible: &[usize],
         is_selected: bool,
     ) {
-        let total_tokens = summary.total_input_tokens
-            + summary.total_output_tokens
-            + summary.total_cache_read_tokens
-            + summary.total_cache_creation_tokens
-            + summary.total_thinking_tokens;
-
-        let cache_tokens = summary.total_cache_read_tokens + summary.total_cache_creation_tokens;
+        let total_tokens = summary.total_tokens();
+        let cache_tokens = summary.total_cache_tokens();
+        let format_tokens = token_formatter(visible);
 
         // Get primary model (highest cost) + count of others, filtering out zero-token models
         let non_zero_models: Vec<_> = summary
@@ -434,7 +813,14 @@ impl DailyView<'_> {
             primary_model
         };
 
-        let sparkline = format_sparkline(total_tokens, max_tokens, 14);
+        let sparkline = match self.metric {
+            DisplayMetric::Tokens => {
+                format_sparkline(total_tokens, scale as u64, self.sparkline_width)
+            }
+            DisplayMetric::Cost => {
+                format_sparkline_scaled(summary.total_cost_usd, scale, self.sparkline_width)
+            }
+        };
 
         // Format date based on view mode
         let date_str = match self.view_mode {
@@ -442,6 +828,7 @@ impl DailyView<'_> {
                 summary.date.format("%Y-%m-%d").to_string()
             }
             DailyViewMode::Monthly => summary.date.format("%Y-%m").to_string(),
+            DailyViewMode::Quarterly => quarter_label(summary.date),
         };
 
         // Selection marker and style modifier
@@ -496,37 +883,62 @@ impl DailyView<'_> {
                     )
                 }
                 COL_INPUT => (
-                    format!("{:>18}", format_number(summary.total_input_tokens)),
+                    format!("{:>18}", format_tokens(summary.total_input_tokens)),
                     Style::default().fg(self.theme.text()),
                 ),
                 COL_OUTPUT => (
-                    format!("{:>18}", format_number(summary.total_output_tokens)),
+                    format!("{:>18}", format_tokens(summary.total_output_tokens)),
                     Style::default().fg(self.theme.text()),
                 ),
                 COL_CACHE => (
-                    format!("{:>18}", format_number(cache_tokens)),
+                    format!("{:>18}", format_tokens(cache_tokens)),
                     Style::default().fg(self.theme.text()),
                 ),
                 COL_TOTAL => (
-                    format!("{:>18}", format_number(total_tokens)),
+                    format!("{:>18}", format_tokens(total_tokens)),
                     Style::default().fg(self.theme.text()),
                 ),
                 COL_COST => {
-                    let cost_color = if self.view_mode == DailyViewMode::Daily {
-                        self.theme
-                            .spike_color(spike_level(summary.total_cost_usd, self.avg_cost))
-                    } else {
-                        self.theme.text()
+                    // Spike colors only make sense for single days
+                    let cost_color = match running_total {
+                        None if self.view_mode == DailyViewMode::Daily => self
+                            .theme
+                            .spike_color(spike_level(summary.total_cost_usd, self.avg_cost)),
+                        _ => self.theme.text(),
                     };
+                    let cost = running_total.unwrap_or(summary.total_cost_usd);
                     (
-                        format!("{:>12}", format!("${:.2}", summary.total_cost_usd)),
+                        format!("{:>12}", format!("${:.2}", cost)),
                         Style::default().fg(cost_color),
                     )
                 }
                 COL_USAGE => (
-                    format!("{:>18}", sparkline),
+                    format!(
+                        "{:>width$}",
+                        sparkline,
+                        width = column_width(COL_USAGE, self.sparkline_width) as usize
+                    ),
                     Style::default().fg(self.theme.bar()),
                 ),
+                COL_NOTES => {
+                    let note = self
+                        .data
+                        .notes
+                        .get(&summary.date)
+                        .map(String::as_str)
+                        .unwrap_or("");
+                    // 2-space gap, then the note truncated to fit (UTF-8 safe)
+                    let max_len = COLUMNS[COL_NOTES].1 as usize - 2;
+                    let note = if note.chars().count() > max_len {
+                        format!("{}…", note.chars().take(max_len - 1).collect::<String>())
+                    } else {
+                        note.to_string()
+                    };
+                    (
+                        format!("  {:<width$}", note, width = max_len),
+                        Style::default().fg(self.theme.muted()),
+                    )
+                }
                 _ => unreachable!(),
             };
 
@@ -633,6 +1045,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: cost,
             models: HashMap::new(),
+            cost_estimated: false,
         }
     }
 
@@ -674,13 +1087,45 @@ mod tests {
         assert_eq!(data.daily_max_tokens, 495);
     }
 
+    #[test]
+    fn test_weekly_scale_window_ignores_old_outlier() {
+        let summaries = vec![
+            make_daily_summary(2023, 3, 8, 1_000_000, 0, 0, 0, 50.0), // outlier, ~3 years back
+            make_daily_summary(2026, 1, 7, 1000, 0, 0, 0, 0.01),
+            make_daily_summary(2026, 1, 14, 4000, 0, 0, 0, 0.04),
+            make_daily_summary(2026, 1, 21, 2000, 0, 0, 0, 0.02),
+        ];
+        let data = DailyData::from_daily_summaries(summaries);
+
+        // All history: the outlier sets the scale and recent weeks flatten
+        assert_eq!(data.scale_max(DailyViewMode::Weekly, None), 1_000_000);
+        let flat = format_sparkline(4000, data.scale_max(DailyViewMode::Weekly, None), 8);
+        assert_eq!(flat, "░░░░░░░░");
+
+        // Trailing 26 weeks: the busiest recent week fills the bar
+        let recent = data.scale_max(DailyViewMode::Weekly, Some(26));
+        assert_eq!(recent, 4000);
+        assert_eq!(format_sparkline(2000, recent, 8), "▓▓▓▓░░░░");
+
+        // Daily and monthly scales are unaffected by the weekly window
+        assert_eq!(data.scale_max(DailyViewMode::Daily, Some(26)), 1_000_000);
+        assert_eq!(data.scale_max(DailyViewMode::Monthly, Some(26)), 1_000_000);
+    }
+
     // ========== DailyView scroll tests ==========
 
+    #[test]
+    fn test_compute_visible_rows() {
+        assert_eq!(compute_visible_rows(23), 15);
+        assert_eq!(compute_visible_rows(60), 52);
+        assert_eq!(compute_visible_rows(5), 0);
+    }
+
     #[test]
     fn test_daily_view_scroll_bounds_empty() {
         let data = DailyData::from_daily_summaries(vec![]);
         assert_eq!(
-            DailyView::max_scroll_offset(&data, DailyViewMode::Daily, VISIBLE_ROWS),
+            DailyView::max_scroll_offset(&data, DailyViewMode::Daily, compute_visible_rows(23)),
             0
         );
     }
@@ -692,9 +1137,9 @@ mod tests {
             make_daily_summary(2024, 1, 15, 200, 100, 20, 10, 0.02),
         ];
         let data = DailyData::from_daily_summaries(summaries);
-        // 2 items < VISIBLE_ROWS (15), so max offset is 0
+        // 2 items < 15 visible rows, so max offset is 0
         assert_eq!(
-            DailyView::max_scroll_offset(&data, DailyViewMode::Daily, VISIBLE_ROWS),
+            DailyView::max_scroll_offset(&data, DailyViewMode::Daily, compute_visible_rows(23)),
             0
         );
     }
@@ -705,13 +1150,59 @@ mod tests {
             .map(|d| make_daily_summary(2024, 1, d, 100, 50, 10, 5, 0.01))
             .collect();
         let data = DailyData::from_daily_summaries(summaries);
-        // 20 items, VISIBLE_ROWS = 15, so max offset = 5
+        // 20 items in 15 rows, one of them the sticky month header
         assert_eq!(
-            DailyView::max_scroll_offset(&data, DailyViewMode::Daily, VISIBLE_ROWS),
-            5
+            DailyView::max_scroll_offset(&data, DailyViewMode::Daily, compute_visible_rows(23)),
+            6
         );
     }
 
+    #[test]
+    fn test_display_rows_month_headers() {
+        let summaries: Vec<DailySummary> = [(1, 30), (1, 31), (2, 1), (2, 2)]
+            .into_iter()
+            .map(|(m, d)| make_daily_summary(2025, m, d, 100, 50, 10, 5, 0.01))
+            .collect();
+        let data = DailyData::from_daily_summaries(summaries);
+        let header = |s: &str| DailyViewRow::Header(s.to_string());
+
+        assert_eq!(
+            data.display_rows(DailyViewMode::Daily, 0, 5),
+            vec![
+                header("── January 2025 ──"),
+                DailyViewRow::Data(0),
+                DailyViewRow::Data(1),
+                header("── February 2025 ──"),
+                DailyViewRow::Data(2),
+            ]
+        );
+        // Starting mid-month keeps the header on top; a header never ends the page
+        assert_eq!(
+            data.display_rows(DailyViewMode::Daily, 1, 3),
+            vec![header("── January 2025 ──"), DailyViewRow::Data(1)]
+        );
+        assert_eq!(data.data_rows_in(DailyViewMode::Daily, 1, 3), 1);
+        // Only the daily mode is grouped
+        assert_eq!(data.data_rows_in(DailyViewMode::Weekly, 0, 3), 2);
+    }
+
+    #[test]
+    fn test_daily_view_renders_month_header() {
+        let data = DailyData::from_daily_summaries(vec![make_daily_summary(
+            2025, 1, 31, 100, 50, 10, 5, 0.01,
+        )]);
+        let view = DailyView::new(&data, 0, DailyViewMode::Daily, Theme::Dark, 0.01)
+            .with_month_headers(true);
+        let visible = view.visible_columns(120);
+        let area = Rect::new(0, 0, 120, 2);
+        let mut buf = Buffer::empty(area);
+        view.render_daily_rows(area, &mut buf, &visible);
+
+        let line = |y| -> String { (0..area.width).map(|x| buf[(x, y)].symbol()).collect() };
+        assert!(line(0).contains("── January 2025 ──"));
+        assert!(line(1).contains("2025-01-31"));
+    }
+
     // ========== DailyData multi-mode tests ==========
 
     #[test]
@@ -746,6 +1237,17 @@ mod tests {
 
         let (monthly, _) = data.for_mode(DailyViewMode::Monthly);
         assert_eq!(monthly.len(), 2); // Jan and Feb
+
+        let (quarterly, _) = data.for_mode(DailyViewMode::Quarterly);
+        assert_eq!(quarterly.len(), 1);
+    }
+
+    #[test]
+    fn test_quarter_label() {
+        let date = |m| NaiveDate::from_ymd_opt(2025, m, 1).unwrap();
+        assert_eq!(quarter_label(date(1)), "2025-Q1");
+        assert_eq!(quarter_label(date(6)), "2025-Q2");
+        assert_eq!(quarter_label(date(10)), "2025-Q4");
     }
 
     #[test]
@@ -753,6 +1255,7 @@ mod tests {
         assert_eq!(DailyViewMode::Daily.label(), "Daily");
         assert_eq!(DailyViewMode::Weekly.label(), "Weekly");
         assert_eq!(DailyViewMode::Monthly.label(), "Monthly");
+        assert_eq!(DailyViewMode::Quarterly.label(), "Quarterly");
     }
 
     #[test]
@@ -760,6 +1263,7 @@ mod tests {
         assert_eq!(DailyViewMode::Daily.date_column_label(), "Date");
         assert_eq!(DailyViewMode::Weekly.date_column_label(), "Week");
         assert_eq!(DailyViewMode::Monthly.date_column_label(), "Month");
+        assert_eq!(DailyViewMode::Quarterly.date_column_label(), "Quarter");
     }
 
     // ========== Responsive column tests ==========
@@ -769,7 +1273,7 @@ mod tests {
     #[test]
     fn test_visible_columns_full_width() {
         // >= 141: all 8 columns visible
-        let cols = visible_columns(141);
+        let cols = visible_columns(141, DEFAULT_SPARKLINE_WIDTH);
         assert_eq!(cols.len(), 8);
         assert_eq!(cols, vec![0, 1, 2, 3, 4, 5, 6, 7]);
     }
@@ -777,7 +1281,7 @@ mod tests {
     #[test]
     fn test_visible_columns_hide_input() {
         // 123..140: 7 columns (Input hidden first)
-        let cols = visible_columns(123);
+        let cols = visible_columns(123, DEFAULT_SPARKLINE_WIDTH);
         assert_eq!(cols.len(), 7);
         assert!(!cols.contains(&COL_INPUT));
         assert!(cols.contains(&COL_USAGE)); // Usage still visible
@@ -786,7 +1290,7 @@ mod tests {
     #[test]
     fn test_visible_columns_hide_input_and_output() {
         // 105..122: 6 columns (Input + Output hidden)
-        let cols = visible_columns(105);
+        let cols = visible_columns(105, DEFAULT_SPARKLINE_WIDTH);
         assert_eq!(cols.len(), 6);
         assert!(!cols.contains(&COL_INPUT));
         assert!(!cols.contains(&COL_OUTPUT));
@@ -796,7 +1300,7 @@ mod tests {
     #[test]
     fn test_visible_columns_hide_three() {
         // 87..104: 5 columns (Input + Output + Cache hidden)
-        let cols = visible_columns(87);
+        let cols = visible_columns(87, DEFAULT_SPARKLINE_WIDTH);
         assert_eq!(cols.len(), 5);
         assert!(!cols.contains(&COL_INPUT));
         assert!(!cols.contains(&COL_OUTPUT));
@@ -804,10 +1308,19 @@ mod tests {
         assert!(cols.contains(&COL_USAGE)); // Usage still visible
     }
 
+    #[test]
+    fn test_token_formatter_compact_when_narrow() {
+        let full = visible_columns(200, DEFAULT_SPARKLINE_WIDTH);
+        assert_eq!(token_formatter(&full)(1_234_567), "1,234,567");
+        let narrow = visible_columns(MIN_TABLE_WIDTH, DEFAULT_SPARKLINE_WIDTH);
+        assert!(narrow.len() < COMPACT_BELOW_COLUMNS);
+        assert_eq!(token_formatter(&narrow)(1_234_567), "1.2M");
+    }
+
     #[test]
     fn test_visible_columns_minimum() {
         // < 87: 4 columns (Date + Model + Total + Cost)
-        let cols = visible_columns(69);
+        let cols = visible_columns(69, DEFAULT_SPARKLINE_WIDTH);
         assert_eq!(cols.len(), 4);
         assert_eq!(cols, vec![COL_DATE, COL_MODEL, COL_TOTAL, COL_COST]);
     }
@@ -815,19 +1328,240 @@ mod tests {
     #[test]
     fn test_table_width_for_all_columns() {
         let all: Vec<usize> = (0..8).collect();
-        assert_eq!(table_width_for(&all), 141);
+        assert_eq!(table_width_for(&all, DEFAULT_SPARKLINE_WIDTH), 141);
     }
 
     #[test]
     fn test_table_width_for_minimum_columns() {
         let min = vec![COL_DATE, COL_MODEL, COL_TOTAL, COL_COST];
-        assert_eq!(table_width_for(&min), 69);
+        assert_eq!(table_width_for(&min, DEFAULT_SPARKLINE_WIDTH), 69);
     }
 
     #[test]
     fn test_visible_columns_wide_terminal() {
         // Very wide terminal should still show all 8
-        let cols = visible_columns(200);
+        let cols = visible_columns(200, DEFAULT_SPARKLINE_WIDTH);
         assert_eq!(cols.len(), 8);
     }
+
+    // ========== sparkline width tests ==========
+
+    #[test]
+    fn test_default_sparkline_width_fits_usage_column() {
+        assert_eq!(DEFAULT_SPARKLINE_WIDTH, 14);
+        assert_eq!(column_width(COL_USAGE, DEFAULT_SPARKLINE_WIDTH), 18);
+    }
+
+    #[test]
+    fn test_custom_sparkline_width_widens_usage_column() {
+        assert_eq!(column_width(COL_USAGE, 30), 34);
+        let all: Vec<usize> = (0..8).collect();
+        assert_eq!(table_width_for(&all, 30), 157);
+        // Wider bar needs more room before all columns fit
+        assert_eq!(visible_columns(141, 30).len(), 7);
+    }
+
+    #[test]
+    fn test_custom_sparkline_width_renders_longer_bar() {
+        let data = DailyData::from_daily_summaries(vec![make_daily_summary(
+            2025, 1, 10, 1000, 500, 0, 0, 0.10,
+        )]);
+        let view = DailyView::new(&data, 0, DailyViewMode::Daily, Theme::Dark, 0.10)
+            .with_sparkline_width(30);
+        let visible = view.visible_columns(200);
+        let area = Rect::new(0, 0, 200, 1);
+        let mut buf = Buffer::empty(area);
+        view.render_daily_rows(area, &mut buf, &visible);
+
+        let row: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        // Single day is the max → fully filled bar of the custom width
+        assert!(row.contains(&"▓".repeat(30)));
+        assert!(!row.contains(&"▓".repeat(31)));
+    }
+
+    fn render_total(view: &DailyView, visible_rows: usize) -> String {
+        let visible = view.visible_columns(200);
+        let area = Rect::new(0, 0, 200, 1);
+        let mut buf = Buffer::empty(area);
+        view.render_total_row(area, &mut buf, &visible, visible_rows);
+        (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_total_row_sums_visible_window() {
+        let data = DailyData::from_daily_summaries(vec![
+            make_daily_summary(2025, 1, 10, 1000, 500, 0, 0, 0.10),
+            make_daily_summary(2025, 1, 11, 2000, 500, 0, 0, 0.20),
+            make_daily_summary(2025, 1, 12, 4000, 500, 0, 0, 0.40),
+        ]);
+
+        // Everything fits: true total, no scroll hint
+        let all = render_total(
+            &DailyView::new(&data, 0, DailyViewMode::Daily, Theme::Dark, 0.10),
+            5,
+        );
+        assert!(all.contains("── Total ──"));
+        assert!(all.contains("7,000"));
+        assert!(all.contains("$0.70"));
+        assert!(!all.contains("scroll for more"));
+
+        // Scrolled window of two rows: last two days only
+        let window = render_total(
+            &DailyView::new(&data, 1, DailyViewMode::Daily, Theme::Dark, 0.10),
+            2,
+        );
+        assert!(window.contains("6,000"));
+        assert!(window.contains("$0.60"));
+        assert!(window.contains("↑↓ scroll for more"));
+    }
+
+    #[test]
+    fn test_filter_keeps_range_and_reaggregates() {
+        let data = DailyData::from_daily_summaries(vec![
+            make_daily_summary(2025, 1, 30, 1000, 0, 0, 0, 0.10),
+            make_daily_summary(2025, 1, 31, 2000, 0, 0, 0, 0.20),
+            make_daily_summary(2025, 2, 1, 4000, 0, 0, 0, 0.40),
+            make_daily_summary(2025, 2, 2, 8000, 0, 0, 0, 0.80),
+        ]);
+        let day = |d| NaiveDate::from_ymd_opt(2025, if d > 20 { 1 } else { 2 }, d).unwrap();
+
+        let filtered = data.filter(day(31), day(1));
+        let dates: Vec<_> = filtered.daily_summaries.iter().map(|s| s.date).collect();
+        assert_eq!(dates, vec![day(31), day(1)]);
+        assert_eq!(filtered.daily_max_tokens, 4000);
+        // Both months now hold a single day each
+        assert_eq!(filtered.monthly_summaries.len(), 2);
+        assert_eq!(filtered.monthly_summaries[0].total_input_tokens, 2000);
+        assert_eq!(filtered.monthly_max_tokens, 4000);
+        let weekly_total: u64 = filtered
+            .weekly_summaries
+            .iter()
+            .map(|s| s.total_input_tokens)
+            .sum();
+        assert_eq!(weekly_total, 6000);
+        assert_eq!(filtered.week_start, data.week_start);
+
+        assert!(data.filter(day(3), day(4)).daily_summaries.is_empty());
+    }
+
+    // ========== notes column tests ==========
+
+    fn notes_data() -> DailyData {
+        let mut notes = HashMap::new();
+        notes.insert(
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            "prod incident".to_string(),
+        );
+        DailyData::from_daily_summaries(vec![
+            make_daily_summary(2025, 1, 10, 1000, 500, 0, 0, 0.10),
+            make_daily_summary(2025, 1, 11, 1000, 500, 0, 0, 0.10),
+        ])
+        .with_notes(notes)
+    }
+
+    #[test]
+    fn test_notes_column_only_with_notes_in_daily_mode() {
+        let with_notes = notes_data();
+        let view = DailyView::new(&with_notes, 0, DailyViewMode::Daily, Theme::Dark, 0.1);
+        assert!(view.visible_columns(170).contains(&COL_NOTES));
+        // Not enough room next to the full table
+        assert!(!view.visible_columns(141).contains(&COL_NOTES));
+
+        let weekly = DailyView::new(&with_notes, 0, DailyViewMode::Weekly, Theme::Dark, 0.1);
+        assert!(!weekly.visible_columns(170).contains(&COL_NOTES));
+
+        let without = DailyData::from_daily_summaries(vec![make_daily_summary(
+            2025, 1, 10, 1000, 500, 0, 0, 0.10,
+        )]);
+        let view = DailyView::new(&without, 0, DailyViewMode::Daily, Theme::Dark, 0.1);
+        assert!(!view.visible_columns(170).contains(&COL_NOTES));
+    }
+
+    #[test]
+    fn test_notes_column_renders_note_for_matching_day() {
+        let data = notes_data();
+        let view = DailyView::new(&data, 0, DailyViewMode::Daily, Theme::Dark, 0.1);
+        let visible = view.visible_columns(170);
+        let area = Rect::new(0, 0, 170, 2);
+        let mut buf = Buffer::empty(area);
+        view.render_daily_rows(area, &mut buf, &visible);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert!(row(0).contains("prod incident"));
+        assert!(!row(1).contains("prod incident"));
+    }
+
+    // ========== DisplayMetric tests ==========
+
+    #[test]
+    fn test_display_metric_toggle() {
+        assert_eq!(DisplayMetric::default(), DisplayMetric::Tokens);
+        assert_eq!(DisplayMetric::Tokens.toggle(), DisplayMetric::Cost);
+        assert_eq!(DisplayMetric::Cost.toggle(), DisplayMetric::Tokens);
+    }
+
+    #[test]
+    fn test_sparkline_follows_display_metric() {
+        // Day 1 has the most tokens, day 2 the highest cost
+        let data = DailyData::from_daily_summaries(vec![
+            make_daily_summary(2025, 1, 1, 8000, 2000, 0, 0, 0.10),
+            make_daily_summary(2025, 1, 2, 1000, 0, 0, 0, 2.00),
+        ]);
+        let full = "▓".repeat(DEFAULT_SPARKLINE_WIDTH);
+        let rows = |metric: DisplayMetric| -> Vec<String> {
+            let view = DailyView::new(&data, 0, DailyViewMode::Daily, Theme::Dark, 1.0)
+                .with_metric(metric);
+            let visible = view.visible_columns(200);
+            let area = Rect::new(0, 0, 200, 2);
+            let mut buf = Buffer::empty(area);
+            view.render_daily_rows(area, &mut buf, &visible);
+            (0..2)
+                .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+                .collect()
+        };
+
+        let tokens = rows(DisplayMetric::Tokens);
+        assert!(tokens[0].contains(&full));
+        assert!(!tokens[1].contains(&full));
+
+        let cost = rows(DisplayMetric::Cost);
+        assert!(!cost[0].contains(&full));
+        assert!(cost[1].contains(&full));
+    }
+
+    #[test]
+    fn test_cumulative_cost_column() {
+        let data = DailyData::from_daily_summaries(vec![
+            make_daily_summary(2025, 1, 1, 1000, 0, 0, 0, 0.50),
+            make_daily_summary(2025, 1, 2, 1000, 0, 0, 0, 1.25),
+            make_daily_summary(2025, 1, 3, 1000, 0, 0, 0, 2.00),
+        ]);
+        // Scrolled past the first day: the running total still includes it
+        let view =
+            DailyView::new(&data, 1, DailyViewMode::Daily, Theme::Dark, 1.0).with_cumulative(true);
+        let visible = view.visible_columns(200);
+        let area = Rect::new(0, 0, 200, 2);
+        let mut buf = Buffer::empty(area);
+        view.render_header(Rect::new(0, 0, 200, 1), &mut buf, &visible);
+        let header: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(header.contains("Cumulative"));
+
+        let mut buf = Buffer::empty(area);
+        view.render_daily_rows(area, &mut buf, &visible);
+        let row = |y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        };
+        assert!(row(0).contains("$1.75"));
+        assert!(row(1).contains("$3.75"));
+    }
 }
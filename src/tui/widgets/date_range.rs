@@ -0,0 +1,273 @@
+//! Date range selector popup for filtering the source detail table
+
+use chrono::NaiveDate;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::tui::theme::Theme;
+
+/// Width and height of the date range popup
+const POPUP_WIDTH: u16 = 40;
+const POPUP_HEIGHT: u16 = 10;
+
+/// Length of a `YYYY-MM-DD` date
+const DATE_LEN: usize = 10;
+
+/// Date input being edited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateField {
+    #[default]
+    From,
+    To,
+}
+
+/// Text input state for the date range selector
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DateRangeInput {
+    pub from: String,
+    pub to: String,
+    pub field: DateField,
+    /// Why the last Enter was rejected
+    pub error: Option<String>,
+}
+
+impl DateRangeInput {
+    /// Input prefilled with the current filter, if any
+    pub fn new(current: Option<(NaiveDate, NaiveDate)>) -> Self {
+        let format = |d: NaiveDate| d.format("%Y-%m-%d").to_string();
+        Self {
+            from: current.map(|(from, _)| format(from)).unwrap_or_default(),
+            to: current.map(|(_, to)| format(to)).unwrap_or_default(),
+            ..Self::default()
+        }
+    }
+
+    fn active_mut(&mut self) -> &mut String {
+        match self.field {
+            DateField::From => &mut self.from,
+            DateField::To => &mut self.to,
+        }
+    }
+
+    /// Type a character into the active field (digits and `-` only)
+    pub fn push(&mut self, c: char) {
+        let field = self.active_mut();
+        if (c.is_ascii_digit() || c == '-') && field.len() < DATE_LEN {
+            field.push(c);
+        }
+        self.error = None;
+    }
+
+    /// Delete the last character of the active field
+    pub fn pop(&mut self) {
+        self.active_mut().pop();
+        self.error = None;
+    }
+
+    /// Switch between the from and to fields
+    pub fn toggle_field(&mut self) {
+        self.field = match self.field {
+            DateField::From => DateField::To,
+            DateField::To => DateField::From,
+        };
+    }
+
+    /// The entered range; `None` when both fields are empty (clears the filter)
+    pub fn parse(&self) -> Result<Option<(NaiveDate, NaiveDate)>, String> {
+        if self.from.is_empty() && self.to.is_empty() {
+            return Ok(None);
+        }
+        let date = |s: &str| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|_| format!("'{}' is not a YYYY-MM-DD date", s))
+        };
+        let (from, to) = (date(&self.from)?, date(&self.to)?);
+        if from > to {
+            return Err("From must not be after To".to_string());
+        }
+        Ok(Some((from, to)))
+    }
+}
+
+/// Date range selector popup overlay
+pub struct DateRangeSelector<'a> {
+    input: &'a DateRangeInput,
+    theme: Theme,
+}
+
+impl<'a> DateRangeSelector<'a> {
+    pub fn new(input: &'a DateRangeInput, theme: Theme) -> Self {
+        Self { input, theme }
+    }
+
+    /// Calculate centered popup area
+    pub fn centered_area(area: Rect) -> Rect {
+        let x = area.x + (area.width.saturating_sub(POPUP_WIDTH)) / 2;
+        let y = area.y + (area.height.saturating_sub(POPUP_HEIGHT)) / 2;
+        Rect {
+            x,
+            y,
+            width: POPUP_WIDTH.min(area.width),
+            height: POPUP_HEIGHT.min(area.height),
+        }
+    }
+
+    fn field_line(&self, label: &'static str, value: &str, active: bool) -> Line<'static> {
+        let (marker, value_style) = if active {
+            (
+                "▸ ",
+                Style::default()
+                    .fg(self.theme.accent())
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            ("  ", Style::default().fg(self.theme.text()))
+        };
+        let cursor = if active { "_" } else { "" };
+        Line::from(vec![
+            Span::styled(marker, value_style),
+            Span::styled(label, Style::default().fg(self.theme.muted())),
+            Span::styled(
+                format!(
+                    "{:<width$}",
+                    format!("{}{}", value, cursor),
+                    width = DATE_LEN + 1
+                ),
+                value_style,
+            ),
+        ])
+    }
+}
+
+impl Widget for DateRangeSelector<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Date range ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.date()));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // [0] Padding
+            Constraint::Length(1), // [1] From
+            Constraint::Length(1), // [2] To
+            Constraint::Length(1), // [3] Error
+            Constraint::Length(1), // [4] Hint line 1
+            Constraint::Length(1), // [5] Hint line 2
+        ])
+        .split(inner);
+
+        let field = self.input.field;
+        Paragraph::new(self.field_line("From  ", &self.input.from, field == DateField::From))
+            .alignment(Alignment::Center)
+            .render(chunks[1], buf);
+        Paragraph::new(self.field_line("To    ", &self.input.to, field == DateField::To))
+            .alignment(Alignment::Center)
+            .render(chunks[2], buf);
+
+        if let Some(error) = &self.input.error {
+            Paragraph::new(Span::styled(
+                error.as_str(),
+                Style::default().fg(self.theme.error()),
+            ))
+            .alignment(Alignment::Center)
+            .render(chunks[3], buf);
+        }
+
+        let hint_line1 = Line::from(vec![
+            Span::styled("Tab", Style::default().fg(self.theme.accent())),
+            Span::styled("  Switch field", Style::default().fg(self.theme.muted())),
+        ]);
+        Paragraph::new(hint_line1)
+            .alignment(Alignment::Center)
+            .render(chunks[4], buf);
+
+        let hint_line2 = Line::from(vec![
+            Span::styled("Enter", Style::default().fg(self.theme.accent())),
+            Span::styled(
+                "  Apply (empty clears)",
+                Style::default().fg(self.theme.muted()),
+            ),
+        ]);
+        Paragraph::new(hint_line2)
+            .alignment(Alignment::Center)
+            .render(chunks[5], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn typed(from: &str, to: &str) -> DateRangeInput {
+        let mut input = DateRangeInput::new(None);
+        from.chars().for_each(|c| input.push(c));
+        input.toggle_field();
+        to.chars().for_each(|c| input.push(c));
+        input
+    }
+
+    #[test]
+    fn test_parse_valid_range() {
+        let input = typed("2026-01-05", "2026-01-31");
+        assert_eq!(
+            input.parse(),
+            Ok(Some((date(2026, 1, 5), date(2026, 1, 31))))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_or_reversed_dates() {
+        assert!(typed("2026-13-01", "2026-01-31").parse().is_err());
+        assert!(typed("2026-02-01", "2026-01-31").parse().is_err());
+        assert!(typed("2026-01-01", "").parse().is_err());
+    }
+
+    #[test]
+    fn test_empty_input_clears_filter() {
+        assert_eq!(DateRangeInput::new(None).parse(), Ok(None));
+    }
+
+    #[test]
+    fn test_input_ignores_letters_and_caps_length() {
+        let input = typed("2026a-01-05xx9", "");
+        assert_eq!(input.from, "2026-01-05");
+    }
+
+    #[test]
+    fn test_new_prefills_current_filter() {
+        let input = DateRangeInput::new(Some((date(2026, 1, 5), date(2026, 1, 31))));
+        assert_eq!(input.from, "2026-01-05");
+        assert_eq!(input.to, "2026-01-31");
+        assert_eq!(input.field, DateField::From);
+    }
+
+    #[test]
+    fn test_renders_fields_and_error() {
+        let area = Rect::new(0, 0, 60, 20);
+        let mut input = typed("2026-02-01", "2026-01-31");
+        input.error = input.parse().err();
+        let mut buf = Buffer::empty(area);
+        DateRangeSelector::new(&input, Theme::Dark)
+            .render(DateRangeSelector::centered_area(area), &mut buf);
+
+        let content: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("Date range"));
+        assert!(content.contains("2026-02-01"));
+        assert!(content.contains("From must not be after To"));
+    }
+}
@@ -15,7 +15,7 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Width and height of the help popup
 const POPUP_WIDTH: u16 = 42;
-const POPUP_HEIGHT: u16 = 21;
+const POPUP_HEIGHT: u16 = 25;
 
 /// Help popup widget showing keyboard shortcuts
 pub struct HelpPopup {
@@ -70,18 +70,22 @@ impl Widget for HelpPopup {
             Constraint::Length(1), // [3] Tab/Shift+Tab
             Constraint::Length(1), // [4] 1-4
             Constraint::Length(1), // [5] Up/Down
-            Constraint::Length(1), // [6] Enter
-            Constraint::Length(1), // [7] Esc
-            Constraint::Length(1), // [8] d/w/m
-            Constraint::Length(1), // [9] Enter (detail)
-            Constraint::Length(1), // [10] Enter (sessions)
-            Constraint::Length(1), // [11] Padding
-            Constraint::Length(1), // [12] General header
-            Constraint::Length(1), // [13] Separator
-            Constraint::Length(1), // [14] Ctrl+C
-            Constraint::Length(1), // [15] ?
-            Constraint::Length(1), // [16] Padding
-            Constraint::Length(1), // [17] Close hint
+            Constraint::Length(1), // [6] PgUp/PgDn
+            Constraint::Length(1), // [7] Home/End
+            Constraint::Length(1), // [8] Enter
+            Constraint::Length(1), // [9] Esc
+            Constraint::Length(1), // [10] d/w/m
+            Constraint::Length(1), // [11] Enter (detail)
+            Constraint::Length(1), // [12] f (detail)
+            Constraint::Length(1), // [13] c (detail)
+            Constraint::Length(1), // [14] Enter (sessions)
+            Constraint::Length(1), // [15] Padding
+            Constraint::Length(1), // [16] General header
+            Constraint::Length(1), // [17] Separator
+            Constraint::Length(1), // [18] Ctrl+C
+            Constraint::Length(1), // [19] ?
+            Constraint::Length(1), // [20] Padding
+            Constraint::Length(1), // [21] Close hint
             Constraint::Min(0),    // Remaining
         ])
         .split(inner);
@@ -110,24 +114,40 @@ impl Widget for HelpPopup {
         render_keybinding(chunks[3], buf, "Tab / Shift+Tab", "Switch view", self.theme);
         render_keybinding(chunks[4], buf, "1 / 2 / 3 / 4", "Jump to tab", self.theme);
         render_keybinding(chunks[5], buf, "Up/Down or j/k", "Navigate", self.theme);
-        render_keybinding(chunks[6], buf, "Enter", "View source details", self.theme);
-        render_keybinding(chunks[7], buf, "Esc", "Back to dashboard", self.theme);
+        render_keybinding(chunks[6], buf, "PgUp / PgDn", "Page up/down", self.theme);
         render_keybinding(
-            chunks[8],
+            chunks[7],
             buf,
-            "d / w / m",
-            "Daily/Weekly/Monthly",
+            "Home/End or g/G",
+            "First/last row",
             self.theme,
         );
+        render_keybinding(chunks[8], buf, "Enter", "View source details", self.theme);
+        render_keybinding(chunks[9], buf, "Esc", "Back to dashboard", self.theme);
         render_keybinding(
-            chunks[9],
+            chunks[10],
+            buf,
+            "d / w / m / q",
+            "Daily/Weekly/Monthly/Quarterly",
+            self.theme,
+        );
+        render_keybinding(
+            chunks[11],
             buf,
             "Enter (detail)",
             "Model breakdown",
             self.theme,
         );
         render_keybinding(
-            chunks[10],
+            chunks[12],
+            buf,
+            "f (detail)",
+            "Filter by date range",
+            self.theme,
+        );
+        render_keybinding(chunks[13], buf, "c (detail)", "Cumulative cost", self.theme);
+        render_keybinding(
+            chunks[14],
             buf,
             "Enter (sessions)",
             "Session details",
@@ -143,18 +163,18 @@ impl Widget for HelpPopup {
         )]);
         Paragraph::new(gen_header)
             .alignment(Alignment::Left)
-            .render(chunks[12], buf);
+            .render(chunks[16], buf);
 
         // Separator
         buf.set_string(
-            chunks[13].x,
-            chunks[13].y,
+            chunks[17].x,
+            chunks[17].y,
             &sep,
             Style::default().fg(self.theme.muted()),
         );
 
-        render_keybinding(chunks[14], buf, "Ctrl+C", "Quit", self.theme);
-        render_keybinding(chunks[15], buf, "?", "Toggle help", self.theme);
+        render_keybinding(chunks[18], buf, "Ctrl+C", "Quit", self.theme);
+        render_keybinding(chunks[19], buf, "?", "Toggle help", self.theme);
 
         // Close hint
         let hint = Line::from(vec![Span::styled(
@@ -163,7 +183,7 @@ impl Widget for HelpPopup {
         )]);
         Paragraph::new(hint)
             .alignment(Alignment::Center)
-            .render(chunks[17], buf);
+            .render(chunks[21], buf);
     }
 }
 
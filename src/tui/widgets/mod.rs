@@ -1,6 +1,7 @@
 //! TUI widgets
 
 pub mod daily;
+pub mod date_range;
 pub mod heatmap;
 pub mod help;
 pub mod legend;
@@ -13,5 +14,7 @@ pub mod sessions;
 pub mod source_detail;
 pub mod spinner;
 pub mod stats;
+pub mod status_bar;
 pub mod tabs;
 pub mod update_popup;
+pub mod utils;
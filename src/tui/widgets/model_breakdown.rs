@@ -17,7 +17,17 @@ use super::overview::format_number;
 /// Width and height of the model breakdown popup
 const POPUP_WIDTH: u16 = 54;
 const POPUP_MIN_HEIGHT: u16 = 10;
-const POPUP_MAX_HEIGHT: u16 = 21;
+const POPUP_MAX_HEIGHT: u16 = 20;
+
+/// Rows around the model list: border (2) + padding (1 top) + header (1) + sep (1)
+/// + padding (1) + footer (1)
+const POPUP_CHROME_HEIGHT: u16 = 7;
+
+/// Model rows that fit in the tallest popup; also the PageUp/PageDown step
+pub const MAX_VISIBLE_MODELS: usize = (POPUP_MAX_HEIGHT - POPUP_CHROME_HEIGHT) as usize;
+
+/// Default decimal places for popup costs
+pub const DEFAULT_COST_PRECISION: usize = 2;
 
 /// State for model breakdown popup
 #[derive(Debug, Clone)]
@@ -26,6 +36,8 @@ pub struct ModelBreakdownState {
     pub date_label: String,
     /// Models sorted by cost descending
     pub models: Vec<(String, ModelUsage)>,
+    /// Index of the first model row shown
+    pub scroll: usize,
 }
 
 impl ModelBreakdownState {
@@ -47,7 +59,27 @@ impl ModelBreakdownState {
                 .partial_cmp(&a.1.cost_usd)
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
-        Self { date_label, models }
+        Self {
+            date_label,
+            models,
+            scroll: 0,
+        }
+    }
+
+    /// Model rows the popup shows at most, for `ModelBreakdownPopup::centered_area`
+    pub fn visible_count(&self) -> usize {
+        self.models.len().min(MAX_VISIBLE_MODELS)
+    }
+
+    /// Scroll up by `rows`, stopping at the first model
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Scroll down by `rows`, stopping once the last model is visible
+    pub fn scroll_down(&mut self, rows: usize) {
+        let max_scroll = self.models.len().saturating_sub(MAX_VISIBLE_MODELS);
+        self.scroll = (self.scroll + rows).min(max_scroll);
     }
 }
 
@@ -55,17 +87,28 @@ impl ModelBreakdownState {
 pub struct ModelBreakdownPopup<'a> {
     state: &'a ModelBreakdownState,
     theme: Theme,
+    cost_precision: usize,
 }
 
 impl<'a> ModelBreakdownPopup<'a> {
     pub fn new(state: &'a ModelBreakdownState, theme: Theme) -> Self {
-        Self { state, theme }
+        Self {
+            state,
+            theme,
+            cost_precision: DEFAULT_COST_PRECISION,
+        }
     }
 
-    /// Calculate centered popup area with dynamic height based on model count
-    pub fn centered_area(area: Rect, model_count: usize) -> Rect {
-        // Height = border (2) + padding (1 top) + header (1) + sep (1) + models + padding (1) + footer (1)
-        let content_height = 7 + model_count as u16;
+    /// Show costs with `cost_precision` decimal places
+    pub fn with_cost_precision(mut self, cost_precision: usize) -> Self {
+        self.cost_precision = cost_precision;
+        self
+    }
+
+    /// Calculate centered popup area with dynamic height based on the number of
+    /// visible model rows (`ModelBreakdownState::visible_count`)
+    pub fn centered_area(area: Rect, visible_models: usize) -> Rect {
+        let content_height = POPUP_CHROME_HEIGHT + visible_models.min(MAX_VISIBLE_MODELS) as u16;
         let height = content_height.clamp(POPUP_MIN_HEIGHT, POPUP_MAX_HEIGHT);
 
         let x = area.x + (area.width.saturating_sub(POPUP_WIDTH)) / 2;
@@ -106,6 +149,11 @@ impl Widget for ModelBreakdownPopup<'_> {
         // Calculate visible rows (minus header, separator, padding, footer)
         let available_rows = padded.height.saturating_sub(4) as usize;
         let models_to_show = self.state.models.len().min(available_rows);
+        let scroll = self
+            .state
+            .scroll
+            .min(self.state.models.len() - models_to_show);
+        let hidden_below = self.state.models.len() - scroll - models_to_show;
 
         // Build layout
         let mut constraints = vec![
@@ -143,7 +191,14 @@ impl Widget for ModelBreakdownPopup<'_> {
         );
 
         // Model rows
-        for (i, (model_name, usage)) in self.state.models.iter().take(models_to_show).enumerate() {
+        for (i, (model_name, usage)) in self
+            .state
+            .models
+            .iter()
+            .skip(scroll)
+            .take(models_to_show)
+            .enumerate()
+        {
             let chunk_idx = i + 2;
             let display = display_name(model_name);
             let truncated = if display.chars().count() > 20 {
@@ -166,16 +221,40 @@ impl Widget for ModelBreakdownPopup<'_> {
                     format!("{:>12}", format_number(total_tokens)),
                     Style::default().fg(self.theme.text()),
                 ),
-                Span::styled(
-                    format!("{:>12}", format!("${:.2}", usage.cost_usd)),
-                    Style::default().fg(self.theme.cost()),
-                ),
+                match format_cost(usage.cost_usd, self.cost_precision) {
+                    Some(cost) => Span::styled(
+                        format!("{:>12}", cost),
+                        Style::default().fg(self.theme.cost()),
+                    ),
+                    // Nonzero but below the smallest visible unit
+                    None => Span::styled(
+                        format!(
+                            "{:>12}",
+                            format!(
+                                "<${:.*}",
+                                self.cost_precision,
+                                min_unit(self.cost_precision)
+                            )
+                        ),
+                        Style::default().fg(self.theme.muted()),
+                    ),
+                },
             ]);
             Paragraph::new(row)
                 .alignment(Alignment::Left)
                 .render(chunks[chunk_idx], buf);
         }
 
+        // Models past the bottom edge, in the padding row above the footer
+        if hidden_below > 0 {
+            Paragraph::new(Line::from(Span::styled(
+                format!("▼ {} more", hidden_below),
+                Style::default().fg(self.theme.muted()),
+            )))
+            .alignment(Alignment::Center)
+            .render(chunks[chunks.len() - 2], buf);
+        }
+
         // Footer hint
         let footer_idx = chunks.len() - 1;
         let footer = Line::from(Span::styled(
@@ -188,6 +267,19 @@ impl Widget for ModelBreakdownPopup<'_> {
     }
 }
 
+/// Cost with `precision` decimals, or `None` if a positive cost would round to zero
+fn format_cost(cost: f64, precision: usize) -> Option<String> {
+    if cost > 0.0 && cost < min_unit(precision) / 2.0 {
+        return None;
+    }
+    Some(format!("${:.*}", precision, cost))
+}
+
+/// Smallest cost shown at `precision` decimals (e.g. 0.01 for 2)
+fn min_unit(precision: usize) -> f64 {
+    10f64.powi(-(precision as i32))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,6 +296,66 @@ mod tests {
         }
     }
 
+    fn many_models(count: usize) -> ModelBreakdownState {
+        let models = (0..count)
+            .map(|i| {
+                (
+                    format!("model-{:02}", i),
+                    make_usage(100, 0, (count - i) as f64),
+                )
+            })
+            .collect();
+        ModelBreakdownState::new("2026-02-05".to_string(), models)
+    }
+
+    #[test]
+    fn test_scroll_clamps_at_top() {
+        let mut state = many_models(20);
+        state.scroll_up(1);
+        assert_eq!(state.scroll, 0);
+        state.scroll_down(3);
+        state.scroll_up(MAX_VISIBLE_MODELS);
+        assert_eq!(state.scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_clamps_at_bottom() {
+        let mut state = many_models(20);
+        state.scroll_down(MAX_VISIBLE_MODELS);
+        state.scroll_down(MAX_VISIBLE_MODELS);
+        assert_eq!(state.scroll, 20 - MAX_VISIBLE_MODELS);
+
+        // Everything fits: nothing to scroll
+        let mut short = many_models(3);
+        short.scroll_down(1);
+        assert_eq!(short.scroll, 0);
+    }
+
+    #[test]
+    fn test_popup_shows_more_indicator() {
+        let mut state = many_models(20);
+        assert_eq!(state.visible_count(), MAX_VISIBLE_MODELS);
+
+        let area = Rect::new(0, 0, 80, 40);
+        let popup_area = ModelBreakdownPopup::centered_area(area, state.visible_count());
+        assert_eq!(popup_area.height, POPUP_MAX_HEIGHT);
+
+        let render = |state: &ModelBreakdownState| {
+            let mut buf = Buffer::empty(area);
+            ModelBreakdownPopup::new(state, Theme::Dark).render(popup_area, &mut buf);
+            buf.content().iter().map(|c| c.symbol()).collect::<String>()
+        };
+        let content = render(&state);
+        assert!(content.contains("Model 00"));
+        assert!(content.contains(&format!("▼ {} more", 20 - MAX_VISIBLE_MODELS)));
+
+        state.scroll_down(MAX_VISIBLE_MODELS);
+        let content = render(&state);
+        assert!(!content.contains("Model 00"));
+        assert!(content.contains("Model 19"));
+        assert!(!content.contains("more"));
+    }
+
     #[test]
     fn test_state_sorts_by_cost_descending() {
         let models = vec![
@@ -298,8 +450,8 @@ mod tests {
         ModelBreakdownPopup::new(&state, Theme::Dark).render(popup_area, &mut buf);
 
         let content: String = buf.content().iter().map(|c| c.symbol()).collect();
-        // display_name converts claude-opus-4-5-20251101 to "Opus 4.5"
-        assert!(content.contains("Opus 4.5"));
+        // display_name converts claude-opus-4-5-20251101 to "Claude Opus 4.5"
+        assert!(content.contains("Claude Opus 4.5"));
     }
 
     #[test]
@@ -319,4 +471,45 @@ mod tests {
         // Should contain truncation marker
         assert!(content.contains('…'));
     }
+
+    #[test]
+    fn test_format_cost_below_precision() {
+        assert_eq!(format_cost(0.004, 2), None);
+        assert_eq!(format_cost(0.006, 2).as_deref(), Some("$0.01"));
+        assert_eq!(format_cost(0.0, 2).as_deref(), Some("$0.00"));
+        assert_eq!(format_cost(0.004, 4).as_deref(), Some("$0.0040"));
+        assert_eq!(format_cost(0.4, 0), None);
+    }
+
+    #[test]
+    fn test_popup_marks_sub_cent_model() {
+        let models = vec![
+            ("claude-sonnet-4-5".to_string(), make_usage(1000, 500, 1.50)),
+            ("claude-haiku-4-5".to_string(), make_usage(300, 20, 0.0004)),
+        ];
+        let state = ModelBreakdownState::new("2026-02-05".to_string(), models);
+
+        let area = Rect::new(0, 0, 80, 30);
+        let popup_area = ModelBreakdownPopup::centered_area(area, state.models.len());
+        let mut buf = Buffer::empty(area);
+        ModelBreakdownPopup::new(&state, Theme::Dark).render(popup_area, &mut buf);
+
+        let content: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("<$0.01"));
+        assert!(!content.contains("$0.00"));
+        let marker = buf
+            .content()
+            .iter()
+            .position(|c| c.symbol() == "<")
+            .unwrap();
+        assert_eq!(buf.content()[marker].fg, Theme::Dark.muted());
+
+        let mut buf = Buffer::empty(area);
+        ModelBreakdownPopup::new(&state, Theme::Dark)
+            .with_cost_precision(4)
+            .render(popup_area, &mut buf);
+        let content: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("$0.0004"));
+        assert!(content.contains("$1.5000"));
+    }
 }
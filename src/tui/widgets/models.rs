@@ -12,7 +12,7 @@ use ratatui::{
 
 use super::overview::format_number;
 use super::tabs::{Tab, TabBar};
-use crate::services::display_name;
+use crate::services::{display_name, provider_for_model};
 use crate::tui::theme::Theme;
 use crate::types::ModelUsage;
 
@@ -30,6 +30,34 @@ pub struct ModelSummary {
     pub name: String,
     pub total_tokens: u64,
     pub cost_usd: f64,
+    /// Input tokens per request
+    pub avg_input_tokens: u64,
+    /// Context window from pricing data, when known
+    pub context_window_tokens: Option<u64>,
+    /// Models collapsed into this row by `--other-threshold` (0 for a real model)
+    pub other_count: usize,
+}
+
+impl ModelSummary {
+    /// Whether this is the collapsed "other" row rather than a model
+    pub fn is_other(&self) -> bool {
+        self.other_count > 0
+    }
+
+    /// Name shown in the table
+    fn label(&self, display: impl Fn(&str) -> String) -> String {
+        if self.is_other() {
+            format!("Other ({} models)", self.other_count)
+        } else {
+            display(&self.name)
+        }
+    }
+
+    /// Average input as a share of the context window, in percent
+    pub fn context_percent(&self) -> Option<f64> {
+        let window = self.context_window_tokens.filter(|&w| w > 0)?;
+        Some((self.avg_input_tokens as f64 / window as f64 * 100.0).round())
+    }
 }
 
 /// Data for the models view
@@ -37,13 +65,18 @@ pub struct ModelSummary {
 pub struct ModelsData {
     /// Models sorted by cost descending
     pub models: Vec<ModelSummary>,
+    /// Per-provider totals sorted by cost descending (`name` is the provider)
+    pub providers: Vec<ModelSummary>,
     /// Total cost across all models (for percentage calculation)
     pub total_cost: f64,
 }
 
 impl ModelsData {
-    /// Create ModelsData from Aggregator::by_model() output
-    pub fn from_model_usage(model_map: &HashMap<String, ModelUsage>) -> Self {
+    /// Create ModelsData from Aggregator::by_model() output.
+    ///
+    /// Models whose share of total cost is below `other_threshold` percent are
+    /// summed into a single trailing "other" row. A threshold of 0 keeps the full list.
+    pub fn from_model_usage(model_map: &HashMap<String, ModelUsage>, other_threshold: f64) -> Self {
         let total_cost: f64 = model_map.values().map(|m| m.cost_usd).sum();
 
         let mut models: Vec<ModelSummary> = model_map
@@ -57,6 +90,9 @@ impl ModelsData {
                     name: name.clone(),
                     total_tokens,
                     cost_usd: usage.cost_usd,
+                    avg_input_tokens: usage.input_tokens.checked_div(usage.count).unwrap_or(0),
+                    context_window_tokens: None,
+                    other_count: 0,
                 }
             })
             .filter(|m| m.total_tokens > 0) // Filter out zero-token models
@@ -69,8 +105,95 @@ impl ModelsData {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
-        Self { models, total_cost }
+        let providers = Self::providers_from_models(&models);
+
+        if other_threshold > 0.0 && total_cost > 0.0 {
+            let cutoff = total_cost * other_threshold / 100.0;
+            let split = models
+                .iter()
+                .position(|m| m.cost_usd < cutoff)
+                .unwrap_or(models.len());
+            // A single tail model is shown as-is rather than collapsed
+            if models.len() - split > 1 {
+                let tail = models.split_off(split);
+                models.push(ModelSummary {
+                    name: String::new(),
+                    total_tokens: tail.iter().map(|m| m.total_tokens).sum(),
+                    cost_usd: tail.iter().map(|m| m.cost_usd).sum(),
+                    avg_input_tokens: 0,
+                    context_window_tokens: None,
+                    other_count: tail.len(),
+                });
+            }
+        }
+
+        Self {
+            models,
+            providers,
+            total_cost,
+        }
+    }
+
+    /// Sum cost-sorted models per provider, keeping the cost-descending order
+    fn providers_from_models(models: &[ModelSummary]) -> Vec<ModelSummary> {
+        let mut providers: Vec<ModelSummary> = Vec::new();
+        for model in models {
+            let name = provider_for_model(&model.name);
+            match providers.iter_mut().find(|p| p.name == name) {
+                Some(provider) => {
+                    provider.total_tokens += model.total_tokens;
+                    provider.cost_usd += model.cost_usd;
+                }
+                None => providers.push(ModelSummary {
+                    name: name.to_string(),
+                    total_tokens: model.total_tokens,
+                    cost_usd: model.cost_usd,
+                    avg_input_tokens: 0,
+                    context_window_tokens: None,
+                    other_count: 0,
+                }),
+            }
+        }
+        providers.sort_by(|a, b| {
+            b.cost_usd
+                .partial_cmp(&a.cost_usd)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        providers
+    }
+
+    /// Fill in each model's context window from `lookup` (model name → tokens)
+    pub fn fill_context_windows(&mut self, lookup: impl Fn(&str) -> Option<u64>) {
+        for model in &mut self.models {
+            if !model.is_other() {
+                model.context_window_tokens = lookup(&model.name);
+            }
+        }
     }
+
+    /// Models whose raw or display name contains `query` (case-insensitive)
+    pub fn filtered_models(&self, query: &str) -> Vec<ModelSummary> {
+        self.models
+            .iter()
+            .filter(|m| matches_filter(&m.name, query))
+            .cloned()
+            .collect()
+    }
+
+    /// Provider rows to show: only when usage spans more than one provider
+    fn visible_providers(&self) -> &[ModelSummary] {
+        if self.providers.len() > 1 {
+            &self.providers
+        } else {
+            &[]
+        }
+    }
+}
+
+/// Whether `name` matches a Models tab filter query; an empty query matches all
+pub fn matches_filter(name: &str, query: &str) -> bool {
+    let query = query.to_lowercase();
+    name.to_lowercase().contains(&query) || display_name(name).to_lowercase().contains(&query)
 }
 
 /// Maximum content width for Models view (consistent with Overview)
@@ -79,11 +202,36 @@ const MAX_CONTENT_WIDTH: u16 = 170;
 /// Table width: Model(30) + Tokens(18) + Cost(12) + Usage(18) = 78
 const TABLE_WIDTH: u16 = 78;
 
+/// Width of the optional Ctx% column
+const CTX_WIDTH: u16 = 8;
+
+/// Narrowest terminal that shows the Ctx% column
+const CTX_MIN_WIDTH: u16 = 120;
+
+/// Context usage above this percentage is highlighted
+const CTX_WARN_PERCENT: f64 = 80.0;
+
+/// Whether the Ctx% column fits in `width`
+fn shows_context_column(width: u16) -> bool {
+    width >= CTX_MIN_WIDTH
+}
+
+/// Table width for `width`, including the Ctx% column when it fits
+fn table_width_for(width: u16) -> u16 {
+    if shows_context_column(width) {
+        TABLE_WIDTH + CTX_WIDTH
+    } else {
+        TABLE_WIDTH
+    }
+}
+
 /// Models view widget
 pub struct ModelsView<'a> {
     data: &'a ModelsData,
     theme: Theme,
     tab: Tab,
+    filter: &'a str,
+    filter_editing: bool,
 }
 
 impl<'a> ModelsView<'a> {
@@ -92,6 +240,8 @@ impl<'a> ModelsView<'a> {
             data,
             theme,
             tab: Tab::Models,
+            filter: "",
+            filter_editing: false,
         }
     }
 
@@ -99,6 +249,13 @@ impl<'a> ModelsView<'a> {
         self.tab = tab;
         self
     }
+
+    /// Only list models matching `filter`; `editing` shows the input cursor
+    pub fn with_filter(mut self, filter: &'a str, editing: bool) -> Self {
+        self.filter = filter;
+        self.filter_editing = editing;
+        self
+    }
 }
 
 impl Widget for ModelsView<'_> {
@@ -114,13 +271,22 @@ impl Widget for ModelsView<'_> {
         };
 
         // Calculate layout with models list
-        let max_model_rows = self.data.models.len().min(10) as u16; // Show up to 10 models
+        let model_count = self.data.filtered_models(self.filter).len();
+        let max_model_rows = model_count.min(10) as u16; // Show up to 10 models
+        let providers = self.data.visible_providers();
+        // Blank line + header + one row per provider
+        let provider_rows = if providers.is_empty() {
+            0
+        } else {
+            providers.len() as u16 + 2
+        };
         let chunks = Layout::vertical([
             Constraint::Length(1),              // Top padding
             Constraint::Length(1),              // Tabs
             Constraint::Length(1),              // Separator
             Constraint::Length(1),              // Header
             Constraint::Length(max_model_rows), // Model rows
+            Constraint::Length(provider_rows),  // Providers section
             Constraint::Length(1),              // Separator
             Constraint::Length(1),              // Keybindings
             Constraint::Min(0),                 // Remaining space
@@ -134,23 +300,47 @@ impl Widget for ModelsView<'_> {
         self.render_separator(chunks[2], buf);
 
         // Render header
-        self.render_header(chunks[3], buf);
+        self.render_header(chunks[3], buf, "Model");
 
         // Render model rows
         self.render_models(chunks[4], buf);
 
+        // Render providers section (header + rows, after a blank line)
+        if provider_rows > 0 {
+            let section = chunks[5];
+            self.render_header(
+                Rect {
+                    y: section.y + 1,
+                    height: 1,
+                    ..section
+                },
+                buf,
+                "Provider",
+            );
+            self.render_rows(
+                Rect {
+                    y: section.y + 2,
+                    height: section.height.saturating_sub(2),
+                    ..section
+                },
+                buf,
+                providers,
+                |name| name.to_string(),
+            );
+        }
+
         // Render separator
-        self.render_separator(chunks[5], buf);
+        self.render_separator(chunks[6], buf);
 
         // Render keybindings
-        self.render_keybindings(chunks[6], buf);
+        self.render_keybindings(chunks[7], buf);
     }
 }
 
 impl ModelsView<'_> {
     /// Calculate horizontal offset to center the table
     fn calculate_table_offset(&self, area_width: u16) -> u16 {
-        area_width.saturating_sub(TABLE_WIDTH) / 2
+        area_width.saturating_sub(table_width_for(area_width)) / 2
     }
 
     fn render_separator(&self, area: Rect, buf: &mut Buffer) {
@@ -163,13 +353,13 @@ impl ModelsView<'_> {
         );
     }
 
-    fn render_header(&self, area: Rect, buf: &mut Buffer) {
+    fn render_header(&self, area: Rect, buf: &mut Buffer, label: &str) {
         let offset = self.calculate_table_offset(area.width);
 
-        // Column widths: Model(30), Tokens(18), Cost(12), Usage(18)
-        let header = Line::from(vec![
+        // Column widths: Model(30), Tokens(18), Cost(12), Usage(18), Ctx%(8)
+        let mut spans = vec![
             Span::styled(
-                format!("{:<30}", "Model"),
+                format!("{:<30}", label),
                 Style::default()
                     .fg(self.theme.text())
                     .add_modifier(Modifier::BOLD),
@@ -192,14 +382,22 @@ impl ModelsView<'_> {
                     .fg(self.theme.text())
                     .add_modifier(Modifier::BOLD),
             ),
-        ]);
+        ];
+        if shows_context_column(area.width) {
+            spans.push(Span::styled(
+                format!("{:>8}", "Ctx%"),
+                Style::default()
+                    .fg(self.theme.text())
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
 
-        let paragraph = Paragraph::new(header).alignment(Alignment::Left);
+        let paragraph = Paragraph::new(Line::from(spans)).alignment(Alignment::Left);
         paragraph.render(
             Rect {
                 x: area.x + offset,
                 y: area.y,
-                width: TABLE_WIDTH.min(area.width),
+                width: table_width_for(area.width).min(area.width),
                 height: area.height,
             },
             buf,
@@ -207,15 +405,21 @@ impl ModelsView<'_> {
     }
 
     pub fn render_models(&self, area: Rect, buf: &mut Buffer) {
+        let models = self.data.filtered_models(self.filter);
+        self.render_rows(area, buf, &models, display_name);
+    }
+
+    /// Render name/tokens/cost/usage rows, labelling each with `label(name)`
+    fn render_rows(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        rows: &[ModelSummary],
+        label: impl Fn(&str) -> String,
+    ) {
         let offset = self.calculate_table_offset(area.width);
 
-        for (i, model) in self
-            .data
-            .models
-            .iter()
-            .take(area.height as usize)
-            .enumerate()
-        {
+        for (i, model) in rows.iter().take(area.height as usize).enumerate() {
             let y = area.y + i as u16;
             if y >= area.y + area.height {
                 break;
@@ -230,14 +434,14 @@ impl ModelsView<'_> {
             let bar = format_percentage_bar(percent, 14);
 
             // Convert to display name and truncate if too long (UTF-8 safe)
-            let name = display_name(&model.name);
+            let name = model.label(&label);
             let name = if name.chars().count() > 28 {
                 format!("{}…", name.chars().take(27).collect::<String>())
             } else {
                 name
             };
 
-            let row = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
                     format!("{:<30}", name),
                     Style::default().fg(self.theme.accent()),
@@ -254,14 +458,17 @@ impl ModelsView<'_> {
                     format!("{:>18}", bar),
                     Style::default().fg(self.theme.bar()),
                 ),
-            ]);
+            ];
+            if shows_context_column(area.width) {
+                spans.push(self.context_span(model));
+            }
 
-            let paragraph = Paragraph::new(row).alignment(Alignment::Left);
+            let paragraph = Paragraph::new(Line::from(spans)).alignment(Alignment::Left);
             paragraph.render(
                 Rect {
                     x: area.x + offset,
                     y,
-                    width: TABLE_WIDTH.min(area.width),
+                    width: table_width_for(area.width).min(area.width),
                     height: 1,
                 },
                 buf,
@@ -269,7 +476,45 @@ impl ModelsView<'_> {
         }
     }
 
+    /// Ctx% cell: "—" without a known context window, highlighted above `CTX_WARN_PERCENT`
+    fn context_span(&self, model: &ModelSummary) -> Span<'static> {
+        match model.context_percent() {
+            Some(percent) => {
+                let color = if percent > CTX_WARN_PERCENT {
+                    self.theme.spike_warn()
+                } else {
+                    self.theme.text()
+                };
+                Span::styled(
+                    format!("{:>8}", format!("{:.0}%", percent)),
+                    Style::default().fg(color),
+                )
+            }
+            None => Span::styled(
+                format!("{:>8}", "—"),
+                Style::default().fg(self.theme.muted()),
+            ),
+        }
+    }
+
     fn render_keybindings(&self, area: Rect, buf: &mut Buffer) {
+        if self.filter_editing || !self.filter.is_empty() {
+            let cursor = if self.filter_editing { "_" } else { "" };
+            let bindings = Paragraph::new(Line::from(vec![
+                Span::styled("Filter: ", Style::default().fg(self.theme.muted())),
+                Span::styled(
+                    format!("{}{}", self.filter, cursor),
+                    Style::default().fg(self.theme.accent()),
+                ),
+                Span::raw("  "),
+                Span::styled("Esc", Style::default().fg(self.theme.accent())),
+                Span::styled(": Clear", Style::default().fg(self.theme.muted())),
+            ]))
+            .alignment(Alignment::Center);
+            bindings.render(area, buf);
+            return;
+        }
+
         let bindings = Paragraph::new(Line::from(vec![
             Span::styled("Ctrl+C", Style::default().fg(self.theme.accent())),
             Span::styled(": Quit", Style::default().fg(self.theme.muted())),
@@ -277,6 +522,9 @@ impl ModelsView<'_> {
             Span::styled("Tab", Style::default().fg(self.theme.accent())),
             Span::styled(": Switch view", Style::default().fg(self.theme.muted())),
             Span::raw("  "),
+            Span::styled("/", Style::default().fg(self.theme.accent())),
+            Span::styled(": Filter", Style::default().fg(self.theme.muted())),
+            Span::raw("  "),
             Span::styled("?", Style::default().fg(self.theme.accent())),
             Span::styled(": Help", Style::default().fg(self.theme.muted())),
         ]))
@@ -324,7 +572,7 @@ mod tests {
     #[test]
     fn test_models_data_empty() {
         let model_map: HashMap<String, ModelUsage> = HashMap::new();
-        let data = ModelsData::from_model_usage(&model_map);
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
 
         assert!(data.models.is_empty());
         assert!((data.total_cost - 0.0).abs() < f64::EPSILON);
@@ -346,7 +594,7 @@ mod tests {
             },
         );
 
-        let data = ModelsData::from_model_usage(&model_map);
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
 
         assert_eq!(data.models.len(), 1);
         assert_eq!(data.models[0].name, "claude-sonnet-4");
@@ -395,7 +643,7 @@ mod tests {
             },
         );
 
-        let data = ModelsData::from_model_usage(&model_map);
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
 
         assert_eq!(data.models.len(), 3);
         // Should be sorted by cost descending: opus (0.50) > sonnet (0.10) > haiku (0.01)
@@ -432,8 +680,207 @@ mod tests {
             },
         );
 
-        let data = ModelsData::from_model_usage(&model_map);
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
 
         assert!((data.total_cost - 0.30).abs() < f64::EPSILON);
     }
+
+    // ========== other bucket tests ==========
+
+    fn usage_with_cost(tokens: u64, cost_usd: f64) -> ModelUsage {
+        ModelUsage {
+            input_tokens: tokens,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            thinking_tokens: 0,
+            cost_usd,
+            count: 1,
+        }
+    }
+
+    fn long_tail_map() -> HashMap<String, ModelUsage> {
+        let mut model_map = HashMap::new();
+        model_map.insert("big".to_string(), usage_with_cost(10_000, 97.0));
+        model_map.insert("tail-a".to_string(), usage_with_cost(300, 0.5));
+        model_map.insert("tail-b".to_string(), usage_with_cost(200, 0.25));
+        model_map.insert("mid".to_string(), usage_with_cost(1_000, 2.25));
+        model_map
+    }
+
+    #[test]
+    fn test_other_bucket_sums_tail() {
+        // Total cost = 100 → 1% cutoff = 1.0; tail-a and tail-b fall below
+        let data = ModelsData::from_model_usage(&long_tail_map(), 1.0);
+
+        let names: Vec<&str> = data.models.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["big", "mid", ""]);
+
+        let other = data.models.last().unwrap();
+        assert_eq!(other.other_count, 2);
+        assert_eq!(other.label(display_name), "Other (2 models)");
+        assert_eq!(other.total_tokens, 500);
+        assert!((other.cost_usd - 0.75).abs() < 1e-9);
+        assert!((data.total_cost - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_other_bucket_zero_threshold_keeps_all() {
+        let data = ModelsData::from_model_usage(&long_tail_map(), 0.0);
+        assert_eq!(data.models.len(), 4);
+        assert!(data.models.iter().all(|m| !m.is_other()));
+    }
+
+    #[test]
+    fn test_other_bucket_single_tail_model_not_collapsed() {
+        // 0.3% cutoff = 0.3 → only tail-b is below, so it stays as-is
+        let data = ModelsData::from_model_usage(&long_tail_map(), 0.3);
+        assert_eq!(data.models.len(), 4);
+        assert_eq!(data.models[3].name, "tail-b");
+    }
+
+    #[test]
+    fn test_model_named_other_is_not_the_bucket() {
+        let mut model_map = long_tail_map();
+        model_map.insert("other".to_string(), usage_with_cost(100, 0.1));
+        let mut data = ModelsData::from_model_usage(&model_map, 0.0);
+        data.fill_context_windows(|_| Some(200_000));
+
+        let other = data.models.iter().find(|m| m.name == "other").unwrap();
+        assert!(!other.is_other());
+        assert_eq!(other.context_window_tokens, Some(200_000));
+    }
+
+    // ========== Providers section ==========
+
+    fn usage(cost_usd: f64) -> ModelUsage {
+        ModelUsage {
+            input_tokens: 1000,
+            output_tokens: 500,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            thinking_tokens: 0,
+            cost_usd,
+            count: 1,
+        }
+    }
+
+    #[test]
+    fn test_providers_summed_per_provider() {
+        let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
+        model_map.insert("claude-sonnet-4".to_string(), usage(2.0));
+        model_map.insert("claude-opus-4-5".to_string(), usage(3.0));
+        model_map.insert("gpt-4o".to_string(), usage(1.0));
+
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
+        assert_eq!(data.providers.len(), 2);
+        assert_eq!(data.providers[0].name, "Anthropic");
+        assert!((data.providers[0].cost_usd - 5.0).abs() < f64::EPSILON);
+        assert_eq!(data.providers[0].total_tokens, 3000);
+        assert_eq!(data.providers[1].name, "OpenAI");
+    }
+
+    #[test]
+    fn test_providers_section_renders_for_two_providers() {
+        let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
+        model_map.insert("claude-sonnet-4".to_string(), usage(2.0));
+        model_map.insert("gemini-2-5-pro".to_string(), usage(1.0));
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
+
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        ModelsView::new(&data, Theme::Dark).render(area, &mut buf);
+
+        let content: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("Provider"));
+        assert!(content.contains("Anthropic"));
+        assert!(content.contains("Google"));
+        assert!(content.contains("$2.00"));
+    }
+
+    #[test]
+    fn test_providers_section_hidden_for_single_provider() {
+        let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
+        model_map.insert("claude-sonnet-4".to_string(), usage(2.0));
+        model_map.insert("claude-opus-4-5".to_string(), usage(3.0));
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
+
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        ModelsView::new(&data, Theme::Dark).render(area, &mut buf);
+
+        let content: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(!content.contains("Provider"));
+    }
+
+    // ========== Filter ==========
+
+    #[test]
+    fn test_matches_filter_raw_and_display_name() {
+        assert!(matches_filter("claude-sonnet-4", ""));
+        assert!(matches_filter("claude-sonnet-4", "SONNET"));
+        assert!(matches_filter("claude-opus-4-5", "opus 4.5"));
+        assert!(!matches_filter("gpt-4o", "claude"));
+    }
+
+    #[test]
+    fn test_filtered_view_lists_only_matching_models() {
+        let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
+        model_map.insert("claude-sonnet-4".to_string(), usage(2.0));
+        model_map.insert("claude-opus-4-5".to_string(), usage(3.0));
+        model_map.insert("gpt-4o".to_string(), usage(1.0));
+        let data = ModelsData::from_model_usage(&model_map, 0.0);
+
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        ModelsView::new(&data, Theme::Dark)
+            .with_filter("gpt", true)
+            .render(area, &mut buf);
+
+        let content: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains(&display_name("gpt-4o")));
+        assert!(!content.contains(&display_name("claude-sonnet-4")));
+        assert!(!content.contains(&display_name("claude-opus-4-5")));
+        assert!(content.contains("Filter: gpt_"));
+    }
+
+    // ========== Context window ==========
+
+    #[test]
+    fn test_context_percent_from_avg_input() {
+        let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
+        model_map.insert(
+            "claude-sonnet-4".to_string(),
+            ModelUsage {
+                count: 4,
+                ..usage_with_cost(680_000, 2.0)
+            },
+        );
+        model_map.insert("gpt-4o".to_string(), usage_with_cost(1_000, 1.0));
+        let mut data = ModelsData::from_model_usage(&model_map, 0.0);
+        data.fill_context_windows(|name| (name == "claude-sonnet-4").then_some(200_000));
+
+        let sonnet = &data.models[0];
+        assert_eq!(sonnet.avg_input_tokens, 170_000);
+        assert_eq!(sonnet.context_percent(), Some(85.0));
+        assert_eq!(data.models[1].context_percent(), None);
+    }
+
+    #[test]
+    fn test_context_column_hidden_when_narrow() {
+        let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
+        model_map.insert("gpt-4o".to_string(), usage_with_cost(1_000, 1.0));
+        let mut data = ModelsData::from_model_usage(&model_map, 0.0);
+        data.fill_context_windows(|_| Some(128_000));
+
+        let render = |width| {
+            let area = Rect::new(0, 0, width, 20);
+            let mut buf = Buffer::empty(area);
+            ModelsView::new(&data, Theme::Dark).render(area, &mut buf);
+            buf.content().iter().map(|c| c.symbol()).collect::<String>()
+        };
+        assert!(render(130).contains("Ctx%"));
+        assert!(render(130).contains("1%"));
+        assert!(!render(119).contains("Ctx%"));
+    }
 }
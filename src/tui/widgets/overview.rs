@@ -1,5 +1,7 @@
 //! Overview layout widget
 
+use std::rc::Rc;
+
 use chrono::NaiveDate;
 use ratatui::{
     buffer::Buffer,
@@ -9,9 +11,11 @@ use ratatui::{
     widgets::{Paragraph, Widget},
 };
 
+use super::daily::DisplayMetric;
 use super::heatmap::Heatmap;
 use super::legend::Legend;
 use super::tabs::{Tab, TabBar};
+use crate::services::display_name;
 use crate::tui::theme::Theme;
 use crate::types::{SourceUsage, TotalSummary};
 
@@ -37,35 +41,114 @@ pub fn format_number(n: u64) -> String {
     result
 }
 
+/// Compact token count for narrow layouts (e.g., 1234567 -> "1.2M", 500000 -> "500K")
+pub fn format_tokens_human(n: u64) -> String {
+    match n {
+        1_000_000.. => with_suffix(n as f64 / 1_000_000.0, "M"),
+        1_000.. => with_suffix(n as f64 / 1_000.0, "K"),
+        _ => n.to_string(),
+    }
+}
+
+/// Cost with a K/M suffix from $1,000 up (e.g., 1234.5 -> "$1.2K"), else to the cent
+pub fn format_cost_short(cost: f64) -> String {
+    if cost >= 1_000_000.0 {
+        format!("${}", with_suffix(cost / 1_000_000.0, "M"))
+    } else if cost >= 1_000.0 {
+        format!("${}", with_suffix(cost / 1_000.0, "K"))
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+/// One decimal place, dropping a trailing ".0"
+fn with_suffix(value: f64, suffix: &str) -> String {
+    let s = format!("{:.1}", value);
+    format!("{}{}", s.strip_suffix(".0").unwrap_or(&s), suffix)
+}
+
 /// Data for the overview display (references to avoid cloning)
 #[derive(Debug)]
 pub struct OverviewData<'a> {
     pub total: &'a TotalSummary,
     pub daily_tokens: &'a [(NaiveDate, u64)],
+    /// Daily cost smoothed over `MOVING_AVG_DAYS`, oldest first
+    pub daily_moving_avg: &'a [(NaiveDate, f64)],
     pub source_usage: &'a [SourceUsage],
     pub selected_source: Option<usize>,
     pub selected_tab: Tab,
+    /// Consecutive days with usage ending today
+    pub streak: u32,
+}
+
+/// Window (days) of the cost moving average shown under the hero stat
+pub const MOVING_AVG_DAYS: usize = 7;
+
+/// " · 7d avg: $1.23/day" from the latest moving-average point, if any
+fn moving_avg_suffix(daily_moving_avg: &[(NaiveDate, f64)]) -> Option<String> {
+    daily_moving_avg
+        .last()
+        .map(|(_, avg)| format!(" · {}d avg: ${:.2}/day", MOVING_AVG_DAYS, avg))
+}
+
+/// "🔥 5-day streak" while there is a streak
+fn streak_badge(streak: u32) -> Option<String> {
+    (streak > 0).then(|| format!("🔥 {}-day streak", streak))
+}
+
+/// "Sonnet 4.5 · Opus 4.5" from a source's costliest models
+fn top_models_label(source: &SourceUsage) -> String {
+    source
+        .by_model
+        .iter()
+        .map(|(model, _)| display_name(model))
+        .collect::<Vec<_>>()
+        .join(" · ")
 }
 
 /// Maximum content width for Overview (keeps layout clean on wide terminals)
 /// 52 weeks * 3-char cells + 4 label = 160, so 170 gives some padding
 const MAX_CONTENT_WIDTH: u16 = 170;
 
+/// Layout chunks holding the sources label and the source bars
+const SOURCES_LABEL_IDX: usize = 5;
+const SOURCE_BARS_IDX: usize = 6;
+
 /// Overview widget combining all elements
 pub struct Overview<'a> {
     data: OverviewData<'a>,
     today: NaiveDate,
     theme: Theme,
+    metric: DisplayMetric,
 }
 
 impl<'a> Overview<'a> {
     pub fn new(data: OverviewData<'a>, today: NaiveDate, theme: Theme) -> Self {
-        Self { data, today, theme }
+        Self {
+            data,
+            today,
+            theme,
+            metric: DisplayMetric::default(),
+        }
+    }
+
+    /// Lead with `metric` in the hero stat and source bars
+    pub fn with_metric(mut self, metric: DisplayMetric) -> Self {
+        self.metric = metric;
+        self
     }
 }
 
-impl Widget for Overview<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+impl Overview<'_> {
+    /// Row of the first source bar when rendered into `area`, if any sources are shown
+    pub fn source_list_y(&self, area: Rect) -> Option<u16> {
+        if self.data.source_usage.is_empty() {
+            return None;
+        }
+        Some(self.layout(area)[SOURCE_BARS_IDX].y)
+    }
+
+    fn layout(&self, area: Rect) -> Rc<[Rect]> {
         // Apply max width constraint and center the content
         let content_width = area.width.min(MAX_CONTENT_WIDTH);
         let x_offset = (area.width.saturating_sub(content_width)) / 2;
@@ -89,29 +172,36 @@ impl Widget for Overview<'_> {
             Constraint::Length(1), // 4: Blank
         ];
 
-        let sources_label_idx = constraints.len(); // 5
+        // 5: Sources label
         constraints.push(Constraint::Length(if show_sources { 1 } else { 0 }));
 
-        let sources_bars_idx = constraints.len(); // 6
+        // 6: Source bars
         constraints.push(Constraint::Length(if show_sources {
             source_rows
         } else {
             0
         }));
 
-        let _blank_after_sources_idx = constraints.len(); // 7
+        // 7: Blank after sources
         constraints.push(Constraint::Length(1));
 
-        let heatmap_idx = constraints.len(); // 8
+        // 8: Heatmap
         constraints.push(Constraint::Fill(1));
 
-        let sep_idx = constraints.len(); // 9
+        // 9: Separator
         constraints.push(Constraint::Length(1));
 
-        let keybindings_idx = constraints.len(); // 10
+        // 10: Keybindings
         constraints.push(Constraint::Length(1));
 
-        let chunks = Layout::vertical(constraints).split(centered_area);
+        Layout::vertical(constraints).split(centered_area)
+    }
+}
+
+impl Widget for Overview<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let chunks = self.layout(area);
+        let show_sources = !self.data.source_usage.is_empty();
 
         // Render tab bar
         TabBar::new(self.data.selected_tab, self.theme).render(chunks[0], buf);
@@ -127,18 +217,18 @@ impl Widget for Overview<'_> {
 
         // Render sources section if present
         if show_sources {
-            self.render_sources_label(chunks[sources_label_idx], buf);
-            self.render_source_bars(chunks[sources_bars_idx], buf);
+            self.render_sources_label(chunks[SOURCES_LABEL_IDX], buf);
+            self.render_source_bars(chunks[SOURCE_BARS_IDX], buf);
         }
 
         // Render heatmap with legend
-        self.render_heatmap_section(chunks[heatmap_idx], buf);
+        self.render_heatmap_section(chunks[8], buf);
 
         // Render separator
-        self.render_separator(chunks[sep_idx], buf);
+        self.render_separator(chunks[9], buf);
 
         // Render keybindings
-        self.render_keybindings(chunks[keybindings_idx], buf);
+        self.render_keybindings(chunks[10], buf);
     }
 }
 
@@ -154,12 +244,11 @@ impl Overview<'_> {
     }
 
     fn render_hero_stat(&self, area: Rect, buf: &mut Buffer) {
-        let total_tokens = self.data.total.total_input_tokens
-            + self.data.total.total_output_tokens
-            + self.data.total.total_cache_read_tokens
-            + self.data.total.total_cache_creation_tokens
-            + self.data.total.total_thinking_tokens;
-        let formatted = format_number(total_tokens);
+        let total_tokens = self.data.total.total_tokens();
+        let (formatted, unit) = match self.metric {
+            DisplayMetric::Tokens => (format_number(total_tokens), "tokens"),
+            DisplayMetric::Cost => (format_cost_short(self.data.total.total_cost_usd), "cost"),
+        };
 
         let hero = Paragraph::new(vec![
             Line::from(Span::styled(
@@ -168,10 +257,7 @@ impl Overview<'_> {
                     .fg(self.theme.accent())
                     .add_modifier(Modifier::BOLD),
             )),
-            Line::from(Span::styled(
-                "tokens",
-                Style::default().fg(self.theme.muted()),
-            )),
+            Line::from(Span::styled(unit, Style::default().fg(self.theme.muted()))),
         ])
         .alignment(Alignment::Center);
 
@@ -179,13 +265,35 @@ impl Overview<'_> {
     }
 
     fn render_sub_stats(&self, area: Rect, buf: &mut Buffer) {
-        let cost_str = format!("Cost: ${:.2}", self.data.total.total_cost_usd);
+        let (mut stat_str, color) = match self.metric {
+            DisplayMetric::Tokens => (
+                format!(
+                    "Cost: {}",
+                    format_cost_short(self.data.total.total_cost_usd)
+                ),
+                self.theme.cost(),
+            ),
+            DisplayMetric::Cost => (
+                format!("Tokens: {}", format_number(self.data.total.total_tokens())),
+                self.theme.text(),
+            ),
+        };
 
-        let stats = Paragraph::new(Line::from(vec![Span::styled(
-            cost_str,
-            Style::default().fg(self.theme.cost()),
-        )]))
-        .alignment(Alignment::Center);
+        if let Some(suffix) = moving_avg_suffix(self.data.daily_moving_avg) {
+            stat_str.push_str(&suffix);
+        }
+
+        let mut spans = vec![Span::styled(stat_str, Style::default().fg(color))];
+        if let Some(badge) = streak_badge(self.data.streak) {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                badge,
+                Style::default()
+                    .fg(self.theme.accent())
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        let stats = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
 
         stats.render(area, buf);
     }
@@ -207,13 +315,11 @@ impl Overview<'_> {
             return;
         }
 
-        let max_tokens = self
-            .data
-            .source_usage
-            .iter()
-            .map(|s| s.total_tokens)
-            .max()
-            .unwrap_or(1);
+        let value = |source: &SourceUsage| match self.metric {
+            DisplayMetric::Tokens => source.total_tokens as f64,
+            DisplayMetric::Cost => source.total_cost_usd,
+        };
+        let max_value = self.data.source_usage.iter().map(value).fold(0.0, f64::max);
 
         // Bar rendering config
         const SOURCE_NAME_WIDTH: usize = 12;
@@ -250,9 +356,13 @@ impl Overview<'_> {
             let name_display = format!("{:>width$}", name, width = SOURCE_NAME_WIDTH);
 
             // Bar representation
-            let ratio = source.total_tokens as f64 / max_tokens as f64;
+            let ratio = if max_value > 0.0 {
+                value(source) / max_value
+            } else {
+                0.0
+            };
             let filled = (ratio * BAR_WIDTH as f64).round() as usize;
-            let filled = if source.total_tokens > 0 {
+            let filled = if value(source) > 0.0 {
                 filled.max(1)
             } else {
                 filled
@@ -260,8 +370,11 @@ impl Overview<'_> {
             let filled = filled.min(BAR_WIDTH);
             let bar = format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled));
 
-            // Token count
-            let count_str = format_number(source.total_tokens);
+            // Token count or cost
+            let count_str = match self.metric {
+                DisplayMetric::Tokens => format_number(source.total_tokens),
+                DisplayMetric::Cost => format!("${:.2}", source.total_cost_usd),
+            };
 
             // Build the line
             let name_style = if is_selected {
@@ -272,14 +385,32 @@ impl Overview<'_> {
                 Style::default().fg(self.theme.text())
             };
 
-            let spans = vec![
+            let mut spans = vec![
                 Span::styled(marker, Style::default().fg(self.theme.accent())),
                 Span::styled(name_display, name_style),
                 Span::raw("  "),
-                Span::styled(&bar, Style::default().fg(self.theme.bar())),
+                Span::styled(
+                    &bar,
+                    Style::default().fg(self.theme.source_color(&source.source)),
+                ),
                 Span::raw("  "),
-                Span::styled(count_str, Style::default().fg(self.theme.text())),
             ];
+            // Top models trail the count, cut off at the edge of the area
+            if source.by_model.is_empty() {
+                spans.push(Span::styled(
+                    count_str,
+                    Style::default().fg(self.theme.text()),
+                ));
+            } else {
+                spans.push(Span::styled(
+                    format!("{:<15}", count_str),
+                    Style::default().fg(self.theme.text()),
+                ));
+                spans.push(Span::styled(
+                    top_models_label(source),
+                    Style::default().fg(self.theme.muted()),
+                ));
+            }
 
             // Render centered
             let line = Line::from(spans);
@@ -344,6 +475,7 @@ impl Overview<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::ModelUsage;
 
     // ========== format_number tests ==========
 
@@ -371,4 +503,62 @@ mod tests {
     fn test_format_number_million() {
         assert_eq!(format_number(1000000), "1,000,000");
     }
+
+    // ========== compact format tests ==========
+
+    #[test]
+    fn test_format_tokens_human() {
+        assert_eq!(format_tokens_human(0), "0");
+        assert_eq!(format_tokens_human(999), "999");
+        assert_eq!(format_tokens_human(1_000), "1K");
+        assert_eq!(format_tokens_human(1_540), "1.5K");
+        assert_eq!(format_tokens_human(500_000), "500K");
+        assert_eq!(format_tokens_human(1_234_567), "1.2M");
+        assert_eq!(format_tokens_human(2_000_000_000), "2000M");
+    }
+
+    #[test]
+    fn test_format_cost_short() {
+        assert_eq!(format_cost_short(0.0), "$0.00");
+        assert_eq!(format_cost_short(999.994), "$999.99");
+        assert_eq!(format_cost_short(1_000.0), "$1K");
+        assert_eq!(format_cost_short(1_234.5), "$1.2K");
+        assert_eq!(format_cost_short(2_500_000.0), "$2.5M");
+    }
+
+    #[test]
+    fn test_moving_avg_suffix_uses_latest_day() {
+        let d = |day| NaiveDate::from_ymd_opt(2026, 1, day).unwrap();
+        assert_eq!(moving_avg_suffix(&[]), None);
+        assert_eq!(
+            moving_avg_suffix(&[(d(1), 9.0), (d(2), 1.234)]).as_deref(),
+            Some(" · 7d avg: $1.23/day")
+        );
+    }
+
+    #[test]
+    fn test_top_models_label() {
+        let source = SourceUsage {
+            source: "claude".to_string(),
+            by_model: vec![
+                ("claude-opus-4-5".to_string(), ModelUsage::default()),
+                ("claude-sonnet-4-5".to_string(), ModelUsage::default()),
+            ],
+            ..SourceUsage::default()
+        };
+        assert_eq!(
+            top_models_label(&source),
+            format!(
+                "{} · {}",
+                display_name("claude-opus-4-5"),
+                display_name("claude-sonnet-4-5")
+            )
+        );
+    }
+
+    #[test]
+    fn test_streak_badge_only_while_active() {
+        assert_eq!(streak_badge(0), None);
+        assert_eq!(streak_badge(5).as_deref(), Some("🔥 5-day streak"));
+    }
 }
@@ -60,6 +60,7 @@ pub struct SessionDetailView<'a> {
     entries: &'a [SessionDetailEntry],
     scroll_offset: usize,
     theme: Theme,
+    loading: bool,
 }
 
 impl<'a> SessionDetailView<'a> {
@@ -74,9 +75,16 @@ impl<'a> SessionDetailView<'a> {
             entries,
             scroll_offset,
             theme,
+            loading: false,
         }
     }
 
+    /// Requests are still being parsed; show a placeholder instead of the table rows
+    pub fn with_loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
     #[allow(dead_code)] // Used in tests
     pub fn max_scroll_offset(count: usize, visible_rows: usize) -> usize {
         count.saturating_sub(visible_rows)
@@ -216,6 +224,17 @@ impl SessionDetailView<'_> {
             Style::default().fg(self.theme.muted()),
         ));
 
+        if !self.session.mixed_utc_offsets.is_empty() {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                format!(
+                    "⚠ mixed UTC offsets ({})",
+                    self.session.mixed_utc_offsets.join(", ")
+                ),
+                Style::default().fg(self.theme.error()),
+            ));
+        }
+
         Paragraph::new(Line::from(spans))
             .alignment(Alignment::Center)
             .render(area, buf);
@@ -265,6 +284,16 @@ impl SessionDetailView<'_> {
     }
 
     fn render_request_rows(&self, area: Rect, buf: &mut Buffer, visible: &[usize]) {
+        if self.loading {
+            Paragraph::new(Line::from(Span::styled(
+                "Loading requests…",
+                Style::default().fg(self.theme.muted()),
+            )))
+            .alignment(Alignment::Center)
+            .render(area, buf);
+            return;
+        }
+
         let tw = table_width_for(visible);
         let offset = area.width.saturating_sub(tw) / 2;
         let start = self.scroll_offset;
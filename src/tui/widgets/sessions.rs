@@ -9,9 +9,10 @@ use ratatui::{
 };
 
 use super::tabs::{Tab, TabBar};
+use super::utils::format_date_relative;
 use crate::services::session_metadata::extract_issue_id;
 use crate::tui::theme::Theme;
-use crate::types::SessionInfo;
+use crate::types::{SessionHighlights, SessionInfo};
 
 /// Maximum content width (consistent with other views)
 const MAX_CONTENT_WIDTH: u16 = 170;
@@ -116,6 +117,7 @@ pub struct SessionsView<'a> {
     selected_tab: Tab,
     sort: SessionSort,
     theme: Theme,
+    highlights: SessionHighlights,
 }
 
 impl<'a> SessionsView<'a> {
@@ -134,6 +136,7 @@ impl<'a> SessionsView<'a> {
             selected_tab,
             sort,
             theme,
+            highlights: SessionHighlights::from_sessions(sessions),
         }
     }
 
@@ -281,8 +284,7 @@ impl SessionsView<'_> {
         visible: &[usize],
         is_selected: bool,
     ) {
-        use chrono::Local;
-
+        let now = chrono::Local::now();
         let selection_modifier = if is_selected {
             Modifier::BOLD | Modifier::REVERSED
         } else {
@@ -294,7 +296,14 @@ impl SessionsView<'_> {
         for (col_idx, &col) in visible.iter().enumerate() {
             let (text, base_style) = match col {
                 COL_PROJECT => {
-                    let marker = if is_selected { "▸ " } else { "  " };
+                    // Selection wins; otherwise flag the busiest sessions
+                    let marker = if is_selected {
+                        "▸ "
+                    } else if self.highlights.contains(&session.session_id) {
+                        "★ "
+                    } else {
+                        "  "
+                    };
                     let name = truncate_str(&session.project, 14);
                     (
                         format!("{}{:<14}", marker, name),
@@ -345,8 +354,7 @@ impl SessionsView<'_> {
                     )
                 }
                 COL_DATE => {
-                    let local = session.created.with_timezone(&Local);
-                    let date_str = local.format("%b %d, %l:%M %p").to_string();
+                    let date_str = format_date_relative(&session.created, &now);
                     let date_str = truncate_str(&date_str, 18);
                     (
                         format!("{:<18}", date_str),
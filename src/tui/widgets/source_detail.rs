@@ -1,5 +1,6 @@
 //! Source detail view - displays per-source daily breakdown
 
+use chrono::NaiveDate;
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
@@ -8,7 +9,7 @@ use ratatui::{
     widgets::{Paragraph, Widget},
 };
 
-use super::daily::{DailyData, DailyView, DailyViewMode};
+use super::daily::{DailyData, DailyView, DailyViewMode, DisplayMetric, DEFAULT_SPARKLINE_WIDTH};
 use super::overview::format_number;
 use crate::tui::theme::Theme;
 use crate::types::StatsData;
@@ -25,6 +26,11 @@ pub struct SourceDetailView<'a> {
     view_mode: DailyViewMode,
     selected_index: Option<usize>,
     theme: Theme,
+    sparkline_width: usize,
+    weekly_scale_weeks: Option<usize>,
+    metric: DisplayMetric,
+    date_filter: Option<(NaiveDate, NaiveDate)>,
+    cumulative: bool,
 }
 
 impl<'a> SourceDetailView<'a> {
@@ -45,8 +51,40 @@ impl<'a> SourceDetailView<'a> {
             view_mode,
             selected_index,
             theme,
+            sparkline_width: DEFAULT_SPARKLINE_WIDTH,
+            weekly_scale_weeks: None,
+            metric: DisplayMetric::default(),
+            date_filter: None,
+            cumulative: false,
         }
     }
+
+    pub fn with_sparkline_width(mut self, sparkline_width: usize) -> Self {
+        self.sparkline_width = sparkline_width;
+        self
+    }
+
+    pub fn with_weekly_scale_weeks(mut self, weeks: Option<usize>) -> Self {
+        self.weekly_scale_weeks = weeks;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: DisplayMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Show the active date range next to the view modes
+    pub fn with_date_filter(mut self, date_filter: Option<(NaiveDate, NaiveDate)>) -> Self {
+        self.date_filter = date_filter;
+        self
+    }
+
+    /// Show the running total instead of each row's cost
+    pub fn with_cumulative(mut self, cumulative: bool) -> Self {
+        self.cumulative = cumulative;
+        self
+    }
 }
 
 impl Widget for SourceDetailView<'_> {
@@ -86,21 +124,21 @@ impl Widget for SourceDetailView<'_> {
             self.theme,
             self.stats_data.daily_avg_cost,
         )
-        .with_selected_index(self.selected_index);
+        .with_selected_index(self.selected_index)
+        .with_sparkline_width(self.sparkline_width)
+        .with_weekly_scale_weeks(self.weekly_scale_weeks)
+        .with_metric(self.metric)
+        .with_cumulative(self.cumulative)
+        .with_month_headers(true);
 
-        daily_view.render_header(chunks[5], buf, &daily_view_visible_columns(chunks[5].width));
-        daily_view.render_daily_rows(chunks[6], buf, &daily_view_visible_columns(chunks[6].width));
+        daily_view.render_header(chunks[5], buf, &daily_view.visible_columns(chunks[5].width));
+        daily_view.render_daily_rows(chunks[6], buf, &daily_view.visible_columns(chunks[6].width));
 
         self.render_separator(chunks[7], buf);
         self.render_keybindings(chunks[8], buf);
     }
 }
 
-/// Get visible columns for the daily table at a given width
-fn daily_view_visible_columns(width: u16) -> Vec<usize> {
-    super::daily::visible_columns(width)
-}
-
 impl SourceDetailView<'_> {
     fn render_source_header(&self, area: Rect, buf: &mut Buffer) {
         let total_tokens = self.stats_data.total_tokens;
@@ -169,6 +207,7 @@ impl SourceDetailView<'_> {
             ('d', DailyViewMode::Daily),
             ('w', DailyViewMode::Weekly),
             ('m', DailyViewMode::Monthly),
+            ('q', DailyViewMode::Quarterly),
         ];
 
         let mut spans = Vec::new();
@@ -186,6 +225,28 @@ impl SourceDetailView<'_> {
             };
             spans.push(Span::styled(format!("{}:{}", key, mode.label()), style));
         }
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("$:{}", self.metric.label()),
+            Style::default().fg(self.theme.muted()),
+        ));
+        spans.push(Span::raw("  "));
+        spans.push(if self.cumulative {
+            Span::styled("c:Cumulative", Style::default().fg(self.theme.accent()))
+        } else {
+            Span::styled("c:Per row", Style::default().fg(self.theme.muted()))
+        });
+        spans.push(Span::raw("  "));
+        match self.date_filter {
+            Some((from, to)) => spans.push(Span::styled(
+                format!("f:{} – {}", from.format("%Y-%m-%d"), to.format("%Y-%m-%d")),
+                Style::default().fg(self.theme.accent()),
+            )),
+            None => spans.push(Span::styled(
+                "f:All dates",
+                Style::default().fg(self.theme.muted()),
+            )),
+        }
 
         let indicator = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
         indicator.render(area, buf);
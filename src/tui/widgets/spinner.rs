@@ -18,20 +18,42 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 const TAGLINE: &str = "Ultra-fast LLM token tracker";
 
 /// Loading stage for display
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)] // Parsing/Aggregating reserved for future progress reporting
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)] // Aggregating reserved for future progress reporting
 pub enum LoadingStage {
-    Scanning,
-    Parsing,
+    /// Looking for data; `found_sources` are labels like `claude-code (47 files)`
+    Scanning {
+        found_sources: Vec<String>,
+    },
+    /// Parsing files; `total` is 0 until the file list is known
+    Parsing {
+        parsed: usize,
+        total: usize,
+    },
     Aggregating,
 }
 
 impl LoadingStage {
-    pub fn message(self) -> &'static str {
+    /// Scanning stage before any source has been found
+    pub fn scanning() -> Self {
+        Self::Scanning {
+            found_sources: Vec::new(),
+        }
+    }
+
+    pub fn message(&self) -> String {
         match self {
-            Self::Scanning => "Scanning files...",
-            Self::Parsing => "Parsing data...",
-            Self::Aggregating => "Aggregating results...",
+            Self::Scanning { found_sources } if found_sources.is_empty() => {
+                "Scanning files...".to_string()
+            }
+            Self::Scanning { found_sources } => {
+                format!("Scanning... found {}", found_sources.join(", "))
+            }
+            Self::Parsing { total: 0, .. } => "Parsing data...".to_string(),
+            Self::Parsing { parsed, total } => {
+                format!("Parsing {}/{}", parsed.min(total), total)
+            }
+            Self::Aggregating => "Aggregating results...".to_string(),
         }
     }
 }
@@ -119,16 +141,16 @@ mod tests {
 
     #[test]
     fn test_spinner_current_char() {
-        let spinner = Spinner::new(0, LoadingStage::Scanning, Theme::Dark);
+        let spinner = Spinner::new(0, LoadingStage::scanning(), Theme::Dark);
         assert_eq!(spinner.current_char(), '⠋');
 
-        let spinner = Spinner::new(5, LoadingStage::Scanning, Theme::Dark);
+        let spinner = Spinner::new(5, LoadingStage::scanning(), Theme::Dark);
         assert_eq!(spinner.current_char(), '⠴');
     }
 
     #[test]
     fn test_spinner_wraps() {
-        let spinner = Spinner::new(10, LoadingStage::Scanning, Theme::Dark);
+        let spinner = Spinner::new(10, LoadingStage::scanning(), Theme::Dark);
         assert_eq!(spinner.current_char(), '⠋'); // 10 % 10 = 0
     }
 
@@ -140,8 +162,33 @@ mod tests {
 
     #[test]
     fn test_loading_stage_message() {
-        assert_eq!(LoadingStage::Scanning.message(), "Scanning files...");
-        assert_eq!(LoadingStage::Parsing.message(), "Parsing data...");
+        assert_eq!(LoadingStage::scanning().message(), "Scanning files...");
+        assert_eq!(
+            LoadingStage::Scanning {
+                found_sources: vec![
+                    "claude-code (47 files)".to_string(),
+                    "opencode (12 files)".to_string()
+                ]
+            }
+            .message(),
+            "Scanning... found claude-code (47 files), opencode (12 files)"
+        );
+        assert_eq!(
+            LoadingStage::Parsing {
+                parsed: 0,
+                total: 0
+            }
+            .message(),
+            "Parsing data..."
+        );
+        assert_eq!(
+            LoadingStage::Parsing {
+                parsed: 42,
+                total: 310
+            }
+            .message(),
+            "Parsing 42/310"
+        );
         assert_eq!(
             LoadingStage::Aggregating.message(),
             "Aggregating results..."
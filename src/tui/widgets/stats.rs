@@ -1,5 +1,6 @@
 //! Stats view widget - displays usage statistics in a card grid
 
+use chrono::Weekday;
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
@@ -11,7 +12,7 @@ use ratatui::{
 use super::overview::format_number;
 use super::tabs::{Tab, TabBar};
 use crate::tui::theme::Theme;
-use crate::types::StatsData;
+use crate::types::{ProviderSummary, StatsData};
 
 /// Maximum content width for Stats view (consistent with other views)
 const MAX_CONTENT_WIDTH: u16 = 170;
@@ -33,6 +34,7 @@ fn cards_per_row(width: u16) -> usize {
 /// Stats view widget
 pub struct StatsView<'a> {
     data: &'a StatsData,
+    providers: &'a [ProviderSummary],
     selected_tab: Tab,
     theme: Theme,
 }
@@ -41,6 +43,7 @@ impl<'a> StatsView<'a> {
     pub fn new(data: &'a StatsData, theme: Theme) -> Self {
         Self {
             data,
+            providers: &[],
             selected_tab: Tab::Stats,
             theme,
         }
@@ -50,6 +53,12 @@ impl<'a> StatsView<'a> {
         self.selected_tab = tab;
         self
     }
+
+    /// Provider totals for the "By provider" line
+    pub fn with_providers(mut self, providers: &'a [ProviderSummary]) -> Self {
+        self.providers = providers;
+        self
+    }
 }
 
 impl Widget for StatsView<'_> {
@@ -76,6 +85,10 @@ impl Widget for StatsView<'_> {
             Constraint::Length(1),           // Title
             Constraint::Length(1),           // Blank
             Constraint::Length(grid_height), // Card grid
+            Constraint::Length(1),           // Cost distribution
+            Constraint::Length(1),           // Efficiency
+            Constraint::Length(1),           // Day of week
+            Constraint::Length(1),           // By provider
             Constraint::Length(1),           // Separator
             Constraint::Length(1),           // Keybindings
             Constraint::Min(0),              // Remaining space
@@ -94,11 +107,23 @@ impl Widget for StatsView<'_> {
         // Render card grid
         self.render_card_grid(chunks[5], buf, cols);
 
+        // Render cost distribution
+        self.render_cost_distribution(chunks[6], buf);
+
+        // Render efficiency metrics
+        self.render_efficiency(chunks[7], buf);
+
+        // Render busiest/quietest weekday
+        self.render_day_of_week(chunks[8], buf);
+
+        // Render per-provider cost
+        self.render_providers(chunks[9], buf);
+
         // Render separator
-        self.render_separator(chunks[6], buf);
+        self.render_separator(chunks[10], buf);
 
         // Render keybindings
-        self.render_keybindings(chunks[7], buf);
+        self.render_keybindings(chunks[11], buf);
     }
 }
 
@@ -242,6 +267,48 @@ impl StatsView<'_> {
         }
     }
 
+    fn render_cost_distribution(&self, area: Rect, buf: &mut Buffer) {
+        let line = Paragraph::new(Line::from(Span::styled(
+            cost_distribution_label(self.data),
+            Style::default().fg(self.theme.muted()),
+        )))
+        .alignment(Alignment::Center);
+        line.render(area, buf);
+    }
+
+    fn render_efficiency(&self, area: Rect, buf: &mut Buffer) {
+        let line = Paragraph::new(Line::from(Span::styled(
+            efficiency_label(self.data),
+            Style::default().fg(self.theme.muted()),
+        )))
+        .alignment(Alignment::Center);
+        line.render(area, buf);
+    }
+
+    fn render_day_of_week(&self, area: Rect, buf: &mut Buffer) {
+        let Some(label) = day_of_week_label(self.data) else {
+            return;
+        };
+        let line = Paragraph::new(Line::from(Span::styled(
+            label,
+            Style::default().fg(self.theme.muted()),
+        )))
+        .alignment(Alignment::Center);
+        line.render(area, buf);
+    }
+
+    fn render_providers(&self, area: Rect, buf: &mut Buffer) {
+        let Some(label) = providers_label(self.providers) else {
+            return;
+        };
+        let line = Paragraph::new(Line::from(Span::styled(
+            label,
+            Style::default().fg(self.theme.muted()),
+        )))
+        .alignment(Alignment::Center);
+        line.render(area, buf);
+    }
+
     fn render_keybindings(&self, area: Rect, buf: &mut Buffer) {
         let bindings = Paragraph::new(Line::from(vec![
             Span::styled("Ctrl+C", Style::default().fg(self.theme.accent())),
@@ -259,6 +326,59 @@ impl StatsView<'_> {
     }
 }
 
+/// One-line daily cost percentile summary
+fn cost_distribution_label(data: &StatsData) -> String {
+    format!(
+        "Cost distribution: P25=${:.2} P50=${:.2} P75=${:.2} P95=${:.2}",
+        data.p25_cost_usd, data.p50_cost_usd, data.p75_cost_usd, data.p95_cost_usd
+    )
+}
+
+/// One-line cache, output and per-request efficiency summary
+fn efficiency_label(data: &StatsData) -> String {
+    format!(
+        "Efficiency: cache hit {:.1}%  output/input {:.2}  {:.0} tokens/request  ${:.4}/1K output",
+        data.cache_hit_ratio * 100.0,
+        data.output_input_ratio,
+        data.avg_tokens_per_request,
+        data.cost_per_1k_output_tokens
+    )
+}
+
+/// Busiest and quietest weekday, if there is any usage
+fn day_of_week_label(data: &StatsData) -> Option<String> {
+    let (busiest, quietest) = (data.busiest_day_of_week?, data.quietest_day_of_week?);
+    Some(format!(
+        "Most active: {}, Least active: {}",
+        weekday_name(busiest),
+        weekday_name(quietest)
+    ))
+}
+
+/// Cost per provider, costliest first, if there is any usage
+fn providers_label(providers: &[ProviderSummary]) -> Option<String> {
+    if providers.is_empty() {
+        return None;
+    }
+    let costs: Vec<String> = providers
+        .iter()
+        .map(|p| format!("{} ${:.2}", p.provider, p.total_cost_usd))
+        .collect();
+    Some(format!("By provider: {}", costs.join(", ")))
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
 /// Internal card representation
 struct StatCard {
     title: String,
@@ -281,11 +401,50 @@ mod tests {
             total_cost: 1.50,
             daily_avg_cost: 0.75,
             active_days: 2,
+            p25_cost_usd: 0.5,
+            p50_cost_usd: 0.75,
+            p75_cost_usd: 1.0,
+            p95_cost_usd: 1.45,
+            cost_std_dev: 0.25,
+            cache_hit_ratio: 0.425,
+            output_input_ratio: 0.35,
+            avg_tokens_per_request: 250.0,
+            cost_per_1k_output_tokens: 0.0123,
+            longest_streak_days: 2,
+            longest_streak_end_date: NaiveDate::from_ymd_opt(2024, 1, 15),
+            busiest_day_of_week: Some(Weekday::Tue),
+            quietest_day_of_week: Some(Weekday::Sat),
         };
         let view = StatsView::new(&data, Theme::Dark);
         let cards = view.build_cards();
 
         assert_eq!(cards.len(), 6);
+        assert_eq!(
+            cost_distribution_label(&data),
+            "Cost distribution: P25=$0.50 P50=$0.75 P75=$1.00 P95=$1.45"
+        );
+        assert_eq!(
+            efficiency_label(&data),
+            "Efficiency: cache hit 42.5%  output/input 0.35  250 tokens/request  $0.0123/1K output"
+        );
+        assert_eq!(
+            day_of_week_label(&data).as_deref(),
+            Some("Most active: Tuesday, Least active: Saturday")
+        );
+    }
+
+    #[test]
+    fn test_providers_label() {
+        assert_eq!(providers_label(&[]), None);
+        let provider = |name: &str, cost: f64| ProviderSummary {
+            provider: name.to_string(),
+            total_cost_usd: cost,
+            ..ProviderSummary::default()
+        };
+        assert_eq!(
+            providers_label(&[provider("Anthropic", 12.5), provider("OpenAI", 3.0)]).as_deref(),
+            Some("By provider: Anthropic $12.50, OpenAI $3.00")
+        );
     }
 
     #[test]
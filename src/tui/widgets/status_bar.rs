@@ -0,0 +1,113 @@
+//! Status bar widget - one-line notices at the bottom of the TUI
+
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    widgets::{Paragraph, Widget},
+};
+
+use crate::tui::theme::Theme;
+use crate::types::CacheWarning;
+
+/// Rows reserved for the status bar below every view
+pub const STATUS_BAR_HEIGHT: u16 = 1;
+
+/// How long a cache warning stays visible
+pub const WARNING_DISPLAY_DURATION: Duration = Duration::from_secs(10);
+
+/// Status bar showing the cache warning from the last load, if any
+pub struct StatusBar<'a> {
+    warning: Option<&'a CacheWarning>,
+    theme: Theme,
+}
+
+impl<'a> StatusBar<'a> {
+    pub fn new(warning: Option<&'a CacheWarning>, theme: Theme) -> Self {
+        Self { warning, theme }
+    }
+
+    /// Message and color for `warning`
+    fn message(&self, warning: &CacheWarning) -> (&'static str, Style) {
+        match warning {
+            CacheWarning::VersionMismatch(_) => (
+                "⚠ Cache rebuilt (version mismatch)",
+                Style::default().fg(self.theme.warning()),
+            ),
+            CacheWarning::Corrupted(_) => (
+                "✗ Cache corrupted, data recomputed",
+                Style::default().fg(self.theme.error()),
+            ),
+            CacheWarning::LoadFailed(_) => (
+                "✗ Cache load failed",
+                Style::default().fg(self.theme.error()),
+            ),
+        }
+    }
+}
+
+impl Widget for StatusBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(warning) = self.warning else {
+            return;
+        };
+        let (text, style) = self.message(warning);
+        Paragraph::new(text)
+            .style(style)
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(warning: Option<&CacheWarning>) -> Buffer {
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(warning, Theme::Dark).render(area, &mut buf);
+        buf
+    }
+
+    fn line(buf: &Buffer) -> String {
+        (0..buf.area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_no_warning_renders_nothing() {
+        assert_eq!(line(&render(None)).trim(), "");
+    }
+
+    #[test]
+    fn test_warning_messages_and_colors() {
+        let cases = [
+            (
+                CacheWarning::VersionMismatch("v1".into()),
+                "⚠ Cache rebuilt (version mismatch)",
+                Theme::Dark.warning(),
+            ),
+            (
+                CacheWarning::Corrupted("bad json".into()),
+                "✗ Cache corrupted, data recomputed",
+                Theme::Dark.error(),
+            ),
+            (
+                CacheWarning::LoadFailed("io".into()),
+                "✗ Cache load failed",
+                Theme::Dark.error(),
+            ),
+        ];
+        for (warning, text, color) in cases {
+            let buf = render(Some(&warning));
+            let rendered = line(&buf);
+            assert_eq!(rendered.trim(), text);
+            let x = rendered.chars().position(|c| c != ' ').unwrap() as u16;
+            assert_eq!(buf[(x, 0)].fg, color);
+        }
+    }
+}
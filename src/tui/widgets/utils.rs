@@ -0,0 +1,85 @@
+//! Formatting helpers shared by widgets
+
+use chrono::{DateTime, Datelike, Local, Utc};
+
+/// Human-friendly age of `dt` as seen at `now`: "just now", "5 minutes ago",
+/// "yesterday", "2 weeks ago", then "Jan 15" (this year) or "Jan 15, 2024"
+pub fn format_date_relative(dt: &DateTime<Utc>, now: &DateTime<Local>) -> String {
+    let elapsed = now.with_timezone(&Utc) - *dt;
+    let plural = |n: i64, unit: &str| {
+        if n == 1 {
+            format!("1 {} ago", unit)
+        } else {
+            format!("{} {}s ago", n, unit)
+        }
+    };
+
+    match elapsed.num_minutes() {
+        m if m < 1 => "just now".to_string(),
+        m if m < 60 => plural(m, "minute"),
+        _ => match elapsed.num_hours() {
+            h if h < 24 => plural(h, "hour"),
+            h if h < 48 => "yesterday".to_string(),
+            _ => match elapsed.num_days() {
+                d if d < 7 => plural(d, "day"),
+                d if d < 14 => "last week".to_string(),
+                d if d < 28 => plural(d / 7, "week"),
+                _ => {
+                    let local = dt.with_timezone(&Local);
+                    if local.year() == now.year() {
+                        local.format("%b %-d").to_string()
+                    } else {
+                        local.format("%b %-d, %Y").to_string()
+                    }
+                }
+            },
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 6, 20, 12, 0, 0).unwrap()
+    }
+
+    fn ago(d: Duration) -> String {
+        let now = now();
+        format_date_relative(&(now.with_timezone(&Utc) - d), &now)
+    }
+
+    #[test]
+    fn test_minutes_and_hours() {
+        assert_eq!(ago(Duration::seconds(59)), "just now");
+        assert_eq!(ago(Duration::seconds(-30)), "just now");
+        assert_eq!(ago(Duration::minutes(1)), "1 minute ago");
+        assert_eq!(ago(Duration::minutes(59)), "59 minutes ago");
+        assert_eq!(ago(Duration::minutes(60)), "1 hour ago");
+        assert_eq!(ago(Duration::hours(23)), "23 hours ago");
+    }
+
+    #[test]
+    fn test_days_and_weeks() {
+        assert_eq!(ago(Duration::hours(24)), "yesterday");
+        assert_eq!(ago(Duration::hours(47)), "yesterday");
+        assert_eq!(ago(Duration::hours(48)), "2 days ago");
+        assert_eq!(ago(Duration::days(6)), "6 days ago");
+        assert_eq!(ago(Duration::days(7)), "last week");
+        assert_eq!(ago(Duration::days(13)), "last week");
+        assert_eq!(ago(Duration::days(14)), "2 weeks ago");
+        assert_eq!(ago(Duration::days(27)), "3 weeks ago");
+    }
+
+    #[test]
+    fn test_older_dates() {
+        assert_eq!(ago(Duration::days(28)), "May 23");
+        let dt = Local
+            .with_ymd_and_hms(2024, 1, 15, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(format_date_relative(&dt, &now()), "Jan 15, 2024");
+    }
+}
@@ -21,6 +21,10 @@ pub enum ToktrackError {
     #[error("pricing error: {0}")]
     Pricing(String),
 
+    /// No CLI produced any usage entries
+    #[error("no usage data found from any CLI")]
+    NoData,
+
     /// Configuration error
     #[error("config error: {0}")]
     #[allow(dead_code)]
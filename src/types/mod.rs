@@ -3,6 +3,8 @@
 mod error;
 mod usage;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 pub use error::*;
 pub use usage::*; // includes SessionMetadata, AutoDetected
 
@@ -17,3 +19,49 @@ pub enum CacheWarning {
     /// Cache version mismatch — needs rebuild
     VersionMismatch(String),
 }
+
+/// Counters collected while parsing usage files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Final lines skipped because they had no trailing newline
+    /// (the CLI was still writing them when the file was read)
+    pub truncated_lines: u64,
+    /// Entries whose reported cost was NaN, infinite or negative (counted as $0)
+    pub invalid_costs: u64,
+}
+
+impl ParseStats {
+    /// Accumulate counters from `other` into `self`
+    pub fn add(&mut self, other: ParseStats) {
+        self.truncated_lines = self.truncated_lines.saturating_add(other.truncated_lines);
+        self.invalid_costs = self.invalid_costs.saturating_add(other.invalid_costs);
+    }
+}
+
+/// Files-parsed progress, shared between the data loader and the loading spinner
+#[derive(Debug, Default)]
+pub struct ParseProgress {
+    parsed: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl ParseProgress {
+    /// Start a new pass over `total` files
+    pub fn start(&self, total: usize) {
+        self.parsed.store(0, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    /// Record one more parsed file
+    pub fn advance(&self) {
+        self.parsed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Current `(parsed, total)` file counts
+    pub fn snapshot(&self) -> (usize, usize) {
+        (
+            self.parsed.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+        )
+    }
+}
@@ -1,9 +1,11 @@
 //! Usage types for token tracking
 
-use chrono::{DateTime, Local, NaiveDate, Utc};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::services::Aggregator;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct StatsData {
     pub total_tokens: u64,
@@ -12,6 +14,27 @@ pub struct StatsData {
     pub total_cost: f64,
     pub daily_avg_cost: f64,
     pub active_days: u32,
+    /// Daily cost percentiles
+    pub p25_cost_usd: f64,
+    pub p50_cost_usd: f64,
+    pub p75_cost_usd: f64,
+    pub p95_cost_usd: f64,
+    /// Standard deviation of daily cost
+    pub cost_std_dev: f64,
+    /// Cache read tokens as a share of all tokens
+    pub cache_hit_ratio: f64,
+    /// Output tokens per input token
+    pub output_input_ratio: f64,
+    /// Tokens per request, counting requests across all models
+    pub avg_tokens_per_request: f64,
+    /// Total cost per 1,000 output tokens
+    pub cost_per_1k_output_tokens: f64,
+    /// Longest run of consecutive days with usage, and the day it ended
+    pub longest_streak_days: u32,
+    pub longest_streak_end_date: Option<NaiveDate>,
+    /// Weekdays with the highest and lowest average daily cost
+    pub busiest_day_of_week: Option<Weekday>,
+    pub quietest_day_of_week: Option<Weekday>,
 }
 
 impl StatsData {
@@ -24,6 +47,19 @@ impl StatsData {
                 total_cost: 0.0,
                 daily_avg_cost: 0.0,
                 active_days: 0,
+                p25_cost_usd: 0.0,
+                p50_cost_usd: 0.0,
+                p75_cost_usd: 0.0,
+                p95_cost_usd: 0.0,
+                cost_std_dev: 0.0,
+                cache_hit_ratio: 0.0,
+                output_input_ratio: 0.0,
+                avg_tokens_per_request: 0.0,
+                cost_per_1k_output_tokens: 0.0,
+                longest_streak_days: 0,
+                longest_streak_end_date: None,
+                busiest_day_of_week: None,
+                quietest_day_of_week: None,
             };
         }
 
@@ -33,16 +69,23 @@ impl StatsData {
         let mut total_tokens: u64 = 0;
         let mut total_cost: f64 = 0.0;
         let mut peak_day: Option<(NaiveDate, u64)> = None;
+        let mut total_input: u64 = 0;
+        let mut total_output: u64 = 0;
+        let mut total_cache_read: u64 = 0;
+        let mut total_requests: u64 = 0;
 
         for summary in summaries {
-            let day_tokens = summary.total_input_tokens
-                + summary.total_output_tokens
-                + summary.total_cache_read_tokens
-                + summary.total_cache_creation_tokens
-                + summary.total_thinking_tokens;
+            let day_tokens = summary.total_tokens();
 
             total_tokens = total_tokens.saturating_add(day_tokens);
             total_cost += summary.total_cost_usd;
+            total_input = total_input.saturating_add(summary.total_input_tokens);
+            total_output = total_output.saturating_add(summary.total_output_tokens);
+            total_cache_read = total_cache_read.saturating_add(summary.total_cache_read_tokens);
+            total_requests = summary
+                .models
+                .values()
+                .fold(total_requests, |acc, usage| acc.saturating_add(usage.count));
 
             match &peak_day {
                 None => peak_day = Some((summary.date, day_tokens)),
@@ -55,6 +98,16 @@ impl StatsData {
 
         let daily_avg_tokens = total_tokens / active_days as u64;
         let daily_avg_cost = total_cost / active_days as f64;
+        let (longest_streak_days, longest_streak_end_date) = Aggregator::longest_streak(summaries);
+        let (busiest_day_of_week, quietest_day_of_week) =
+            DayOfWeekStats::busiest_and_quietest(&Aggregator::by_day_of_week(summaries));
+        let ratio = |num: f64, denom: u64| {
+            if denom == 0 {
+                0.0
+            } else {
+                num / denom as f64
+            }
+        };
 
         Self {
             total_tokens,
@@ -63,6 +116,19 @@ impl StatsData {
             total_cost,
             daily_avg_cost,
             active_days,
+            p25_cost_usd: Aggregator::percentile_cost(summaries, 0.25),
+            p50_cost_usd: Aggregator::percentile_cost(summaries, 0.5),
+            p75_cost_usd: Aggregator::percentile_cost(summaries, 0.75),
+            p95_cost_usd: Aggregator::percentile_cost(summaries, 0.95),
+            cost_std_dev: Aggregator::variance_cost(summaries).sqrt(),
+            cache_hit_ratio: ratio(total_cache_read as f64, total_tokens),
+            output_input_ratio: ratio(total_output as f64, total_input),
+            avg_tokens_per_request: ratio(total_tokens as f64, total_requests),
+            cost_per_1k_output_tokens: ratio(total_cost * 1000.0, total_output),
+            longest_streak_days,
+            longest_streak_end_date,
+            busiest_day_of_week,
+            quietest_day_of_week,
         }
     }
 }
@@ -85,10 +151,19 @@ pub struct UsageEntry {
     /// Provider ID (e.g., "anthropic", "github-copilot")
     #[serde(default)]
     pub provider: Option<String>,
+    /// Cost was computed from pricing data rather than logged by the CLI
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cost_estimated: bool,
+    /// Working directory of the session that produced this entry (Claude Code only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// Git branch checked out when this entry was recorded (Claude Code only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
 }
 
 impl UsageEntry {
-    #[allow(dead_code)]
+    /// Sum of all five token fields
     pub fn total_tokens(&self) -> u64 {
         self.input_tokens
             + self.output_tokens
@@ -97,10 +172,29 @@ impl UsageEntry {
             + self.thinking_tokens
     }
 
+    /// Whether the reported cost is unusable (NaN, infinite or negative)
+    pub fn has_invalid_cost(&self) -> bool {
+        self.cost_usd.is_some_and(|c| !c.is_finite() || c < 0.0)
+    }
+
+    /// Cost for aggregation: missing or invalid costs count as 0 so totals stay finite
+    pub fn cost_or_zero(&self) -> f64 {
+        if self.has_invalid_cost() {
+            0.0
+        } else {
+            self.cost_usd.unwrap_or(0.0)
+        }
+    }
+
     /// Convert UTC timestamp to local timezone date.
     /// Ensures date grouping matches the user's local calendar.
     pub fn local_date(&self) -> NaiveDate {
-        self.timestamp.with_timezone(&Local).date_naive()
+        self.date_in(&Local)
+    }
+
+    /// Calendar date of the timestamp in `tz`
+    pub fn date_in<Tz: TimeZone>(&self, tz: &Tz) -> NaiveDate {
+        self.timestamp.with_timezone(tz).date_naive()
     }
 
     pub fn dedup_hash(&self) -> Option<String> {
@@ -129,6 +223,24 @@ pub struct DailySummary {
     pub total_thinking_tokens: u64,
     pub total_cost_usd: f64,
     pub models: HashMap<String, ModelUsage>,
+    /// Some of the day's cost was estimated from pricing instead of logged `costUSD`
+    #[serde(default)]
+    pub cost_estimated: bool,
+}
+
+impl DailySummary {
+    /// Sum of all five token totals
+    pub fn total_tokens(&self) -> u64 {
+        self.total_input_tokens
+            + self.total_output_tokens
+            + self.total_cache_tokens()
+            + self.total_thinking_tokens
+    }
+
+    /// Cache read plus cache creation tokens
+    pub fn total_cache_tokens(&self) -> u64 {
+        self.total_cache_read_tokens + self.total_cache_creation_tokens
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -144,6 +256,15 @@ pub struct ModelUsage {
 }
 
 impl ModelUsage {
+    /// Sum of all five token fields
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_read_tokens
+            + self.cache_creation_tokens
+            + self.thinking_tokens
+    }
+
     pub fn add(&mut self, entry: &UsageEntry, cost: f64) {
         self.input_tokens = self.input_tokens.saturating_add(entry.input_tokens);
         self.output_tokens = self.output_tokens.saturating_add(entry.output_tokens);
@@ -159,6 +280,45 @@ impl ModelUsage {
     }
 }
 
+/// Usage within one local hour of a day (`hour` 0 = midnight–1 AM)
+#[derive(Debug, Clone, Serialize, PartialEq, Default)]
+pub struct HourlySummary {
+    pub hour: u8,
+    pub total_input_tokens: u64,
+    pub total_output_tokens: u64,
+    pub total_cost_usd: f64,
+    pub models: HashMap<String, ModelUsage>,
+}
+
+/// Usage on one weekday across all days in range
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DayOfWeekStats {
+    pub day: Weekday,
+    pub total_cost_usd: f64,
+    pub total_tokens: u64,
+    /// Cost per day with usage on this weekday
+    pub avg_cost_usd: f64,
+    pub day_count: u32,
+}
+
+impl DayOfWeekStats {
+    /// Weekdays with the highest and lowest `avg_cost_usd`, ignoring weekdays
+    /// without usage; the earlier weekday wins a tie
+    pub fn busiest_and_quietest(days: &[DayOfWeekStats]) -> (Option<Weekday>, Option<Weekday>) {
+        let mut busiest: Option<&DayOfWeekStats> = None;
+        let mut quietest: Option<&DayOfWeekStats> = None;
+        for day in days.iter().filter(|d| d.day_count > 0) {
+            if busiest.is_none_or(|b| day.avg_cost_usd > b.avg_cost_usd) {
+                busiest = Some(day);
+            }
+            if quietest.is_none_or(|q| day.avg_cost_usd < q.avg_cost_usd) {
+                quietest = Some(day);
+            }
+        }
+        (busiest.map(|d| d.day), quietest.map(|d| d.day))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct TotalSummary {
     pub total_input_tokens: u64,
@@ -172,16 +332,74 @@ pub struct TotalSummary {
     pub day_count: u64,
 }
 
+impl TotalSummary {
+    /// Sum of all five token totals
+    pub fn total_tokens(&self) -> u64 {
+        self.total_input_tokens
+            + self.total_output_tokens
+            + self.total_cache_read_tokens
+            + self.total_cache_creation_tokens
+            + self.total_thinking_tokens
+    }
+}
+
 /// Usage aggregated by source CLI (claude, opencode, gemini, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct SourceUsage {
     pub source: String,
     pub total_tokens: u64,
     pub total_cost_usd: f64,
+    /// Costliest models of this source, at most `SOURCE_TOP_MODELS`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub by_model: Vec<(String, ModelUsage)>,
+}
+
+/// Usage aggregated by provider (vendor) across source CLIs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProviderSummary {
+    pub provider: String,
+    pub total_tokens: u64,
+    pub total_cost_usd: f64,
+    /// Source CLIs with usage from this provider, sorted by name
+    pub sources: Vec<String>,
+}
+
+/// Models kept in `SourceUsage::by_model`
+pub const SOURCE_TOP_MODELS: usize = 5;
+
+/// Per-model usage of one source, costliest model first
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SourceModelBreakdown {
+    pub source: String,
+    pub models: Vec<(String, ModelUsage)>,
+}
+
+/// Claude Code usage aggregated by git branch
+#[derive(Debug, Clone, Serialize, PartialEq, Default)]
+pub struct BranchUsage {
+    /// Branch name ("unknown" for usage without a branch or on a detached HEAD)
+    pub branch: String,
+    /// Sessions on this branch; None when aggregated from individual entries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_count: Option<u64>,
+    pub total_tokens: u64,
+    pub total_cost_usd: f64,
+}
+
+/// Claude Code usage for one project (working directory basename)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ProjectSummary {
+    pub project_name: String,
+    /// Most recently seen full path with this basename
+    pub project_path: String,
+    pub total_cost_usd: f64,
+    pub total_tokens: u64,
+    pub day_count: usize,
+    pub daily: Vec<DailySummary>,
 }
 
 /// A single Claude Code session with metadata and aggregated cost/token data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)] // Fields reserved for session detail view and future features
 pub struct SessionInfo {
     pub session_id: String,
@@ -205,10 +423,62 @@ pub struct SessionInfo {
     pub total_tokens: u64,
     /// Most-used model in this session
     pub primary_model: String,
+    /// Distinct UTC offsets in the session's timestamps, only when more than one
+    /// (travel, VPN). A data-quality hint; aggregation normalizes to UTC regardless.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mixed_utc_offsets: Vec<String>,
     /// Sidecar metadata (populated from ~/.toktrack/sessions/)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<SessionMetadata>,
 }
 
+/// Session ids of the standout sessions in a report
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SessionHighlights {
+    /// Session with the highest total cost
+    pub most_expensive: Option<String>,
+    /// Session with the most total tokens
+    pub most_tokens: Option<String>,
+}
+
+impl SessionHighlights {
+    /// Pick the max-cost and max-token sessions. Ties keep the first session.
+    pub fn from_sessions(sessions: &[SessionInfo]) -> Self {
+        let most_expensive = sessions
+            .iter()
+            .filter(|s| s.total_cost_usd > 0.0)
+            .reduce(|best, s| {
+                if s.total_cost_usd > best.total_cost_usd {
+                    s
+                } else {
+                    best
+                }
+            })
+            .map(|s| s.session_id.clone());
+        let most_tokens = sessions
+            .iter()
+            .filter(|s| s.total_tokens > 0)
+            .reduce(|best, s| {
+                if s.total_tokens > best.total_tokens {
+                    s
+                } else {
+                    best
+                }
+            })
+            .map(|s| s.session_id.clone());
+        Self {
+            most_expensive,
+            most_tokens,
+        }
+    }
+
+    /// Whether `session_id` is one of the highlighted sessions
+    pub fn contains(&self, session_id: &str) -> bool {
+        self.most_expensive.as_deref() == Some(session_id)
+            || self.most_tokens.as_deref() == Some(session_id)
+    }
+}
+
 /// Sidecar metadata for a Claude Code session.
 /// Stored as `~/.toktrack/sessions/<session-id>.json`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -239,8 +509,96 @@ pub struct AutoDetected {
     pub issue_id_source: Option<String>,
 }
 
+/// Where a session detail entry's cost came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostSource {
+    /// Cost recorded by the CLI in its logs
+    Logged,
+    /// Cost computed from token counts and model pricing
+    Pricing,
+}
+
+/// Per-token rates (USD) used to price an entry
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CostRates {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_creation: f64,
+    /// Reasoning price, or the output price when the model has none
+    pub thinking: f64,
+}
+
+impl CostRates {
+    /// Cost of `entry`'s tokens at these rates
+    pub fn cost(&self, entry: &UsageEntry) -> f64 {
+        (entry.input_tokens as f64 * self.input)
+            + (entry.cache_read_tokens as f64 * self.cache_read)
+            + (entry.cache_creation_tokens as f64 * self.cache_creation)
+            + (entry.output_tokens as f64 * self.output)
+    }
+
+    /// Cost of each of `entry`'s token types at these rates, thinking tokens included
+    pub fn breakdown(&self, entry: &UsageEntry) -> CostBreakdown {
+        let mut breakdown = CostBreakdown {
+            input_cost: entry.input_tokens as f64 * self.input,
+            output_cost: entry.output_tokens as f64 * self.output,
+            cache_read_cost: entry.cache_read_tokens as f64 * self.cache_read,
+            cache_creation_cost: entry.cache_creation_tokens as f64 * self.cache_creation,
+            thinking_cost: entry.thinking_tokens as f64 * self.thinking,
+            total: 0.0,
+        };
+        breakdown.total = breakdown.input_cost
+            + breakdown.output_cost
+            + breakdown.cache_read_cost
+            + breakdown.cache_creation_cost
+            + breakdown.thinking_cost;
+        breakdown
+    }
+}
+
+/// Cost (USD) of an entry split by token type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CostBreakdown {
+    pub input_cost: f64,
+    pub output_cost: f64,
+    pub cache_read_cost: f64,
+    pub cache_creation_cost: f64,
+    pub thinking_cost: f64,
+    pub total: f64,
+}
+
+impl CostBreakdown {
+    /// CSV header matching `to_csv_row`
+    pub const CSV_HEADER: &'static str =
+        "input_cost,output_cost,cache_read_cost,cache_creation_cost,thinking_cost,total_cost";
+
+    /// Comma-separated components in `CSV_HEADER` order
+    pub fn to_csv_row(self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.input_cost,
+            self.output_cost,
+            self.cache_read_cost,
+            self.cache_creation_cost,
+            self.thinking_cost,
+            self.total
+        )
+    }
+
+    fn add(&mut self, other: &CostBreakdown) {
+        self.input_cost += other.input_cost;
+        self.output_cost += other.output_cost;
+        self.cache_read_cost += other.cache_read_cost;
+        self.cache_creation_cost += other.cache_creation_cost;
+        self.thinking_cost += other.thinking_cost;
+        self.total += other.total;
+    }
+}
+
 /// A single API request within a session detail view
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SessionDetailEntry {
     pub timestamp: DateTime<Utc>,
     pub model: String,
@@ -249,6 +607,85 @@ pub struct SessionDetailEntry {
     pub cache_read_tokens: u64,
     pub cache_creation_tokens: u64,
     pub cost_usd: f64,
+    pub cost_source: CostSource,
+    /// Rates used when `cost_source` is `Pricing` (None if the model had no pricing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rates: Option<CostRates>,
+    /// Cost per token type, alongside `rates`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_breakdown: Option<CostBreakdown>,
+}
+
+/// Timestamp granularity for the session detail view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DetailGranularity {
+    /// Keep exact request timestamps
+    #[default]
+    Exact,
+    /// Round down to the minute
+    Minute,
+    /// Round down to the hour
+    Hour,
+}
+
+impl std::str::FromStr for DetailGranularity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(Self::Exact),
+            "minute" => Ok(Self::Minute),
+            "hour" => Ok(Self::Hour),
+            _ => Err(format!("'{s}' is not one of: exact, minute, hour")),
+        }
+    }
+}
+
+impl DetailGranularity {
+    /// Round `ts` down to the start of its bucket
+    fn bucket(self, ts: DateTime<Utc>) -> DateTime<Utc> {
+        let truncate = |ts: DateTime<Utc>, secs: i64| {
+            DateTime::from_timestamp(ts.timestamp().div_euclid(secs) * secs, 0).unwrap_or(ts)
+        };
+        match self {
+            Self::Exact => ts,
+            Self::Minute => truncate(ts, 60),
+            Self::Hour => truncate(ts, 3600),
+        }
+    }
+
+    /// Round timestamps of time-sorted entries and merge adjacent entries
+    /// that share a bucket, model and cost provenance. `Exact` returns entries unchanged.
+    pub fn apply(self, entries: Vec<SessionDetailEntry>) -> Vec<SessionDetailEntry> {
+        if self == Self::Exact {
+            return entries;
+        }
+        let mut merged: Vec<SessionDetailEntry> = Vec::with_capacity(entries.len());
+        for mut entry in entries {
+            entry.timestamp = self.bucket(entry.timestamp);
+            match merged.last_mut() {
+                Some(last)
+                    if last.timestamp == entry.timestamp
+                        && last.model == entry.model
+                        && last.cost_source == entry.cost_source
+                        && last.rates == entry.rates =>
+                {
+                    last.input_tokens += entry.input_tokens;
+                    last.output_tokens += entry.output_tokens;
+                    last.cache_read_tokens += entry.cache_read_tokens;
+                    last.cache_creation_tokens += entry.cache_creation_tokens;
+                    last.cost_usd += entry.cost_usd;
+                    if let (Some(total), Some(more)) =
+                        (last.cost_breakdown.as_mut(), entry.cost_breakdown.as_ref())
+                    {
+                        total.add(more);
+                    }
+                }
+                _ => merged.push(entry),
+            }
+        }
+        merged
+    }
 }
 
 #[cfg(test)]
@@ -275,6 +712,7 @@ mod tests {
             total_thinking_tokens: 0,
             total_cost_usd: cost,
             models: HashMap::new(),
+            cost_estimated: false,
         }
     }
 
@@ -288,6 +726,51 @@ mod tests {
         assert!((data.total_cost - 0.0).abs() < f64::EPSILON);
         assert!((data.daily_avg_cost - 0.0).abs() < f64::EPSILON);
         assert_eq!(data.active_days, 0);
+        assert_eq!(data.p50_cost_usd, 0.0);
+        assert_eq!(data.cost_std_dev, 0.0);
+        assert_eq!(data.cache_hit_ratio, 0.0);
+        assert_eq!(data.avg_tokens_per_request, 0.0);
+    }
+
+    #[test]
+    fn test_stats_data_efficiency_metrics() {
+        let mut day1 = make_summary(2024, 1, 10, 600, 200, 1000, 200, 0.30);
+        day1.models.insert(
+            "claude".to_string(),
+            ModelUsage {
+                count: 3,
+                ..ModelUsage::default()
+            },
+        );
+        let mut day2 = make_summary(2024, 1, 11, 400, 200, 600, 0, 0.30);
+        day2.models.insert(
+            "gpt".to_string(),
+            ModelUsage {
+                count: 2,
+                ..ModelUsage::default()
+            },
+        );
+        let data = StatsData::from_daily_summaries(&[day1, day2]);
+
+        // 3200 tokens in total, 1600 of them cache reads
+        assert!((data.cache_hit_ratio - 0.5).abs() < 1e-9);
+        assert!((data.output_input_ratio - 0.4).abs() < 1e-9);
+        assert!((data.avg_tokens_per_request - 640.0).abs() < 1e-9);
+        assert!((data.cost_per_1k_output_tokens - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_data_efficiency_zero_denominators() {
+        // Cache-only day: no input, output or requests
+        let data = StatsData::from_daily_summaries(&[make_summary(2024, 1, 10, 0, 0, 0, 0, 0.0)]);
+        assert_eq!(data.cache_hit_ratio, 0.0);
+        assert_eq!(data.output_input_ratio, 0.0);
+        assert_eq!(data.avg_tokens_per_request, 0.0);
+        assert_eq!(data.cost_per_1k_output_tokens, 0.0);
+
+        let data = StatsData::from_daily_summaries(&[make_summary(2024, 1, 10, 0, 0, 500, 0, 0.1)]);
+        assert_eq!(data.cache_hit_ratio, 1.0);
+        assert_eq!(data.output_input_ratio, 0.0);
     }
 
     #[test]
@@ -324,6 +807,16 @@ mod tests {
         assert!((data.total_cost - 0.35).abs() < f64::EPSILON);
         assert!((data.daily_avg_cost - 0.35 / 3.0).abs() < 0.001);
         assert_eq!(data.active_days, 3);
+        assert_eq!(data.longest_streak_days, 1);
+        assert_eq!(
+            data.longest_streak_end_date,
+            NaiveDate::from_ymd_opt(2024, 1, 10)
+        );
+        assert!((data.p50_cost_usd - 0.10).abs() < 1e-9);
+        assert!((data.p25_cost_usd - 0.075).abs() < 1e-9);
+        assert!((data.p95_cost_usd - 0.19).abs() < 1e-9);
+        assert!(data.p75_cost_usd <= data.p95_cost_usd);
+        assert!(data.cost_std_dev > 0.0);
     }
 
     #[test]
@@ -358,6 +851,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         assert_eq!(entry.total_tokens(), 180);
     }
@@ -377,10 +873,34 @@ mod tests {
             request_id: None,
             source: Some("gemini".into()),
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         assert_eq!(entry.total_tokens(), 210);
     }
 
+    #[test]
+    fn test_daily_summary_total_tokens() {
+        let mut summary = make_summary(2026, 1, 1, 100, 50, 20, 10, 0.0);
+        summary.total_thinking_tokens = 30;
+        assert_eq!(summary.total_tokens(), 210);
+        assert_eq!(summary.total_cache_tokens(), 30);
+    }
+
+    #[test]
+    fn test_total_summary_total_tokens() {
+        let total = TotalSummary {
+            total_input_tokens: 100,
+            total_output_tokens: 50,
+            total_cache_read_tokens: 20,
+            total_cache_creation_tokens: 10,
+            total_thinking_tokens: 30,
+            ..TotalSummary::default()
+        };
+        assert_eq!(total.total_tokens(), 210);
+    }
+
     #[test]
     fn test_usage_entry_dedup_hash() {
         let entry = UsageEntry {
@@ -396,6 +916,9 @@ mod tests {
             request_id: Some("req456".into()),
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         assert_eq!(entry.dedup_hash(), Some("msg123:req456".into()));
     }
@@ -415,6 +938,9 @@ mod tests {
             request_id: Some("req456".into()),
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         assert_eq!(entry.dedup_hash(), None);
     }
@@ -434,6 +960,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         assert_eq!(entry.dedup_hash(), Some("msg789:gpt-4:100:50".into()));
     }
@@ -458,6 +987,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
 
         let local_date = entry.local_date();
@@ -482,6 +1014,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         let local = late_entry.local_date();
         let utc_naive = late_utc.date_naive();
@@ -508,6 +1043,9 @@ mod tests {
             request_id: None,
             source: None,
             provider: None,
+            cost_estimated: false,
+            project_path: None,
+            git_branch: None,
         };
         usage.add(&entry, 0.01);
 
@@ -517,4 +1055,75 @@ mod tests {
         assert_eq!(usage.cost_usd, 0.01);
         assert_eq!(usage.count, 1);
     }
+
+    #[test]
+    fn test_session_highlights_empty() {
+        let highlights = SessionHighlights::from_sessions(&[]);
+        assert_eq!(highlights, SessionHighlights::default());
+        assert!(!highlights.contains("any"));
+    }
+
+    fn make_detail(ts: &str, model: &str, input: u64) -> SessionDetailEntry {
+        SessionDetailEntry {
+            timestamp: ts.parse().unwrap(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: 10,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cost_usd: 0.01,
+            cost_source: CostSource::Logged,
+            rates: None,
+            cost_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn test_detail_granularity_minute_merges_same_minute() {
+        let entries = vec![
+            make_detail("2026-01-20T09:00:05Z", "Claude Sonnet 4", 100),
+            make_detail("2026-01-20T09:00:40Z", "Claude Sonnet 4", 200),
+            make_detail("2026-01-20T09:01:10Z", "Claude Sonnet 4", 300),
+        ];
+
+        let merged = DetailGranularity::Minute.apply(entries);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged[0].timestamp.to_rfc3339(),
+            "2026-01-20T09:00:00+00:00"
+        );
+        assert_eq!(merged[0].input_tokens, 300);
+        assert_eq!(merged[0].output_tokens, 20);
+        assert!((merged[0].cost_usd - 0.02).abs() < 1e-9);
+        assert_eq!(merged[1].input_tokens, 300);
+    }
+
+    #[test]
+    fn test_detail_granularity_keeps_cost_sources_apart() {
+        let priced = SessionDetailEntry {
+            cost_source: CostSource::Pricing,
+            ..make_detail("2026-01-20T09:00:40Z", "Claude Sonnet 4", 200)
+        };
+        let entries = vec![
+            make_detail("2026-01-20T09:00:05Z", "Claude Sonnet 4", 100),
+            priced,
+        ];
+        assert_eq!(DetailGranularity::Minute.apply(entries).len(), 2);
+    }
+
+    #[test]
+    fn test_detail_granularity_keeps_different_models_apart() {
+        let entries = vec![
+            make_detail("2026-01-20T09:00:05Z", "Claude Sonnet 4", 100),
+            make_detail("2026-01-20T09:20:00Z", "Claude Haiku 4.5", 100),
+        ];
+
+        assert_eq!(DetailGranularity::Hour.apply(entries.clone()).len(), 2);
+        assert_eq!(
+            DetailGranularity::Exact.apply(entries)[0]
+                .timestamp
+                .to_rfc3339(),
+            "2026-01-20T09:00:05+00:00"
+        );
+    }
 }
@@ -0,0 +1,144 @@
+//! Model name variants seen across CLIs and providers, and the canonical
+//! name each one normalizes to
+
+use toktrack::services::normalizer::normalize_model_name;
+
+fn assert_normalizes(cases: &[(&str, &str)]) {
+    for (input, expected) in cases {
+        assert_eq!(
+            normalize_model_name(input),
+            *expected,
+            "normalize_model_name({:?})",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_claude_legacy_models() {
+    assert_normalizes(&[
+        ("claude-instant-1", "claude-instant-1"),
+        ("claude-instant-1.2", "claude-instant-1-2"),
+        ("claude-1.3", "claude-1-3"),
+        ("claude-2", "claude-2"),
+        ("claude-2.0", "claude-2-0"),
+        ("claude-2.1", "claude-2-1"),
+    ]);
+}
+
+#[test]
+fn test_claude_3_models() {
+    assert_normalizes(&[
+        ("claude-3-opus-20240229", "claude-3-opus"),
+        ("claude-3-sonnet-20240229", "claude-3-sonnet"),
+        ("claude-3-haiku-20240307", "claude-3-haiku"),
+        ("claude-3-opus-latest", "claude-3-opus-latest"),
+        ("claude-opus-3", "claude-3-opus"),
+        ("anthropic/claude-3-haiku", "claude-3-haiku"),
+    ]);
+}
+
+#[test]
+fn test_claude_3_5_and_3_7_models() {
+    assert_normalizes(&[
+        ("claude-3-5-sonnet-20241022", "claude-3-5-sonnet"),
+        ("claude-3-5-sonnet-20240620", "claude-3-5-sonnet"),
+        ("claude-3.5-sonnet", "claude-3-5-sonnet"),
+        ("claude-sonnet-3.5", "claude-3-5-sonnet"),
+        ("claude-sonnet-3-5", "claude-3-5-sonnet"),
+        ("claude-3-5-haiku-20241022", "claude-3-5-haiku"),
+        ("claude-3.5-haiku", "claude-3-5-haiku"),
+        ("claude-3-5-sonnet-latest", "claude-3-5-sonnet-latest"),
+        ("claude-3-7-sonnet-20250219", "claude-3-7-sonnet"),
+        ("claude-3.7-sonnet", "claude-3-7-sonnet"),
+        (
+            "openrouter/anthropic/claude-3.7-sonnet",
+            "claude-3-7-sonnet",
+        ),
+    ]);
+}
+
+#[test]
+fn test_claude_4_models() {
+    assert_normalizes(&[
+        ("claude-sonnet-4-20250514", "claude-sonnet-4"),
+        ("claude-opus-4-20250514", "claude-opus-4"),
+        ("claude-opus-4-1-20250805", "claude-opus-4-1"),
+        ("claude-opus-4.1", "claude-opus-4-1"),
+        ("claude-sonnet-4.5", "claude-sonnet-4-5"),
+        ("claude-sonnet-4-5-20250929", "claude-sonnet-4-5"),
+        ("claude-haiku-4-5-20251001", "claude-haiku-4-5"),
+        ("claude-opus-4-5-20251101", "claude-opus-4-5"),
+        ("claude-4-sonnet", "claude-sonnet-4"),
+        ("claude-4-opus", "claude-opus-4"),
+        ("anthropic/claude-sonnet-4", "claude-sonnet-4"),
+        ("anthropic/claude-sonnet-4.5", "claude-sonnet-4-5"),
+    ]);
+}
+
+#[test]
+fn test_gpt_models() {
+    assert_normalizes(&[
+        ("gpt-3.5-turbo", "gpt-3-5-turbo"),
+        ("gpt-3.5-turbo-0125", "gpt-3-5-turbo-0125"),
+        ("gpt-4", "gpt-4"),
+        ("gpt-4-turbo", "gpt-4-turbo"),
+        ("gpt-4-turbo-2024-04-09", "gpt-4-turbo"),
+        ("gpt-4o", "gpt-4o"),
+        ("gpt-4o-2024-08-06", "gpt-4o"),
+        ("gpt-4o-mini-2024-07-18", "gpt-4o-mini"),
+        ("gpt-4.1", "gpt-4-1"),
+        ("gpt-4.1-mini-2025-04-14", "gpt-4-1-mini"),
+        ("gpt-5", "gpt-5"),
+        ("gpt-5-codex", "gpt-5-codex"),
+        ("openai/gpt-4o", "gpt-4o"),
+    ]);
+}
+
+#[test]
+fn test_openai_o_series_and_codex() {
+    assert_normalizes(&[
+        ("o1", "o1"),
+        ("o1-2024-12-17", "o1"),
+        ("o1-mini-2024-09-12", "o1-mini"),
+        ("o3-mini", "o3-mini"),
+        ("o4-mini-2025-04-16", "o4-mini"),
+        ("codex-mini-latest", "codex-mini-latest"),
+    ]);
+}
+
+#[test]
+fn test_gemini_models() {
+    assert_normalizes(&[
+        ("gemini-1.5-pro", "gemini-1-5-pro"),
+        ("gemini-1.5-flash-002", "gemini-1-5-flash-002"),
+        ("gemini-2.0-flash", "gemini-2-0-flash"),
+        ("gemini-2.5-pro", "gemini-2-5-pro"),
+        ("gemini-2.5-flash-lite", "gemini-2-5-flash-lite"),
+        ("models/gemini-2.5-pro", "gemini-2-5-pro"),
+        ("google/gemini-2.5-flash", "gemini-2-5-flash"),
+    ]);
+}
+
+#[test]
+fn test_mistral_models() {
+    assert_normalizes(&[
+        ("mistral-large-latest", "mistral-large-latest"),
+        ("mistral-large-2411", "mistral-large-2411"),
+        ("mistral/mistral-small-3.1", "mistral-small-3-1"),
+        ("codestral-2501", "codestral-2501"),
+        ("open-mixtral-8x22b", "open-mixtral-8x22b"),
+        ("mistralai/devstral-medium", "devstral-medium"),
+    ]);
+}
+
+#[test]
+fn test_unrelated_names_are_kept() {
+    assert_normalizes(&[
+        ("", ""),
+        ("unknown", "unknown"),
+        ("<synthetic>", "<synthetic>"),
+        ("claude-code", "claude-code"),
+        ("model-12345678-extra", "model-12345678-extra"),
+    ]);
+}